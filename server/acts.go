@@ -0,0 +1,120 @@
+package main
+
+import (
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/FlowingSPDG/vmix-utility/server/core"
+	"github.com/gin-gonic/gin"
+)
+
+// ActsEvent is single ACTS activator message. e.g. "ACTS OK Input 2 1" .
+type ActsEvent struct {
+	Name  string `json:"name"`  // activator name. e.g. "Input", "Overlay1", "Recording" .
+	Input string `json:"input"` // input number, empty for global activators.
+	Value string `json:"value"` // "1"/"0" or value such as volume.
+}
+
+// ParseActs parses ACTS message body. e.g. "Input 2 1" or "Recording 1" .
+func ParseActs(body string) (ActsEvent, bool) {
+	f := strings.Fields(body)
+	switch len(f) {
+	case 2:
+		return ActsEvent{Name: f[0], Value: f[1]}, true
+	case 3:
+		return ActsEvent{Name: f[0], Input: f[1], Value: f[2]}, true
+	}
+	return ActsEvent{}, false
+}
+
+// ActsCache keeps latest ACTS values received from vMix.
+type ActsCache struct {
+	mu     sync.RWMutex
+	values map[string]map[string]string // name -> input -> value.
+}
+
+// NewActsCache creates ActsCache.
+func NewActsCache() *ActsCache {
+	return &ActsCache{
+		values: make(map[string]map[string]string),
+	}
+}
+
+// Update stores activator value and returns true if value changed.
+func (a *ActsCache) Update(ev ActsEvent) bool {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	inputs, ok := a.values[ev.Name]
+	if !ok {
+		inputs = make(map[string]string)
+		a.values[ev.Name] = inputs
+	}
+	if inputs[ev.Input] == ev.Value {
+		return false
+	}
+	inputs[ev.Input] = ev.Value
+	return true
+}
+
+// Snapshot returns copy of cached values.
+func (a *ActsCache) Snapshot() map[string]map[string]string {
+	a.mu.RLock()
+	defer a.mu.RUnlock()
+	snapshot := make(map[string]map[string]string, len(a.values))
+	for name, inputs := range a.values {
+		m := make(map[string]string, len(inputs))
+		for k, v := range inputs {
+			m[k] = v
+		}
+		snapshot[name] = m
+	}
+	return snapshot
+}
+
+// HandleTCPMessage handles subscribed messages from vMix TCP API.
+// ACTS messages are cached and published to events, recording tally path latency.
+// TALLY messages refresh program and preview of primary host with XMLTEXT.
+func HandleTCPMessage(msg TCPMessage) {
+	if msg.Status != "OK" {
+		return
+	}
+	if msg.Command == "TALLY" {
+		events.Publish(Event{Topic: "tally", Host: "primary", Data: msg.Body})
+		go afv.OnProgram(programFromTally(msg.Body))
+		if tallyRefresh != nil {
+			tallyRefresh.Trigger()
+		}
+		return
+	}
+	if msg.Command != "ACTS" {
+		return
+	}
+	ev, ok := ParseActs(msg.Body)
+	if !ok {
+		return
+	}
+	trace := &core.Trace{Received: msg.ReceivedAt}
+	changed := actsCache.Update(ev)
+	trace.Cached = time.Now()
+	latency.Record(core.LatencyStageReceiveToCache, trace.Cached.Sub(trace.Received))
+	if !changed {
+		return
+	}
+	poller.TouchHost(*vmixaddr)
+	events.Publish(Event{Topic: "acts", Host: "primary", Data: ev, Trace: trace})
+	if ev.Name == "Input" && ev.Value == "1" {
+		if n, err := strconv.Atoi(ev.Input); err == nil {
+			go afv.OnProgram(n)
+		}
+	}
+}
+
+// GetActsHandler returns latest ACTS values for [GET] /api/acts .
+func GetActsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"acts": actsCache.Snapshot(),
+	})
+}
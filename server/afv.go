@@ -0,0 +1,152 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+
+	"github.com/gin-gonic/gin"
+)
+
+// AFVRule is audio-follow-video rule. When Input goes to program, Unmute inputs are faded up and Mute inputs faded down.
+type AFVRule struct {
+	Input      string   `json:"input"`       // input reference. number, key or title.
+	Unmute     []string `json:"unmute"`      // inputs to unmute.
+	Mute       []string `json:"mute"`        // inputs to mute.
+	MuteOthers bool     `json:"mute_others"` // mute every audio input not in Unmute.
+	FadeMs     int      `json:"fade_ms"`     // fade duration. 0 switches immediately.
+}
+
+// AFV applies audio-follow-video rules of primary vMix host on program changes, reported by TALLY or ACTS.
+type AFV struct {
+	mu      sync.Mutex
+	program int // last program input number.
+}
+
+// NewAFV creates AFV.
+func NewAFV() *AFV {
+	return &AFV{}
+}
+
+// programFromTally returns program input number of TALLY body. e.g. "0120" -> 2 .
+func programFromTally(tally string) int {
+	if i := strings.IndexByte(tally, '1'); i >= 0 {
+		return i + 1
+	}
+	return 0
+}
+
+// OnProgram applies rule of program input when program changed.
+func (a *AFV) OnProgram(program int) {
+	a.mu.Lock()
+	changed := program != a.program
+	a.program = program
+	a.mu.Unlock()
+	if !changed || program == 0 {
+		return
+	}
+	rules := config.Get().AFVRules[*vmixaddr]
+	if len(rules) == 0 {
+		return
+	}
+	s, err := hostState(*vmixaddr)
+	if err != nil {
+		return
+	}
+	for _, rule := range rules {
+		if in := s.FindInput(rule.Input); in != nil && in.Number == program {
+			if err := ApplyAFVRule(*vmixaddr, s, rule); err != nil {
+				log.Printf("Failed to apply audio follow video rule for input %s : %v\n", rule.Input, err)
+			}
+			return
+		}
+	}
+}
+
+// AFVActions returns functions applying rule: unmute first, then mute.
+func AFVActions(s *State, rule AFVRule) []FunctionCall {
+	unmuted := make(map[string]bool)
+	calls := []FunctionCall{}
+	for _, ref := range rule.Unmute {
+		in := s.FindInput(ref)
+		if in == nil {
+			continue
+		}
+		unmuted[in.Key] = true
+		calls = append(calls, FunctionCall{Function: "AudioOn", Params: map[string]string{"Input": in.Key}})
+		if rule.FadeMs > 0 {
+			calls = append(calls, FunctionCall{Function: "SetVolumeFade", Params: map[string]string{"Input": in.Key, "Value": "100," + strconv.Itoa(rule.FadeMs)}})
+		}
+	}
+	mute := []*StateInput{}
+	if rule.MuteOthers {
+		for i := range s.Inputs {
+			if !unmuted[s.Inputs[i].Key] {
+				mute = append(mute, &s.Inputs[i])
+			}
+		}
+	} else {
+		for _, ref := range rule.Mute {
+			if in := s.FindInput(ref); in != nil && !unmuted[in.Key] {
+				mute = append(mute, in)
+			}
+		}
+	}
+	for _, in := range mute {
+		if rule.FadeMs > 0 {
+			calls = append(calls, FunctionCall{Function: "SetVolumeFade", Params: map[string]string{"Input": in.Key, "Value": "0," + strconv.Itoa(rule.FadeMs)}})
+		} else {
+			calls = append(calls, FunctionCall{Function: "AudioOff", Params: map[string]string{"Input": in.Key}})
+		}
+	}
+	return calls
+}
+
+// ApplyAFVRule sends functions of rule to host.
+func ApplyAFVRule(host string, s *State, rule AFVRule) error {
+	for _, call := range AFVActions(s, rule) {
+		if err := SendFunctionTo(host, call.Function, call.Params); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// GetAFVRulesHandler returns audio-follow-video rules for [GET] /api/afv?host=... .
+func GetAFVRulesHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	rules := config.Get().AFVRules[host]
+	if rules == nil {
+		rules = []AFVRule{}
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":  host,
+		"rules": rules,
+	})
+}
+
+// PutAFVRulesHandler saves audio-follow-video rules for [PUT] /api/afv?host=... .
+func PutAFVRulesHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	rules := []AFVRule{}
+	if err := c.ShouldBindJSON(&rules); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	for _, rule := range rules {
+		if rule.Input == "" || rule.FadeMs < 0 {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("invalid rule %+v", rule)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.AFVRules[host] = rules
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetAFVRulesHandler(c)
+}
@@ -0,0 +1,22 @@
+package main
+
+import "testing"
+
+func TestAFVActions(t *testing.T) {
+	s := DefaultMockState()
+	calls := AFVActions(s, AFVRule{Input: "2", Unmute: []string{"2"}, MuteOthers: true, FadeMs: 500})
+	if len(calls) != 2+len(s.Inputs)-1 {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	if calls[0].Function != "AudioOn" || calls[1].Params["Value"] != "100,500" || calls[2].Params["Value"] != "0,500" {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+
+	calls = AFVActions(s, AFVRule{Input: "2", Unmute: []string{"2"}, Mute: []string{"3", "2"}})
+	if len(calls) != 2 || calls[1].Function != "AudioOff" || calls[1].Params["Input"] != s.Inputs[2].Key {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	if n := programFromTally("0210"); n != 3 {
+		t.Fatalf("expected program 3, got %d", n)
+	}
+}
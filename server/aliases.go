@@ -0,0 +1,78 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// InputAliases returns logical input aliases of host. e.g. "CAM 1" -> input key .
+func InputAliases(host string) map[string]string {
+	aliases := config.Get().InputAliases[host]
+	if aliases == nil {
+		aliases = map[string]string{}
+	}
+	return aliases
+}
+
+// ResolveInputAlias returns input reference aliased by ref on host, or ref itself if it is not alias.
+func ResolveInputAlias(host, ref string) string {
+	if input, ok := InputAliases(host)[strings.TrimSpace(ref)]; ok {
+		return input
+	}
+	return ref
+}
+
+// resolveAliasParams returns params with aliased Input replaced by input reference of host.
+// params is copied only when it is changed.
+func resolveAliasParams(host string, params map[string]string) map[string]string {
+	ref, ok := params["Input"]
+	if !ok {
+		return params
+	}
+	input := ResolveInputAlias(host, ref)
+	if input == ref {
+		return params
+	}
+	resolved := make(map[string]string, len(params))
+	for k, v := range params {
+		resolved[k] = v
+	}
+	resolved["Input"] = input
+	return resolved
+}
+
+// GetInputAliasesHandler returns input aliases of host for [GET] /api/aliases?host=... .
+func GetInputAliasesHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"aliases": InputAliases(host),
+	})
+}
+
+// PutInputAliasesHandler saves input aliases of host for [PUT] /api/aliases?host=... .
+// Body maps alias to input number, key or title of host. e.g. {"CAM 1":"3","GFX":"Lower Third"} .
+func PutInputAliasesHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	aliases := map[string]string{}
+	if err := c.ShouldBindJSON(&aliases); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	for alias, input := range aliases {
+		if strings.TrimSpace(alias) != alias || alias == "" || input == "" {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("invalid alias %q -> %q", alias, input)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.InputAliases[host] = aliases
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetInputAliasesHandler(c)
+}
@@ -0,0 +1,29 @@
+package main
+
+import "testing"
+
+func TestResolveAliasParams(t *testing.T) {
+	if err := config.Update(func(cfg *Config) {
+		cfg.InputAliases["http://studio-a:8088"] = map[string]string{"CAM 1": "3"}
+		cfg.InputAliases["http://studio-b:8088"] = map[string]string{"CAM 1": "7"}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) {
+		delete(cfg.InputAliases, "http://studio-a:8088")
+		delete(cfg.InputAliases, "http://studio-b:8088")
+	})
+
+	params := map[string]string{"Input": "CAM 1", "Duration": "500"}
+	a := resolveAliasParams("http://studio-a:8088", params)
+	b := resolveAliasParams("http://studio-b:8088", params)
+	if a["Input"] != "3" || b["Input"] != "7" || a["Duration"] != "500" {
+		t.Fatalf("unexpected params %v %v", a, b)
+	}
+	if params["Input"] != "CAM 1" {
+		t.Fatal("params must not be modified")
+	}
+	if p := resolveAliasParams("http://studio-a:8088", map[string]string{"Input": "5"}); p["Input"] != "5" {
+		t.Fatalf("unexpected params %v", p)
+	}
+}
@@ -0,0 +1,56 @@
+package main
+
+import (
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// AppSnapshot is whole utility state in single payload, so new windows and external clients hydrate in one round-trip.
+// Events with generation greater than Generation happened after snapshot.
+type AppSnapshot struct {
+	Generation  uint64            `json:"generation"`
+	Connections []Connection      `json:"connections"`
+	Statuses    []*HostStatus     `json:"statuses"`
+	States      map[string]*State `json:"states"` // connection name -> last polled state. Offline hosts are omitted.
+	Tally       map[string]string `json:"tally"`  // connection name -> tally. e.g. "0120" .
+	Shortcuts   []Shortcut        `json:"shortcuts"`
+	Rehearsal   bool              `json:"rehearsal"`
+	Background  BackgroundStatus  `json:"background"`
+	Operators   []Operator        `json:"operators"`
+	Config      Config            `json:"config"` // secrets are masked.
+}
+
+// BuildAppSnapshot collects snapshot from polled states and settings. vMix hosts are not requested.
+// Generation is read first, so events published while collecting are delivered again rather than lost.
+func BuildAppSnapshot() AppSnapshot {
+	cfg := config.Get()
+	snap := AppSnapshot{
+		Generation:  events.Generation(),
+		Connections: Connections(),
+		Statuses:    poller.Statuses(),
+		States:      make(map[string]*State),
+		Tally:       make(map[string]string),
+		Shortcuts:   cfg.Shortcuts,
+		Rehearsal:   Rehearsal(),
+		Background:  Background(),
+		Operators:   operators.Active(time.Now()),
+		Config:      redactConfig(cfg),
+	}
+	if snap.Shortcuts == nil {
+		snap.Shortcuts = []Shortcut{}
+	}
+	for _, status := range snap.Statuses {
+		if status.Online && status.State != nil {
+			snap.States[status.Name] = status.State
+			snap.Tally[status.Name] = status.State.Tally()
+		}
+	}
+	return snap
+}
+
+// GetAppSnapshotHandler returns whole utility state for [GET] /api/snapshot .
+func GetAppSnapshotHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, BuildAppSnapshot())
+}
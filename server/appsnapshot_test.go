@@ -0,0 +1,45 @@
+package main
+
+import "testing"
+
+func TestBuildAppSnapshot(t *testing.T) {
+	p := NewPoller()
+	p.statuses[*vmixaddr] = &HostStatus{Name: "primary", Addr: *vmixaddr, Online: true, State: DefaultMockState()}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	gen := events.Generation()
+	events.Publish(Event{Topic: "test"})
+	snap := BuildAppSnapshot()
+	if snap.Generation != gen+1 {
+		t.Fatalf("expected generation %d, got %d", gen+1, snap.Generation)
+	}
+	if snap.States["primary"] == nil || snap.Tally["primary"] != DefaultMockState().Tally() {
+		t.Fatalf("unexpected snapshot %+v", snap)
+	}
+}
+
+func TestAppSnapshotRedactsSecrets(t *testing.T) {
+	config.Update(func(cfg *Config) {
+		cfg.MQTT.Password = "broker-secret"
+		cfg.Chat.Token = "oauth:secret"
+		cfg.StreamHealth["http://redact:8088"] = StreamHealthSettings{Platform: PlatformCheck{APIKey: "youtube-key"}}
+	})
+	defer config.Update(func(cfg *Config) {
+		cfg.MQTT.Password = ""
+		cfg.Chat.Token = ""
+		delete(cfg.StreamHealth, "http://redact:8088")
+	})
+
+	cfg := BuildAppSnapshot().Config
+	if cfg.MQTT.Password != secretMask || cfg.Chat.Token != secretMask || cfg.Chat.APIKey != "" {
+		t.Fatalf("expected secrets masked, got %+v %+v", cfg.MQTT, cfg.Chat)
+	}
+	if key := cfg.StreamHealth["http://redact:8088"].Platform.APIKey; key != secretMask {
+		t.Fatalf("expected platform key masked, got %q", key)
+	}
+	if config.Get().MQTT.Password != "broker-secret" {
+		t.Fatal("expected stored password untouched")
+	}
+}
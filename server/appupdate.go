@@ -0,0 +1,247 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+	"unicode"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Utility release channels.
+const (
+	UpdateChannelStable = "stable" // full releases only. Default, for venues.
+	UpdateChannelBeta   = "beta"   // prereleases too.
+)
+
+// appUpdateCacheTTL is how long fetched releases are reused, staying well within GitHub API rate limit.
+const appUpdateCacheTTL = 10 * time.Minute
+
+// updateChannels are known release channels.
+var updateChannels = []string{UpdateChannelStable, UpdateChannelBeta}
+
+// updateEndpoint is release API listing recent releases newest first. Channels are not separate endpoints:
+// both read this list, and stable skips prereleases of it, so changelog of either channel covers several releases.
+// GitHub /releases/latest is not used for stable, since it returns single release without older changelogs.
+var updateEndpoint = "https://api.github.com/repos/FlowingSPDG/vmix-utility/releases?per_page=20"
+
+// knownUpdateChannel reports whether channel is one of updateChannels.
+func knownUpdateChannel(channel string) bool {
+	for _, c := range updateChannels {
+		if c == channel {
+			return true
+		}
+	}
+	return false
+}
+
+// AppRelease is release of utility.
+type AppRelease struct {
+	Tag         string    `json:"tag_name"` // e.g. "v0.6.0" or "v0.6.0-beta.1" .
+	Name        string    `json:"name"`
+	Prerelease  bool      `json:"prerelease"`
+	Draft       bool      `json:"draft"`
+	PublishedAt time.Time `json:"published_at"`
+	Body        string    `json:"body"` // changelog in Markdown.
+	URL         string    `json:"html_url"`
+}
+
+// splitReleaseTag splits tag into dotted numeric version and prerelease suffix. e.g. "v0.6.0-beta.1" is "0.6.0" and "beta.1" .
+func splitReleaseTag(tag string) (string, string) {
+	tag = strings.TrimPrefix(strings.TrimSpace(tag), "v")
+	if i := strings.IndexByte(tag, '-'); i >= 0 {
+		return tag[:i], tag[i+1:]
+	}
+	return tag, ""
+}
+
+// NewerRelease reports whether release tag is newer than current version. Development builds are never outdated.
+func NewerRelease(current, tag string) bool {
+	if current == "" || current == "dev" {
+		return false
+	}
+	cv, cpre := splitReleaseTag(current)
+	tv, tpre := splitReleaseTag(tag)
+	if c := CompareVersions(cv, tv); c != 0 {
+		return c < 0
+	}
+	switch {
+	case cpre == tpre:
+		return false
+	case cpre == "":
+		return false // release is prerelease of version already installed.
+	case tpre == "":
+		return true // full release of installed prerelease.
+	}
+	digits := func(s string) string {
+		return strings.TrimLeftFunc(s, func(r rune) bool { return !unicode.IsDigit(r) })
+	}
+	return CompareVersions(digits(cpre), digits(tpre)) < 0
+}
+
+// AppUpdates fetches releases of utility and filters them per channel.
+type AppUpdates struct {
+	mu      sync.Mutex // held while fetching, so concurrent requests on cold cache share single fetch.
+	client  *http.Client
+	fetched time.Time
+	cache   []AppRelease // every release of updateEndpoint, newest first. nil until fetched.
+}
+
+// NewAppUpdates creates AppUpdates.
+func NewAppUpdates() *AppUpdates {
+	return &AppUpdates{
+		client: &http.Client{Timeout: 10 * time.Second},
+	}
+}
+
+// Releases returns releases of channel, newest first. Drafts are skipped, and prereleases unless channel is beta.
+func (u *AppUpdates) Releases(channel string, now time.Time) ([]AppRelease, error) {
+	if !knownUpdateChannel(channel) {
+		return nil, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown update channel %q", channel))
+	}
+	all, err := u.fetch(now)
+	if err != nil {
+		return nil, err
+	}
+	releases := []AppRelease{}
+	for _, r := range all {
+		if r.Draft || (r.Prerelease && channel != UpdateChannelBeta) {
+			continue
+		}
+		releases = append(releases, r)
+	}
+	return releases, nil
+}
+
+// fetch returns every release, fetched again once cache is older than appUpdateCacheTTL.
+func (u *AppUpdates) fetch(now time.Time) ([]AppRelease, error) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	if u.cache != nil && now.Sub(u.fetched) < appUpdateCacheTTL {
+		return u.cache, nil
+	}
+
+	req, err := http.NewRequest(http.MethodGet, updateEndpoint, nil)
+	if err != nil {
+		return nil, NewAppError(ErrKindInternal, "", err)
+	}
+	req.Header.Set("Accept", "application/vnd.github+json")
+	resp, err := u.client.Do(req)
+	if err != nil {
+		return nil, NewAppError(ErrKindUnreachable, updateEndpoint, err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, NewAppError(ErrKindUnreachable, updateEndpoint, fmt.Errorf("unexpected status %s", resp.Status))
+	}
+	b, err := ioutil.ReadAll(resp.Body)
+	if err != nil {
+		return nil, NewAppError(ErrKindUnreachable, updateEndpoint, err)
+	}
+	all := []AppRelease{}
+	if err := json.Unmarshal(b, &all); err != nil {
+		return nil, NewAppError(ErrKindUnreachable, updateEndpoint, err)
+	}
+	u.cache = all
+	u.fetched = now
+	return all, nil
+}
+
+// updateChannel returns configured channel, stable if unset.
+func updateChannel() string {
+	if channel := config.Get().UpdateChannel; channel != "" {
+		return channel
+	}
+	return UpdateChannelStable
+}
+
+// GetUpdateChannelHandler returns release channel for [GET] /api/update/channel .
+func GetUpdateChannelHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"channel":  updateChannel(),
+		"channels": updateChannels,
+		"version":  appVersion,
+	})
+}
+
+// SetUpdateChannelRequest is request body of SetUpdateChannelHandler.
+type SetUpdateChannelRequest struct {
+	Channel string `json:"channel"` // UpdateChannelStable or UpdateChannelBeta.
+}
+
+// SetUpdateChannelHandler sets release channel for [PUT] /api/update/channel .
+func SetUpdateChannelHandler(c *gin.Context) {
+	req := SetUpdateChannelRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if !knownUpdateChannel(req.Channel) {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown update channel %q", req.Channel)))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.UpdateChannel = req.Channel
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetUpdateChannelHandler(c)
+}
+
+// GetAppUpdateHandler returns latest release of channel and whether it is newer than running version for [GET] /api/update .
+func GetAppUpdateHandler(c *gin.Context) {
+	channel := updateChannel()
+	releases, err := appUpdates.Releases(channel, time.Now())
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	var latest *AppRelease
+	if len(releases) > 0 {
+		latest = &releases[0]
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"channel":   channel,
+		"version":   appVersion,
+		"latest":    latest,
+		"available": latest != nil && NewerRelease(appVersion, latest.Tag),
+	})
+}
+
+// GetChangelogHandler returns release notes of channel for [GET] /api/update/changelog?limit=10&newer=true .
+// newer limits releases to ones newer than running version, such as to show what update brings.
+func GetChangelogHandler(c *gin.Context) {
+	channel := updateChannel()
+	limit, err := strconv.Atoi(c.DefaultQuery("limit", "10"))
+	if err != nil || limit <= 0 {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid limit %q", c.Query("limit"))))
+		return
+	}
+	releases, err := appUpdates.Releases(channel, time.Now())
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	changelog := []AppRelease{}
+	for _, r := range releases {
+		if len(changelog) >= limit {
+			break
+		}
+		if c.Query("newer") == "true" && !NewerRelease(appVersion, r.Tag) {
+			continue
+		}
+		changelog = append(changelog, r)
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"channel":   channel,
+		"version":   appVersion,
+		"changelog": changelog,
+	})
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"sync"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+func TestNewerRelease(t *testing.T) {
+	for _, c := range []struct {
+		current, tag string
+		newer        bool
+	}{
+		{"v0.5.0", "v0.6.0", true},
+		{"v0.6.0", "v0.5.9", false},
+		{"v0.6.0", "v0.6.0", false},
+		{"v0.6.0", "v0.6.0-beta.1", false},
+		{"v0.6.0-beta.1", "v0.6.0", true},
+		{"v0.6.0-beta.9", "v0.6.0-beta.10", true},
+		{"dev", "v9.0.0", false},
+	} {
+		if got := NewerRelease(c.current, c.tag); got != c.newer {
+			t.Errorf("NewerRelease(%q, %q) = %v", c.current, c.tag, got)
+		}
+	}
+}
+
+func TestAppUpdatesChannels(t *testing.T) {
+	requests := 0
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		requests++
+		w.Write([]byte(`[
+			{"tag_name":"v0.7.0-beta.1","prerelease":true,"body":"beta"},
+			{"tag_name":"v0.6.1-draft","draft":true},
+			{"tag_name":"v0.6.0","body":"stable"}
+		]`))
+	}))
+	defer srv.Close()
+	prev := updateEndpoint
+	updateEndpoint = srv.URL
+	defer func() { updateEndpoint = prev }()
+
+	u := NewAppUpdates()
+	now := time.Now()
+	stable, err := u.Releases(UpdateChannelStable, now)
+	if err != nil || len(stable) != 1 || stable[0].Tag != "v0.6.0" {
+		t.Fatalf("expected stable release only, got %+v %v", stable, err)
+	}
+	beta, err := u.Releases(UpdateChannelBeta, now)
+	if err != nil || len(beta) != 2 || beta[0].Tag != "v0.7.0-beta.1" {
+		t.Fatalf("expected prerelease on beta, got %+v %v", beta, err)
+	}
+	if _, err := u.Releases(UpdateChannelStable, now.Add(time.Minute)); err != nil || requests != 1 {
+		t.Fatalf("expected cached releases, got %d requests %v", requests, err)
+	}
+	if _, err := u.Releases("nightly", now); err == nil {
+		t.Fatal("expected unknown channel refused")
+	}
+}
+
+func TestAppUpdatesColdFetchSerialized(t *testing.T) {
+	requests := int32(0)
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		atomic.AddInt32(&requests, 1)
+		time.Sleep(50 * time.Millisecond)
+		w.Write([]byte(`[{"tag_name":"v0.6.0"}]`))
+	}))
+	defer srv.Close()
+	prev := updateEndpoint
+	updateEndpoint = srv.URL
+	defer func() { updateEndpoint = prev }()
+
+	u := NewAppUpdates()
+	now := time.Now()
+	wg := &sync.WaitGroup{}
+	for i := 0; i < 5; i++ {
+		wg.Add(1)
+		go func(channel string) {
+			defer wg.Done()
+			if releases, err := u.Releases(channel, now); err != nil || len(releases) != 1 {
+				t.Errorf("expected release, got %+v %v", releases, err)
+			}
+		}(updateChannels[i%len(updateChannels)])
+	}
+	wg.Wait()
+	if n := atomic.LoadInt32(&requests); n != 1 {
+		t.Fatalf("expected concurrent requests on cold cache to share single fetch, got %d fetches", n)
+	}
+}
@@ -0,0 +1,208 @@
+package main
+
+import (
+	"bytes"
+	"encoding/binary"
+	"fmt"
+	"log"
+	"net"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Art-Net protocol constants.
+const (
+	artNetDefaultListen = ":6454"
+	artNetOpDMX         = 0x5000
+	artNetHeaderLength  = 18
+)
+
+var artNetID = []byte("Art-Net\x00")
+
+// ArtNetSettings is Art-Net trigger configuration.
+type ArtNetSettings struct {
+	Enabled  bool            `json:"enabled"`
+	Listen   string          `json:"listen"`   // UDP listen address. Default ":6454".
+	Universe int             `json:"universe"` // 15 bit port address. Net<<8 | SubNet<<4 | Universe.
+	Mappings []ArtNetMapping `json:"mappings"`
+}
+
+// ArtNetMapping fires action when DMX channel rises above threshold. e.g. channel 10 > 127 ⇒ OverlayInput1In .
+type ArtNetMapping struct {
+	Channel   int            `json:"channel"`   // 1-512.
+	Threshold int            `json:"threshold"` // fires when value goes from <= threshold to > threshold.
+	Action    TriggerAction  `json:"action"`
+	Release   *TriggerAction `json:"release,omitempty"` // optional action when value falls back to <= threshold.
+}
+
+// ArtDMX is decoded ArtDMX packet.
+type ArtDMX struct {
+	Sequence byte
+	Universe int
+	Data     []byte
+}
+
+// ParseArtDMX decodes ArtDMX packet. ok is false for other Art-Net packets.
+func ParseArtDMX(b []byte) (ArtDMX, bool, error) {
+	if len(b) < 10 || !bytes.Equal(b[:8], artNetID) {
+		return ArtDMX{}, false, fmt.Errorf("not an Art-Net packet")
+	}
+	if binary.LittleEndian.Uint16(b[8:10]) != artNetOpDMX {
+		return ArtDMX{}, false, nil
+	}
+	if len(b) < artNetHeaderLength {
+		return ArtDMX{}, false, fmt.Errorf("ArtDMX packet too short")
+	}
+	n := int(binary.BigEndian.Uint16(b[16:18]))
+	if n > 512 || len(b) < artNetHeaderLength+n {
+		return ArtDMX{}, false, fmt.Errorf("invalid ArtDMX length %d", n)
+	}
+	return ArtDMX{
+		Sequence: b[12],
+		Universe: int(b[15]&0x7F)<<8 | int(b[14]),
+		Data:     b[artNetHeaderLength : artNetHeaderLength+n],
+	}, true, nil
+}
+
+// ArtNetTrigger listens Art-Net and fires mapped actions on channel threshold crossings.
+type ArtNetTrigger struct {
+	mu       sync.Mutex
+	settings ArtNetSettings
+	conn     net.PacketConn
+	levels   [512]byte // last received levels of configured universe.
+	lastSeen time.Time
+	lastErr  string
+}
+
+// NewArtNetTrigger creates ArtNetTrigger.
+func NewArtNetTrigger() *ArtNetTrigger {
+	return &ArtNetTrigger{}
+}
+
+// Settings returns current settings.
+func (t *ArtNetTrigger) Settings() ArtNetSettings {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.settings
+}
+
+// Status returns settings, last packet time and last error.
+func (t *ArtNetTrigger) Status() gin.H {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return gin.H{
+		"settings":  t.settings,
+		"listening": t.conn != nil,
+		"last_seen": t.lastSeen,
+		"error":     t.lastErr,
+	}
+}
+
+// Configure replaces settings and restarts listener.
+func (t *ArtNetTrigger) Configure(settings ArtNetSettings) error {
+	if settings.Listen == "" {
+		settings.Listen = artNetDefaultListen
+	}
+	if settings.Universe < 0 || settings.Universe > 0x7FFF {
+		return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid universe %d", settings.Universe))
+	}
+	for _, m := range settings.Mappings {
+		if m.Channel < 1 || m.Channel > 512 || m.Threshold < 0 || m.Threshold > 255 {
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid mapping channel %d threshold %d", m.Channel, m.Threshold))
+		}
+	}
+	t.mu.Lock()
+	if t.conn != nil {
+		t.conn.Close()
+		t.conn = nil
+	}
+	t.settings = settings
+	t.levels = [512]byte{}
+	t.lastErr = ""
+	if !settings.Enabled {
+		t.mu.Unlock()
+		return nil
+	}
+	conn, err := net.ListenPacket("udp", settings.Listen)
+	if err != nil {
+		t.lastErr = err.Error()
+		t.mu.Unlock()
+		return NewAppError(ErrKindInternal, "", err)
+	}
+	t.conn = conn
+	t.mu.Unlock()
+	log.Printf("Art-Net trigger listening on %s universe %d\n", settings.Listen, settings.Universe)
+	go t.serve(conn)
+	return nil
+}
+
+func (t *ArtNetTrigger) serve(conn net.PacketConn) {
+	buf := make([]byte, 1024)
+	for {
+		n, _, err := conn.ReadFrom(buf)
+		if err != nil {
+			return // closed by Configure.
+		}
+		dmx, ok, err := ParseArtDMX(buf[:n])
+		if err != nil || !ok {
+			continue
+		}
+		t.handle(dmx, time.Now())
+	}
+}
+
+// handle updates levels and fires mappings crossing threshold.
+func (t *ArtNetTrigger) handle(dmx ArtDMX, now time.Time) {
+	t.mu.Lock()
+	if dmx.Universe != t.settings.Universe {
+		t.mu.Unlock()
+		return
+	}
+	var fire []TriggerAction
+	for _, m := range t.settings.Mappings {
+		if m.Channel > len(dmx.Data) {
+			continue
+		}
+		prev, cur := int(t.levels[m.Channel-1]), int(dmx.Data[m.Channel-1])
+		switch {
+		case prev <= m.Threshold && cur > m.Threshold:
+			fire = append(fire, m.Action)
+		case prev > m.Threshold && cur <= m.Threshold && m.Release != nil:
+			fire = append(fire, *m.Release)
+		}
+	}
+	copy(t.levels[:], dmx.Data)
+	t.lastSeen = now
+	t.mu.Unlock()
+	for _, a := range fire {
+		go a.Fire(fmt.Sprintf("artnet universe %d", dmx.Universe))
+	}
+}
+
+// GetArtNetTriggerHandler returns Art-Net trigger settings for [GET] /api/triggers/artnet .
+func GetArtNetTriggerHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, artNetTrigger.Status())
+}
+
+// PutArtNetTriggerHandler saves Art-Net trigger settings for [PUT] /api/triggers/artnet .
+func PutArtNetTriggerHandler(c *gin.Context) {
+	settings := ArtNetSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := artNetTrigger.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.ArtNetTrigger = artNetTrigger.Settings()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetArtNetTriggerHandler(c)
+}
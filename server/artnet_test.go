@@ -0,0 +1,57 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func artDMXPacket(universe int, data []byte) []byte {
+	b := append([]byte("Art-Net\x00"), 0x00, 0x50, 0, 14, 1, 0, byte(universe), byte(universe>>8), byte(len(data)>>8), byte(len(data)))
+	return append(b, data...)
+}
+
+func TestParseArtDMX(t *testing.T) {
+	dmx, ok, err := ParseArtDMX(artDMXPacket(0x102, []byte{0, 200}))
+	if err != nil || !ok {
+		t.Fatalf("failed to parse: %v", err)
+	}
+	if dmx.Universe != 0x102 || len(dmx.Data) != 2 || dmx.Data[1] != 200 {
+		t.Fatalf("unexpected packet %+v", dmx)
+	}
+	if _, _, err := ParseArtDMX([]byte("not art-net")); err == nil {
+		t.Fatal("expected error")
+	}
+}
+
+func TestArtNetThreshold(t *testing.T) {
+	SetRehearsal(true)
+	defer SetRehearsal(false)
+	ch, unsubscribe := events.Subscribe(8)
+	defer unsubscribe()
+
+	a := NewArtNetTrigger()
+	a.settings = ArtNetSettings{Universe: 1, Mappings: []ArtNetMapping{{Channel: 2, Threshold: 127, Action: TriggerAction{Host: "http://a:8088", FunctionCall: FunctionCall{Function: "OverlayInput1In"}}}}}
+	a.handle(ArtDMX{Universe: 1, Data: []byte{0, 100}}, time.Now())
+	a.handle(ArtDMX{Universe: 1, Data: []byte{0, 200}}, time.Now())
+	a.handle(ArtDMX{Universe: 1, Data: []byte{0, 255}}, time.Now())
+
+	timeout := time.After(time.Second)
+	fired := 0
+	for fired == 0 {
+		select {
+		case ev := <-ch:
+			if ev.Topic == "trigger" {
+				fired++
+			}
+		case <-timeout:
+			t.Fatal("trigger not fired")
+		}
+	}
+	select {
+	case ev := <-ch:
+		if ev.Topic == "trigger" {
+			t.Fatal("trigger fired twice")
+		}
+	case <-time.After(100 * time.Millisecond):
+	}
+}
@@ -0,0 +1,177 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/gin-gonic/gin"
+)
+
+// AssetInfo is hashed media file.
+type AssetInfo struct {
+	Size   int64  `json:"size"`
+	SHA256 string `json:"sha256"`
+}
+
+// AssetMismatch is file existing on both machines with different content.
+type AssetMismatch struct {
+	Path    string    `json:"path"`
+	Primary AssetInfo `json:"primary"`
+	Backup  AssetInfo `json:"backup"`
+}
+
+// AssetReport is result of comparing media folders of primary and backup machines.
+type AssetReport struct {
+	PrimaryFolder    string          `json:"primary_folder"`
+	BackupFolder     string          `json:"backup_folder"`
+	Matched          int             `json:"matched"`
+	Mismatched       []AssetMismatch `json:"mismatched"`
+	MissingOnBackup  []string        `json:"missing_on_backup"`
+	MissingOnPrimary []string        `json:"missing_on_primary"`
+}
+
+// HashFolder hashes every file under root. Keys are slash separated relative paths.
+func HashFolder(root string) (map[string]AssetInfo, error) {
+	assets := make(map[string]AssetInfo)
+	err := filepath.Walk(root, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if info.IsDir() {
+			return nil
+		}
+		rel, err := filepath.Rel(root, path)
+		if err != nil {
+			return err
+		}
+		sum, err := hashFile(path)
+		if err != nil {
+			return err
+		}
+		assets[filepath.ToSlash(rel)] = AssetInfo{Size: info.Size(), SHA256: sum}
+		return nil
+	})
+	return assets, err
+}
+
+func hashFile(path string) (string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+	h := sha256.New()
+	if _, err := io.Copy(h, f); err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(h.Sum(nil)), nil
+}
+
+// CompareAssets hashes both folders and reports differences.
+func CompareAssets(primaryFolder, backupFolder string) (*AssetReport, error) {
+	primary, err := HashFolder(primaryFolder)
+	if err != nil {
+		return nil, fmt.Errorf("failed to hash primary folder : %w", err)
+	}
+	backup, err := HashFolder(backupFolder)
+	if err != nil {
+		return nil, fmt.Errorf("failed to hash backup folder : %w", err)
+	}
+	report := &AssetReport{
+		PrimaryFolder:    primaryFolder,
+		BackupFolder:     backupFolder,
+		Mismatched:       []AssetMismatch{},
+		MissingOnBackup:  []string{},
+		MissingOnPrimary: []string{},
+	}
+	for path, p := range primary {
+		b, ok := backup[path]
+		switch {
+		case !ok:
+			report.MissingOnBackup = append(report.MissingOnBackup, path)
+		case p != b:
+			report.Mismatched = append(report.Mismatched, AssetMismatch{Path: path, Primary: p, Backup: b})
+		default:
+			report.Matched++
+		}
+	}
+	for path := range backup {
+		if _, ok := primary[path]; !ok {
+			report.MissingOnPrimary = append(report.MissingOnPrimary, path)
+		}
+	}
+	sort.Strings(report.MissingOnBackup)
+	sort.Strings(report.MissingOnPrimary)
+	sort.Slice(report.Mismatched, func(i, j int) bool { return report.Mismatched[i].Path < report.Mismatched[j].Path })
+	return report, nil
+}
+
+// GetMediaFoldersHandler returns media folder per vMix host for [GET] /api/assets/folders .
+func GetMediaFoldersHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"folders": config.Get().MediaFolders,
+	})
+}
+
+// PutMediaFoldersHandler saves media folder per vMix host for [PUT] /api/assets/folders .
+func PutMediaFoldersHandler(c *gin.Context) {
+	folders := map[string]string{}
+	if err := c.ShouldBindJSON(&folders); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.MediaFolders = folders
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"folders": folders,
+	})
+}
+
+// VerifyAssetsRequest Request JSON for VerifyAssetsHandler
+type VerifyAssetsRequest struct {
+	Primary string `json:"primary"` // primary vMix host, key of media folders.
+	Backup  string `json:"backup"`  // backup vMix host, key of media folders.
+}
+
+// VerifyAssetsHandler compares media folders of primary and backup hosts for [POST] /api/assets/verify .
+func VerifyAssetsHandler(c *gin.Context) {
+	req := VerifyAssetsRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	folders := config.Get().MediaFolders
+	primary, ok := folders[req.Primary]
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, req.Primary, fmt.Errorf("media folder not configured")))
+		return
+	}
+	backup, ok := folders[req.Backup]
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, req.Backup, fmt.Errorf("media folder not configured")))
+		return
+	}
+	report, err := CompareAssets(primary, backup)
+	if errors.Is(err, os.ErrNotExist) {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", err))
+		return
+	}
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"report": report,
+	})
+}
@@ -0,0 +1,95 @@
+package main
+
+import (
+	"encoding/json"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+// writeAssets writes files under root. Keys are slash separated relative paths.
+func writeAssets(t *testing.T, root string, files map[string]string) {
+	t.Helper()
+	for name, content := range files {
+		path := filepath.Join(root, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := ioutil.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+func TestVerifyAssetsHandler(t *testing.T) {
+	primary, backup := t.TempDir(), t.TempDir()
+	writeAssets(t, primary, map[string]string{"opening.mp4": "video", "logos/logo.png": "v1", "only-primary.wav": "audio"})
+	writeAssets(t, backup, map[string]string{"opening.mp4": "video", "logos/logo.png": "v2", "only-backup.wav": "audio"})
+	missing := filepath.Join(t.TempDir(), "missing")
+	prev := config.Get().MediaFolders
+	config.Update(func(cfg *Config) {
+		cfg.MediaFolders = map[string]string{"http://main:8088": primary, "http://backup:8088": backup, "http://missing:8088": missing}
+	})
+	defer config.Update(func(cfg *Config) { cfg.MediaFolders = prev })
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.POST("/api/assets/verify", VerifyAssetsHandler)
+	verify := func(body string) *httptest.ResponseRecorder {
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodPost, "/api/assets/verify", strings.NewReader(body)))
+		return w
+	}
+
+	w := verify(`{"primary":"http://main:8088","backup":"http://backup:8088"}`)
+	res := struct {
+		Report AssetReport `json:"report"`
+	}{}
+	if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || w.Code != http.StatusOK {
+		t.Fatalf("expected report, got %d %s", w.Code, w.Body)
+	}
+	report := res.Report
+	if report.Matched != 1 || len(report.Mismatched) != 1 || report.Mismatched[0].Path != "logos/logo.png" {
+		t.Fatalf("expected changed file reported by slash separated path, got %+v", report)
+	}
+	if len(report.MissingOnBackup) != 1 || report.MissingOnBackup[0] != "only-primary.wav" ||
+		len(report.MissingOnPrimary) != 1 || report.MissingOnPrimary[0] != "only-backup.wav" {
+		t.Fatalf("expected missing files on each side, got %+v", report)
+	}
+
+	for _, c := range []struct {
+		name string
+		body string
+		code int
+	}{
+		// folders are resolved only from settings, so request can not point at other paths.
+		{"path as host", `{"primary":"` + filepath.ToSlash(primary) + `","backup":"http://backup:8088"}`, http.StatusNotFound},
+		{"traversal as host", `{"primary":"http://main:8088","backup":"../../etc"}`, http.StatusNotFound},
+		{"missing folder", `{"primary":"http://main:8088","backup":"http://missing:8088"}`, http.StatusNotFound},
+		{"invalid body", `{"primary":`, http.StatusBadRequest},
+	} {
+		if w := verify(c.body); w.Code != c.code {
+			t.Errorf("%s : expected %d, got %d %s", c.name, c.code, w.Code, w.Body)
+		}
+	}
+}
+
+func TestPutMediaFoldersHandler(t *testing.T) {
+	prev := config.Get().MediaFolders
+	defer config.Update(func(cfg *Config) { cfg.MediaFolders = prev })
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/assets/folders", PutMediaFoldersHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/assets/folders", strings.NewReader(`{"http://main:8088":"D:\\Media"}`)))
+	if w.Code != http.StatusOK || config.Get().MediaFolders["http://main:8088"] != `D:\Media` {
+		t.Fatalf("expected media folders saved, got %d %s", w.Code, w.Body)
+	}
+}
@@ -0,0 +1,192 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// auditionBuses are vMix audio buses that can be soloed besides master.
+const auditionBuses = "ABCDEFG"
+
+// AuditionInput is input soloed from utility, heard on operator headphones.
+type AuditionInput struct {
+	Key    string    `json:"key"`
+	Number int       `json:"number"`
+	Title  string    `json:"title"`
+	Since  time.Time `json:"since"`
+}
+
+// Audition solos inputs and buses to vMix headphones output, so operator can check audio before taking input.
+// Soloed input is un-soloed automatically when it goes to program, since it is then heard on program anyway.
+type Audition struct {
+	mu     sync.Mutex
+	inputs map[string]map[string]AuditionInput // vMix host -> input key -> soloed input.
+}
+
+// NewAudition creates Audition.
+func NewAudition() *Audition {
+	return &Audition{inputs: make(map[string]map[string]AuditionInput)}
+}
+
+// Soloed returns soloed inputs per host.
+func (a *Audition) Soloed() map[string][]AuditionInput {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	soloed := make(map[string][]AuditionInput, len(a.inputs))
+	for host, inputs := range a.inputs {
+		for _, in := range inputs {
+			soloed[host] = append(soloed[host], in)
+		}
+	}
+	return soloed
+}
+
+// Solo solos input. Exclusive un-solos inputs soloed before, so only this input is heard.
+func (a *Audition) Solo(host, ref string, exclusive bool) (AuditionInput, error) {
+	s, err := hostState(host)
+	if err != nil {
+		return AuditionInput{}, err
+	}
+	in := s.FindInput(ref)
+	if in == nil {
+		return AuditionInput{}, NewAppError(ErrKindNotFound, host, fmt.Errorf("input %q not found", ref))
+	}
+	if exclusive {
+		for _, other := range a.Soloed()[host] {
+			if other.Key != in.Key {
+				if err := a.Unsolo(host, other.Key); err != nil {
+					return AuditionInput{}, err
+				}
+			}
+		}
+	}
+	if err := SendFunctionTo(host, "SoloOn", map[string]string{"Input": in.Key}); err != nil {
+		return AuditionInput{}, err
+	}
+	soloed := AuditionInput{Key: in.Key, Number: in.Number, Title: in.Title, Since: time.Now()}
+	a.mu.Lock()
+	if a.inputs[host] == nil {
+		a.inputs[host] = make(map[string]AuditionInput)
+	}
+	a.inputs[host][in.Key] = soloed
+	a.mu.Unlock()
+	a.publish(host)
+	return soloed, nil
+}
+
+// Unsolo un-solos input by key.
+func (a *Audition) Unsolo(host, key string) error {
+	if err := SendFunctionTo(host, "SoloOff", map[string]string{"Input": key}); err != nil {
+		return err
+	}
+	a.mu.Lock()
+	delete(a.inputs[host], key)
+	if len(a.inputs[host]) == 0 {
+		delete(a.inputs, host)
+	}
+	a.mu.Unlock()
+	a.publish(host)
+	return nil
+}
+
+// SoloBus solos or un-solos audio bus A-G to headphones.
+func (a *Audition) SoloBus(host, bus string, on bool) error {
+	bus = strings.ToUpper(bus)
+	if len(bus) != 1 || !strings.Contains(auditionBuses, bus) {
+		return NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("unknown bus %q. A-G are available", bus))
+	}
+	function := "BusXSoloOff"
+	if on {
+		function = "BusXSoloOn"
+	}
+	return SendFunctionTo(host, function, map[string]string{"Value": bus})
+}
+
+func (a *Audition) publish(host string) {
+	events.Publish(Event{Topic: "audition", Host: host, Data: a.Soloed()[host]})
+}
+
+// tick un-solos inputs that went to program.
+func (a *Audition) tick() {
+	for host, inputs := range a.Soloed() {
+		status, ok := poller.Status(host)
+		if !ok || status.State == nil {
+			continue
+		}
+		for _, in := range inputs {
+			if in.Number != status.State.Active {
+				continue
+			}
+			if err := a.Unsolo(host, in.Key); err != nil {
+				log.Printf("Failed to un-solo input %s on program : %v\n", in.Title, err)
+			}
+		}
+	}
+}
+
+// Run checks soloed inputs every tick.
+func (a *Audition) Run(tick time.Duration) {
+	t := time.NewTicker(tick)
+	defer t.Stop()
+	for range t.C {
+		a.tick()
+	}
+}
+
+// GetAuditionHandler returns soloed inputs for [GET] /api/audition .
+func GetAuditionHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"soloed": audition.Soloed(),
+	})
+}
+
+// AuditionRequest is request body of AuditionHandler.
+type AuditionRequest struct {
+	Host      string `json:"host"`      // vMix host. Primary vMix host is used if empty.
+	Input     string `json:"input"`     // input to solo. Either Input or Bus is required.
+	Bus       string `json:"bus"`       // audio bus A-G to solo.
+	Off       bool   `json:"off"`       // un-solo instead.
+	Exclusive bool   `json:"exclusive"` // un-solo other inputs soloed from utility.
+}
+
+// AuditionHandler solos or un-solos input or bus to headphones for [POST] /api/audition .
+func AuditionHandler(c *gin.Context) {
+	req := AuditionRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if req.Host == "" {
+		req.Host = *vmixaddr
+	}
+	var err error
+	switch {
+	case req.Bus != "":
+		err = audition.SoloBus(req.Host, req.Bus, !req.Off)
+	case req.Input == "":
+		err = NewAppError(ErrKindInvalidRequest, req.Host, fmt.Errorf("input or bus is required"))
+	case req.Off:
+		var s *State
+		if s, err = hostState(req.Host); err == nil {
+			if in := s.FindInput(req.Input); in != nil {
+				err = audition.Unsolo(req.Host, in.Key)
+			} else {
+				err = NewAppError(ErrKindNotFound, req.Host, fmt.Errorf("input %q not found", req.Input))
+			}
+		}
+	default:
+		_, err = audition.Solo(req.Host, req.Input, req.Exclusive)
+	}
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	poller.Touch()
+	GetAuditionHandler(c)
+}
@@ -0,0 +1,41 @@
+package main
+
+import "testing"
+
+func TestAuditionUnsoloOnProgram(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	s := DefaultMockState()
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "mock", Addr: addr, Online: true, State: s}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	a := NewAudition()
+	if _, err := a.Solo(addr, "3", false); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := a.Solo(addr, "4", true); err != nil {
+		t.Fatal(err)
+	}
+	if soloed := a.Soloed()[addr]; len(soloed) != 1 || soloed[0].Number != 4 {
+		t.Fatalf("expected only input 4 soloed, got %+v", soloed)
+	}
+
+	s.Active = 4
+	a.tick()
+	if soloed := a.Soloed()[addr]; len(soloed) != 0 {
+		t.Fatalf("expected input on program to be un-soloed, got %+v", soloed)
+	}
+	want := []string{"SoloOn", "SoloOff", "SoloOn", "SoloOff"}
+	calls := m.Calls()
+	if len(calls) != len(want) {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	for i, fn := range want {
+		if calls[i].Function != fn {
+			t.Fatalf("call %d : expected %s, got %+v", i, fn, calls[i])
+		}
+	}
+}
@@ -0,0 +1,172 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// AutoPilotSettings is configuration of automatic camera rotation.
+type AutoPilotSettings struct {
+	Host       string `json:"host"`        // vMix host. Primary vMix host is used if empty.
+	Group      string `json:"group"`       // input group to rotate.
+	IntervalMs int64  `json:"interval_ms"` // time on program per input.
+	Transition string `json:"transition"`  // "Cut" or "Fade".
+	DurationMs int    `json:"duration_ms"` // fade duration.
+}
+
+// AutoPilotStatus is auto-pilot state published as "autopilot" event.
+type AutoPilotStatus struct {
+	AutoPilotSettings
+	Enabled     bool      `json:"enabled"`
+	Paused      bool      `json:"paused"` // paused by manual cut.
+	PauseReason string    `json:"pause_reason,omitempty"`
+	LastSwitch  time.Time `json:"last_switch"`
+	Program     int       `json:"program"` // input number auto-pilot put on program.
+}
+
+// AutoPilot cycles program through input group. It pauses when program is changed by someone else.
+type AutoPilot struct {
+	mu     sync.Mutex
+	status AutoPilotStatus
+}
+
+// NewAutoPilot creates disabled AutoPilot.
+func NewAutoPilot() *AutoPilot {
+	return &AutoPilot{status: AutoPilotStatus{AutoPilotSettings: AutoPilotSettings{IntervalMs: 10000, Transition: "Cut"}}}
+}
+
+// Status returns current status.
+func (a *AutoPilot) Status() AutoPilotStatus {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	return a.status
+}
+
+// Configure applies settings and enables or disables auto-pilot. Enabling resumes paused auto-pilot.
+func (a *AutoPilot) Configure(settings AutoPilotSettings, enabled bool) error {
+	if settings.Host == "" {
+		settings.Host = *vmixaddr
+	}
+	if settings.Transition != "Cut" && settings.Transition != "Fade" {
+		return NewAppError(ErrKindInvalidRequest, settings.Host, fmt.Errorf("unknown transition %q", settings.Transition))
+	}
+	if settings.IntervalMs < 1000 {
+		return NewAppError(ErrKindInvalidRequest, settings.Host, fmt.Errorf("interval must be at least 1000ms"))
+	}
+	if enabled {
+		if _, ok := InputGroups(settings.Host)[settings.Group]; !ok {
+			return NewAppError(ErrKindNotFound, settings.Host, fmt.Errorf("input group %q not found", settings.Group))
+		}
+	}
+	a.mu.Lock()
+	a.status.AutoPilotSettings = settings
+	a.status.Enabled = enabled
+	a.status.Paused = false
+	a.status.PauseReason = ""
+	a.status.Program = 0
+	a.status.LastSwitch = time.Time{}
+	s := a.status
+	a.mu.Unlock()
+	events.Publish(Event{Topic: "autopilot", Host: settings.Host, Data: s})
+	return nil
+}
+
+// nextAfter returns input following program in group, or first input if program is not in group.
+func nextAfter(inputs []*StateInput, program int) *StateInput {
+	for i, in := range inputs {
+		if in.Number == program {
+			return inputs[(i+1)%len(inputs)]
+		}
+	}
+	return inputs[0]
+}
+
+// tick pauses on manual cut, and switches to next input when interval passed.
+func (a *AutoPilot) tick(now time.Time) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	st := &a.status
+	if !st.Enabled || st.Paused || !BackgroundActive() || !pairing.IsActive() {
+		return
+	}
+	status, ok := poller.Status(st.Host)
+	if !ok || status.State == nil {
+		return
+	}
+	s := status.State
+	// Polled state reflects our own switch only after transition finished.
+	settled := status.LastPoll.After(st.LastSwitch.Add(time.Duration(st.DurationMs) * time.Millisecond))
+	if st.Program != 0 && !settled {
+		return
+	}
+	if st.Program != 0 && s.Active != st.Program {
+		st.Paused = true
+		st.PauseReason = fmt.Sprintf("manual cut to input %d detected", s.Active)
+		log.Printf("Auto-pilot paused : %s\n", st.PauseReason)
+		events.Publish(Event{Topic: "autopilot", Host: st.Host, Data: *st})
+		return
+	}
+	if now.Sub(st.LastSwitch) < time.Duration(st.IntervalMs)*time.Millisecond {
+		return
+	}
+	inputs := resolveGroup(s, InputGroups(st.Host)[st.Group])
+	if len(inputs) == 0 {
+		return
+	}
+	next := nextAfter(inputs, s.Active)
+	params := map[string]string{"Input": next.Key}
+	if st.Transition == "Fade" && st.DurationMs > 0 {
+		params["Duration"] = strconv.Itoa(st.DurationMs)
+	}
+	if err := SendFunctionTo(st.Host, st.Transition, params); err != nil {
+		log.Printf("Auto-pilot failed to switch to input %d : %v\n", next.Number, err)
+		return
+	}
+	st.Program = next.Number
+	st.LastSwitch = now
+	poller.TouchHost(st.Host)
+	events.Publish(Event{Topic: "autopilot", Host: st.Host, Data: *st})
+}
+
+// Run checks auto-pilot every tick.
+func (a *AutoPilot) Run(tick time.Duration) {
+	t := time.NewTicker(tick)
+	defer t.Stop()
+	for now := range t.C {
+		tasks.Beat("autopilot", tick)
+		a.tick(now)
+	}
+}
+
+// GetAutoPilotHandler returns auto-pilot status for [GET] /api/autopilot .
+func GetAutoPilotHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"autopilot": autoPilot.Status(),
+	})
+}
+
+// PutAutoPilotRequest is request body of PutAutoPilotHandler.
+type PutAutoPilotRequest struct {
+	AutoPilotSettings
+	Enabled bool `json:"enabled"`
+}
+
+// PutAutoPilotHandler configures, starts, resumes or stops auto-pilot for [PUT] /api/autopilot .
+func PutAutoPilotHandler(c *gin.Context) {
+	req := PutAutoPilotRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := autoPilot.Configure(req.AutoPilotSettings, req.Enabled); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetAutoPilotHandler(c)
+}
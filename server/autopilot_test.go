@@ -0,0 +1,105 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestAutoPilotRotatesAndPausesOnManualCut(t *testing.T) {
+	m := startMock(t)
+	host := "http://" + m.HTTPAddr
+	config.Update(func(cfg *Config) {
+		cfg.InputGroups[host] = map[string][]string{"cams": {"2", "3"}}
+	})
+	defer config.Update(func(cfg *Config) { delete(cfg.InputGroups, host) })
+
+	now := time.Now()
+	state := DefaultMockState()
+	p := NewPoller()
+	p.statuses[host] = &HostStatus{Name: "autopilot", Addr: host, Online: true, State: state, LastPoll: now.Add(-time.Second)}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	a := NewAutoPilot()
+	if err := a.Configure(AutoPilotSettings{Host: host, Group: "cams", IntervalMs: 1000, Transition: "Cut"}, true); err != nil {
+		t.Fatal(err)
+	}
+	// poll reports program at given time, as if vMix was polled.
+	poll := func(active int, at time.Time) {
+		state.Active = active
+		p.statuses[host].LastPoll = at
+	}
+	cuts := func() int {
+		return len(m.Calls())
+	}
+
+	a.tick(now)
+	calls := m.Calls()
+	if len(calls) != 1 || calls[0].Function != "Cut" || calls[0].Params["Input"] != state.Inputs[2].Key || a.Status().Program != 3 {
+		t.Fatalf("expected cut to input following program in group, got %+v %+v", calls, a.Status())
+	}
+
+	// poll before cut finished still shows previous program, which is not manual cut.
+	a.tick(now.Add(500 * time.Millisecond))
+	if a.Status().Paused || cuts() != 1 {
+		t.Fatalf("expected auto-pilot to wait for poll after its own cut, got %+v", a.Status())
+	}
+
+	poll(3, now.Add(600*time.Millisecond))
+	a.tick(now.Add(700 * time.Millisecond))
+	if cuts() != 1 {
+		t.Fatal("expected no cut before interval passed")
+	}
+	a.tick(now.Add(1100 * time.Millisecond))
+	if cuts() != 2 || a.Status().Program != 2 {
+		t.Fatalf("expected rotation to wrap to first input of group, got %+v", a.Status())
+	}
+
+	poll(6, now.Add(1300*time.Millisecond))
+	a.tick(now.Add(1400 * time.Millisecond))
+	if st := a.Status(); !st.Paused || st.PauseReason == "" {
+		t.Fatalf("expected manual cut to pause auto-pilot, got %+v", st)
+	}
+	a.tick(now.Add(5 * time.Second))
+	if cuts() != 2 {
+		t.Fatal("expected paused auto-pilot not to cut")
+	}
+
+	if err := a.Configure(a.Status().AutoPilotSettings, true); err != nil || a.Status().Paused {
+		t.Fatalf("expected enabling again to resume, got %+v %v", a.Status(), err)
+	}
+	a.tick(now.Add(6 * time.Second))
+	if calls := m.Calls(); len(calls) != 3 || calls[2].Params["Input"] != state.Inputs[1].Key {
+		t.Fatalf("expected resumed auto-pilot to cut to first input of group after program outside group, got %+v", calls)
+	}
+}
+
+func TestAutoPilotConfigure(t *testing.T) {
+	a := NewAutoPilot()
+	for _, c := range []struct {
+		name     string
+		settings AutoPilotSettings
+		kind     ErrorKind
+	}{
+		{"unknown transition", AutoPilotSettings{Group: "cams", IntervalMs: 1000, Transition: "Wipe"}, ErrKindInvalidRequest},
+		{"short interval", AutoPilotSettings{Group: "cams", IntervalMs: 500, Transition: "Cut"}, ErrKindInvalidRequest},
+		{"unknown group", AutoPilotSettings{Group: "autopilot_missing", IntervalMs: 1000, Transition: "Fade"}, ErrKindNotFound},
+	} {
+		if err := a.Configure(c.settings, true); AsAppError(err).Kind != c.kind {
+			t.Errorf("%s : expected %s, got %v", c.name, c.kind, err)
+		}
+	}
+	if a.Status().Enabled {
+		t.Fatal("expected refused settings not to enable auto-pilot")
+	}
+}
+
+func TestNextAfter(t *testing.T) {
+	inputs := []*StateInput{{Number: 2}, {Number: 3}, {Number: 5}}
+	for program, want := range map[int]int{2: 3, 3: 5, 5: 2, 7: 2} {
+		if got := nextAfter(inputs, program).Number; got != want {
+			t.Errorf("nextAfter program %d = %d, expected %d", program, got, want)
+		}
+	}
+}
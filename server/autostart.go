@@ -0,0 +1,77 @@
+package main
+
+import (
+	"net/http"
+	"os"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// autoStartName is name of login item registered for utility.
+const autoStartName = "vmix-utility"
+
+// autoStartCommand returns command line launching utility with current flags.
+// Headless adds -headless, so utility starts without opening browser on permanently installed machines.
+func autoStartCommand(headless bool) (string, error) {
+	exe, err := os.Executable()
+	if err != nil {
+		return "", err
+	}
+	args := []string{`"` + exe + `"`}
+	for _, arg := range os.Args[1:] {
+		if arg == "-headless" || arg == "--headless" || strings.HasPrefix(arg, "-headless=") {
+			continue
+		}
+		args = append(args, `"`+arg+`"`)
+	}
+	if headless {
+		args = append(args, "-headless")
+	}
+	return strings.Join(args, " "), nil
+}
+
+// GetAutoStartHandler returns whether utility starts at login for [GET] /api/autostart .
+func GetAutoStartHandler(c *gin.Context) {
+	command, err := autoStartEnabled()
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"enabled":  command != "",
+		"headless": strings.Contains(command, "-headless"),
+		"command":  command,
+	})
+}
+
+// PutAutoStartHandler registers or removes login item for [PUT] /api/autostart .
+// Current flags are registered, so utility starts with same vMix hosts and settings file.
+func PutAutoStartHandler(c *gin.Context) {
+	req := struct {
+		Enabled  bool `json:"enabled"`
+		Headless bool `json:"headless"` // start without opening browser.
+	}{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if !req.Enabled {
+		if err := removeAutoStart(); err != nil {
+			AbortWithAppError(c, err)
+			return
+		}
+		GetAutoStartHandler(c)
+		return
+	}
+	command, err := autoStartCommand(req.Headless)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	if err := setAutoStart(command); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetAutoStartHandler(c)
+}
@@ -0,0 +1,18 @@
+// +build !windows
+
+package main
+
+import "fmt"
+
+// autoStartEnabled is available only on Windows. Use systemd or launchd to start utility as service instead.
+func autoStartEnabled() (string, error) {
+	return "", nil
+}
+
+func setAutoStart(command string) error {
+	return NewAppError(ErrKindUnsupported, "", fmt.Errorf("start at login is supported only on Windows"))
+}
+
+func removeAutoStart() error {
+	return nil
+}
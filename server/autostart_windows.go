@@ -0,0 +1,48 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"fmt"
+	"os/exec"
+	"strings"
+)
+
+// autoStartKey is registry key of login items of current user.
+const autoStartKey = `HKCU\Software\Microsoft\Windows\CurrentVersion\Run`
+
+// autoStartEnabled returns registered command line, or empty if not registered.
+func autoStartEnabled() (string, error) {
+	out, err := exec.Command("reg", "query", autoStartKey, "/v", autoStartName).Output()
+	if err != nil {
+		// reg exits with 1 when value does not exist.
+		return "", nil
+	}
+	sc := bufio.NewScanner(bytes.NewReader(out))
+	for sc.Scan() {
+		line := strings.TrimSpace(sc.Text())
+		if i := strings.Index(line, "REG_SZ"); i >= 0 && strings.HasPrefix(line, autoStartName) {
+			return strings.TrimSpace(line[i+len("REG_SZ"):]), nil
+		}
+	}
+	return "", nil
+}
+
+// setAutoStart registers command line to run at login.
+func setAutoStart(command string) error {
+	if out, err := exec.Command("reg", "add", autoStartKey, "/v", autoStartName, "/t", "REG_SZ", "/d", command, "/f").CombinedOutput(); err != nil {
+		return NewAppError(ErrKindInternal, "", fmt.Errorf("reg add : %v : %s", err, bytes.TrimSpace(out)))
+	}
+	return nil
+}
+
+// removeAutoStart removes login item. Removing missing item is not an error.
+func removeAutoStart() error {
+	if command, err := autoStartEnabled(); err != nil || command == "" {
+		return err
+	}
+	if out, err := exec.Command("reg", "delete", autoStartKey, "/v", autoStartName, "/f").CombinedOutput(); err != nil {
+		return NewAppError(ErrKindInternal, "", fmt.Errorf("reg delete : %v : %s", err, bytes.TrimSpace(out)))
+	}
+	return nil
+}
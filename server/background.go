@@ -0,0 +1,75 @@
+package main
+
+import (
+	"log"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// BackgroundStatus is state of background activity: polling, watchdog, stream health probes, auto-pilot, pairing heartbeat and settings sync.
+type BackgroundStatus struct {
+	Enabled bool      `json:"enabled"`
+	Since   time.Time `json:"since"` // last pause or resume.
+}
+
+var (
+	backgroundMu     sync.RWMutex
+	backgroundStatus = BackgroundStatus{Enabled: true}
+)
+
+// BackgroundActive reports whether background activity is running. Loops skip their work while paused.
+func BackgroundActive() bool {
+	backgroundMu.RLock()
+	defer backgroundMu.RUnlock()
+	return backgroundStatus.Enabled
+}
+
+// Background returns state of background activity.
+func Background() BackgroundStatus {
+	backgroundMu.RLock()
+	defer backgroundMu.RUnlock()
+	return backgroundStatus
+}
+
+// SetBackgroundActivity pauses or resumes background activity, so network and vMix hosts stay quiet. Settings are kept as is.
+// Functions sent by operator are not affected.
+func SetBackgroundActivity(enabled bool) {
+	backgroundMu.Lock()
+	changed := backgroundStatus.Enabled != enabled
+	if changed {
+		backgroundStatus = BackgroundStatus{Enabled: enabled, Since: time.Now()}
+	}
+	st := backgroundStatus
+	backgroundMu.Unlock()
+	if !changed {
+		return
+	}
+	log.Printf("Background activity : %v\n", enabled)
+	events.Publish(Event{Topic: "background", Data: st})
+	if enabled {
+		poller.Touch()
+	}
+}
+
+// GetBackgroundHandler returns background activity state for [GET] /api/background .
+func GetBackgroundHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"background": Background(),
+	})
+}
+
+// PutBackgroundHandler pauses or resumes background activity for [PUT] /api/background .
+func PutBackgroundHandler(c *gin.Context) {
+	req := struct {
+		Enabled bool `json:"enabled"`
+	}{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	SetBackgroundActivity(req.Enabled)
+	GetBackgroundHandler(c)
+}
@@ -0,0 +1,23 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestWatchdogIgnoresPausedTime(t *testing.T) {
+	addr := "http://192.0.2.2:8088"
+	status := &HostStatus{Name: "backup", Addr: addr, LastSeen: time.Now().Add(-time.Hour)}
+	SetBackgroundActivity(false)
+	if BackgroundActive() {
+		t.Fatal("expected background activity paused")
+	}
+	SetBackgroundActivity(true)
+	defer SetBackgroundActivity(true)
+
+	w := NewWatchdog()
+	w.check(status, WatchdogSettings{}, time.Now())
+	if h := w.Hosts()[addr]; h.Stage != WatchdogOK {
+		t.Fatalf("expected idle time counted from resume, got stage %q", h.Stage)
+	}
+}
@@ -0,0 +1,120 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"testing"
+)
+
+// benchInputs is number of inputs of benchmark fixtures, representative of large productions.
+const benchInputs = 250
+
+// benchStateXML generates vMix XML with n inputs mixing cameras, video lists with files, titles with text fields and layered inputs.
+func benchStateXML(n int) []byte {
+	buf := &bytes.Buffer{}
+	fmt.Fprint(buf, `<?xml version="1.0"?><vmix><version>27.0.0.49</version><edition>4K</edition><preset>C:\shows\bench.vmix</preset><inputs>`)
+	for i := 1; i <= n; i++ {
+		switch i % 4 {
+		case 0:
+			fmt.Fprintf(buf, `<input key="key-%d" number="%d" type="Capture" title="Camera %d" state="Running" position="0" duration="0" loop="False" muted="False" volume="100" balance="0" solo="False" audiobusses="M">Camera %d</input>`, i, i, i, i)
+		case 1:
+			fmt.Fprintf(buf, `<input key="key-%d" number="%d" type="VideoList" title="Clips %d" state="Paused" position="1200" duration="30000" loop="True">Clips %d<list>`, i, i, i, i)
+			for j := 0; j < 20; j++ {
+				selected := ""
+				if j == 3 {
+					selected = ` selected="true"`
+				}
+				fmt.Fprintf(buf, `<item%s>C:\clips\%d\clip_%02d.mp4</item>`, selected, i, j)
+			}
+			fmt.Fprint(buf, `</list></input>`)
+		case 2:
+			fmt.Fprintf(buf, `<input key="key-%d" number="%d" type="GT" title="Title %d" state="Paused" position="0" duration="0" loop="False">Title %d<text index="0" name="Headline.Text">Headline %d</text><text index="1" name="Description.Text">Description</text><color index="0" name="Rectangle1.Fill">#FF0000</color></input>`, i, i, i, i, i)
+		case 3:
+			fmt.Fprintf(buf, `<input key="key-%d" number="%d" type="Mix" title="PiP %d" state="Paused" position="0" duration="0" loop="False">PiP %d<overlay index="0" key="key-%d"><position panX="-0.5" panY="0.5" zoomX="0.5" zoomY="0.5"/></overlay><overlay index="1" key="key-%d"/></input>`, i, i, i, i, i-1, i-3)
+		}
+	}
+	fmt.Fprint(buf, `</inputs><overlays><overlay number="1">3</overlay><overlay number="2"/></overlays><preview>2</preview><active>4</active>`)
+	fmt.Fprint(buf, `<fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500"/></transitions><recording>True</recording><streaming>False</streaming><dynamic><input1></input1></dynamic></vmix>`)
+	return buf.Bytes()
+}
+
+func benchState(b *testing.B) *State {
+	s, err := ParseState(benchStateXML(benchInputs))
+	if err != nil {
+		b.Fatal(err)
+	}
+	return s
+}
+
+func BenchmarkDecodeState(b *testing.B) {
+	raw := benchStateXML(benchInputs)
+	b.SetBytes(int64(len(raw)))
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		if _, err := ParseState(raw); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+func BenchmarkVideoListSources(b *testing.B) {
+	s := benchState(b)
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		for j := range s.Inputs {
+			s.Inputs[j].Source = s.Inputs[j].listSource()
+		}
+	}
+}
+
+func BenchmarkInputCacheUpdate(b *testing.B) {
+	a, c := benchState(b), benchState(b)
+	c.Inputs[10].Position = 5000
+	cache := NewInputCache()
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		if i%2 == 0 {
+			cache.Update(a)
+		} else {
+			cache.Update(c)
+		}
+	}
+}
+
+func BenchmarkDiffStates(b *testing.B) {
+	a, c := benchState(b), benchState(b)
+	c.Inputs[10].Title = "Renamed"
+	c.Active = 8
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		DiffStates(a, c)
+	}
+}
+
+func BenchmarkEventJSON(b *testing.B) {
+	ev := Event{Topic: "snapshot", Host: "primary", Data: benchState(b), Gen: 1}
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		if _, err := json.Marshal(ev); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+func BenchmarkDiffJSON(b *testing.B) {
+	a, c := benchState(b), benchState(b)
+	c.Inputs[10].State = InputRunning
+	av, err := toJSONValue(a)
+	if err != nil {
+		b.Fatal(err)
+	}
+	cv, err := toJSONValue(c)
+	if err != nil {
+		b.Fatal(err)
+	}
+	b.ReportAllocs()
+	for i := 0; i < b.N; i++ {
+		DiffJSON(av, cv)
+	}
+}
@@ -0,0 +1,141 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"net/url"
+	"regexp"
+	"strconv"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// urlPlaceholder matches placeholder of browser URL template. e.g. "{clock}" or "{score.main.home_score}" .
+var urlPlaceholder = regexp.MustCompile(`\{([a-z0-9_.\-]+)\}`)
+
+// RenderURLTemplate replaces placeholders of URL template with current values, query escaped.
+// Placeholders are {clock} (show clock display), {score.<scoreboard>.<field>}, {dynamic.<slot>} (e.g. dynamic.value1) and {host}.
+func RenderURLTemplate(tmpl, host string) (string, error) {
+	var missing []string
+	out := urlPlaceholder.ReplaceAllStringFunc(tmpl, func(m string) string {
+		name := m[1 : len(m)-1]
+		v, ok := urlPlaceholderValue(name, host)
+		if !ok {
+			missing = append(missing, name)
+			return m
+		}
+		return url.QueryEscape(v)
+	})
+	if len(missing) > 0 {
+		return "", fmt.Errorf("unknown placeholder %s", strings.Join(missing, ", "))
+	}
+	if _, err := url.Parse(out); err != nil {
+		return "", err
+	}
+	return out, nil
+}
+
+func urlPlaceholderValue(name, host string) (string, bool) {
+	parts := strings.Split(name, ".")
+	switch {
+	case name == "clock":
+		return showClock.Status().Display, true
+	case name == "host":
+		return host, true
+	case parts[0] == "score" && len(parts) == 3:
+		sb, ok := scoreboards.Get(parts[1])
+		if !ok {
+			return "", false
+		}
+		v, ok := sb.Fields()[parts[2]]
+		return v, ok
+	case parts[0] == "dynamic" && len(parts) == 2:
+		s, err := hostState(host)
+		if err != nil {
+			return "", false
+		}
+		for _, kind := range []string{"input", "value"} {
+			if !strings.HasPrefix(parts[1], kind) {
+				continue
+			}
+			n, err := strconv.Atoi(strings.TrimPrefix(parts[1], kind))
+			if err != nil {
+				return "", false
+			}
+			if slot := s.Dynamic.Slot(kind, n); slot != nil {
+				return *slot, true
+			}
+		}
+	}
+	return "", false
+}
+
+// BrowserNavigateRequest is request body of BrowserNavigateHandler.
+type BrowserNavigateRequest struct {
+	Host  string `json:"host"`  // vMix host. Primary vMix host is used if host and group are empty.
+	Group string `json:"group"` // host group, to repoint same browser input on every machine at once.
+	Input string `json:"input"` // Web Browser input. number, key, title or alias.
+	URL   string `json:"url"`   // URL template. e.g. "http://scores.local/?home={score.main.home_score}" .
+}
+
+// BrowserNavigateResult is result of BrowserNavigate on single host.
+type BrowserNavigateResult struct {
+	Host  string    `json:"host"`
+	URL   string    `json:"url"`
+	Error *AppError `json:"error,omitempty"`
+}
+
+// BrowserNavigate renders URL per host, since placeholders such as dynamic slots differ between hosts, and navigates browser input.
+func BrowserNavigate(host, ref, tmpl string) (string, error) {
+	s, err := hostState(host)
+	if err != nil {
+		return "", err
+	}
+	in := s.FindInput(ResolveInputAlias(host, ref))
+	if in == nil {
+		return "", NewAppError(ErrKindNotFound, host, fmt.Errorf("input %q not found", ref))
+	}
+	if in.Type != "Browser" {
+		return "", NewAppError(ErrKindUnsupported, host, fmt.Errorf("input %q is %s, not Web Browser", in.Title, in.Type))
+	}
+	u, err := RenderURLTemplate(tmpl, host)
+	if err != nil {
+		return "", NewAppError(ErrKindInvalidRequest, host, err)
+	}
+	return u, SendFunctionTo(host, "BrowserNavigate", map[string]string{"Input": in.Key, "Value": u})
+}
+
+// BrowserNavigateHandler changes URL of Web Browser input on host or host group for [POST] /api/browser/navigate .
+// Hosts are processed independently, and result of each host is returned.
+func BrowserNavigateHandler(c *gin.Context) {
+	req := BrowserNavigateRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if req.Input == "" || req.URL == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, req.Host, fmt.Errorf("input and url are required")))
+		return
+	}
+	hosts, err := ResolveHosts(req.Host, req.Group)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	poller.Touch()
+	results := make([]BrowserNavigateResult, 0, len(hosts))
+	for _, host := range hosts {
+		u, err := BrowserNavigate(host, req.Input, req.URL)
+		r := BrowserNavigateResult{Host: host, URL: u}
+		if err != nil {
+			r.Error = AsAppError(err)
+		} else {
+			announceAction(c, host, "BrowserNavigate")
+		}
+		results = append(results, r)
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"results": results,
+	})
+}
@@ -0,0 +1,35 @@
+package main
+
+import "testing"
+
+func TestBrowserNavigate(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	s := DefaultMockState()
+	s.Inputs = append(s.Inputs, StateInput{Key: "b8b4e2ad-0007-4c1a-9a51-000000000007", Number: 7, Type: "Browser", Title: "Dashboard"})
+	s.Dynamic.Value1 = "Round 2"
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "mock", Addr: addr, Online: true, State: s}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	u, err := BrowserNavigate(addr, "Dashboard", "http://graphics.local/?round={dynamic.value1}")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if u != "http://graphics.local/?round=Round+2" {
+		t.Fatalf("unexpected url %s", u)
+	}
+	calls := m.Calls()
+	if len(calls) != 1 || calls[0].Function != "BrowserNavigate" || calls[0].Params["Value"] != u {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+
+	if _, err := BrowserNavigate(addr, "Camera 1", "http://graphics.local/"); err == nil {
+		t.Fatal("expected non browser input to be rejected")
+	}
+	if _, err := RenderURLTemplate("http://graphics.local/{dynamic.value5}", addr); err == nil {
+		t.Fatal("expected unknown placeholder to be rejected")
+	}
+}
@@ -0,0 +1,41 @@
+package main
+
+import (
+	"net/http"
+	"runtime"
+
+	"github.com/gin-gonic/gin"
+)
+
+// CacheStats is size of in-memory cache, to watch memory of long running deployments with many hosts.
+type CacheStats struct {
+	Name    string `json:"name"`
+	Entries int    `json:"entries"`
+	Limit   int    `json:"limit"`           // maximum evictable entries. 0 is not evicted.
+	Bytes   int64  `json:"bytes,omitempty"` // approximate size of raw data held, where known.
+	Evicted uint64 `json:"evicted"`
+}
+
+// CacheStatsAll returns size of every cache.
+func CacheStatsAll() []CacheStats {
+	return []CacheStats{
+		inputCache.Stats(),
+		{Name: "statuses", Entries: len(poller.Statuses())},
+		snapshots.Stats(),
+		rawXML.Stats(),
+		offline.Stats(),
+		history.Stats(),
+		logBuffer.Stats(),
+	}
+}
+
+// GetCacheStatsHandler returns size of caches and Go heap for [GET] /api/cache/stats .
+func GetCacheStatsHandler(c *gin.Context) {
+	mem := runtime.MemStats{}
+	runtime.ReadMemStats(&mem)
+	c.JSON(http.StatusOK, gin.H{
+		"caches":       CacheStatsAll(),
+		"heap_bytes":   mem.HeapAlloc,
+		"heap_objects": mem.HeapObjects,
+	})
+}
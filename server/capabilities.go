@@ -0,0 +1,145 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Features gated by vMix edition or version.
+const (
+	FeatureMultiView   = "multiview"   // multiview on external outputs.
+	FeatureReplay      = "replay"      // instant replay.
+	FeatureCall        = "call"        // vMix Call.
+	FeatureMultiCorder = "multicorder" // MultiCorder ISO recording.
+	FeatureTCPAPI      = "tcp_api"     // TCP API on port 8099.
+)
+
+// Capabilities is feature set of vMix host, derived from edition and version.
+type Capabilities struct {
+	Edition      string `json:"edition"`
+	Version      string `json:"version"`
+	MajorVersion int    `json:"major_version"`
+	MaxInputs    int    `json:"max_inputs"`
+	CallChannels int    `json:"call_channels"`
+	MultiView    bool   `json:"multiview"`
+	Replay       bool   `json:"replay"`
+	Call         bool   `json:"call"`
+	MultiCorder  bool   `json:"multicorder"`
+	TCPAPI       bool   `json:"tcp_api"`
+}
+
+// editionCapabilities is feature table by edition, following vMix edition comparison.
+// Trial has every Pro feature.
+var editionCapabilities = map[string]Capabilities{
+	"basic":    {MaxInputs: 4},
+	"basic hd": {MaxInputs: 4},
+	"sd":       {MaxInputs: 1000, MultiView: true, CallChannels: 1},
+	"hd":       {MaxInputs: 1000, MultiView: true, CallChannels: 1},
+	"4k":       {MaxInputs: 1000, MultiView: true, CallChannels: 2, Replay: true, MultiCorder: true},
+	"pro":      {MaxInputs: 1000, MultiView: true, CallChannels: 8, Replay: true, MultiCorder: true},
+	"max":      {MaxInputs: 1000, MultiView: true, CallChannels: 8, Replay: true, MultiCorder: true},
+	"trial":    {MaxInputs: 1000, MultiView: true, CallChannels: 8, Replay: true, MultiCorder: true},
+}
+
+// tcpAPIMinVersion is first vMix major version with TCP API.
+const tcpAPIMinVersion = 21
+
+// majorVersion returns major version of vMix version string. e.g. "24.0.0.72" -> 24 .
+func majorVersion(version string) int {
+	n, _ := strconv.Atoi(strings.SplitN(version, ".", 2)[0])
+	return n
+}
+
+// CapabilitiesFor returns capabilities of edition and version. Unknown edition has Basic capabilities.
+func CapabilitiesFor(edition, version string) Capabilities {
+	caps, ok := editionCapabilities[strings.ToLower(strings.TrimSpace(edition))]
+	if !ok {
+		caps = editionCapabilities["basic"]
+	}
+	caps.Edition = edition
+	caps.Version = version
+	caps.MajorVersion = majorVersion(version)
+	caps.Call = caps.CallChannels > 0
+	caps.TCPAPI = caps.MajorVersion >= tcpAPIMinVersion
+	return caps
+}
+
+// Supports reports whether feature is available.
+func (c Capabilities) Supports(feature string) bool {
+	switch feature {
+	case FeatureMultiView:
+		return c.MultiView
+	case FeatureReplay:
+		return c.Replay
+	case FeatureCall:
+		return c.Call
+	case FeatureMultiCorder:
+		return c.MultiCorder
+	case FeatureTCPAPI:
+		return c.TCPAPI
+	}
+	return true
+}
+
+// HostCapabilities returns capabilities of polled host.
+func HostCapabilities(addr string) (Capabilities, error) {
+	status, ok := poller.Status(addr)
+	if !ok || status.State == nil {
+		return Capabilities{}, NewAppError(ErrKindNotLoaded, addr, fmt.Errorf("vMix state not loaded"))
+	}
+	return CapabilitiesFor(status.Edition, status.Version), nil
+}
+
+// RequireFeature returns unsupported error if host is known not to support feature.
+// Hosts not polled yet are allowed, so vMix reports the error itself.
+func RequireFeature(addr, feature string) error {
+	caps, err := HostCapabilities(addr)
+	if err != nil {
+		return nil
+	}
+	if !caps.Supports(feature) {
+		return NewAppError(ErrKindUnsupported, addr, fmt.Errorf("%s is not supported on vMix %s %s", feature, caps.Edition, caps.Version))
+	}
+	return nil
+}
+
+// FunctionFeature returns feature required by vMix function, or empty string.
+func FunctionFeature(function string) string {
+	f := strings.ToLower(function)
+	switch {
+	case strings.HasPrefix(f, "replay"):
+		return FeatureReplay
+	case strings.Contains(f, "multicorder"):
+		return FeatureMultiCorder
+	case strings.HasPrefix(f, "videocall"):
+		return FeatureCall
+	}
+	return ""
+}
+
+// RequireFunction returns unsupported error if host edition cannot run function.
+func RequireFunction(addr, function string) error {
+	if feature := FunctionFeature(function); feature != "" {
+		return RequireFeature(addr, feature)
+	}
+	return nil
+}
+
+// GetCapabilitiesHandler returns capabilities of host for [GET] /api/capabilities?host=... .
+// Primary vMix host is used if host is empty.
+func GetCapabilitiesHandler(c *gin.Context) {
+	addr := c.DefaultQuery("host", *vmixaddr)
+	caps, err := HostCapabilities(addr)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":         addr,
+		"capabilities": caps,
+	})
+}
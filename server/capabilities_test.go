@@ -0,0 +1,20 @@
+package main
+
+import "testing"
+
+func TestCapabilitiesFor(t *testing.T) {
+	basic := CapabilitiesFor("Basic HD", "24.0.0.72")
+	if basic.MaxInputs != 4 || basic.Replay || basic.Call || !basic.TCPAPI {
+		t.Fatalf("unexpected Basic HD capabilities: %+v", basic)
+	}
+	pro := CapabilitiesFor("Pro", "20.0.0.31")
+	if !pro.Supports(FeatureReplay) || !pro.Supports(FeatureCall) || pro.Supports(FeatureTCPAPI) {
+		t.Fatalf("unexpected Pro capabilities: %+v", pro)
+	}
+	if f := FunctionFeature("ReplayPlay"); f != FeatureReplay {
+		t.Fatalf("ReplayPlay requires %q", f)
+	}
+	if f := FunctionFeature("Cut"); f != "" {
+		t.Fatalf("Cut requires %q", f)
+	}
+}
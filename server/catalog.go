@@ -0,0 +1,158 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Input type groups used by function catalog. Names are "type" attribute of vMix XML.
+var (
+	anyInput      []string // nil matches every input type.
+	videoInputs   = []string{"Video", "VideoList", "Capture", "NDI", "Stream", "SRT", "Image", "Photos", "Colour", "Browser", "PowerPoint", "GT", "Xaml", "Mix", "VirtualSet", "Replay", "VideoCall", "VideoDelay"}
+	playerInputs  = []string{"Video", "VideoList", "AudioFile", "Photos", "PowerPoint"}
+	listInputs    = []string{"VideoList"}
+	pictureInputs = []string{"Photos", "PowerPoint"}
+	titleInputs   = []string{"GT", "Xaml"}
+	browserInputs = []string{"Browser"}
+	replayInputs  = []string{"Replay"}
+	callInputs    = []string{"VideoCall"}
+	layerInputs   = []string{"Mix", "VirtualSet", "GT", "Xaml", "Colour", "Blank"}
+	// vMix XML does not tell whether camera supports PTZ, so PTZ functions are offered for every camera source.
+	ptzInputs = []string{"Capture", "NDI"}
+)
+
+// CatalogFunction is vMix function applicable to inputs.
+type CatalogFunction struct {
+	Name       string   `json:"name"`
+	Category   string   `json:"category"`              // e.g. "playback" .
+	Value      string   `json:"value,omitempty"`       // description of Value parameter, empty if not used.
+	InputTypes []string `json:"input_types,omitempty"` // applicable input types. Empty for every input.
+}
+
+// functionCatalog is catalog of vMix functions taking Input parameter.
+var functionCatalog = []CatalogFunction{
+	{"PreviewInput", "switching", "", anyInput},
+	{"ActiveInput", "switching", "", anyInput},
+	{"Cut", "switching", "", anyInput},
+	{"Fade", "switching", "", anyInput},
+	{"Merge", "switching", "", anyInput},
+	{"OverlayInput1", "overlay", "", anyInput},
+	{"OverlayInput2", "overlay", "", anyInput},
+	{"OverlayInput3", "overlay", "", anyInput},
+	{"OverlayInput4", "overlay", "", anyInput},
+	{"SetInputName", "input", "name", anyInput},
+	{"CloseInput", "input", "", anyInput},
+	{"AudioOn", "audio", "", anyInput},
+	{"AudioOff", "audio", "", anyInput},
+	{"SetVolume", "audio", "volume 0-100", anyInput},
+	{"SetVolumeFade", "audio", "volume,milliseconds", anyInput},
+	{"SetBalance", "audio", "-1 to 1", anyInput},
+	{"SoloOn", "audio", "", anyInput},
+	{"SoloOff", "audio", "", anyInput},
+	{"SetZoom", "position", "0-5", videoInputs},
+	{"SetPanX", "position", "-2 to 2", videoInputs},
+	{"SetPanY", "position", "-2 to 2", videoInputs},
+	{"SetCrop", "position", "X1,Y1,X2,Y2", videoInputs},
+	{"SetMultiViewOverlay", "layers", "layer,input", layerInputs},
+	{"MultiViewOverlayOn", "layers", "layer 1-10", layerInputs},
+	{"MultiViewOverlayOff", "layers", "layer 1-10", layerInputs},
+	{"Play", "playback", "", playerInputs},
+	{"Pause", "playback", "", playerInputs},
+	{"PlayPause", "playback", "", playerInputs},
+	{"Restart", "playback", "", playerInputs},
+	{"LoopOn", "playback", "", playerInputs},
+	{"LoopOff", "playback", "", playerInputs},
+	{"SetPosition", "playback", "milliseconds", playerInputs},
+	{"NextItem", "list", "", listInputs},
+	{"PreviousItem", "list", "", listInputs},
+	{"SelectIndex", "list", "index starting at 1", listInputs},
+	{"ListShuffle", "list", "", listInputs},
+	{"ListAdd", "list", "file path", listInputs},
+	{"ListRemoveAll", "list", "", listInputs},
+	{"NextPicture", "pictures", "", pictureInputs},
+	{"PreviousPicture", "pictures", "", pictureInputs},
+	{"SetText", "title", "text", titleInputs},
+	{"SetTextColour", "title", "colour", titleInputs},
+	{"SetImage", "title", "file path or URL", titleInputs},
+	{"SetCountdown", "title", "hh:mm:ss", titleInputs},
+	{"StartCountdown", "title", "", titleInputs},
+	{"StopCountdown", "title", "", titleInputs},
+	{"TitleBeginAnimation", "title", "animation page", titleInputs},
+	{"BrowserNavigate", "browser", "URL", browserInputs},
+	{"BrowserReload", "browser", "", browserInputs},
+	{"BrowserBack", "browser", "", browserInputs},
+	{"BrowserForward", "browser", "", browserInputs},
+	{"PTZHome", "ptz", "", ptzInputs},
+	{"PTZMoveUp", "ptz", "speed 0-1", ptzInputs},
+	{"PTZMoveDown", "ptz", "speed 0-1", ptzInputs},
+	{"PTZMoveLeft", "ptz", "speed 0-1", ptzInputs},
+	{"PTZMoveRight", "ptz", "speed 0-1", ptzInputs},
+	{"PTZMoveStop", "ptz", "", ptzInputs},
+	{"PTZZoomIn", "ptz", "speed 0-1", ptzInputs},
+	{"PTZZoomOut", "ptz", "speed 0-1", ptzInputs},
+	{"PTZZoomStop", "ptz", "", ptzInputs},
+	{"PTZFocusAuto", "ptz", "", ptzInputs},
+	{"ReplayPlay", "replay", "", replayInputs},
+	{"ReplayPause", "replay", "", replayInputs},
+	{"ReplayMarkIn", "replay", "", replayInputs},
+	{"ReplayMarkOut", "replay", "", replayInputs},
+	{"VideoCallAudioSource", "call", "Master, Headphones or bus A-G", callInputs},
+	{"VideoCallVideoSource", "call", "Output1-4", callInputs},
+}
+
+// appliesTo reports whether function is applicable to input type.
+func (f CatalogFunction) appliesTo(inputType string) bool {
+	if len(f.InputTypes) == 0 {
+		return true
+	}
+	for _, t := range f.InputTypes {
+		if t == inputType {
+			return true
+		}
+	}
+	return false
+}
+
+// ApplicableFunctions returns catalog functions applicable to input and supported by capabilities.
+func ApplicableFunctions(in *StateInput, caps Capabilities) []CatalogFunction {
+	functions := []CatalogFunction{}
+	for _, f := range functionCatalog {
+		if !f.appliesTo(in.Type) {
+			continue
+		}
+		if feature := FunctionFeature(f.Name); feature != "" && !caps.Supports(feature) {
+			continue
+		}
+		functions = append(functions, f)
+	}
+	return functions
+}
+
+// GetApplicableFunctionsHandler returns functions applicable to input for [GET] /api/inputs/functions?host=...&input=... .
+// Intended for context menus of inputs.
+func GetApplicableFunctionsHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	s, err := hostState(host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	in := s.FindInput(ResolveInputAlias(host, c.Query("input")))
+	if in == nil {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, host, fmt.Errorf("input %q not found", c.Query("input"))))
+		return
+	}
+	caps, err := HostCapabilities(host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":      host,
+		"input":     in.Key,
+		"type":      in.Type,
+		"functions": ApplicableFunctions(in, caps),
+	})
+}
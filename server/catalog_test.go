@@ -0,0 +1,25 @@
+package main
+
+import "testing"
+
+func TestApplicableFunctions(t *testing.T) {
+	names := func(in StateInput, caps Capabilities) map[string]bool {
+		m := map[string]bool{}
+		for _, f := range ApplicableFunctions(&in, caps) {
+			m[f.Name] = true
+		}
+		return m
+	}
+	pro := CapabilitiesFor("Pro", "24.0.0.72")
+	list := names(StateInput{Type: "VideoList"}, pro)
+	if !list["NextItem"] || !list["Play"] || !list["Cut"] || list["PTZHome"] || list["SetText"] {
+		t.Fatalf("unexpected VideoList functions %v", list)
+	}
+	camera := names(StateInput{Type: "Capture"}, pro)
+	if !camera["PTZHome"] || camera["NextItem"] {
+		t.Fatalf("unexpected Capture functions %v", camera)
+	}
+	if replay := names(StateInput{Type: "Replay"}, CapabilitiesFor("Basic HD", "24.0.0.72")); replay["ReplayPlay"] {
+		t.Fatal("replay functions must be hidden on editions without replay")
+	}
+}
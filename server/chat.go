@@ -0,0 +1,485 @@
+package main
+
+import (
+	"bufio"
+	"crypto/tls"
+	"encoding/json"
+	"fmt"
+	"log"
+	"math/rand"
+	"net"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Chat platforms.
+const (
+	ChatPlatformTwitch  = "twitch"
+	ChatPlatformYouTube = "youtube"
+)
+
+// Chat roles allowed to run commands. Broadcaster is owner of channel.
+const (
+	ChatRoleBroadcaster = "broadcaster"
+	ChatRoleModerator   = "moderator"
+	ChatRoleVIP         = "vip"
+	ChatRoleSubscriber  = "subscriber"
+	ChatRoleEveryone    = "everyone"
+)
+
+const (
+	twitchIRCAddr          = "irc.chat.twitch.tv:6697"
+	youTubeLiveChatURL     = "https://www.googleapis.com/youtube/v3/liveChat/messages"
+	chatReconnectInterval  = 10 * time.Second
+	chatDefaultPerMinute   = 6 // commands fired per minute across whole chat, so raid cannot flood switcher.
+	youTubeMinPollInterval = 2 * time.Second
+)
+
+// ChatCommand maps chat keyword to shortcut. e.g. "!replay" by moderators runs shortcut "Replay" .
+type ChatCommand struct {
+	Keyword     string   `json:"keyword"`      // first word of message, case-insensitive. e.g. "!replay" .
+	Shortcut    string   `json:"shortcut"`     // shortcut name.
+	Roles       []string `json:"roles"`        // roles allowed to run command. Broadcaster and moderators if empty.
+	CooldownSec int      `json:"cooldown_sec"` // minimum seconds between runs of command.
+}
+
+// ChatSettings is chat integration configuration. Disabled by default.
+type ChatSettings struct {
+	Enabled       bool          `json:"enabled"`
+	Platform      string        `json:"platform"`        // ChatPlatformTwitch or ChatPlatformYouTube.
+	Channel       string        `json:"channel"`         // Twitch channel. e.g. "flowingspdg" .
+	Username      string        `json:"username"`        // Twitch login of token. Anonymous read-only login if empty.
+	Token         string        `json:"token"`           // Twitch OAuth token, or YouTube OAuth access token.
+	APIKey        string        `json:"api_key"`         // YouTube Data API key, used when token is empty.
+	LiveChatID    string        `json:"live_chat_id"`    // YouTube live chat ID of broadcast.
+	RatePerMinute int           `json:"rate_per_minute"` // commands fired per minute across chat. Default 6.
+	Commands      []ChatCommand `json:"commands"`
+}
+
+// ChatMessage is message received from chat.
+type ChatMessage struct {
+	Platform string
+	User     string
+	Text     string
+	Roles    []string
+}
+
+// ParseTwitchMessage parses IRC line with tags. ok is false for lines other than chat messages.
+// e.g. "@badges=moderator/1;display-name=Mod;mod=1 :mod!mod@mod.tmi.twitch.tv PRIVMSG #channel :!replay" .
+func ParseTwitchMessage(line string) (ChatMessage, bool) {
+	tags := map[string]string{}
+	if strings.HasPrefix(line, "@") {
+		i := strings.IndexByte(line, ' ')
+		if i < 0 {
+			return ChatMessage{}, false
+		}
+		for _, tag := range strings.Split(line[1:i], ";") {
+			kv := strings.SplitN(tag, "=", 2)
+			if len(kv) == 2 {
+				tags[kv[0]] = kv[1]
+			}
+		}
+		line = line[i+1:]
+	}
+	if !strings.HasPrefix(line, ":") {
+		return ChatMessage{}, false
+	}
+	parts := strings.SplitN(line[1:], " ", 4)
+	if len(parts) < 4 || parts[1] != "PRIVMSG" {
+		return ChatMessage{}, false
+	}
+	msg := ChatMessage{Platform: ChatPlatformTwitch, Text: strings.TrimPrefix(parts[3], ":")}
+	msg.User = tags["display-name"]
+	if msg.User == "" {
+		msg.User = strings.SplitN(parts[0], "!", 2)[0]
+	}
+	badges := tags["badges"]
+	if strings.Contains(badges, "broadcaster/") {
+		msg.Roles = append(msg.Roles, ChatRoleBroadcaster)
+	}
+	if tags["mod"] == "1" || strings.Contains(badges, "moderator/") {
+		msg.Roles = append(msg.Roles, ChatRoleModerator)
+	}
+	if tags["vip"] == "1" || strings.Contains(badges, "vip/") {
+		msg.Roles = append(msg.Roles, ChatRoleVIP)
+	}
+	if tags["subscriber"] == "1" {
+		msg.Roles = append(msg.Roles, ChatRoleSubscriber)
+	}
+	return msg, true
+}
+
+// chatAllowed reports whether user with roles may run command.
+func chatAllowed(cmd ChatCommand, roles []string) bool {
+	allowed := cmd.Roles
+	if len(allowed) == 0 {
+		allowed = []string{ChatRoleBroadcaster, ChatRoleModerator}
+	}
+	for _, a := range allowed {
+		if a == ChatRoleEveryone {
+			return true
+		}
+		for _, r := range roles {
+			if a == r {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// ChatBridge runs shortcuts by chat commands. Every matched command, fired or refused, is published as "chat" event
+// and recorded in action history.
+type ChatBridge struct {
+	mu       sync.Mutex
+	settings ChatSettings
+	restart  chan struct{}
+	conn     net.Conn
+	lastRun  map[string]time.Time // keyword -> last run.
+	fired    []time.Time          // runs within last minute.
+	gen      int                  // incremented by Configure, so connection closed by it is not reported as error.
+	lastErr  string
+}
+
+// NewChatBridge creates ChatBridge. Call Run to connect.
+func NewChatBridge() *ChatBridge {
+	return &ChatBridge{restart: make(chan struct{}, 1), lastRun: make(map[string]time.Time)}
+}
+
+// Configure replaces settings and reconnects.
+func (b *ChatBridge) Configure(settings ChatSettings) error {
+	if settings.Enabled {
+		switch settings.Platform {
+		case ChatPlatformTwitch:
+			if settings.Channel == "" {
+				return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("Twitch channel is required"))
+			}
+		case ChatPlatformYouTube:
+			if settings.LiveChatID == "" || (settings.Token == "" && settings.APIKey == "") {
+				return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("YouTube live chat ID and token or API key are required"))
+			}
+		default:
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown chat platform %q", settings.Platform))
+		}
+	}
+	for _, cmd := range settings.Commands {
+		if cmd.Keyword == "" || cmd.Shortcut == "" || cmd.CooldownSec < 0 {
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid chat command %q", cmd.Keyword))
+		}
+	}
+	if settings.RatePerMinute <= 0 {
+		settings.RatePerMinute = chatDefaultPerMinute
+	}
+	settings.Channel = strings.ToLower(strings.TrimPrefix(settings.Channel, "#"))
+	b.mu.Lock()
+	b.settings = settings
+	b.gen++
+	b.lastErr = ""
+	conn := b.conn
+	b.mu.Unlock()
+	if conn != nil {
+		conn.Close()
+	}
+	select {
+	case b.restart <- struct{}{}:
+	default:
+	}
+	return nil
+}
+
+// Settings returns current settings.
+func (b *ChatBridge) Settings() ChatSettings {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return b.settings
+}
+
+// Status returns settings without secrets and last error.
+func (b *ChatBridge) Status() gin.H {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	settings := b.settings
+	settings.Token = maskSecret(settings.Token)
+	settings.APIKey = maskSecret(settings.APIKey)
+	return gin.H{
+		"settings": settings,
+		"error":    b.lastErr,
+	}
+}
+
+// Handle runs command of message if user may run it and rate limit allows. Result is "fired", "denied", "standby", "cooldown",
+// "rate_limited" or "failed", or empty for messages that are not commands.
+func (b *ChatBridge) Handle(msg ChatMessage, now time.Time) string {
+	fields := strings.Fields(msg.Text)
+	if len(fields) == 0 {
+		return ""
+	}
+	b.mu.Lock()
+	var cmd *ChatCommand
+	for i := range b.settings.Commands {
+		if strings.EqualFold(b.settings.Commands[i].Keyword, fields[0]) {
+			cmd = &b.settings.Commands[i]
+			break
+		}
+	}
+	if cmd == nil {
+		b.mu.Unlock()
+		return ""
+	}
+	keyword, shortcut := cmd.Keyword, cmd.Shortcut
+	recent := b.fired[:0]
+	for _, at := range b.fired {
+		if now.Sub(at) < time.Minute {
+			recent = append(recent, at)
+		}
+	}
+	b.fired = recent
+	result := "fired"
+	switch {
+	case !chatAllowed(*cmd, msg.Roles):
+		result = "denied"
+	case !pairing.IsActive():
+		result = "standby"
+	case now.Sub(b.lastRun[keyword]) < time.Duration(cmd.CooldownSec)*time.Second:
+		result = "cooldown"
+	case len(b.fired) >= b.settings.RatePerMinute:
+		result = "rate_limited"
+	default:
+		b.lastRun[keyword] = now
+		b.fired = append(b.fired, now)
+	}
+	b.mu.Unlock()
+
+	var err error
+	if result == "fired" {
+		if sc, ok := findShortcut(shortcut); !ok {
+			err = fmt.Errorf("shortcut %q not found", shortcut)
+		} else {
+			err = TriggerAction{Host: sc.Host, FunctionCall: sc.FunctionCall}.Fire(fmt.Sprintf("chat %s %s", msg.Platform, msg.User))
+		}
+		if err != nil {
+			result = "failed"
+		}
+	}
+	data := gin.H{"platform": msg.Platform, "user": msg.User, "command": keyword, "shortcut": shortcut, "result": result, "at": now}
+	if err != nil {
+		data["error"] = err.Error()
+	}
+	events.Publish(Event{Topic: "chat", Data: data})
+	log.Printf("Chat %s %s %s : %s\n", msg.Platform, msg.User, keyword, result)
+	return result
+}
+
+// Run connects to chat forever while enabled, reconnecting on failure or configuration change.
+func (b *ChatBridge) Run() {
+	for {
+		b.mu.Lock()
+		settings, gen := b.settings, b.gen
+		b.mu.Unlock()
+		if settings.Enabled {
+			var err error
+			switch settings.Platform {
+			case ChatPlatformTwitch:
+				err = b.runTwitch(settings)
+			case ChatPlatformYouTube:
+				err = b.runYouTube(settings)
+			}
+			if err != nil {
+				b.mu.Lock()
+				if gen == b.gen {
+					log.Printf("Chat %s : %v\n", settings.Platform, err)
+					b.lastErr = err.Error()
+				}
+				b.mu.Unlock()
+			}
+		}
+		select {
+		case <-b.restart:
+		case <-time.After(chatReconnectInterval):
+		}
+	}
+}
+
+// runTwitch reads Twitch IRC until connection fails or is closed by Configure.
+func (b *ChatBridge) runTwitch(settings ChatSettings) error {
+	conn, err := tls.DialWithDialer(&net.Dialer{Timeout: 10 * time.Second}, "tcp", twitchIRCAddr, nil)
+	if err != nil {
+		return err
+	}
+	defer conn.Close()
+	b.mu.Lock()
+	b.conn = conn
+	b.mu.Unlock()
+	defer func() {
+		b.mu.Lock()
+		b.conn = nil
+		b.mu.Unlock()
+	}()
+
+	nick, pass := strings.ToLower(settings.Username), settings.Token
+	if nick == "" || pass == "" {
+		nick, pass = fmt.Sprintf("justinfan%d", 10000+rand.Intn(90000)), ""
+	}
+	if pass != "" && !strings.HasPrefix(pass, "oauth:") {
+		pass = "oauth:" + pass
+	}
+	login := "CAP REQ :twitch.tv/tags twitch.tv/commands\r\n"
+	if pass != "" {
+		login += "PASS " + pass + "\r\n"
+	}
+	login += "NICK " + nick + "\r\nJOIN #" + settings.Channel + "\r\n"
+	if _, err := conn.Write([]byte(login)); err != nil {
+		return err
+	}
+	b.mu.Lock()
+	b.lastErr = ""
+	b.mu.Unlock()
+	log.Printf("Chat joined Twitch channel %s\n", settings.Channel)
+
+	r := bufio.NewReader(conn)
+	for {
+		// Twitch pings every 5 minutes.
+		conn.SetReadDeadline(time.Now().Add(6 * time.Minute))
+		line, err := r.ReadString('\n')
+		if err != nil {
+			return err
+		}
+		line = strings.TrimRight(line, "\r\n")
+		switch {
+		case strings.HasPrefix(line, "PING "):
+			if _, err := conn.Write([]byte("PONG " + strings.TrimPrefix(line, "PING ") + "\r\n")); err != nil {
+				return err
+			}
+		case strings.Contains(line, " NOTICE * :Login authentication failed"):
+			return fmt.Errorf("Twitch login authentication failed")
+		default:
+			if msg, ok := ParseTwitchMessage(line); ok {
+				go b.Handle(msg, time.Now())
+			}
+		}
+	}
+}
+
+// youTubeChatResponse is response of liveChatMessages.list.
+type youTubeChatResponse struct {
+	NextPageToken         string `json:"nextPageToken"`
+	PollingIntervalMillis int    `json:"pollingIntervalMillis"`
+	Items                 []struct {
+		Snippet struct {
+			DisplayMessage string `json:"displayMessage"`
+		} `json:"snippet"`
+		AuthorDetails struct {
+			DisplayName     string `json:"displayName"`
+			IsChatOwner     bool   `json:"isChatOwner"`
+			IsChatModerator bool   `json:"isChatModerator"`
+			IsChatSponsor   bool   `json:"isChatSponsor"`
+		} `json:"authorDetails"`
+	} `json:"items"`
+	Error *struct {
+		Message string `json:"message"`
+	} `json:"error"`
+}
+
+// runYouTube polls YouTube live chat until request fails or settings change.
+// Messages already in chat when polling starts are skipped, so old commands do not fire on connect.
+func (b *ChatBridge) runYouTube(settings ChatSettings) error {
+	client := &http.Client{Timeout: 10 * time.Second}
+	pageToken := ""
+	first := true
+	for {
+		q := url.Values{}
+		q.Set("liveChatId", settings.LiveChatID)
+		q.Set("part", "snippet,authorDetails")
+		if pageToken != "" {
+			q.Set("pageToken", pageToken)
+		}
+		if settings.Token == "" {
+			q.Set("key", settings.APIKey)
+		}
+		req, err := http.NewRequest(http.MethodGet, youTubeLiveChatURL+"?"+q.Encode(), nil)
+		if err != nil {
+			return err
+		}
+		if settings.Token != "" {
+			req.Header.Set("Authorization", "Bearer "+settings.Token)
+		}
+		resp, err := client.Do(req)
+		if err != nil {
+			return err
+		}
+		res := youTubeChatResponse{}
+		err = json.NewDecoder(resp.Body).Decode(&res)
+		resp.Body.Close()
+		if err != nil {
+			return err
+		}
+		if res.Error != nil {
+			return fmt.Errorf("YouTube API : %s", res.Error.Message)
+		}
+		b.mu.Lock()
+		b.lastErr = ""
+		b.mu.Unlock()
+		if !first {
+			for _, item := range res.Items {
+				msg := ChatMessage{Platform: ChatPlatformYouTube, User: item.AuthorDetails.DisplayName, Text: item.Snippet.DisplayMessage}
+				if item.AuthorDetails.IsChatOwner {
+					msg.Roles = append(msg.Roles, ChatRoleBroadcaster)
+				}
+				if item.AuthorDetails.IsChatModerator {
+					msg.Roles = append(msg.Roles, ChatRoleModerator)
+				}
+				if item.AuthorDetails.IsChatSponsor {
+					msg.Roles = append(msg.Roles, ChatRoleSubscriber)
+				}
+				go b.Handle(msg, time.Now())
+			}
+		}
+		first = false
+		pageToken = res.NextPageToken
+
+		interval := time.Duration(res.PollingIntervalMillis) * time.Millisecond
+		if interval < youTubeMinPollInterval {
+			interval = youTubeMinPollInterval
+		}
+		select {
+		case <-b.restart:
+			// Give restart back to Run, which reconnects with new settings at once.
+			b.restart <- struct{}{}
+			return nil
+		case <-time.After(interval):
+		}
+	}
+}
+
+// GetChatHandler returns chat integration settings for [GET] /api/triggers/chat .
+func GetChatHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, chatBridge.Status())
+}
+
+// PutChatHandler saves chat integration settings and reconnects for [PUT] /api/triggers/chat .
+func PutChatHandler(c *gin.Context) {
+	settings := ChatSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	prev := chatBridge.Settings()
+	settings.Token = keepSecret(settings.Token, prev.Token)
+	settings.APIKey = keepSecret(settings.APIKey, prev.APIKey)
+	if err := chatBridge.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Chat = chatBridge.Settings()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetChatHandler(c)
+}
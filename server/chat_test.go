@@ -0,0 +1,107 @@
+package main
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestParseTwitchMessage(t *testing.T) {
+	msg, ok := ParseTwitchMessage("@badges=moderator/1,subscriber/12;display-name=ModUser;mod=1;subscriber=1 :moduser!moduser@moduser.tmi.twitch.tv PRIVMSG #channel :!replay now")
+	if !ok || msg.User != "ModUser" || msg.Text != "!replay now" {
+		t.Fatalf("unexpected message %+v %v", msg, ok)
+	}
+	if !chatAllowed(ChatCommand{}, msg.Roles) {
+		t.Fatalf("expected moderator allowed by default, roles %v", msg.Roles)
+	}
+	msg, ok = ParseTwitchMessage(":viewer!viewer@viewer.tmi.twitch.tv PRIVMSG #channel :!replay")
+	if !ok || msg.User != "viewer" || chatAllowed(ChatCommand{}, msg.Roles) {
+		t.Fatalf("expected viewer without tags denied, got %+v %v", msg, ok)
+	}
+	if !chatAllowed(ChatCommand{Roles: []string{ChatRoleEveryone}}, msg.Roles) {
+		t.Fatal("expected everyone allowed")
+	}
+	if _, ok := ParseTwitchMessage(":tmi.twitch.tv 001 justinfan12345 :Welcome, GLHF!"); ok {
+		t.Fatal("expected numeric reply not to be chat message")
+	}
+}
+
+func TestChatBridgeHandle(t *testing.T) {
+	SetRehearsal(true)
+	defer SetRehearsal(false)
+	config.Update(func(cfg *Config) {
+		cfg.Shortcuts = append(cfg.Shortcuts, Shortcut{Name: "chat_test", FunctionCall: FunctionCall{Function: "ReplayPlayLastEvent"}})
+	})
+	defer config.Update(func(cfg *Config) {
+		cfg.Shortcuts = cfg.Shortcuts[:len(cfg.Shortcuts)-1]
+	})
+
+	b := NewChatBridge()
+	if err := b.Configure(ChatSettings{RatePerMinute: 2, Commands: []ChatCommand{
+		{Keyword: "!replay", Shortcut: "chat_test", CooldownSec: 10},
+		{Keyword: "!hype", Shortcut: "chat_test", Roles: []string{ChatRoleEveryone}},
+	}}); err != nil {
+		t.Fatal(err)
+	}
+	mod := []string{ChatRoleModerator}
+	now := time.Now()
+	for i, c := range []struct {
+		text   string
+		roles  []string
+		at     time.Duration
+		result string
+	}{
+		{"hello", nil, 0, ""},
+		{"!REPLAY", nil, 0, "denied"},
+		{"!replay", mod, 0, "fired"},
+		{"!replay", mod, 5 * time.Second, "cooldown"},
+		{"!hype", nil, 6 * time.Second, "fired"},
+		{"!hype", nil, 7 * time.Second, "rate_limited"},
+		{"!replay", mod, 61 * time.Second, "fired"},
+	} {
+		msg := ChatMessage{Platform: ChatPlatformTwitch, User: "user", Text: c.text, Roles: c.roles}
+		if result := b.Handle(msg, now.Add(c.at)); result != c.result {
+			t.Fatalf("%d: expected %q for %q, got %q", i, c.result, c.text, result)
+		}
+	}
+
+	if err := b.Configure(ChatSettings{Enabled: true, Platform: ChatPlatformYouTube}); err == nil {
+		t.Fatal("expected YouTube without live chat ID refused")
+	}
+}
+
+func TestPutChatKeepsMaskedSecrets(t *testing.T) {
+	defer config.Update(func(cfg *Config) { cfg.Chat = ChatSettings{} })
+	defer chatBridge.Configure(ChatSettings{})
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/triggers/chat", PutChatHandler)
+	put := func(body string) ChatSettings {
+		t.Helper()
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/triggers/chat", strings.NewReader(body)))
+		res := struct {
+			Settings ChatSettings `json:"settings"`
+		}{}
+		if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || w.Code != http.StatusOK {
+			t.Fatalf("unexpected response %d %s", w.Code, w.Body.String())
+		}
+		return res.Settings
+	}
+
+	got := put(`{"platform":"youtube","live_chat_id":"chat","api_key":"key"}`)
+	if got.APIKey != secretMask {
+		t.Fatalf("expected API key masked, got %q", got.APIKey)
+	}
+	got.LiveChatID = "other"
+	b, _ := json.Marshal(got)
+	put(string(b))
+	if key := config.Get().Chat.APIKey; key != "key" || chatBridge.Settings().APIKey != "key" {
+		t.Fatalf("expected API key kept when mask is saved back, got %q", key)
+	}
+}
@@ -0,0 +1,363 @@
+package main
+
+import (
+	"bytes"
+	"encoding/binary"
+	"fmt"
+	"log"
+	"net"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Show clock modes.
+const (
+	ClockModeStopwatch = "stopwatch" // counts up from zero.
+	ClockModeCountdown = "countdown" // counts down from Duration.
+)
+
+// ClockStatus is show clock state published as "clock" event.
+type ClockStatus struct {
+	Mode      string `json:"mode"`
+	Running   bool   `json:"running"`
+	ElapsedMs int64  `json:"elapsed_ms"`
+	// RemainingMs is time left for countdown. Negative after target passed.
+	RemainingMs int64  `json:"remaining_ms"`
+	OverTime    bool   `json:"over_time"` // countdown passed target.
+	Display     string `json:"display"`   // formatted as [-]HH:MM:SS .
+}
+
+// defaultClockOSCAddress is OSC address prefix of show clock messages if settings do not set it.
+const defaultClockOSCAddress = "/vmix-utility/clock"
+
+// ClockSettings is where show clock is pushed to, saved in settings.
+type ClockSettings struct {
+	TitleInput string   `json:"title_input"` // vMix title input updated on change. Empty to disable.
+	TitleField string   `json:"title_field"` // text name of title. e.g. "Clock.Text" .
+	OSCTargets []string `json:"osc_targets"` // UDP host:port of OSC receivers, such as lighting desks or QLab.
+	OSCAddress string   `json:"osc_address"` // OSC address prefix. Default: "/vmix-utility/clock" .
+}
+
+// ShowClock is master show clock shared by every output.
+type ShowClock struct {
+	mu        sync.Mutex
+	mode      string
+	duration  time.Duration // countdown length.
+	elapsed   time.Duration // accumulated time before startedAt.
+	startedAt time.Time     // zero if stopped.
+
+	settings ClockSettings
+	osc      []*net.UDPAddr
+	oscConn  net.PacketConn // opened on first OSC message.
+}
+
+// NewShowClock creates stopped stopwatch.
+func NewShowClock() *ShowClock {
+	return &ShowClock{mode: ClockModeStopwatch}
+}
+
+// Configure replaces where clock is pushed to.
+func (c *ShowClock) Configure(settings ClockSettings) error {
+	if settings.OSCAddress == "" {
+		settings.OSCAddress = defaultClockOSCAddress
+	}
+	if !strings.HasPrefix(settings.OSCAddress, "/") {
+		return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("OSC address must start with \"/\""))
+	}
+	osc := make([]*net.UDPAddr, 0, len(settings.OSCTargets))
+	for _, target := range settings.OSCTargets {
+		addr, err := net.ResolveUDPAddr("udp", target)
+		if err != nil {
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid OSC target %q : %v", target, err))
+		}
+		osc = append(osc, addr)
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.settings = settings
+	c.osc = osc
+	return nil
+}
+
+// Settings returns where clock is pushed to.
+func (c *ShowClock) Settings() ClockSettings {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.settings
+}
+
+// Status returns current clock status.
+func (c *ShowClock) Status() ClockStatus {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.status(time.Now())
+}
+
+func (c *ShowClock) status(now time.Time) ClockStatus {
+	elapsed := c.elapsed
+	if !c.startedAt.IsZero() {
+		elapsed += now.Sub(c.startedAt)
+	}
+	s := ClockStatus{
+		Mode:      c.mode,
+		Running:   !c.startedAt.IsZero(),
+		ElapsedMs: int64(elapsed / time.Millisecond),
+	}
+	shown := elapsed
+	if c.mode == ClockModeCountdown {
+		remaining := c.duration - elapsed
+		s.RemainingMs = int64(remaining / time.Millisecond)
+		s.OverTime = remaining < 0
+		shown = remaining
+	}
+	s.Display = formatClock(shown, c.mode == ClockModeCountdown)
+	return s
+}
+
+// formatClock formats duration as [-]HH:MM:SS. Positive countdowns are rounded up so that 00:00:00 means finished.
+func formatClock(d time.Duration, countdown bool) string {
+	sign := ""
+	if d < 0 {
+		sign = "-"
+		d = -d
+	}
+	secs := int64(d / time.Second)
+	if countdown && sign == "" {
+		secs = int64((d + time.Second - 1) / time.Second)
+	}
+	return fmt.Sprintf("%s%02d:%02d:%02d", sign, secs/3600, secs/60%60, secs%60)
+}
+
+// ClockCommand is control command for show clock.
+type ClockCommand struct {
+	Command    string `json:"command"`     // "start", "stop", "reset" or "set".
+	Mode       string `json:"mode"`        // for "set".
+	DurationMs int64  `json:"duration_ms"` // countdown length for "set".
+}
+
+// Apply executes command.
+func (c *ShowClock) Apply(cmd ClockCommand) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	now := time.Now()
+	switch cmd.Command {
+	case "start":
+		if c.startedAt.IsZero() {
+			c.startedAt = now
+		}
+	case "stop":
+		if !c.startedAt.IsZero() {
+			c.elapsed += now.Sub(c.startedAt)
+			c.startedAt = time.Time{}
+		}
+	case "reset":
+		c.elapsed = 0
+		if !c.startedAt.IsZero() {
+			c.startedAt = now
+		}
+	case "set":
+		if cmd.Mode != ClockModeStopwatch && cmd.Mode != ClockModeCountdown {
+			return fmt.Errorf("unknown mode %q", cmd.Mode)
+		}
+		c.mode = cmd.Mode
+		c.duration = time.Duration(cmd.DurationMs) * time.Millisecond
+		c.elapsed = 0
+		c.startedAt = time.Time{}
+	default:
+		return fmt.Errorf("unknown command %q", cmd.Command)
+	}
+	return nil
+}
+
+// Run checks clock every tick, publishing status and updating vMix title and bound scoreboards when display changes.
+func (c *ShowClock) Run(tick time.Duration) {
+	t := time.NewTicker(tick)
+	defer t.Stop()
+	last := ""
+	for range t.C {
+		s := c.Status()
+		if s.Display == last {
+			continue
+		}
+		if events.Wanted("clock") {
+			events.Publish(Event{Topic: "clock", Data: s})
+		}
+		c.pushTitle(s.Display)
+		c.pushOSC(s)
+		scoreboards.ClockTick(s.Display)
+		last = s.Display
+	}
+}
+
+// pushTitle updates vMix title every second without plugin hooks and show statistics.
+func (c *ShowClock) pushTitle(display string) {
+	c.mu.Lock()
+	input, field := c.settings.TitleInput, c.settings.TitleField
+	c.mu.Unlock()
+	if input == "" || field == "" || vmix == nil {
+		return
+	}
+	params := map[string]string{"Input": input, "SelectedName": field, "Value": display}
+	if err := SendTitleUpdate(*vmixaddr, "SetText", params); err != nil {
+		log.Printf("Failed to update show clock title %s : %v\n", input, err)
+	}
+}
+
+// pushOSC sends clock status to OSC targets as <address>/display (string), <address>/running and
+// <address>/overtime (int 0/1) and <address>/elapsed and <address>/remaining (int seconds).
+func (c *ShowClock) pushOSC(s ClockStatus) {
+	c.mu.Lock()
+	targets, address := c.osc, c.settings.OSCAddress
+	if len(targets) > 0 && c.oscConn == nil {
+		conn, err := net.ListenPacket("udp", ":0")
+		if err != nil {
+			c.mu.Unlock()
+			log.Printf("Failed to open OSC socket : %v\n", err)
+			return
+		}
+		c.oscConn = conn
+	}
+	conn := c.oscConn
+	c.mu.Unlock()
+	if len(targets) == 0 {
+		return
+	}
+	running, overTime := int32(0), int32(0)
+	if s.Running {
+		running = 1
+	}
+	if s.OverTime {
+		overTime = 1
+	}
+	msgs := [][]byte{
+		encodeOSC(address+"/display", s.Display),
+		encodeOSC(address+"/running", running),
+		encodeOSC(address+"/overtime", overTime),
+		encodeOSC(address+"/elapsed", int32(s.ElapsedMs/1000)),
+		encodeOSC(address+"/remaining", int32(s.RemainingMs/1000)),
+	}
+	for _, target := range targets {
+		for _, msg := range msgs {
+			if _, err := conn.WriteTo(msg, target); err != nil {
+				log.Printf("Failed to send show clock to OSC %s : %v\n", target, err)
+				break
+			}
+		}
+	}
+}
+
+// encodeOSC encodes OSC message with string and int32 arguments.
+func encodeOSC(address string, args ...interface{}) []byte {
+	b := &bytes.Buffer{}
+	writeOSCString(b, address)
+	tags := ","
+	for _, arg := range args {
+		switch arg.(type) {
+		case string:
+			tags += "s"
+		case int32:
+			tags += "i"
+		}
+	}
+	writeOSCString(b, tags)
+	for _, arg := range args {
+		switch v := arg.(type) {
+		case string:
+			writeOSCString(b, v)
+		case int32:
+			binary.Write(b, binary.BigEndian, v)
+		}
+	}
+	return b.Bytes()
+}
+
+// writeOSCString writes null terminated string padded to multiple of 4 bytes.
+func writeOSCString(b *bytes.Buffer, s string) {
+	b.WriteString(s)
+	b.Write(make([]byte, 4-len(s)%4))
+}
+
+// GetClockHandler returns show clock status for [GET] /api/clock .
+func GetClockHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"clock": showClock.Status(),
+	})
+}
+
+// ClockCommandHandler executes show clock command for [POST] /api/clock/command .
+func ClockCommandHandler(c *gin.Context) {
+	cmd := ClockCommand{}
+	if err := c.ShouldBindJSON(&cmd); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := showClock.Apply(cmd); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	s := showClock.Status()
+	events.Publish(Event{Topic: "clock", Data: s})
+	c.JSON(http.StatusOK, gin.H{
+		"clock": s,
+	})
+}
+
+// ClockTitleRequest Request JSON for PutClockTitleHandler
+type ClockTitleRequest struct {
+	Input string `json:"input"`
+	Field string `json:"field"`
+}
+
+// PutClockTitleHandler sets vMix title updated by show clock for [PUT] /api/clock/title .
+func PutClockTitleHandler(c *gin.Context) {
+	req := ClockTitleRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	settings := showClock.Settings()
+	settings.TitleInput, settings.TitleField = req.Input, req.Field
+	if err := saveClockSettings(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"input": req.Input,
+		"field": req.Field,
+	})
+}
+
+// GetClockSettingsHandler returns where show clock is pushed to for [GET] /api/clock/settings .
+func GetClockSettingsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"settings": showClock.Settings(),
+	})
+}
+
+// PutClockSettingsHandler saves vMix title and OSC targets of show clock for [PUT] /api/clock/settings .
+func PutClockSettingsHandler(c *gin.Context) {
+	settings := ClockSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := saveClockSettings(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetClockSettingsHandler(c)
+}
+
+// saveClockSettings applies settings to show clock and saves them, so title and OSC targets survive restart.
+func saveClockSettings(settings ClockSettings) error {
+	if err := showClock.Configure(settings); err != nil {
+		return err
+	}
+	return config.Update(func(cfg *Config) {
+		cfg.Clock = showClock.Settings()
+	})
+}
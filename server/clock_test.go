@@ -0,0 +1,99 @@
+package main
+
+import (
+	"bytes"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestEncodeOSC(t *testing.T) {
+	got := encodeOSC("/clock", "00:01:02", int32(62))
+	want := append([]byte("/clock\x00\x00,si\x00"), append([]byte("00:01:02\x00\x00\x00\x00"), 0, 0, 0, 62)...)
+	if !bytes.Equal(got, want) {
+		t.Fatalf("unexpected OSC message %q", got)
+	}
+}
+
+func TestShowClockOSCAndSettings(t *testing.T) {
+	l, err := net.ListenPacket("udp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer l.Close()
+	prev := showClock
+	showClock = NewShowClock()
+	defer func() { showClock = prev }()
+	defer config.Update(func(cfg *Config) { cfg.Clock = ClockSettings{} })
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/clock/settings", PutClockSettingsHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/clock/settings",
+		strings.NewReader(`{"title_input":"Clock","title_field":"Time.Text","osc_targets":["`+l.LocalAddr().String()+`"]}`)))
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected settings saved, got %d %s", w.Code, w.Body)
+	}
+	if saved := config.Get().Clock; saved.TitleInput != "Clock" || saved.OSCAddress != defaultClockOSCAddress || len(saved.OSCTargets) != 1 {
+		t.Fatalf("expected settings persisted, got %+v", saved)
+	}
+
+	showClock.pushOSC(ClockStatus{Display: "00:00:05", Running: true, ElapsedMs: 5000})
+	buf := make([]byte, 256)
+	l.SetReadDeadline(time.Now().Add(2 * time.Second))
+	n, _, err := l.ReadFrom(buf)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if want := encodeOSC(defaultClockOSCAddress+"/display", "00:00:05"); !bytes.Equal(buf[:n], want) {
+		t.Fatalf("unexpected OSC message %q", buf[:n])
+	}
+
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/clock/settings", strings.NewReader(`{"osc_targets":["no-port"]}`)))
+	if w.Code != http.StatusBadRequest {
+		t.Fatalf("expected invalid OSC target refused, got %d %s", w.Code, w.Body)
+	}
+}
+
+func TestShowClockOverTime(t *testing.T) {
+	l, err := net.ListenPacket("udp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer l.Close()
+	clock := NewShowClock()
+	if err := clock.Configure(ClockSettings{OSCTargets: []string{l.LocalAddr().String()}}); err != nil {
+		t.Fatal(err)
+	}
+	clock.Apply(ClockCommand{Command: "set", Mode: ClockModeCountdown, DurationMs: 1})
+	if s := clock.Status(); s.OverTime {
+		t.Fatalf("expected stopped countdown before target not over time, got %+v", s)
+	}
+	clock.Apply(ClockCommand{Command: "start"})
+	time.Sleep(10 * time.Millisecond)
+	s := clock.Status()
+	if !s.OverTime || s.RemainingMs >= 0 {
+		t.Fatalf("expected countdown past target over time, got %+v", s)
+	}
+
+	clock.pushOSC(s)
+	want := encodeOSC(defaultClockOSCAddress+"/overtime", int32(1))
+	buf := make([]byte, 256)
+	l.SetReadDeadline(time.Now().Add(2 * time.Second))
+	for {
+		n, _, err := l.ReadFrom(buf)
+		if err != nil {
+			t.Fatalf("expected over time sent over OSC : %v", err)
+		}
+		if bytes.Equal(buf[:n], want) {
+			break
+		}
+	}
+}
@@ -0,0 +1,118 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/url"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// companionManifestFormat identifies manifest layout, bumped on incompatible changes.
+const companionManifestFormat = "vmix-utility-companion/1"
+
+// CompanionButton is button of Bitfocus Companion. Action and Options are those of generic HTTP module,
+// so button can be created by copying them, or by importer reading manifest.
+type CompanionButton struct {
+	Category string            `json:"category"` // e.g. "Shortcuts" or "Input groups (primary)" .
+	Text     string            `json:"text"`
+	Action   string            `json:"action"`  // generic HTTP module action. e.g. "post" .
+	Options  map[string]string `json:"options"` // e.g. {"url":"http://.../api/shortcuts/run/Intro","body":"","contenttype":"application/json"} .
+}
+
+// CompanionHost is vMix host listed in manifest.
+type CompanionHost struct {
+	Name     string   `json:"name"`
+	Addr     string   `json:"addr"`
+	Priority string   `json:"priority"`
+	Groups   []string `json:"groups"` // host groups host belongs to.
+}
+
+// CompanionManifest lists configured hosts, groups and shortcuts with buttons running them, to auto-populate Companion.
+type CompanionManifest struct {
+	Format      string                         `json:"format"`
+	GeneratedAt time.Time                      `json:"generated_at"`
+	BaseURL     string                         `json:"base_url"`
+	Hosts       []CompanionHost                `json:"hosts"`
+	HostGroups  map[string][]string            `json:"host_groups"`
+	InputGroups map[string]map[string][]string `json:"input_groups"` // vMix host -> group name -> inputs.
+	Buttons     []CompanionButton              `json:"buttons"`
+}
+
+// companionPost returns button posting body to URL.
+func companionPost(category, text, u string, body interface{}) CompanionButton {
+	b := ""
+	if body != nil {
+		j, _ := json.Marshal(body)
+		b = string(j)
+	}
+	return CompanionButton{Category: category, Text: text, Action: "post", Options: map[string]string{"url": u, "body": b, "contenttype": "application/json"}}
+}
+
+// BuildCompanionManifest builds manifest with button URLs under baseURL. e.g. "http://192.168.0.10:8080" .
+func BuildCompanionManifest(baseURL string, now time.Time) CompanionManifest {
+	baseURL = strings.TrimSuffix(baseURL, "/")
+	cfg := config.Get()
+	m := CompanionManifest{
+		Format:      companionManifestFormat,
+		GeneratedAt: now,
+		BaseURL:     baseURL,
+		Hosts:       []CompanionHost{},
+		HostGroups:  cfg.HostGroups,
+		InputGroups: map[string]map[string][]string{},
+		Buttons:     []CompanionButton{},
+	}
+	for _, conn := range Connections() {
+		if IsOfflineAddr(conn.Addr) {
+			continue
+		}
+		h := CompanionHost{Name: conn.Name, Addr: conn.Addr, Priority: conn.Priority, Groups: []string{}}
+		for group, addrs := range cfg.HostGroups {
+			for _, addr := range addrs {
+				if addr == conn.Addr {
+					h.Groups = append(h.Groups, group)
+				}
+			}
+		}
+		sort.Strings(h.Groups)
+		m.Hosts = append(m.Hosts, h)
+	}
+	for _, sc := range cfg.Shortcuts {
+		m.Buttons = append(m.Buttons, companionPost("Shortcuts", sc.Name, baseURL+"/api/shortcuts/run/"+url.PathEscape(sc.Name), nil))
+	}
+	for _, h := range m.Hosts {
+		groups := InputGroups(h.Addr)
+		if len(groups) == 0 {
+			continue
+		}
+		m.InputGroups[h.Addr] = groups
+		names := make([]string, 0, len(groups))
+		for name := range groups {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+		category := fmt.Sprintf("Input groups (%s)", h.Name)
+		for _, name := range names {
+			u := baseURL + "/api/groups/inputs/" + url.PathEscape(name) + "/action?host=" + url.QueryEscape(h.Addr)
+			for _, action := range groupActions() {
+				m.Buttons = append(m.Buttons, companionPost(category, name+" "+action, u, InputGroupActionRequest{Action: action}))
+			}
+		}
+	}
+	return m
+}
+
+// GetCompanionManifestHandler returns manifest for Bitfocus Companion for [GET] /api/companion/manifest?base_url=... .
+// Button URLs use URL utility was reached by unless base_url is given, such as when Companion runs on another machine.
+// Manifest is attached as file when download=1.
+func GetCompanionManifestHandler(c *gin.Context) {
+	m := BuildCompanionManifest(c.DefaultQuery("base_url", RequestBaseURL(c)), time.Now())
+	if c.Query("download") == "1" {
+		c.Header("Content-Disposition", `attachment; filename="vmix_utility_companion.json"`)
+	}
+	c.JSON(http.StatusOK, m)
+}
@@ -0,0 +1,35 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestBuildCompanionManifest(t *testing.T) {
+	if err := config.Update(func(cfg *Config) {
+		cfg.Shortcuts = []Shortcut{{Name: "Intro Roll", FunctionCall: FunctionCall{Function: "Play", Params: map[string]string{"Input": "4"}}}}
+		cfg.InputGroups[*vmixaddr] = map[string][]string{"Cameras": {"2", "3"}}
+		cfg.HostGroups["main"] = []string{*vmixaddr}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) {
+		cfg.Shortcuts = nil
+		delete(cfg.InputGroups, *vmixaddr)
+		delete(cfg.HostGroups, "main")
+	})
+
+	m := BuildCompanionManifest("http://10.0.0.5:8080/", time.Now())
+	if len(m.Hosts) == 0 || m.Hosts[0].Addr != *vmixaddr || len(m.Hosts[0].Groups) != 1 || m.Hosts[0].Groups[0] != "main" {
+		t.Fatalf("expected primary host in group main, got %+v", m.Hosts)
+	}
+	if len(m.Buttons) != 1+len(groupActions()) {
+		t.Fatalf("expected shortcut and group action buttons, got %+v", m.Buttons)
+	}
+	if b := m.Buttons[0]; b.Text != "Intro Roll" || b.Action != "post" || b.Options["url"] != "http://10.0.0.5:8080/api/shortcuts/run/Intro%20Roll" {
+		t.Fatalf("unexpected shortcut button %+v", b)
+	}
+	if b := m.Buttons[1]; b.Text != "Cameras preview_next" || b.Options["body"] != `{"action":"preview_next"}` {
+		t.Fatalf("unexpected group button %+v", b)
+	}
+}
@@ -0,0 +1,103 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Condition is check against cached state of vMix host, evaluated before sending functions.
+// e.g. {"field":"preview","op":"ne","value":"@program"} sends Cut only if preview differs from program,
+// {"field":"input.state","input":"5","op":"ne","value":"Paused"} skips if input 5 is paused.
+type Condition struct {
+	Field string `json:"field"`           // "preview", "program", "recording", "streaming", "external", "fade_to_black", "input.state" or "input.loop".
+	Input string `json:"input,omitempty"` // input reference or alias for "input.*" fields.
+	Op    string `json:"op"`              // "eq" or "ne".
+	Value string `json:"value"`           // expected value. "@field" refers to another field. e.g. "@program" .
+}
+
+// String formats condition for logs and responses. e.g. "preview ne @program" .
+func (cond Condition) String() string {
+	field := cond.Field
+	if cond.Input != "" {
+		field += "[" + cond.Input + "]"
+	}
+	return fmt.Sprintf("%s %s %s", field, cond.Op, cond.Value)
+}
+
+// conditionField returns value of field in state.
+func conditionField(host string, s *State, field, input string) (string, error) {
+	switch field {
+	case "preview":
+		return strconv.Itoa(s.Preview), nil
+	case "program":
+		return strconv.Itoa(s.Active), nil
+	case "recording":
+		return boolString(s.Recording), nil
+	case "streaming":
+		return boolString(s.Streaming), nil
+	case "external":
+		return boolString(s.External), nil
+	case "fade_to_black":
+		return boolString(s.FadeToBlack), nil
+	case "input.state", "input.loop":
+		in := s.FindInput(ResolveInputAlias(host, input))
+		if in == nil {
+			return "", fmt.Errorf("input %q not found", input)
+		}
+		if field == "input.loop" {
+			return boolString(in.Loop), nil
+		}
+		return string(in.State), nil
+	}
+	return "", fmt.Errorf("unknown condition field %q", field)
+}
+
+// EvalCondition reports whether condition holds for state of host.
+// Input values of "preview" and "program" may be input references, compared by input number.
+func EvalCondition(host string, s *State, cond Condition) (bool, error) {
+	actual, err := conditionField(host, s, cond.Field, cond.Input)
+	if err != nil {
+		return false, err
+	}
+	expected := cond.Value
+	switch {
+	case strings.HasPrefix(expected, "@"):
+		if expected, err = conditionField(host, s, strings.TrimPrefix(expected, "@"), cond.Input); err != nil {
+			return false, err
+		}
+	case cond.Field == "preview" || cond.Field == "program":
+		if in := s.FindInput(ResolveInputAlias(host, expected)); in != nil {
+			expected = strconv.Itoa(in.Number)
+		}
+	}
+	switch cond.Op {
+	case "eq":
+		return strings.EqualFold(actual, expected), nil
+	case "ne":
+		return !strings.EqualFold(actual, expected), nil
+	}
+	return false, fmt.Errorf("unknown condition operator %q", cond.Op)
+}
+
+// CheckConditions returns first condition not met by latest polled state of host, or nil if every condition holds.
+// Conditions cannot be evaluated without state, so error is returned instead of sending functions blindly.
+func CheckConditions(host string, conds []Condition) (*Condition, error) {
+	if len(conds) == 0 {
+		return nil, nil
+	}
+	s, err := hostState(host)
+	if err != nil {
+		return nil, err
+	}
+	for i := range conds {
+		ok, err := EvalCondition(host, s, conds[i])
+		if err != nil {
+			return nil, NewAppError(ErrKindInvalidRequest, host, err)
+		}
+		if !ok {
+			return &conds[i], nil
+		}
+	}
+	return nil, nil
+}
@@ -0,0 +1,31 @@
+package main
+
+import "testing"
+
+func TestEvalCondition(t *testing.T) {
+	s := DefaultMockState()
+	s.Active, s.Preview = 1, 2
+	s.Inputs[4].State = "Paused"
+	cases := []struct {
+		cond Condition
+		want bool
+	}{
+		{Condition{Field: "preview", Op: "ne", Value: "@program"}, true},
+		{Condition{Field: "program", Op: "eq", Value: "1"}, true},
+		{Condition{Field: "program", Op: "eq", Value: s.Inputs[0].Key}, true},
+		{Condition{Field: "input.state", Input: "5", Op: "ne", Value: "paused"}, false},
+		{Condition{Field: "recording", Op: "eq", Value: "false"}, true},
+	}
+	for _, c := range cases {
+		got, err := EvalCondition(*vmixaddr, s, c.cond)
+		if err != nil {
+			t.Fatalf("%s : %v", c.cond, err)
+		}
+		if got != c.want {
+			t.Fatalf("%s : expected %v, got %v", c.cond, c.want, got)
+		}
+	}
+	if _, err := EvalCondition(*vmixaddr, s, Condition{Field: "input.state", Input: "99", Op: "eq"}); err == nil {
+		t.Fatal("expected missing input error")
+	}
+}
@@ -0,0 +1,394 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"log"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/FlowingSPDG/vmix-utility/server/core"
+)
+
+// Config is persistent settings saved as JSON.
+type Config struct {
+	UpdatedAt  time.Time `json:"updated_at"`  // last modification, used to resolve sync conflicts.
+	UpdatedBy  string    `json:"updated_by"`  // hostname of machine made last modification.
+	AppVersion string    `json:"app_version"` // utility version made last modification, to back up settings before migration by newer version.
+
+	HardwareProfiles map[string]HardwareProfile      `json:"hardware_profiles"` // vMix host -> profile.
+	MediaFolders     map[string]string               `json:"media_folders"`     // vMix host -> media folder path, reachable from this machine.
+	TCPSubscriptions map[string]TCPSubscription      `json:"tcp_subscriptions"` // vMix host -> TCP API subscription preferences.
+	Connections      []Connection                    `json:"connections"`       // additional vMix hosts to poll.
+	Shortcuts        []Shortcut                      `json:"shortcuts"`         // named functions bound to hotkeys.
+	StartupActions   map[string][]StartupAction      `json:"startup_actions"`   // vMix host -> functions sent once host is confirmed online after launch.
+	LatestVersion    LatestVersion                   `json:"latest_version"`    // known latest vMix version for upgrade advice.
+	HostGroups       map[string][]string             `json:"host_groups"`       // group name -> vMix hosts, such as mirrored main/backup machines.
+	OnAirProtect     OnAirProtectSettings            `json:"on_air_protect"`    // refuse destructive functions while recording or streaming.
+	InputGroups      map[string]map[string][]string  `json:"input_groups"`      // vMix host -> group name -> inputs. e.g. "Cameras" .
+	InputAliases     map[string]map[string]string    `json:"input_aliases"`     // vMix host -> alias -> input. e.g. "CAM 1" -> "3" .
+	AFVRules         map[string][]AFVRule            `json:"afv_rules"`         // vMix host -> audio follow video rules.
+	SerialTrigger    SerialSettings                  `json:"serial_trigger"`    // serial port GPIO trigger.
+	ArtNetTrigger    ArtNetSettings                  `json:"artnet_trigger"`    // Art-Net DMX trigger.
+	Timecode         TimecodeSettings                `json:"timecode"`          // timecode source for cue chase.
+	TimecodeCues     []TimecodeCue                   `json:"timecode_cues"`     // cues armed against timecode.
+	CueWarnings      CueWarningSettings              `json:"cue_warnings"`      // title showing next timecode cue.
+	Clock            ClockSettings                   `json:"clock"`             // vMix title and OSC targets of show clock.
+	ReplayPresets    map[string]ReplayPreset         `json:"replay_presets"`    // preset name -> replay moment. e.g. "football" .
+	PiPPresets       map[string]PiPPreset            `json:"pip_presets"`       // preset name -> picture-in-picture position. e.g. "corner" .
+	Scoreboards      map[string]Scoreboard           `json:"scoreboards"`       // scoreboard name -> teams, scores and title mapping, restored after restart.
+	GraphicTemplates map[string]string               `json:"graphic_templates"` // template name -> user-defined overlay HTML. e.g. "{{.title}}" .
+	LayerLooks       map[string]InputLooks           `json:"layer_looks"`       // vMix host -> input key -> look name -> pan and zoom.
+	StreamHealth     map[string]StreamHealthSettings `json:"stream_health"`     // vMix host -> stream health monitor.
+	Watchdog         WatchdogSettings                `json:"watchdog"`          // idle connection watchdog.
+	Sessions         map[string]Session              `json:"sessions"`          // operator -> frontend session restored after restart.
+	Talkback         map[string]TalkbackChannel      `json:"talkback"`          // channel name -> push-to-talk announcement. e.g. "PA" .
+	EventRates       map[string]float64              `json:"event_rates"`       // topic -> maximum events per second, coalescing faster events.
+	HTTPPools        map[string]HTTPPoolSettings     `json:"http_pools"`        // vMix host -> keep-alive and connection pooling.
+	MQTT             MQTTSettings                    `json:"mqtt"`              // MQTT broker for venue automation.
+	DataFeeds        map[string]DataFeed             `json:"data_feeds"`        // feed name -> JSON data feed mapped into titles. e.g. "scoreboard" .
+	GSI              GSISettings                     `json:"gsi"`               // CS2 and Dota 2 Game State Integration listener.
+	Chat             ChatSettings                    `json:"chat"`              // Twitch or YouTube chat commands.
+	UpdateChannel    string                          `json:"update_channel"`    // utility release channel, UpdateChannelStable or UpdateChannelBeta.
+}
+
+// ConfigStore loads and saves Config from file.
+type ConfigStore struct {
+	mu     sync.Mutex
+	path   string
+	config Config
+
+	syncMu sync.Mutex // serializes push and pull, which access shared copy without holding mu.
+
+	syncTarget configSyncTarget // shared folder or WebDAV URL. Nil to disable sync.
+	syncBase   configStamp      // shared copy as of last successful push or pull, to detect conflicts.
+	conflict   *Config          // shared copy changed concurrently with local settings, until resolved.
+	lastSync   time.Time        // last successful push or pull.
+}
+
+// LoadConfigStore loads config from path. Missing file results in empty config.
+// Settings saved by other utility version are backed up as is before being loaded, so they can be rolled back
+// if this version migrates them badly.
+func LoadConfigStore(path string) (*ConfigStore, error) {
+	s := &ConfigStore{path: path}
+	b, err := ioutil.ReadFile(path)
+	if err != nil && !os.IsNotExist(err) {
+		return nil, err
+	}
+	if err == nil {
+		if err := json.Unmarshal(b, &s.config); err != nil {
+			return nil, err
+		}
+		if s.config.AppVersion != appVersion {
+			if backup, err := s.writeBackup(b, s.config.AppVersion); err != nil {
+				log.Printf("Failed to back up settings before update : %v\n", err)
+			} else {
+				log.Printf("Settings of version %q backed up to %s\n", s.config.AppVersion, backup.Name)
+			}
+		}
+	}
+	s.normalize()
+	return s, nil
+}
+
+func (s *ConfigStore) normalize() {
+	if s.config.HardwareProfiles == nil {
+		s.config.HardwareProfiles = make(map[string]HardwareProfile)
+	}
+	if s.config.MediaFolders == nil {
+		s.config.MediaFolders = make(map[string]string)
+	}
+	if s.config.TCPSubscriptions == nil {
+		s.config.TCPSubscriptions = make(map[string]TCPSubscription)
+	}
+	if s.config.StartupActions == nil {
+		s.config.StartupActions = make(map[string][]StartupAction)
+	}
+	if s.config.AFVRules == nil {
+		s.config.AFVRules = make(map[string][]AFVRule)
+	}
+	if s.config.InputAliases == nil {
+		s.config.InputAliases = make(map[string]map[string]string)
+	}
+	if s.config.InputGroups == nil {
+		s.config.InputGroups = make(map[string]map[string][]string)
+	}
+	if s.config.StreamHealth == nil {
+		s.config.StreamHealth = make(map[string]StreamHealthSettings)
+	}
+	if s.config.LayerLooks == nil {
+		s.config.LayerLooks = make(map[string]InputLooks)
+	}
+	if s.config.Talkback == nil {
+		s.config.Talkback = make(map[string]TalkbackChannel)
+	}
+	if s.config.EventRates == nil {
+		s.config.EventRates = make(map[string]float64, len(core.DefaultEventRates))
+		for topic, rate := range core.DefaultEventRates {
+			s.config.EventRates[topic] = rate
+		}
+	}
+	if s.config.DataFeeds == nil {
+		s.config.DataFeeds = make(map[string]DataFeed)
+	}
+	if s.config.HTTPPools == nil {
+		s.config.HTTPPools = make(map[string]HTTPPoolSettings)
+	}
+	if s.config.Sessions == nil {
+		s.config.Sessions = make(map[string]Session)
+	}
+	if s.config.PiPPresets == nil {
+		s.config.PiPPresets = make(map[string]PiPPreset)
+	}
+	if s.config.ReplayPresets == nil {
+		s.config.ReplayPresets = make(map[string]ReplayPreset)
+	}
+	if s.config.Scoreboards == nil {
+		s.config.Scoreboards = make(map[string]Scoreboard)
+	}
+	if s.config.GraphicTemplates == nil {
+		s.config.GraphicTemplates = make(map[string]string)
+	}
+	if s.config.HostGroups == nil {
+		s.config.HostGroups = make(map[string][]string)
+	}
+}
+
+// Get returns deep copy of current config, so callers can read it without holding lock.
+func (s *ConfigStore) Get() Config {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	c := Config{}
+	b, err := json.Marshal(s.config)
+	if err == nil {
+		err = json.Unmarshal(b, &c)
+	}
+	if err != nil {
+		return s.config
+	}
+	return c
+}
+
+// secretMask replaces passwords, tokens and API keys in settings returned by API.
+const secretMask = "********"
+
+// maskSecret returns secretMask for non-empty secret.
+func maskSecret(secret string) string {
+	if secret == "" {
+		return ""
+	}
+	return secretMask
+}
+
+// keepSecret returns stored secret when posted value is secretMask, so settings read from API can be saved back
+// without replacing secrets with mask.
+func keepSecret(posted, stored string) string {
+	if posted == secretMask {
+		return stored
+	}
+	return posted
+}
+
+// redactConfig returns copy of cfg with secrets masked, for handlers returning whole settings.
+func redactConfig(cfg Config) Config {
+	cfg.MQTT.Password = maskSecret(cfg.MQTT.Password)
+	cfg.Chat.Token = maskSecret(cfg.Chat.Token)
+	cfg.Chat.APIKey = maskSecret(cfg.Chat.APIKey)
+	cfg.GSI.Token = maskSecret(cfg.GSI.Token)
+	streamHealth := make(map[string]StreamHealthSettings, len(cfg.StreamHealth))
+	for host, settings := range cfg.StreamHealth {
+		streamHealth[host] = redactStreamHealth(settings)
+	}
+	cfg.StreamHealth = streamHealth
+	feeds := make(map[string]DataFeed, len(cfg.DataFeeds))
+	for name, feed := range cfg.DataFeeds {
+		headers := make(map[string]string, len(feed.Headers))
+		for k, v := range feed.Headers {
+			headers[k] = maskSecret(v)
+		}
+		feed.Headers = headers
+		feeds[name] = feed
+	}
+	cfg.DataFeeds = feeds
+	return cfg
+}
+
+// Update modifies config with fn and saves it to file. Settings are pushed to shared copy after lock is released,
+// so readers of config are not blocked by slow shared folder or WebDAV server.
+func (s *ConfigStore) Update(fn func(c *Config)) error {
+	s.mu.Lock()
+	fn(&s.config)
+	s.normalize()
+	s.config.UpdatedAt = time.Now()
+	s.config.UpdatedBy, _ = os.Hostname()
+	s.config.AppVersion = appVersion
+	err := s.save()
+	target := s.syncTarget
+	s.mu.Unlock()
+	if err != nil {
+		return err
+	}
+	if target != nil {
+		if err := s.Push(false); err != nil {
+			log.Printf("Failed to push settings to %s : %v\n", target, err)
+		}
+	}
+	return nil
+}
+
+func (s *ConfigStore) save() error {
+	b, err := json.MarshalIndent(s.config, "", "  ")
+	if err != nil {
+		return err
+	}
+	return ioutil.WriteFile(s.path, b, 0644)
+}
+
+// Replicate replaces local settings with remote if remote is newer, keeping its modification time,
+// such as settings of primary replicated to standby secondary. Returns true if local settings were replaced.
+func (s *ConfigStore) Replicate(remote Config) (bool, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if !remote.UpdatedAt.After(s.config.UpdatedAt) {
+		return false, nil
+	}
+	s.config = withMachineLocal(remote, s.config)
+	s.normalize()
+	if err := s.save(); err != nil {
+		return false, err
+	}
+	return true, nil
+}
+
+// maxConfigBackups is number of settings backups kept. Oldest are removed.
+const maxConfigBackups = 20
+
+// ConfigBackup is settings file backed up before update or rollback.
+type ConfigBackup struct {
+	Name      string    `json:"name"`    // file name in backups folder. e.g. "vmix_utility_20240101_120000.000_v1.2.0.json" .
+	Version   string    `json:"version"` // utility version saved settings. Empty for settings older than version tracking.
+	CreatedAt time.Time `json:"created_at"`
+	Size      int64     `json:"size"`
+}
+
+// backupDir is folder of settings backups, next to settings file.
+func (s *ConfigStore) backupDir() string {
+	return filepath.Join(filepath.Dir(s.path), "backups")
+}
+
+// writeBackup writes settings file content b saved by version into backups folder, removing old backups.
+func (s *ConfigStore) writeBackup(b []byte, version string) (ConfigBackup, error) {
+	if err := os.MkdirAll(s.backupDir(), 0755); err != nil {
+		return ConfigBackup{}, err
+	}
+	label := version
+	if label == "" {
+		label = "unknown"
+	}
+	base := strings.TrimSuffix(filepath.Base(s.path), filepath.Ext(s.path))
+	now := time.Now()
+	name := fmt.Sprintf("%s_%s_%s.json", base, now.Format("20060102_150405.000"), unsafeFileChars.ReplaceAllString(label, "_"))
+	if err := ioutil.WriteFile(filepath.Join(s.backupDir(), name), b, 0644); err != nil {
+		return ConfigBackup{}, err
+	}
+	backups, err := s.backups()
+	if err == nil && len(backups) > maxConfigBackups {
+		for _, old := range backups[maxConfigBackups:] {
+			os.Remove(filepath.Join(s.backupDir(), old.Name))
+		}
+	}
+	return ConfigBackup{Name: name, Version: version, CreatedAt: now, Size: int64(len(b))}, nil
+}
+
+// Backup backs up current settings, such as right before installing update.
+func (s *ConfigStore) Backup() (ConfigBackup, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	b, err := json.MarshalIndent(s.config, "", "  ")
+	if err != nil {
+		return ConfigBackup{}, err
+	}
+	return s.writeBackup(b, s.config.AppVersion)
+}
+
+// backups lists backups, newest first.
+func (s *ConfigStore) backups() ([]ConfigBackup, error) {
+	files, err := ioutil.ReadDir(s.backupDir())
+	if os.IsNotExist(err) {
+		return []ConfigBackup{}, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	backups := []ConfigBackup{}
+	for _, f := range files {
+		if f.IsDir() || filepath.Ext(f.Name()) != ".json" {
+			continue
+		}
+		backup := ConfigBackup{Name: f.Name(), CreatedAt: f.ModTime(), Size: f.Size()}
+		saved := struct {
+			AppVersion string `json:"app_version"`
+		}{}
+		if b, err := ioutil.ReadFile(filepath.Join(s.backupDir(), f.Name())); err == nil && json.Unmarshal(b, &saved) == nil {
+			backup.Version = saved.AppVersion
+		}
+		backups = append(backups, backup)
+	}
+	// names start with backup time, so they sort chronologically.
+	sort.Slice(backups, func(i, j int) bool { return backups[i].Name > backups[j].Name })
+	return backups, nil
+}
+
+// Backups lists backups, newest first.
+func (s *ConfigStore) Backups() ([]ConfigBackup, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.backups()
+}
+
+// Rollback replaces settings with newest backup saved by version, or backup named version.
+// Current settings are backed up first, so rollback itself can be undone.
+func (s *ConfigStore) Rollback(version string) (ConfigBackup, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	backups, err := s.backups()
+	if err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInternal, "", err)
+	}
+	var target *ConfigBackup
+	for i := range backups {
+		if backups[i].Name == version || backups[i].Version == version {
+			target = &backups[i]
+			break
+		}
+	}
+	if target == nil {
+		return ConfigBackup{}, NewAppError(ErrKindNotFound, "", fmt.Errorf("no settings backup of version %q", version))
+	}
+	b, err := ioutil.ReadFile(filepath.Join(s.backupDir(), target.Name))
+	if err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInternal, "", err)
+	}
+	restored := Config{}
+	if err := json.Unmarshal(b, &restored); err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("backup %s is broken : %v", target.Name, err))
+	}
+	current, err := json.MarshalIndent(s.config, "", "  ")
+	if err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInternal, "", err)
+	}
+	if _, err := s.writeBackup(current, s.config.AppVersion); err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInternal, "", err)
+	}
+	s.config = restored
+	s.normalize()
+	if err := s.save(); err != nil {
+		return ConfigBackup{}, NewAppError(ErrKindInternal, "", err)
+	}
+	return *target, nil
+}
@@ -0,0 +1,123 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+)
+
+// GetConfigSyncHandler returns settings sync status for [GET] /api/config/sync .
+func GetConfigSyncHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"sync": config.SyncStatus(),
+	})
+}
+
+// PushConfigSyncHandler writes local settings to shared copy for [POST] /api/config/sync/push?force=true .
+// Shared copy changed by other machine since last sync is overwritten only with force.
+func PushConfigSyncHandler(c *gin.Context) {
+	if config.SyncStatus().Dir == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("settings sync is not enabled")))
+		return
+	}
+	if err := config.Push(c.Query("force") == "true"); err != nil {
+		abortWithSyncError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"sync": config.SyncStatus(),
+	})
+}
+
+// PullConfigSyncHandler replaces local settings with shared copy for [POST] /api/config/sync/pull?force=true .
+// Local settings changed since last sync are replaced only with force.
+func PullConfigSyncHandler(c *gin.Context) {
+	if config.SyncStatus().Dir == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("settings sync is not enabled")))
+		return
+	}
+	pulled, err := config.Pull(c.Query("force") == "true")
+	if err != nil {
+		abortWithSyncError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"pulled": pulled,
+		"sync":   config.SyncStatus(),
+	})
+}
+
+// abortWithSyncError aborts with conflict error as is, and with any other sync error as internal error.
+func abortWithSyncError(c *gin.Context, err error) {
+	if AsAppError(err).Kind == ErrKindConflict {
+		AbortWithAppError(c, err)
+		return
+	}
+	AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+}
+
+// GetConfigConflictHandler returns local settings and conflicting shared copy for [GET] /api/config/sync/conflict ,
+// so operator can compare them before pushing or pulling with force.
+func GetConfigConflictHandler(c *gin.Context) {
+	local, remote, ok := config.Conflict()
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("no settings sync conflict")))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"local":  redactConfig(local),
+		"remote": redactConfig(remote),
+	})
+}
+
+// GetConfigBackupsHandler lists settings backups, newest first, for [GET] /api/config/backups .
+func GetConfigBackupsHandler(c *gin.Context) {
+	backups, err := config.Backups()
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"version": appVersion,
+		"backups": backups,
+	})
+}
+
+// CreateConfigBackupHandler backs up current settings for [POST] /api/config/backups , such as by installer before update.
+func CreateConfigBackupHandler(c *gin.Context) {
+	backup, err := config.Backup()
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"backup": backup,
+	})
+}
+
+// RollbackConfigHandler restores settings backup for [POST] /api/config/rollback?version=... .
+// version is utility version whose newest backup is restored, or backup name. Available backups are listed
+// when version is not found. Triggers and bridges configured at startup pick up restored settings after restart.
+func RollbackConfigHandler(c *gin.Context) {
+	backup, err := config.Rollback(c.Query("version"))
+	if err != nil {
+		if AsAppError(err).Kind == ErrKindNotFound {
+			backups, _ := config.Backups()
+			c.AbortWithStatusJSON(http.StatusNotFound, gin.H{
+				"error":   AsAppError(err),
+				"backups": backups,
+			})
+			return
+		}
+		AbortWithAppError(c, err)
+		return
+	}
+	log.Printf("Settings rolled back to %s\n", backup.Name)
+	events.Publish(Event{Topic: "config", Data: config.SyncStatus()})
+	c.JSON(http.StatusOK, gin.H{
+		"restored":         backup,
+		"restart_required": true,
+	})
+}
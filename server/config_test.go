@@ -0,0 +1,51 @@
+package main
+
+import (
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestConfigBackupAndRollback(t *testing.T) {
+	dir, err := ioutil.TempDir("", "vmix_utility_backup")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	path := filepath.Join(dir, "vmix_utility.json")
+	if err := ioutil.WriteFile(path, []byte(`{"app_version":"v1.0.0","host_groups":{"old":["http://a:8088"]}}`), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	s, err := LoadConfigStore(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	backups, err := s.Backups()
+	if err != nil || len(backups) != 1 || backups[0].Version != "v1.0.0" {
+		t.Fatalf("expected settings of previous version backed up on load, got %+v %v", backups, err)
+	}
+
+	if err := s.Update(func(cfg *Config) {
+		delete(cfg.HostGroups, "old")
+	}); err != nil {
+		t.Fatal(err)
+	}
+	if s.Get().AppVersion != appVersion {
+		t.Fatalf("expected app version recorded, got %q", s.Get().AppVersion)
+	}
+	if _, err := s.Rollback("v0.9.0"); err == nil || AsAppError(err).Kind != ErrKindNotFound {
+		t.Fatalf("expected unknown version not found, got %v", err)
+	}
+	restored, err := s.Rollback("v1.0.0")
+	if err != nil || restored.Name != backups[0].Name {
+		t.Fatalf("unexpected rollback %+v %v", restored, err)
+	}
+	if len(s.Get().HostGroups["old"]) != 1 {
+		t.Fatalf("expected settings restored, got %+v", s.Get().HostGroups)
+	}
+	if backups, _ := s.Backups(); len(backups) != 2 || backups[0].Version != appVersion {
+		t.Fatalf("expected settings before rollback backed up, got %+v", backups)
+	}
+}
@@ -0,0 +1,353 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"log"
+	"net/http"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// configSyncTarget is shared copy of settings, such as file in cloud storage folder or on WebDAV server.
+type configSyncTarget interface {
+	Read() ([]byte, error) // error satisfies os.IsNotExist if nothing was shared yet.
+	Write(b []byte) error
+	String() string
+}
+
+// folderSyncTarget shares settings file in folder synced by Dropbox, OneDrive or on SMB file share.
+type folderSyncTarget struct {
+	file string
+}
+
+func (t folderSyncTarget) Read() ([]byte, error) {
+	return ioutil.ReadFile(t.file)
+}
+
+// Write writes to temporary file first, so other machines never read partially written settings.
+func (t folderSyncTarget) Write(b []byte) error {
+	tmp := t.file + ".tmp"
+	if err := ioutil.WriteFile(tmp, b, 0644); err != nil {
+		return err
+	}
+	return os.Rename(tmp, t.file)
+}
+
+func (t folderSyncTarget) String() string {
+	return filepath.Dir(t.file)
+}
+
+// webDAVSyncTarget shares settings file on WebDAV server, such as Nextcloud. Credentials may be given in URL.
+type webDAVSyncTarget struct {
+	url    *url.URL
+	client *http.Client
+}
+
+func (t webDAVSyncTarget) Read() ([]byte, error) {
+	resp, err := t.client.Get(t.url.String())
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode == http.StatusNotFound {
+		return nil, &os.PathError{Op: "get", Path: t.String(), Err: os.ErrNotExist}
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("WebDAV GET %s : %s", t, resp.Status)
+	}
+	return ioutil.ReadAll(resp.Body)
+}
+
+func (t webDAVSyncTarget) Write(b []byte) error {
+	req, err := http.NewRequest(http.MethodPut, t.url.String(), bytes.NewReader(b))
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	resp, err := t.client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	switch resp.StatusCode {
+	case http.StatusOK, http.StatusCreated, http.StatusNoContent:
+		return nil
+	}
+	return fmt.Errorf("WebDAV PUT %s : %s", t, resp.Status)
+}
+
+// String returns URL without password.
+func (t webDAVSyncTarget) String() string {
+	u := *t.url
+	if u.User != nil {
+		u.User = url.User(u.User.Username())
+	}
+	return u.String()
+}
+
+// newConfigSyncTarget returns WebDAV target for http(s) URL, and shared folder target otherwise.
+// Settings file name is appended to folder and to WebDAV URL ending with "/".
+func newConfigSyncTarget(target, name string) (configSyncTarget, error) {
+	if !strings.HasPrefix(target, "http://") && !strings.HasPrefix(target, "https://") {
+		return folderSyncTarget{file: filepath.Join(target, name)}, nil
+	}
+	u, err := url.Parse(target)
+	if err != nil || u.Host == "" {
+		return nil, fmt.Errorf("invalid WebDAV URL %q", target)
+	}
+	if strings.HasSuffix(u.Path, "/") {
+		u.Path += name
+	}
+	return webDAVSyncTarget{url: u, client: &http.Client{Timeout: 10 * time.Second}}, nil
+}
+
+// configStamp identifies version of settings by modification time and machine.
+type configStamp struct {
+	UpdatedAt time.Time `json:"updated_at"`
+	UpdatedBy string    `json:"updated_by"`
+}
+
+func stampOf(c Config) configStamp {
+	return configStamp{UpdatedAt: c.UpdatedAt, UpdatedBy: c.UpdatedBy}
+}
+
+func (a configStamp) equal(b configStamp) bool {
+	return a.UpdatedAt.Equal(b.UpdatedAt) && a.UpdatedBy == b.UpdatedBy
+}
+
+// withMachineLocal returns shared settings with machine-local settings of local, such as paths and pools that
+// differ per machine. Shared copy is written with withMachineLocal(config, Config{}), so they are never shared.
+func withMachineLocal(shared, local Config) Config {
+	shared.MediaFolders = local.MediaFolders
+	shared.HTTPPools = local.HTTPPools
+	return shared
+}
+
+// ConfigSyncStatus is status of settings sync.
+type ConfigSyncStatus struct {
+	Dir       string       `json:"dir"` // shared folder or WebDAV URL.
+	LastSync  time.Time    `json:"last_sync"`
+	UpdatedAt time.Time    `json:"updated_at"`
+	UpdatedBy string       `json:"updated_by"`
+	Conflict  *configStamp `json:"conflict,omitempty"` // shared copy changed on other machine while local settings changed too.
+}
+
+// syncBaseFile stores stamp of shared copy as of last sync next to settings file, so conflicts are detected across restarts.
+func (s *ConfigStore) syncBaseFile() string {
+	return s.path + ".sync"
+}
+
+// EnableSync sets shared folder or WebDAV URL used for settings sync.
+func (s *ConfigStore) EnableSync(target string) error {
+	t, err := newConfigSyncTarget(target, filepath.Base(s.path))
+	if err != nil {
+		return err
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.syncTarget = t
+	s.syncBase = configStamp{}
+	s.conflict = nil
+	if b, err := ioutil.ReadFile(s.syncBaseFile()); err == nil {
+		json.Unmarshal(b, &s.syncBase)
+	}
+	return nil
+}
+
+// SyncStatus returns settings sync status.
+func (s *ConfigStore) SyncStatus() ConfigSyncStatus {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	status := ConfigSyncStatus{LastSync: s.lastSync, UpdatedAt: s.config.UpdatedAt, UpdatedBy: s.config.UpdatedBy}
+	if s.syncTarget != nil {
+		status.Dir = s.syncTarget.String()
+	}
+	if s.conflict != nil {
+		stamp := stampOf(*s.conflict)
+		status.Conflict = &stamp
+	}
+	return status
+}
+
+// Conflict returns local settings and conflicting shared copy, or false if there is no conflict.
+func (s *ConfigStore) Conflict() (Config, Config, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.conflict == nil {
+		return Config{}, Config{}, false
+	}
+	return s.config, *s.conflict, true
+}
+
+// conflictError records remote as conflicting shared copy.
+func (s *ConfigStore) conflictError(remote Config) error {
+	s.conflict = &remote
+	return NewAppError(ErrKindConflict, "", fmt.Errorf("settings were changed by %s at %s since last sync, and on this machine too. Push or pull with force to resolve",
+		remote.UpdatedBy, remote.UpdatedAt.Format(time.RFC3339)))
+}
+
+// synced records stamp of shared copy after push or pull.
+func (s *ConfigStore) synced(stamp configStamp) {
+	s.syncBase = stamp
+	s.conflict = nil
+	s.lastSync = time.Now()
+	if b, err := json.Marshal(stamp); err == nil {
+		if err := ioutil.WriteFile(s.syncBaseFile(), b, 0644); err != nil {
+			log.Printf("Failed to save settings sync state : %v\n", err)
+		}
+	}
+}
+
+// readRemote reads shared copy without holding mu. ok is false if nothing was shared yet.
+func (s *ConfigStore) readRemote() (Config, bool, error) {
+	s.mu.Lock()
+	target := s.syncTarget
+	s.mu.Unlock()
+	b, err := target.Read()
+	if os.IsNotExist(err) {
+		return Config{}, false, nil
+	}
+	if err != nil {
+		return Config{}, false, err
+	}
+	remote := Config{}
+	if err := json.Unmarshal(b, &remote); err != nil {
+		return Config{}, false, fmt.Errorf("shared settings are broken : %v", err)
+	}
+	return remote, true, nil
+}
+
+// Push writes local settings to shared copy. Unless force is true, shared copy changed by other machine since
+// last sync is not overwritten and conflict error is returned instead.
+func (s *ConfigStore) Push(force bool) error {
+	s.syncMu.Lock()
+	defer s.syncMu.Unlock()
+	remote, ok, err := s.readRemote()
+	if err != nil {
+		return err
+	}
+	return s.push(remote, ok, force)
+}
+
+// push writes local settings over remote. Caller must hold syncMu, and mu is held only while settings are read,
+// not while shared copy is written.
+func (s *ConfigStore) push(remote Config, ok, force bool) error {
+	s.mu.Lock()
+	local := stampOf(s.config)
+	if ok && !force {
+		if rs := stampOf(remote); !rs.equal(s.syncBase) && !rs.equal(local) {
+			err := s.conflictError(remote)
+			s.mu.Unlock()
+			return err
+		}
+	}
+	b, err := json.MarshalIndent(withMachineLocal(s.config, Config{}), "", "  ")
+	target := s.syncTarget
+	s.mu.Unlock()
+	if err != nil {
+		return err
+	}
+	if err := target.Write(b); err != nil {
+		return err
+	}
+	s.mu.Lock()
+	s.synced(local)
+	s.mu.Unlock()
+	return nil
+}
+
+// Pull replaces local settings with shared copy if shared copy changed since last sync. Unless force is true,
+// local settings changed since last sync too are kept and conflict error is returned instead.
+// Returns true if local settings were replaced.
+func (s *ConfigStore) Pull(force bool) (bool, error) {
+	s.syncMu.Lock()
+	defer s.syncMu.Unlock()
+	remote, ok, err := s.readRemote()
+	if err != nil {
+		return false, err
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if !ok {
+		return false, &os.PathError{Op: "pull", Path: s.syncTarget.String(), Err: os.ErrNotExist}
+	}
+	return s.pull(remote, force)
+}
+
+// pull replaces local settings with remote. Caller must hold syncMu and mu.
+func (s *ConfigStore) pull(remote Config, force bool) (bool, error) {
+	rs, local := stampOf(remote), stampOf(s.config)
+	if rs.equal(local) {
+		s.synced(rs)
+		return false, nil
+	}
+	if !force {
+		if rs.equal(s.syncBase) {
+			return false, nil
+		}
+		if !local.equal(s.syncBase) && !local.UpdatedAt.IsZero() {
+			return false, s.conflictError(remote)
+		}
+	}
+	s.config = withMachineLocal(remote, s.config)
+	s.normalize()
+	if err := s.save(); err != nil {
+		return false, err
+	}
+	s.synced(rs)
+	return true, nil
+}
+
+// Sync reconciles local settings and shared copy: changes on either side are pushed or pulled,
+// and changes on both sides since last sync are reported as conflict. Returns true if local settings were replaced.
+func (s *ConfigStore) Sync() (bool, error) {
+	s.syncMu.Lock()
+	defer s.syncMu.Unlock()
+	remote, ok, err := s.readRemote()
+	if err != nil {
+		return false, err
+	}
+	s.mu.Lock()
+	if !ok || (stampOf(remote).equal(s.syncBase) && !stampOf(s.config).equal(s.syncBase)) {
+		s.mu.Unlock()
+		return false, s.push(remote, ok, false)
+	}
+	defer s.mu.Unlock()
+	return s.pull(remote, false)
+}
+
+// WatchSync reconciles settings with shared copy every interval.
+func (s *ConfigStore) WatchSync(interval time.Duration) {
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	conflicted := false
+	for range t.C {
+		if !BackgroundActive() {
+			continue
+		}
+		pulled, err := s.Sync()
+		if AsAppError(err).Kind == ErrKindConflict {
+			if !conflicted {
+				log.Printf("Settings sync conflict : %v\n", err)
+				events.Publish(Event{Topic: "config", Data: s.SyncStatus()})
+			}
+			conflicted = true
+			continue
+		}
+		conflicted = false
+		if err != nil {
+			log.Printf("Failed to sync settings : %v\n", err)
+			continue
+		}
+		if pulled {
+			log.Println("Settings updated from shared copy")
+			events.Publish(Event{Topic: "config", Data: s.SyncStatus()})
+		}
+	}
+}
@@ -0,0 +1,190 @@
+package main
+
+import (
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"sync"
+	"testing"
+	"time"
+)
+
+// newSyncedStore loads settings of machine name in dir, synced to target.
+func newSyncedStore(t *testing.T, dir, name, target string) *ConfigStore {
+	t.Helper()
+	if err := os.MkdirAll(filepath.Join(dir, name), 0755); err != nil {
+		t.Fatal(err)
+	}
+	s, err := LoadConfigStore(filepath.Join(dir, name, "vmix_utility.json"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := s.EnableSync(target); err != nil {
+		t.Fatal(err)
+	}
+	return s
+}
+
+func testConcurrentEdits(t *testing.T, target string) {
+	dir, err := ioutil.TempDir("", "vmix_utility_sync")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	a := newSyncedStore(t, dir, "a", target)
+	b := newSyncedStore(t, dir, "b", target)
+
+	if err := a.Update(func(cfg *Config) { cfg.HostGroups["main"] = []string{"http://a:8088"} }); err != nil {
+		t.Fatal(err)
+	}
+	if pulled, err := b.Sync(); err != nil || !pulled {
+		t.Fatalf("expected fresh machine to pull shared settings, got %v %v", pulled, err)
+	}
+
+	// both machines edit between sync ticks.
+	a.Update(func(cfg *Config) { cfg.HostGroups["from_a"] = []string{"http://a:8088"} })
+	b.Update(func(cfg *Config) { cfg.HostGroups["from_b"] = []string{"http://b:8088"} })
+
+	if _, ok := b.Get().HostGroups["from_b"]; !ok {
+		t.Fatal("expected local edit kept on conflict")
+	}
+	if b.SyncStatus().Conflict == nil {
+		t.Fatal("expected conflict recorded when pushing over newer shared copy")
+	}
+	if _, err := b.Sync(); AsAppError(err).Kind != ErrKindConflict {
+		t.Fatalf("expected conflict on sync, got %v", err)
+	}
+	local, remote, ok := b.Conflict()
+	if _, inRemote := remote.HostGroups["from_a"]; !ok || !inRemote || local.HostGroups["from_b"] == nil {
+		t.Fatalf("expected both versions surfaced, got %+v %+v", local.HostGroups, remote.HostGroups)
+	}
+	if pulled, err := a.Sync(); err != nil || pulled {
+		t.Fatalf("expected shared copy of a untouched by b, got %v %v", pulled, err)
+	}
+
+	// b resolves by pulling shared copy.
+	if pulled, err := b.Pull(true); err != nil || !pulled {
+		t.Fatalf("expected forced pull, got %v %v", pulled, err)
+	}
+	if _, ok := b.Get().HostGroups["from_a"]; !ok || b.SyncStatus().Conflict != nil {
+		t.Fatalf("expected conflict resolved, got %+v", b.SyncStatus())
+	}
+	b.Update(func(cfg *Config) { cfg.HostGroups["from_b"] = []string{"http://b:8088"} })
+	if pulled, err := a.Sync(); err != nil || !pulled || a.Get().HostGroups["from_b"] == nil {
+		t.Fatalf("expected edit of b pulled after resolution, got %v %v", pulled, err)
+	}
+}
+
+func TestConfigSyncFolderConflict(t *testing.T) {
+	shared, err := ioutil.TempDir("", "vmix_utility_shared")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(shared)
+	testConcurrentEdits(t, shared)
+}
+
+func TestConfigSyncWebDAVConflict(t *testing.T) {
+	mu := sync.Mutex{}
+	files := map[string][]byte{}
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if u, p, ok := r.BasicAuth(); !ok || u != "operator" || p != "secret" {
+			w.WriteHeader(http.StatusUnauthorized)
+			return
+		}
+		mu.Lock()
+		defer mu.Unlock()
+		switch r.Method {
+		case http.MethodGet:
+			b, ok := files[r.URL.Path]
+			if !ok {
+				w.WriteHeader(http.StatusNotFound)
+				return
+			}
+			w.Write(b)
+		case http.MethodPut:
+			b, _ := ioutil.ReadAll(r.Body)
+			files[r.URL.Path] = b
+			w.WriteHeader(http.StatusCreated)
+		}
+	}))
+	defer srv.Close()
+	target := "http://operator:secret@" + srv.Listener.Addr().String() + "/dav/show/"
+	testConcurrentEdits(t, target)
+	if _, ok := files["/dav/show/vmix_utility.json"]; !ok {
+		t.Fatalf("expected settings file under WebDAV folder, got %v", files)
+	}
+	if s, _ := newConfigSyncTarget(target, "vmix_utility.json"); s.String() != "http://operator@"+srv.Listener.Addr().String()+"/dav/show/vmix_utility.json" {
+		t.Fatalf("expected password hidden, got %s", s)
+	}
+}
+
+func TestConfigSyncKeepsMachineLocalSettings(t *testing.T) {
+	dir, err := ioutil.TempDir("", "vmix_utility_sync")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	shared := filepath.Join(dir, "shared")
+	if err := os.MkdirAll(shared, 0755); err != nil {
+		t.Fatal(err)
+	}
+	a := newSyncedStore(t, dir, "a", shared)
+	b := newSyncedStore(t, dir, "b", shared)
+	b.Update(func(cfg *Config) { cfg.MediaFolders["http://b:8088"] = `D:\media` })
+
+	a.Update(func(cfg *Config) {
+		cfg.MediaFolders["http://a:8088"] = `C:\media`
+		cfg.HostGroups["main"] = []string{"http://a:8088"}
+	})
+	if _, err := b.Pull(true); err != nil {
+		t.Fatal(err)
+	}
+	got := b.Get()
+	if got.HostGroups["main"] == nil || got.MediaFolders["http://a:8088"] != "" || got.MediaFolders["http://b:8088"] != `D:\media` {
+		t.Fatalf("expected shared settings pulled and media folders kept per machine, got %+v %+v", got.HostGroups, got.MediaFolders)
+	}
+}
+
+func TestConfigSyncPushDoesNotBlockReaders(t *testing.T) {
+	release := make(chan struct{})
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		<-release
+		if r.Method == http.MethodGet {
+			w.WriteHeader(http.StatusNotFound)
+		}
+	}))
+	defer srv.Close()
+	defer close(release)
+	dir, err := ioutil.TempDir("", "vmix_utility_sync")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	s := newSyncedStore(t, dir, "a", srv.URL+"/dav/")
+
+	done := make(chan struct{})
+	go func() {
+		defer close(done)
+		s.Update(func(cfg *Config) { cfg.HostGroups["main"] = []string{"http://a:8088"} })
+	}()
+	visible := make(chan struct{})
+	go func() {
+		for s.Get().HostGroups["main"] == nil {
+			time.Sleep(5 * time.Millisecond)
+		}
+		close(visible)
+	}()
+	select {
+	case <-visible:
+	case <-time.After(time.Second):
+		t.Fatal("settings readers blocked by push to shared copy")
+	}
+	select {
+	case <-done:
+		t.Fatal("expected push still in flight")
+	default:
+	}
+}
@@ -0,0 +1,16 @@
+package core
+
+// Core is single value holding core services, so features get them from it instead of package globals.
+type Core struct {
+	Events  *EventHub
+	Latency *LatencyRecorder
+}
+
+// New creates Core.
+func New() *Core {
+	latency := NewLatencyRecorder()
+	return &Core{
+		Events:  NewEventHub(latency),
+		Latency: latency,
+	}
+}
@@ -0,0 +1,7 @@
+// Package core is frontend-agnostic part of vmix-utility backend. It does not depend on HTTP framework or settings,
+// so it can be reused and unit-tested on its own.
+//
+// It holds only event bus and tally path latency tracing so far. Connections, polling and caches are not extracted
+// yet and still live in package main, so core is not usable as standalone library for CLI or headless mode.
+// See doc.go of server for what remains.
+package core
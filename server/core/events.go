@@ -0,0 +1,216 @@
+package core
+
+import (
+	"log"
+	"sync"
+	"time"
+)
+
+// slowSubscriberLimit is number of consecutive dropped events before subscriber is evicted.
+// Evicted clients reconnect and fetch fresh state instead of receiving long stale backlog.
+const slowSubscriberLimit = 128
+
+// eventBacklogSize is number of recent events kept for clients resuming from cursor, such as SSE and long-poll clients.
+const eventBacklogSize = 1024
+
+// Event is message delivered to event subscribers such as WebSocket clients.
+type Event struct {
+	Topic string      `json:"topic"`          // e.g. "acts" .
+	Host  string      `json:"host,omitempty"` // connection name for host scoped events. e.g. "primary" .
+	Data  interface{} `json:"data"`
+	Gen   uint64      `json:"gen"` // generation assigned by EventHub. Snapshots carry generation they reflect.
+
+	Trace *Trace `json:"-"` // set on tally path events to measure latency.
+}
+
+// subscription is state of single subscriber.
+type subscription struct {
+	ch          chan Event
+	client      string                  // e.g. "main" or "history".
+	wants       func(topic string) bool // topics consumed by subscriber. nil for every topic.
+	delivered   uint64
+	dropped     uint64
+	consecutive int // dropped events since last successful delivery.
+}
+
+// SubscriberStats is delivery statistics of subscriber.
+type SubscriberStats struct {
+	Client    string `json:"client"`
+	Queued    int    `json:"queued"`
+	Capacity  int    `json:"capacity"`
+	Delivered uint64 `json:"delivered"`
+	Dropped   uint64 `json:"dropped"`
+}
+
+// EventHubStats is delivery statistics of EventHub.
+type EventHubStats struct {
+	Subscribers []SubscriberStats `json:"subscribers"`
+	Evicted     uint64            `json:"evicted"`
+	Coalesced   uint64            `json:"coalesced"` // events replaced by newer event of same topic and host before delivery.
+}
+
+// EventHub broadcasts events to subscribers. Publish never blocks:
+// when subscriber queue is full oldest queued event is dropped, and subscribers that keep falling behind are evicted.
+type EventHub struct {
+	mu        sync.Mutex
+	subs      map[chan Event]*subscription
+	evicted   uint64
+	gen       uint64 // generation of last published event.
+	rates     map[string]float64
+	throttles map[string]*eventThrottle // topic and host -> throttle.
+	coalesced uint64
+	backlog   []Event // ring of last eventBacklogSize events.
+	next      int     // index of backlog overwritten by next event.
+	latency   *LatencyRecorder
+}
+
+// NewEventHub creates EventHub recording fan-out latency of tally path events to latency. latency may be nil.
+func NewEventHub(latency *LatencyRecorder) *EventHub {
+	return &EventHub{
+		subs:      make(map[chan Event]*subscription),
+		rates:     make(map[string]float64),
+		throttles: make(map[string]*eventThrottle),
+		latency:   latency,
+	}
+}
+
+// Subscribe registers new subscriber receiving every topic. Returned function must be called to unsubscribe.
+// Channel is closed when subscriber is evicted.
+func (h *EventHub) Subscribe(buffer int) (<-chan Event, func()) {
+	return h.SubscribeTo(buffer, "", nil)
+}
+
+// SubscribeTo registers new subscriber receiving only topics wants reports, so producers can skip work nobody consumes.
+// wants may change its answer over time, such as WebSocket client changing subscription.
+func (h *EventHub) SubscribeTo(buffer int, client string, wants func(topic string) bool) (<-chan Event, func()) {
+	ch := make(chan Event, buffer)
+	h.mu.Lock()
+	h.subs[ch] = &subscription{ch: ch, client: client, wants: wants}
+	h.mu.Unlock()
+	return ch, func() {
+		h.mu.Lock()
+		if _, ok := h.subs[ch]; ok {
+			delete(h.subs, ch)
+			close(ch)
+		}
+		h.mu.Unlock()
+	}
+}
+
+// Publish sends event to every subscriber without blocking. Publishers hold mu, so receive of oldest event and send of new one cannot race with other publishers.
+// Events of throttled topics may be delayed and coalesced, see SetRates.
+func (h *EventHub) Publish(ev Event) {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	if h.throttle(ev, time.Now()) {
+		return
+	}
+	h.deliver(ev)
+}
+
+// deliver assigns generation and sends event to subscribers. Caller must hold mu.
+// Tally path events are stamped as emitted here, so time spent waiting for mu or throttled is counted before emit.
+func (h *EventHub) deliver(ev Event) {
+	h.gen++
+	ev.Gen = h.gen
+	if ev.Trace != nil {
+		trace := *ev.Trace
+		trace.Emitted = time.Now()
+		ev.Trace = &trace
+		if h.latency != nil {
+			h.latency.Record(LatencyStageCacheToEmit, trace.Emitted.Sub(trace.Cached))
+		}
+	}
+	if len(h.backlog) < eventBacklogSize {
+		h.backlog = append(h.backlog, ev)
+	} else {
+		h.backlog[h.next] = ev
+		h.next = (h.next + 1) % eventBacklogSize
+	}
+	for ch, sub := range h.subs {
+		if sub.wants != nil && !sub.wants(ev.Topic) {
+			continue
+		}
+		select {
+		case ch <- ev:
+			sub.delivered++
+			sub.consecutive = 0
+			continue
+		default:
+		}
+		// Queue is full. Drop oldest event so subscriber catches up with latest state.
+		sub.dropped++
+		sub.consecutive++
+		if sub.consecutive >= slowSubscriberLimit {
+			log.Printf("Evicting slow event subscriber after %d dropped events\n", sub.consecutive)
+			delete(h.subs, ch)
+			close(ch)
+			h.evicted++
+			continue
+		}
+		select {
+		case <-ch:
+		default:
+		}
+		select {
+		case ch <- ev:
+			sub.delivered++
+		default:
+		}
+	}
+}
+
+// Generation returns generation of last published event.
+func (h *EventHub) Generation() uint64 {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	return h.gen
+}
+
+// Since returns retained events published after generation gen, oldest first.
+// complete is false when events after gen were already discarded, and client should fetch state again.
+func (h *EventHub) Since(gen uint64) (evs []Event, complete bool) {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	evs = []Event{}
+	if gen >= h.gen {
+		return evs, true
+	}
+	ordered := append(append([]Event{}, h.backlog[h.next:]...), h.backlog[:h.next]...)
+	complete = len(ordered) > 0 && ordered[0].Gen <= gen+1
+	for _, ev := range ordered {
+		if ev.Gen > gen {
+			evs = append(evs, ev)
+		}
+	}
+	return evs, complete
+}
+
+// Wanted reports whether any subscriber consumes topic. Periodic producers skip building payloads while false.
+func (h *EventHub) Wanted(topic string) bool {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	for _, sub := range h.subs {
+		if sub.wants == nil || sub.wants(topic) {
+			return true
+		}
+	}
+	return false
+}
+
+// Stats returns delivery statistics.
+func (h *EventHub) Stats() EventHubStats {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	stats := EventHubStats{Subscribers: make([]SubscriberStats, 0, len(h.subs)), Evicted: h.evicted, Coalesced: h.coalesced}
+	for ch, sub := range h.subs {
+		stats.Subscribers = append(stats.Subscribers, SubscriberStats{
+			Client:    sub.client,
+			Queued:    len(ch),
+			Capacity:  cap(ch),
+			Delivered: sub.delivered,
+			Dropped:   sub.dropped,
+		})
+	}
+	return stats
+}
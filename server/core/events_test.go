@@ -0,0 +1,124 @@
+package core
+
+import (
+	"testing"
+	"time"
+)
+
+func TestEventHubDropsOldestAndEvicts(t *testing.T) {
+	h := NewEventHub(nil)
+	ch, unsubscribe := h.Subscribe(2)
+	defer unsubscribe()
+
+	for i := 0; i < 3; i++ {
+		h.Publish(Event{Topic: "test", Data: i})
+	}
+	if ev := <-ch; ev.Data != 1 {
+		t.Fatalf("expected oldest event to be dropped, got %+v", ev)
+	}
+	if ev := <-ch; ev.Data != 2 {
+		t.Fatalf("unexpected event %+v", ev)
+	}
+
+	for i := 0; i < 2+slowSubscriberLimit; i++ {
+		h.Publish(Event{Topic: "test", Data: i})
+	}
+	for range ch {
+	}
+	stats := h.Stats()
+	if len(stats.Subscribers) != 0 || stats.Evicted != 1 {
+		t.Fatalf("slow subscriber not evicted: %+v", stats)
+	}
+}
+
+func TestEventHubSkipsUnwantedTopics(t *testing.T) {
+	h := NewEventHub(nil)
+	if h.Wanted("clock") {
+		t.Fatal("expected no consumer without subscribers")
+	}
+	ch, unsubscribe := h.SubscribeTo(2, "main", func(topic string) bool { return topic == "status" })
+	defer unsubscribe()
+	if h.Wanted("clock") || !h.Wanted("status") {
+		t.Fatal("unexpected wanted topics")
+	}
+	h.Publish(Event{Topic: "clock"})
+	h.Publish(Event{Topic: "status"})
+	if ev := <-ch; ev.Topic != "status" {
+		t.Fatalf("expected only status event, got %+v", ev)
+	}
+	if s := h.Stats().Subscribers[0]; s.Client != "main" || s.Delivered != 1 {
+		t.Fatalf("unexpected stats %+v", s)
+	}
+}
+
+func TestEventHubCoalescesThrottledTopics(t *testing.T) {
+	h := NewEventHub(nil)
+	h.SetRates(map[string]float64{"status": 20})
+	ch, unsubscribe := h.Subscribe(16)
+	defer unsubscribe()
+
+	for i := 0; i < 3; i++ {
+		h.Publish(Event{Topic: "status", Host: "primary", Data: i})
+	}
+	h.Publish(Event{Topic: "acts", Data: "Input 1 1"})
+	want := []interface{}{0, "Input 1 1", 2}
+	for _, data := range want {
+		select {
+		case ev := <-ch:
+			if ev.Data != data {
+				t.Fatalf("expected %v, got %+v", data, ev)
+			}
+		case <-time.After(time.Second):
+			t.Fatalf("expected %v to be delivered", data)
+		}
+	}
+	if stats := h.Stats(); stats.Coalesced != 1 {
+		t.Fatalf("expected 1 coalesced event, got %+v", stats)
+	}
+}
+
+func TestEventHubBacklog(t *testing.T) {
+	h := NewEventHub(nil)
+	for i := 0; i < 3; i++ {
+		h.Publish(Event{Topic: "test", Data: i})
+	}
+	evs, complete := h.Since(1)
+	if !complete || len(evs) != 2 || evs[0].Gen != 2 || evs[1].Data != 2 {
+		t.Fatalf("expected events after cursor, got %+v %v", evs, complete)
+	}
+	for i := 0; i < eventBacklogSize; i++ {
+		h.Publish(Event{Topic: "test", Data: i})
+	}
+	if evs, complete = h.Since(1); complete || len(evs) != eventBacklogSize || evs[0].Gen != 4 {
+		t.Fatalf("expected discarded events to be reported, got %d events from %d, %v", len(evs), evs[0].Gen, complete)
+	}
+	if evs, complete = h.Since(h.Generation()); !complete || len(evs) != 0 {
+		t.Fatalf("expected no events at current cursor, got %+v", evs)
+	}
+}
+
+func TestEventHubStampsTraceAtFanOut(t *testing.T) {
+	latency := NewLatencyRecorder()
+	h := NewEventHub(latency)
+	h.SetRates(map[string]float64{"acts": 10})
+	ch, unsubscribe := h.Subscribe(16)
+	defer unsubscribe()
+
+	h.Publish(Event{Topic: "acts", Host: "primary", Data: 0})
+	<-ch
+	cached := time.Now()
+	trace := &Trace{Received: cached, Cached: cached}
+	// held by throttle until interval passes, so emit is stamped when it is actually delivered.
+	h.Publish(Event{Topic: "acts", Host: "primary", Data: 1, Trace: trace})
+	select {
+	case ev := <-ch:
+		if ev.Trace == nil || ev.Trace.Emitted.Sub(cached) < 50*time.Millisecond || !trace.Emitted.IsZero() {
+			t.Fatalf("expected copy of trace stamped at delivery, got %+v", ev.Trace)
+		}
+		if stage := latency.Report().Stages[1]; stage.Stage != LatencyStageCacheToEmit || stage.Count != 1 {
+			t.Fatalf("expected fan-out latency recorded, got %+v", stage)
+		}
+	case <-time.After(time.Second):
+		t.Fatal("expected throttled event to be delivered")
+	}
+}
@@ -0,0 +1,84 @@
+package core
+
+import "time"
+
+// DefaultEventRates are maximum events per second of state topics, where only latest event matters.
+// Topics such as "acts" and "trigger" are never coalesced, since every event is meaningful.
+var DefaultEventRates = map[string]float64{
+	"status":        10,
+	"outputs":       10,
+	"dynamic":       10,
+	"clock":         10,
+	"stream_health": 10,
+}
+
+// eventThrottle is delivery state of single topic and host.
+type eventThrottle struct {
+	last    time.Time // last delivery.
+	pending *Event    // latest event waiting for interval, replaced by newer events.
+}
+
+// SetRates sets maximum events per second by topic. Events published faster are coalesced,
+// delivering latest event of same topic and host once interval passed. 0 or missing topic is unlimited.
+func (h *EventHub) SetRates(rates map[string]float64) {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	h.rates = make(map[string]float64, len(rates))
+	for topic, rate := range rates {
+		if rate > 0 {
+			h.rates[topic] = rate
+		}
+	}
+}
+
+// Rates returns maximum events per second by topic.
+func (h *EventHub) Rates() map[string]float64 {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	rates := make(map[string]float64, len(h.rates))
+	for topic, rate := range h.rates {
+		rates[topic] = rate
+	}
+	return rates
+}
+
+// throttle reports whether event is held for later delivery. Caller must hold mu.
+func (h *EventHub) throttle(ev Event, now time.Time) bool {
+	rate, ok := h.rates[ev.Topic]
+	if !ok {
+		return false
+	}
+	interval := time.Duration(float64(time.Second) / rate)
+	key := ev.Topic + "\x00" + ev.Host
+	t, ok := h.throttles[key]
+	if !ok {
+		t = &eventThrottle{}
+		h.throttles[key] = t
+	}
+	if t.pending != nil {
+		t.pending = &ev
+		h.coalesced++
+		return true
+	}
+	if wait := interval - now.Sub(t.last); wait > 0 {
+		t.pending = &ev
+		time.AfterFunc(wait, func() { h.flush(key) })
+		return true
+	}
+	t.last = now
+	return false
+}
+
+// flush delivers pending event of throttle key.
+func (h *EventHub) flush(key string) {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	t, ok := h.throttles[key]
+	if !ok || t.pending == nil {
+		return
+	}
+	ev := *t.pending
+	t.pending = nil
+	t.last = time.Now()
+	h.deliver(ev)
+}
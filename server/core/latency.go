@@ -0,0 +1,115 @@
+package core
+
+import (
+	"sort"
+	"sync"
+	"time"
+)
+
+// TallyLatencyBudget is target time from ACTS receipt to WebSocket send.
+const TallyLatencyBudget = 50 * time.Millisecond
+
+// latencySamples is number of samples kept per stage.
+const latencySamples = 1024
+
+// Tally path stages.
+const (
+	LatencyStageReceiveToCache = "receive_to_cache" // ACTS receipt -> cache update.
+	LatencyStageCacheToEmit    = "cache_to_emit"    // cache update -> event fan-out to subscribers.
+	LatencyStageEmitToSend     = "emit_to_send"     // event fan-out -> WebSocket send.
+	LatencyStageTotal          = "total"            // ACTS receipt -> WebSocket send.
+)
+
+var latencyStages = []string{LatencyStageReceiveToCache, LatencyStageCacheToEmit, LatencyStageEmitToSend, LatencyStageTotal}
+
+// Trace holds timestamps of single event on tally path.
+type Trace struct {
+	Received time.Time
+	Cached   time.Time
+	Emitted  time.Time // stamped by EventHub at fan-out.
+}
+
+// LatencyRecorder keeps recent latency samples per stage in ring buffers.
+type LatencyRecorder struct {
+	mu      sync.Mutex
+	samples map[string][]time.Duration
+	next    map[string]int
+	counts  map[string]int
+}
+
+// NewLatencyRecorder creates LatencyRecorder.
+func NewLatencyRecorder() *LatencyRecorder {
+	return &LatencyRecorder{
+		samples: make(map[string][]time.Duration),
+		next:    make(map[string]int),
+		counts:  make(map[string]int),
+	}
+}
+
+// Record adds sample for stage.
+func (l *LatencyRecorder) Record(stage string, d time.Duration) {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	l.counts[stage]++
+	if len(l.samples[stage]) < latencySamples {
+		l.samples[stage] = append(l.samples[stage], d)
+		return
+	}
+	l.samples[stage][l.next[stage]] = d
+	l.next[stage] = (l.next[stage] + 1) % latencySamples
+}
+
+// LatencyStageReport is statistics of single stage.
+type LatencyStageReport struct {
+	Stage string  `json:"stage"`
+	Count int     `json:"count"` // total samples since start.
+	AvgMs float64 `json:"avg_ms"`
+	P50Ms float64 `json:"p50_ms"`
+	P95Ms float64 `json:"p95_ms"`
+	MaxMs float64 `json:"max_ms"`
+}
+
+// LatencyReport is tally path latency report.
+type LatencyReport struct {
+	BudgetMs     float64              `json:"budget_ms"`
+	WithinBudget float64              `json:"within_budget"` // ratio of recent total samples within budget. 0-1.
+	Stages       []LatencyStageReport `json:"stages"`
+}
+
+// Report returns statistics over recent samples.
+func (l *LatencyRecorder) Report() LatencyReport {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	report := LatencyReport{
+		BudgetMs: durationMs(TallyLatencyBudget),
+		Stages:   make([]LatencyStageReport, 0, len(latencyStages)),
+	}
+	for _, stage := range latencyStages {
+		samples := append([]time.Duration(nil), l.samples[stage]...)
+		sort.Slice(samples, func(i, j int) bool { return samples[i] < samples[j] })
+		r := LatencyStageReport{Stage: stage, Count: l.counts[stage]}
+		if len(samples) > 0 {
+			var sum time.Duration
+			within := 0
+			for _, d := range samples {
+				sum += d
+				if d <= TallyLatencyBudget {
+					within++
+				}
+			}
+			r.AvgMs = durationMs(sum / time.Duration(len(samples)))
+			r.P50Ms = durationMs(samples[len(samples)*50/100])
+			r.P95Ms = durationMs(samples[len(samples)*95/100])
+			r.MaxMs = durationMs(samples[len(samples)-1])
+			if stage == LatencyStageTotal {
+				report.WithinBudget = float64(within) / float64(len(samples))
+			}
+		}
+		report.Stages = append(report.Stages, r)
+	}
+	return report
+}
+
+func durationMs(d time.Duration) float64 {
+	return float64(d) / float64(time.Millisecond)
+}
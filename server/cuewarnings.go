@@ -0,0 +1,139 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// CueWarningSettings is vMix title input showing next timecode cue and countdown to it, e.g. on talent monitors.
+type CueWarningSettings struct {
+	Host           string `json:"host"`            // vMix host. Primary vMix host is used if empty.
+	Input          string `json:"input"`           // title input. Empty to disable.
+	NameField      string `json:"name_field"`      // text field for cue name. e.g. "NextCue.Text" .
+	CountdownField string `json:"countdown_field"` // text field for countdown. e.g. "Countdown.Text" .
+	IntervalMs     int    `json:"interval_ms"`     // update interval. Default: 500 .
+	IdleText       string `json:"idle_text"`       // name shown when no cue is upcoming.
+}
+
+// CueWarnings writes next cue name and countdown into vMix title. Text is sent only when it changes.
+type CueWarnings struct {
+	mu       sync.Mutex
+	settings CueWarningSettings
+	lastPush time.Time
+	texts    map[string]string // field -> last sent text.
+}
+
+// NewCueWarnings creates disabled CueWarnings.
+func NewCueWarnings() *CueWarnings {
+	return &CueWarnings{texts: make(map[string]string)}
+}
+
+// cueWarningTexts returns cue name and countdown shown for next cue.
+func cueWarningTexts(settings CueWarningSettings, cue TimecodeCue, remaining time.Duration, ok bool) (string, string) {
+	if !ok {
+		return settings.IdleText, ""
+	}
+	name := cue.Name
+	if name == "" {
+		name = cue.ID
+	}
+	return name, formatClock(remaining, true)
+}
+
+// Configure replaces settings. Texts are sent again on next tick.
+func (w *CueWarnings) Configure(settings CueWarningSettings) error {
+	if settings.Input != "" && settings.NameField == "" && settings.CountdownField == "" {
+		return NewAppError(ErrKindInvalidRequest, settings.Host, fmt.Errorf("name_field or countdown_field is required"))
+	}
+	if settings.IntervalMs < 0 {
+		return NewAppError(ErrKindInvalidRequest, settings.Host, fmt.Errorf("invalid interval %dms", settings.IntervalMs))
+	}
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	w.settings = settings
+	w.texts = make(map[string]string)
+	return nil
+}
+
+// Settings returns current settings.
+func (w *CueWarnings) Settings() CueWarningSettings {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	return w.settings
+}
+
+// tick pushes texts when interval passed.
+func (w *CueWarnings) tick(now time.Time) {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	st := w.settings
+	if st.Input == "" {
+		return
+	}
+	interval := time.Duration(st.IntervalMs) * time.Millisecond
+	if interval <= 0 {
+		interval = 500 * time.Millisecond
+	}
+	if now.Sub(w.lastPush) < interval {
+		return
+	}
+	w.lastPush = now
+	host := st.Host
+	if host == "" {
+		host = *vmixaddr
+	}
+	cue, remaining, ok := timecode.NextCue()
+	name, countdown := cueWarningTexts(st, cue, remaining, ok)
+	for field, text := range map[string]string{st.NameField: name, st.CountdownField: countdown} {
+		if prev, sent := w.texts[field]; field == "" || (sent && prev == text) {
+			continue
+		}
+		params := map[string]string{"Input": st.Input, "SelectedName": field, "Value": text}
+		if err := SendFunctionTo(host, "SetText", params); err != nil {
+			log.Printf("Failed to update cue warning title %s : %v\n", st.Input, err)
+			continue
+		}
+		w.texts[field] = text
+	}
+}
+
+// Run checks cue warnings every tick.
+func (w *CueWarnings) Run(tick time.Duration) {
+	t := time.NewTicker(tick)
+	defer t.Stop()
+	for now := range t.C {
+		w.tick(now)
+	}
+}
+
+// GetCueWarningsHandler returns cue warning settings for [GET] /api/timecode/warnings .
+func GetCueWarningsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"warnings": cueWarnings.Settings(),
+	})
+}
+
+// PutCueWarningsHandler saves cue warning settings for [PUT] /api/timecode/warnings .
+func PutCueWarningsHandler(c *gin.Context) {
+	settings := CueWarningSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := cueWarnings.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.CueWarnings = settings
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetCueWarningsHandler(c)
+}
@@ -0,0 +1,455 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"io/ioutil"
+	"log"
+	"math"
+	"net/http"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Data feed sources. URL feed is polled, and push feed is posted to /api/feeds/ingest/:name by data provider,
+// such as game state integration of game client, or by plugin.
+const (
+	DataFeedSourceURL  = "url"
+	DataFeedSourcePush = "push"
+)
+
+// dataFeedMinInterval is shortest poll interval of URL feed, to avoid hammering tournament APIs.
+const dataFeedMinInterval = 250 * time.Millisecond
+
+// maxDataFeedSize is largest feed document accepted.
+const maxDataFeedSize = 4 << 20
+
+// FeedMapping maps field of feed document to title text or graphic field.
+type FeedMapping struct {
+	Path       string   `json:"path"`       // dot separated path in JSON document. e.g. "map.team_ct.score" or "players.0.name" .
+	Field      string   `json:"field"`      // title text name, e.g. "ScoreCT.Text", or graphic field, e.g. "home_score" .
+	Transforms []string `json:"transforms"` // applied in order. e.g. ["default:0", "prefix:CT "] .
+}
+
+// DataFeed is JSON data feed mapped into title input and/or graphic, such as esports scoreboard.
+type DataFeed struct {
+	Source     string            `json:"source"`      // DataFeedSourceURL or DataFeedSourcePush.
+	URL        string            `json:"url"`         // polled URL of URL feed.
+	Headers    map[string]string `json:"headers"`     // request headers of URL feed. e.g. API key .
+	IntervalMs int               `json:"interval_ms"` // poll interval of URL feed.
+	Host       string            `json:"host"`        // vMix host of title input. Primary vMix host is used if empty.
+	Input      string            `json:"input"`       // title input updated by SetText. Empty to disable.
+	Graphic    string            `json:"graphic"`     // graphic whose fields are updated. Empty to disable.
+	Mappings   []FeedMapping     `json:"mappings"`
+}
+
+// DataFeedStatus is ingest status of feed.
+type DataFeedStatus struct {
+	Updated time.Time         `json:"updated"` // last document applied.
+	Error   string            `json:"error"`   // error of last document. Empty on success.
+	Values  map[string]string `json:"values"`  // field -> value last applied.
+}
+
+// Validate checks feed settings.
+func (f DataFeed) Validate() error {
+	switch f.Source {
+	case DataFeedSourceURL:
+		if !strings.HasPrefix(f.URL, "http://") && !strings.HasPrefix(f.URL, "https://") {
+			return fmt.Errorf("feed URL must be http or https")
+		}
+	case DataFeedSourcePush:
+	default:
+		return fmt.Errorf("unknown feed source %q", f.Source)
+	}
+	for _, m := range f.Mappings {
+		if m.Path == "" || m.Field == "" {
+			return fmt.Errorf("mapping needs path and field")
+		}
+		for _, t := range m.Transforms {
+			if _, err := applyFeedTransform("", t); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+// FeedValue returns value at dot separated path of decoded JSON document. Numbers decoded as json.Number keep their
+// text, such as 64-bit player IDs, and objects and arrays are returned as JSON.
+func FeedValue(doc interface{}, path string) (string, bool) {
+	v := doc
+	for _, key := range strings.Split(path, ".") {
+		switch node := v.(type) {
+		case map[string]interface{}:
+			child, ok := node[key]
+			if !ok {
+				return "", false
+			}
+			v = child
+		case []interface{}:
+			i, err := strconv.Atoi(key)
+			if err != nil || i < 0 || i >= len(node) {
+				return "", false
+			}
+			v = node[i]
+		default:
+			return "", false
+		}
+	}
+	switch value := v.(type) {
+	case nil:
+		return "", true
+	case string:
+		return value, true
+	case json.Number:
+		return value.String(), true
+	case float64:
+		return strconv.FormatFloat(value, 'f', -1, 64), true
+	case bool:
+		return strconv.FormatBool(value), true
+	}
+	b, _ := json.Marshal(v)
+	return string(b), true
+}
+
+// applyFeedTransform applies single transformation rule "op" or "op:arg" to value.
+//
+//	upper, lower, trim        change case or trim spaces.
+//	default:x                 x if value is empty.
+//	prefix:x, suffix:x        add x before or after non-empty value.
+//	truncate:n                keep first n characters.
+//	replace:old=new           replace every old with new.
+//	map:a=x;b=y               replace whole value a with x, b with y, or keep value.
+//	round:n                   round number to n decimals.
+//	clock                     format seconds as m:ss. e.g. "95" -> "1:35" .
+func applyFeedTransform(value, rule string) (string, error) {
+	op, arg := rule, ""
+	if i := strings.IndexByte(rule, ':'); i >= 0 {
+		op, arg = rule[:i], rule[i+1:]
+	}
+	switch op {
+	case "upper":
+		return strings.ToUpper(value), nil
+	case "lower":
+		return strings.ToLower(value), nil
+	case "trim":
+		return strings.TrimSpace(value), nil
+	case "default":
+		if value == "" {
+			return arg, nil
+		}
+		return value, nil
+	case "prefix":
+		if value == "" {
+			return value, nil
+		}
+		return arg + value, nil
+	case "suffix":
+		if value == "" {
+			return value, nil
+		}
+		return value + arg, nil
+	case "truncate":
+		n, err := strconv.Atoi(arg)
+		if err != nil || n < 0 {
+			return "", fmt.Errorf("invalid transform %q", rule)
+		}
+		if r := []rune(value); len(r) > n {
+			return string(r[:n]), nil
+		}
+		return value, nil
+	case "replace":
+		kv := strings.SplitN(arg, "=", 2)
+		if len(kv) != 2 || kv[0] == "" {
+			return "", fmt.Errorf("invalid transform %q", rule)
+		}
+		return strings.ReplaceAll(value, kv[0], kv[1]), nil
+	case "map":
+		for _, pair := range strings.Split(arg, ";") {
+			kv := strings.SplitN(pair, "=", 2)
+			if len(kv) != 2 {
+				return "", fmt.Errorf("invalid transform %q", rule)
+			}
+			if kv[0] == value {
+				return kv[1], nil
+			}
+		}
+		return value, nil
+	case "round":
+		n, err := strconv.Atoi(arg)
+		if err != nil || n < 0 {
+			return "", fmt.Errorf("invalid transform %q", rule)
+		}
+		f, err := strconv.ParseFloat(value, 64)
+		if err != nil {
+			return value, nil
+		}
+		return strconv.FormatFloat(f, 'f', n, 64), nil
+	case "clock":
+		f, err := strconv.ParseFloat(value, 64)
+		if err != nil {
+			return value, nil
+		}
+		s := int(math.Max(0, math.Ceil(f)))
+		return fmt.Sprintf("%d:%02d", s/60, s%60), nil
+	}
+	return "", fmt.Errorf("unknown transform %q", rule)
+}
+
+// MapFeed returns fields of feed document with transformations applied. Mappings whose path is missing are skipped.
+func MapFeed(feed DataFeed, doc interface{}) (map[string]string, error) {
+	fields := map[string]string{}
+	for _, m := range feed.Mappings {
+		value, ok := FeedValue(doc, m.Path)
+		if !ok {
+			continue
+		}
+		for _, t := range m.Transforms {
+			var err error
+			if value, err = applyFeedTransform(value, t); err != nil {
+				return nil, err
+			}
+		}
+		fields[m.Field] = value
+	}
+	return fields, nil
+}
+
+// DataFeeds ingests feed documents and applies changed fields. URL feeds are polled while configured.
+type DataFeeds struct {
+	mu      sync.Mutex
+	status  map[string]*DataFeedStatus
+	pollers map[string]chan struct{} // feed name -> stop channel of URL poller.
+	client  *http.Client
+}
+
+// NewDataFeeds creates DataFeeds.
+func NewDataFeeds() *DataFeeds {
+	return &DataFeeds{
+		status:  make(map[string]*DataFeedStatus),
+		pollers: make(map[string]chan struct{}),
+		client:  &http.Client{Timeout: 5 * time.Second},
+	}
+}
+
+// Configure starts and stops URL pollers to match feeds. Every poller restarts with new settings.
+func (d *DataFeeds) Configure(feeds map[string]DataFeed) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	for name, stop := range d.pollers {
+		close(stop)
+		delete(d.pollers, name)
+	}
+	for name := range d.status {
+		if _, ok := feeds[name]; !ok {
+			delete(d.status, name)
+		}
+	}
+	for name, feed := range feeds {
+		if feed.Source != DataFeedSourceURL {
+			continue
+		}
+		stop := make(chan struct{})
+		d.pollers[name] = stop
+		go d.poll(name, feed, stop)
+	}
+}
+
+// poll fetches URL feed every interval until stopped.
+func (d *DataFeeds) poll(name string, feed DataFeed, stop chan struct{}) {
+	interval := time.Duration(feed.IntervalMs) * time.Millisecond
+	if interval < dataFeedMinInterval {
+		interval = dataFeedMinInterval
+	}
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	for {
+		b, err := d.fetch(feed)
+		if err == nil {
+			err = d.Ingest(name, b)
+		} else {
+			d.setError(name, err)
+		}
+		if err != nil {
+			log.Printf("Data feed %s : %v\n", name, err)
+		}
+		select {
+		case <-stop:
+			return
+		case <-t.C:
+		}
+	}
+}
+
+func (d *DataFeeds) fetch(feed DataFeed) ([]byte, error) {
+	req, err := http.NewRequest(http.MethodGet, feed.URL, nil)
+	if err != nil {
+		return nil, err
+	}
+	for k, v := range feed.Headers {
+		req.Header.Set(k, v)
+	}
+	resp, err := d.client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("feed responded %s", resp.Status)
+	}
+	return ioutil.ReadAll(io.LimitReader(resp.Body, maxDataFeedSize))
+}
+
+func (d *DataFeeds) setError(name string, err error) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	st := d.statusOf(name)
+	st.Error = err.Error()
+}
+
+// statusOf returns status of feed, creating it. Caller must hold mu.
+func (d *DataFeeds) statusOf(name string) *DataFeedStatus {
+	st, ok := d.status[name]
+	if !ok {
+		st = &DataFeedStatus{Values: map[string]string{}}
+		d.status[name] = st
+	}
+	return st
+}
+
+// Ingest applies feed document to title input and graphic of feed. Only fields changed since last document are sent.
+func (d *DataFeeds) Ingest(name string, b []byte) error {
+	feed, ok := config.Get().DataFeeds[name]
+	if !ok {
+		return NewAppError(ErrKindNotFound, "", fmt.Errorf("data feed %q not found", name))
+	}
+	var doc interface{}
+	dec := json.NewDecoder(bytes.NewReader(b))
+	dec.UseNumber()
+	err := dec.Decode(&doc)
+	var fields map[string]string
+	if err == nil {
+		fields, err = MapFeed(feed, doc)
+	}
+	if err != nil {
+		err = NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("data feed %s : %w", name, err))
+		d.setError(name, err)
+		return err
+	}
+
+	d.mu.Lock()
+	st := d.statusOf(name)
+	changed := map[string]string{}
+	for field, value := range fields {
+		if prev, ok := st.Values[field]; !ok || prev != value {
+			changed[field] = value
+			st.Values[field] = value
+		}
+	}
+	st.Updated = time.Now()
+	st.Error = ""
+	d.mu.Unlock()
+	if len(changed) == 0 {
+		return nil
+	}
+
+	if feed.Graphic != "" {
+		graphics.Update(feed.Graphic, func(g *Graphic) {
+			for field, value := range changed {
+				g.Fields[field] = value
+			}
+		})
+	}
+	if feed.Input != "" {
+		host := feed.Host
+		if host == "" {
+			host = *vmixaddr
+		}
+		names := make([]string, 0, len(changed))
+		for field := range changed {
+			names = append(names, field)
+		}
+		sort.Strings(names)
+		for _, field := range names {
+			params := map[string]string{"Input": feed.Input, "SelectedName": field, "Value": changed[field]}
+			if err := SendFunctionTo(host, "SetText", params); err != nil {
+				// Forget value so next document retries it.
+				d.mu.Lock()
+				delete(d.statusOf(name).Values, field)
+				d.mu.Unlock()
+				log.Printf("Failed to update data feed %s title field %s : %v\n", name, field, err)
+			}
+		}
+	}
+	return nil
+}
+
+// Status returns ingest status of every feed.
+func (d *DataFeeds) Status() map[string]DataFeedStatus {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	status := make(map[string]DataFeedStatus, len(d.status))
+	for name, st := range d.status {
+		c := *st
+		c.Values = make(map[string]string, len(st.Values))
+		for k, v := range st.Values {
+			c.Values[k] = v
+		}
+		status[name] = c
+	}
+	return status
+}
+
+// GetDataFeedsHandler returns data feeds and their status for [GET] /api/feeds .
+func GetDataFeedsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"feeds":  config.Get().DataFeeds,
+		"status": dataFeeds.Status(),
+	})
+}
+
+// PutDataFeedsHandler saves data feeds for [PUT] /api/feeds .
+func PutDataFeedsHandler(c *gin.Context) {
+	feeds := map[string]DataFeed{}
+	if err := c.ShouldBindJSON(&feeds); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for name, feed := range feeds {
+		if err := feed.Validate(); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, feed.Host, fmt.Errorf("data feed %q : %w", name, err)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.DataFeeds = feeds
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	dataFeeds.Configure(feeds)
+	GetDataFeedsHandler(c)
+}
+
+// IngestDataFeedHandler applies posted JSON document to push feed for [POST] /api/feeds/ingest/:name .
+func IngestDataFeedHandler(c *gin.Context) {
+	b, err := ioutil.ReadAll(http.MaxBytesReader(c.Writer, c.Request.Body, maxDataFeedSize))
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := dataFeeds.Ingest(c.Param("name"), b); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"status": dataFeeds.Status()[c.Param("name")],
+	})
+}
@@ -0,0 +1,69 @@
+package main
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
+
+func TestMapFeed(t *testing.T) {
+	feed := DataFeed{Mappings: []FeedMapping{
+		{Path: "map.team_ct.name", Field: "TeamCT.Text", Transforms: []string{"upper", "truncate:4"}},
+		{Path: "map.team_ct.score", Field: "ScoreCT.Text"},
+		{Path: "map.phase", Field: "Phase.Text", Transforms: []string{"map:live=LIVE;warmup=WARMUP"}},
+		{Path: "round.clock", Field: "Clock.Text", Transforms: []string{"clock"}},
+		{Path: "players.1.steamid", Field: "Player.Text"},
+		{Path: "map.missing", Field: "Missing.Text"},
+	}}
+	dec := json.NewDecoder(strings.NewReader(`{"map":{"team_ct":{"name":"Natus Vincere","score":12},"phase":"live"},"round":{"clock":95},"players":[{},{"steamid":76561197960287930}]}`))
+	dec.UseNumber()
+	var doc interface{}
+	if err := dec.Decode(&doc); err != nil {
+		t.Fatal(err)
+	}
+	fields, err := MapFeed(feed, doc)
+	if err != nil {
+		t.Fatal(err)
+	}
+	expected := map[string]string{"TeamCT.Text": "NATU", "ScoreCT.Text": "12", "Phase.Text": "LIVE", "Clock.Text": "1:35", "Player.Text": "76561197960287930"}
+	if len(fields) != len(expected) {
+		t.Fatalf("expected %v, got %v", expected, fields)
+	}
+	for k, v := range expected {
+		if fields[k] != v {
+			t.Fatalf("expected %s = %q, got %q", k, v, fields[k])
+		}
+	}
+	if err := (DataFeed{Source: DataFeedSourcePush, Mappings: []FeedMapping{{Path: "a", Field: "b", Transforms: []string{"shout"}}}}).Validate(); err == nil {
+		t.Fatal("expected unknown transform refused")
+	}
+}
+
+func TestIngestDataFeed(t *testing.T) {
+	m := startMock(t)
+	feeds := NewDataFeeds()
+	config.Update(func(cfg *Config) {
+		cfg.DataFeeds["datafeed_test"] = DataFeed{Source: DataFeedSourcePush, Host: "http://" + m.HTTPAddr, Input: "1", Graphic: "datafeed_test", Mappings: []FeedMapping{
+			{Path: "home", Field: "Home.Text"},
+			{Path: "away", Field: "Away.Text"},
+		}}
+	})
+	defer config.Update(func(cfg *Config) { delete(cfg.DataFeeds, "datafeed_test") })
+
+	if err := feeds.Ingest("datafeed_test", []byte(`{"home":1,"away":0}`)); err != nil {
+		t.Fatal(err)
+	}
+	if err := feeds.Ingest("datafeed_test", []byte(`{"home":2,"away":0}`)); err != nil {
+		t.Fatal(err)
+	}
+	calls := m.Calls()
+	if len(calls) != 3 || calls[2].Params["SelectedName"] != "Home.Text" || calls[2].Params["Value"] != "2" {
+		t.Fatalf("expected only changed field sent again, got %+v", calls)
+	}
+	if g, ok := graphics.Get("datafeed_test"); !ok || g.Fields["Home.Text"] != "2" || g.Fields["Away.Text"] != "0" {
+		t.Fatalf("expected graphic fields updated, got %+v", g)
+	}
+	if err := feeds.Ingest("datafeed_test", []byte(`{`)); err == nil || feeds.Status()["datafeed_test"].Error == "" {
+		t.Fatalf("expected invalid document reported, got %v", err)
+	}
+}
@@ -0,0 +1,148 @@
+package main
+
+import (
+	"fmt"
+	"net"
+	"net/http"
+	"net/url"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// deepLinkScheme is URL scheme of deep links. e.g. "vmix-utility://output/program" .
+// Scheme is registered on Windows by [PUT] /api/deeplink , which opens http://localhost:8080/open/?url=%1 in browser.
+// Pin /open/... links directly on other OS.
+const deepLinkScheme = "vmix-utility"
+
+// deepLinkCommand returns command line registered for scheme. Windows replaces %1 by link, which is opened in
+// default browser through /open/ of this server.
+func deepLinkCommand() string {
+	_, port, err := net.SplitHostPort(*hostaddr)
+	if err != nil || port == "" {
+		port = "8080"
+	}
+	return "rundll32 url.dll,FileProtocolHandler http://localhost:" + port + *basePath + "/open/?url=%1"
+}
+
+// DeepLink is parsed deep link to frontend view or action.
+type DeepLink struct {
+	Target string     `json:"target"` // "generator", "tree", "developer", "output", "graphics", "inputs" or "shortcut".
+	Name   string     `json:"name"`   // output bus, graphic or shortcut name.
+	Query  url.Values `json:"query"`  // passed to view. e.g. host and key for "inputs" .
+}
+
+// ParseDeepLink parses "vmix-utility://target/name?query" or path form "/target/name?query".
+func ParseDeepLink(raw string) (DeepLink, error) {
+	u, err := url.Parse(raw)
+	if err != nil {
+		return DeepLink{}, err
+	}
+	path := u.Path
+	switch u.Scheme {
+	case deepLinkScheme:
+		// Host part of "vmix-utility://output/program" is target.
+		path = u.Host + u.Path
+	case "":
+	default:
+		return DeepLink{}, fmt.Errorf("unknown scheme %q", u.Scheme)
+	}
+	parts := strings.SplitN(strings.Trim(path, "/"), "/", 2)
+	link := DeepLink{Target: parts[0], Query: u.Query()}
+	if len(parts) == 2 {
+		link.Name, _ = url.PathUnescape(parts[1])
+	}
+	switch link.Target {
+	case "", "generator":
+		link.Target = "generator"
+	case "tree", "developer", "inputs":
+	case "output", "graphics", "shortcut":
+		if link.Name == "" {
+			return DeepLink{}, fmt.Errorf("%s link requires name. e.g. %s://%s/name", link.Target, deepLinkScheme, link.Target)
+		}
+	default:
+		return DeepLink{}, fmt.Errorf("unknown deep link target %q", link.Target)
+	}
+	return link, nil
+}
+
+// Route returns frontend route of link. e.g. "/output/program" .
+// "inputs" opens input tree of host+key given by query. "shortcut" opens confirmation view, which runs shortcut
+// only when operator confirms it, so opening link never sends function by itself.
+func (l DeepLink) Route() string {
+	route := "/"
+	switch l.Target {
+	case "tree", "inputs":
+		route = "/tree"
+	case "developer":
+		route = "/developer"
+	case "output", "graphics", "shortcut":
+		route = "/" + l.Target + "/" + url.PathEscape(l.Name)
+	}
+	if len(l.Query) > 0 {
+		route += "?" + l.Query.Encode()
+	}
+	return route
+}
+
+// OpenDeepLinkHandler opens deep link for [GET] /open/*link or /open/?url=vmix-utility://... .
+// Every link only redirects to frontend. Shortcut links redirect to confirmation view, since GET must not
+// send functions, such as when link is prefetched or opened by other site.
+func OpenDeepLinkHandler(c *gin.Context) {
+	raw := c.Request.URL.RawQuery
+	if strings.HasPrefix(raw, "url=") {
+		// Links passed by registered scheme are not escaped, so query of link is everything after url= .
+		raw = strings.TrimPrefix(raw, "url=")
+		if !strings.Contains(raw, "://") {
+			raw, _ = url.QueryUnescape(raw)
+		}
+	} else {
+		raw = strings.TrimPrefix(c.Request.URL.EscapedPath(), *basePath+"/open")
+		if q := c.Request.URL.RawQuery; q != "" {
+			raw += "?" + q
+		}
+	}
+	link, err := ParseDeepLink(raw)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	c.Redirect(http.StatusFound, *basePath+"/#"+link.Route())
+}
+
+// GetDeepLinkHandler returns whether deep link scheme is registered for [GET] /api/deeplink .
+func GetDeepLinkHandler(c *gin.Context) {
+	command, err := deepLinkRegistered()
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"scheme":     deepLinkScheme,
+		"registered": command != "",
+		"command":    command,
+	})
+}
+
+// PutDeepLinkHandler registers or removes deep link scheme for current user for [PUT] /api/deeplink .
+// Registering again updates port and base path of registered command.
+func PutDeepLinkHandler(c *gin.Context) {
+	req := struct {
+		Registered bool `json:"registered"`
+	}{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	var err error
+	if req.Registered {
+		err = registerDeepLink(deepLinkCommand())
+	} else {
+		err = unregisterDeepLink()
+	}
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetDeepLinkHandler(c)
+}
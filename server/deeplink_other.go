@@ -0,0 +1,18 @@
+// +build !windows
+
+package main
+
+import "fmt"
+
+// deepLinkRegistered is available only on Windows. Register scheme with .desktop file or Info.plist instead.
+func deepLinkRegistered() (string, error) {
+	return "", nil
+}
+
+func registerDeepLink(command string) error {
+	return NewAppError(ErrKindUnsupported, "", fmt.Errorf("registering %s:// scheme is supported only on Windows", deepLinkScheme))
+}
+
+func unregisterDeepLink() error {
+	return nil
+}
@@ -0,0 +1,77 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestParseDeepLink(t *testing.T) {
+	cases := []struct {
+		raw   string
+		route string
+		name  string
+	}{
+		{"vmix-utility://output/program", "/output/program", "program"},
+		{"vmix-utility://inputs?host=studio-b&key=abc", "/tree?host=studio-b&key=abc", ""},
+		{"/graphics/lower%20third", "/graphics/lower%20third", "lower third"},
+		{"vmix-utility://", "/", ""},
+		{"vmix-utility://shortcut/Intro", "/shortcut/Intro", "Intro"},
+	}
+	for _, tc := range cases {
+		link, err := ParseDeepLink(tc.raw)
+		if err != nil {
+			t.Fatalf("%s : %v", tc.raw, err)
+		}
+		if link.Route() != tc.route || link.Name != tc.name {
+			t.Fatalf("%s : unexpected route %q name %q", tc.raw, link.Route(), link.Name)
+		}
+	}
+	for _, raw := range []string{"http://example.com/output/program", "vmix-utility://shortcut", "vmix-utility://settings"} {
+		if _, err := ParseDeepLink(raw); err == nil {
+			t.Fatalf("expected %s to be rejected", raw)
+		}
+	}
+}
+
+func TestOpenShortcutLinkDoesNotRun(t *testing.T) {
+	m := startMock(t)
+	config.Update(func(cfg *Config) {
+		cfg.Shortcuts = append(cfg.Shortcuts, Shortcut{Name: "deeplink_test", Host: "http://" + m.HTTPAddr, FunctionCall: FunctionCall{Function: "Cut"}})
+	})
+	defer config.Update(func(cfg *Config) {
+		cfg.Shortcuts = cfg.Shortcuts[:len(cfg.Shortcuts)-1]
+	})
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/open/*link", OpenDeepLinkHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/open/shortcut/deeplink_test", nil))
+	if w.Code != http.StatusFound || w.Header().Get("Location") != *basePath+"/#/shortcut/deeplink_test" {
+		t.Fatalf("expected redirect to confirmation view, got %d %s", w.Code, w.Header().Get("Location"))
+	}
+	if calls := m.Calls(); len(calls) != 0 {
+		t.Fatalf("expected shortcut not run by opening link, got %+v", calls)
+	}
+}
+
+func TestOpenLinkPassedByScheme(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/open/*link", OpenDeepLinkHandler)
+	for _, q := range []string{
+		"url=vmix-utility://inputs?host=http://127.0.0.1:8088&key=abc",
+		"url=" + url.QueryEscape("vmix-utility://inputs?host=http://127.0.0.1:8088&key=abc"),
+	} {
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/open/?"+q, nil))
+		want := *basePath + "/#/tree?host=" + url.QueryEscape("http://127.0.0.1:8088") + "&key=abc"
+		if w.Code != http.StatusFound || w.Header().Get("Location") != want {
+			t.Fatalf("%s : expected redirect to %s, got %d %s", q, want, w.Code, w.Header().Get("Location"))
+		}
+	}
+}
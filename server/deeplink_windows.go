@@ -0,0 +1,54 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"fmt"
+	"os/exec"
+	"strings"
+)
+
+// deepLinkKey is registry key of URL scheme of current user.
+const deepLinkKey = `HKCU\Software\Classes\` + deepLinkScheme
+
+// deepLinkRegistered returns command line opening scheme, or empty if scheme is not registered.
+func deepLinkRegistered() (string, error) {
+	out, err := exec.Command("reg", "query", deepLinkKey+`\shell\open\command`, "/ve").Output()
+	if err != nil {
+		// reg exits with 1 when key does not exist.
+		return "", nil
+	}
+	sc := bufio.NewScanner(bytes.NewReader(out))
+	for sc.Scan() {
+		// Name of default value is localized, such as "(Default)" .
+		if line := sc.Text(); strings.Contains(line, "REG_SZ") {
+			return strings.TrimSpace(line[strings.Index(line, "REG_SZ")+len("REG_SZ"):]), nil
+		}
+	}
+	return "", nil
+}
+
+// registerDeepLink registers scheme opened by command line.
+func registerDeepLink(command string) error {
+	for _, args := range [][]string{
+		{"add", deepLinkKey, "/ve", "/t", "REG_SZ", "/d", "URL:" + deepLinkScheme, "/f"},
+		{"add", deepLinkKey, "/v", "URL Protocol", "/t", "REG_SZ", "/d", "", "/f"},
+		{"add", deepLinkKey + `\shell\open\command`, "/ve", "/t", "REG_SZ", "/d", command, "/f"},
+	} {
+		if out, err := exec.Command("reg", args...).CombinedOutput(); err != nil {
+			return NewAppError(ErrKindInternal, "", fmt.Errorf("reg add : %v : %s", err, bytes.TrimSpace(out)))
+		}
+	}
+	return nil
+}
+
+// unregisterDeepLink removes scheme. Removing missing scheme is not an error.
+func unregisterDeepLink() error {
+	if command, err := deepLinkRegistered(); err != nil || command == "" {
+		return err
+	}
+	if out, err := exec.Command("reg", "delete", deepLinkKey, "/f").CombinedOutput(); err != nil {
+		return NewAppError(ErrKindInternal, "", fmt.Errorf("reg delete : %v : %s", err, bytes.TrimSpace(out)))
+	}
+	return nil
+}
@@ -0,0 +1,247 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"io/ioutil"
+	"net"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Diagnostic step results.
+const (
+	DiagnosticOK      = "ok"
+	DiagnosticFailed  = "failed"
+	DiagnosticSkipped = "skipped" // earlier step it depends on failed.
+)
+
+const (
+	diagnoseDefaultPort    = 8088
+	diagnoseDefaultTCPPort = 8099
+	diagnoseTimeout        = 5 * time.Second
+)
+
+// DiagnosticStep is result of single layer of connection test.
+type DiagnosticStep struct {
+	Name       string `json:"name"` // "resolve", "reachability", "http_api", "xml_parse", "tcp_api" or "acts_subscribe".
+	Status     string `json:"status"`
+	DurationMs int64  `json:"duration_ms"`
+	Detail     string `json:"detail,omitempty"`
+	Hint       string `json:"hint,omitempty"` // likely cause and fix of failure.
+}
+
+// ConnectionDiagnosis is layered connection test report of vMix host, to triage "can't connect" without packet captures.
+type ConnectionDiagnosis struct {
+	Host    string           `json:"host"`
+	Port    int              `json:"port"`
+	TCPPort int              `json:"tcp_port"`
+	OK      bool             `json:"ok"` // every step passed.
+	At      time.Time        `json:"at"`
+	Steps   []DiagnosticStep `json:"steps"`
+	Ports   []PortProbe      `json:"ports,omitempty"` // common vMix ports, probed when HTTP or TCP API is unreachable.
+	Hints   []string         `json:"hints,omitempty"`
+}
+
+// diagnosis accumulates steps, skipping steps whose dependency failed.
+type diagnosis struct {
+	ConnectionDiagnosis
+	failed map[string]bool
+}
+
+// step runs check unless any of steps in after failed or was skipped.
+func (d *diagnosis) step(name string, after []string, check func() (detail, hint string, err error)) {
+	s := DiagnosticStep{Name: name, Status: DiagnosticOK}
+	for _, dep := range after {
+		if d.failed[dep] {
+			s.Status = DiagnosticSkipped
+			s.Detail = dep + " failed"
+			d.failed[name] = true
+			d.Steps = append(d.Steps, s)
+			return
+		}
+	}
+	start := time.Now()
+	detail, hint, err := check()
+	s.DurationMs = time.Since(start).Milliseconds()
+	s.Detail, s.Hint = detail, hint
+	if err != nil {
+		s.Status = DiagnosticFailed
+		s.Detail = err.Error()
+		d.failed[name] = true
+		d.OK = false
+	}
+	d.Steps = append(d.Steps, s)
+}
+
+// dialHint explains TCP dial error.
+func dialHint(err error, port int, api string) string {
+	msg := err.Error()
+	switch {
+	case strings.Contains(msg, "refused"):
+		return fmt.Sprintf("host is up but nothing listens on port %d. Check vMix is running and %s is enabled on this port", port, api)
+	case strings.Contains(msg, "timeout"):
+		return fmt.Sprintf("no answer on port %d. Check host is powered on and Windows Firewall allows port %d", port, port)
+	case strings.Contains(msg, "unreachable"):
+		return "no route to host. Check network cable, VLAN and IP address"
+	}
+	return ""
+}
+
+// DiagnoseConnection tests vMix host layer by layer: name resolution, TCP reachability, HTTP API, XML,
+// TCP API handshake and ACTS subscription. ICMP needs privileges on most systems, so reachability is tested by TCP.
+func DiagnoseConnection(host string, port, tcpPort int, timeout time.Duration) ConnectionDiagnosis {
+	d := &diagnosis{
+		ConnectionDiagnosis: ConnectionDiagnosis{Host: host, Port: port, TCPPort: tcpPort, OK: true, At: time.Now(), Steps: []DiagnosticStep{}},
+		failed:              make(map[string]bool),
+	}
+	httpAddr := net.JoinHostPort(host, strconv.Itoa(port))
+	tcpAddr := net.JoinHostPort(host, strconv.Itoa(tcpPort))
+
+	d.step("resolve", nil, func() (string, string, error) {
+		addrs, err := net.LookupHost(host)
+		if err != nil {
+			return "", "check host name, or use IP address of vMix machine", err
+		}
+		return strings.Join(addrs, ", "), "", nil
+	})
+	d.step("reachability", []string{"resolve"}, func() (string, string, error) {
+		conn, err := net.DialTimeout("tcp", httpAddr, timeout)
+		if err != nil {
+			return "", dialHint(err, port, "Web Controller"), err
+		}
+		conn.Close()
+		return "TCP connect to " + httpAddr, "", nil
+	})
+
+	var body []byte
+	d.step("http_api", []string{"reachability"}, func() (string, string, error) {
+		client := &http.Client{Timeout: timeout}
+		resp, err := client.Get("http://" + httpAddr + "/api")
+		if err != nil {
+			return "", "port accepts connection but HTTP did not answer. Check another application does not use this port", err
+		}
+		defer resp.Body.Close()
+		switch {
+		case resp.StatusCode == http.StatusUnauthorized:
+			return "", "Web Controller requires password. Disable password or allow this machine in vMix Web Controller settings", fmt.Errorf("HTTP %s", resp.Status)
+		case resp.StatusCode != http.StatusOK:
+			return "", "", fmt.Errorf("HTTP %s", resp.Status)
+		}
+		body, err = ioutil.ReadAll(resp.Body)
+		if err != nil {
+			return "", "", err
+		}
+		return fmt.Sprintf("%d bytes", len(body)), "", nil
+	})
+	d.step("xml_parse", []string{"http_api"}, func() (string, string, error) {
+		s, err := ParseState(body)
+		if err != nil {
+			return "", "response is not vMix XML. Check port is vMix Web Controller, not another web server", err
+		}
+		return fmt.Sprintf("vMix %s %s, %d inputs", s.Version, s.Edition, len(s.Inputs)), "", nil
+	})
+
+	var conn net.Conn
+	var r *bufio.Reader
+	d.step("tcp_api", []string{"resolve"}, func() (string, string, error) {
+		var err error
+		conn, err = net.DialTimeout("tcp", tcpAddr, timeout)
+		if err != nil {
+			return "", dialHint(err, tcpPort, "TCP API"), err
+		}
+		conn.SetDeadline(time.Now().Add(timeout))
+		r = bufio.NewReader(conn)
+		msg, err := readTCPMessage(r)
+		if err != nil {
+			return "", "connected but no VERSION greeting. Check port is vMix TCP API", err
+		}
+		if msg.Command != "VERSION" || msg.Status != "OK" {
+			return "", "unexpected greeting. Check port is vMix TCP API", fmt.Errorf("unexpected greeting %q", msg.Command+" "+msg.Status+" "+msg.Body)
+		}
+		return "vMix " + msg.Body, "", nil
+	})
+	if conn != nil {
+		defer conn.Close()
+	}
+	d.step("acts_subscribe", []string{"tcp_api"}, func() (string, string, error) {
+		if _, err := conn.Write([]byte("SUBSCRIBE ACTS\r\n")); err != nil {
+			return "", "", err
+		}
+		for {
+			msg, err := readTCPMessage(r)
+			if err != nil {
+				return "", "", err
+			}
+			if msg.Command != "SUBSCRIBE" {
+				continue
+			}
+			if msg.Status != "OK" {
+				return "", "", fmt.Errorf("SUBSCRIBE %s %s", msg.Status, msg.Body)
+			}
+			return "SUBSCRIBE OK " + msg.Body, "", nil
+		}
+	})
+	if !d.failed["resolve"] && (d.failed["reachability"] || d.failed["tcp_api"]) {
+		d.Ports = ScanPorts(host, vMixPorts(port, tcpPort), timeout)
+		d.Hints = PortHints(d.Ports)
+	}
+	return d.ConnectionDiagnosis
+}
+
+// parseDiagnoseTarget returns host name and ports. host may be vMix URL, such as "http://192.168.0.20:8088",
+// whose port is used unless port is given.
+func parseDiagnoseTarget(host, port, tcpPort string) (string, int, int, error) {
+	p, tp := diagnoseDefaultPort, diagnoseDefaultTCPPort
+	if strings.Contains(host, "://") {
+		u, err := url.Parse(host)
+		if err != nil {
+			return "", 0, 0, err
+		}
+		host = u.Hostname()
+		if u.Port() != "" {
+			p, _ = strconv.Atoi(u.Port())
+		}
+	}
+	if host == "" {
+		return "", 0, 0, fmt.Errorf("host is required")
+	}
+	var err error
+	if port != "" {
+		if p, err = strconv.Atoi(port); err != nil {
+			return "", 0, 0, fmt.Errorf("invalid port %q", port)
+		}
+	}
+	if tcpPort != "" {
+		if tp, err = strconv.Atoi(tcpPort); err != nil {
+			return "", 0, 0, fmt.Errorf("invalid TCP port %q", tcpPort)
+		}
+	}
+	if p <= 0 || p > 65535 || tp <= 0 || tp > 65535 {
+		return "", 0, 0, fmt.Errorf("port out of range")
+	}
+	return host, p, tp, nil
+}
+
+// DiagnoseConnectionHandler tests connection to vMix for [GET] /api/diagnose?host=...&port=8088&tcp_port=8099 .
+// host defaults to primary vMix host.
+func DiagnoseConnectionHandler(c *gin.Context) {
+	addr := c.DefaultQuery("host", *vmixaddr)
+	if IsOfflineAddr(addr) {
+		AbortWithAppError(c, NewAppError(ErrKindUnsupported, addr, fmt.Errorf("offline connection has no network")))
+		return
+	}
+	host, port, tcpPort, err := parseDiagnoseTarget(addr, c.Query("port"), c.Query("tcp_port"))
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, addr, err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"diagnosis": DiagnoseConnection(host, port, tcpPort, diagnoseTimeout),
+	})
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"net"
+	"strconv"
+	"testing"
+	"time"
+)
+
+func TestDiagnoseConnection(t *testing.T) {
+	m := startMock(t)
+	_, httpPort, _ := net.SplitHostPort(m.HTTPAddr)
+	_, tcpPort, _ := net.SplitHostPort(m.TCPAddr)
+	host, port, tp, err := parseDiagnoseTarget("http://127.0.0.1:"+httpPort, "", tcpPort)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	d := DiagnoseConnection(host, port, tp, time.Second)
+	if !d.OK || len(d.Steps) != 6 {
+		t.Fatalf("expected every step ok, got %+v", d)
+	}
+
+	ln, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	closed := ln.Addr().(*net.TCPAddr).Port
+	ln.Close()
+	d = DiagnoseConnection(host, closed, tp, time.Second)
+	status := map[string]string{}
+	for _, s := range d.Steps {
+		status[s.Name] = s.Status
+	}
+	if d.OK || status["reachability"] != DiagnosticFailed || status["xml_parse"] != DiagnosticSkipped || status["acts_subscribe"] != DiagnosticOK {
+		t.Fatalf("expected HTTP failure with TCP API ok, got %+v", d.Steps)
+	}
+	if d.Steps[1].Hint == "" {
+		t.Fatalf("expected hint for refused port, got %+v", d.Steps[1])
+	}
+
+	if _, _, _, err := parseDiagnoseTarget("127.0.0.1", strconv.Itoa(70000), ""); err == nil {
+		t.Fatal("expected port out of range refused")
+	}
+}
@@ -0,0 +1,21 @@
+// Command server is vmix-utility backend, serving web frontend and REST/WebSocket API over vMix hosts.
+//
+// Event bus and tally path latency tracing live in package core, held by single core.Core value.
+// events and latency globals of main.go are its fields, and events.go aliases core types for features.
+//
+// Rest of core is still in package main, wired by globals declared in main.go, but layered
+// so it can be told apart from API handlers and features built on it:
+//
+//   - Connections: hostclients.go (pooled HTTP client per host), tcp.go, offline.go, pollbackoff.go.
+//   - Polling and state: poller.go, state.go, statefallback.go, watchdog.go.
+//   - Caches: inputcache.go, snapshot.go, rawxml.go, history.go, cachestats.go.
+//   - Functions: function.go, the only path sending functions to vMix.
+//
+// Extraction of these into core is not done. Remaining steps, in order:
+//
+//   - TODO: move AppError and error kinds to core, since every layer returns them.
+//   - TODO: move State and its XML parsing to core. Methods on State defined by features must become functions first.
+//   - TODO: give hostclients.go, tcp.go and inputcache.go settings as arguments instead of reading config global.
+//   - TODO: replace calls from poller into features, such as startup actions and upgrade advice, by events,
+//     then move poller behind interface taking connections from caller.
+package main
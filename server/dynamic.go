@@ -0,0 +1,68 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+
+	"github.com/gin-gonic/gin"
+)
+
+// DynamicRequest is request body of PutDynamicHandler.
+type DynamicRequest struct {
+	Kind  string `json:"kind"`  // "input" or "value".
+	Slot  int    `json:"slot"`  // 1-4.
+	Value string `json:"value"` // input reference or alias for "input" slots.
+}
+
+// DynamicFunction returns function setting dynamic slot. Input aliases of host are resolved for input slots.
+func DynamicFunction(host string, req DynamicRequest) (FunctionCall, error) {
+	if (&StateDynamic{}).Slot(req.Kind, req.Slot) == nil {
+		return FunctionCall{}, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("invalid dynamic slot %s %d. input or value 1-4 are available", req.Kind, req.Slot))
+	}
+	value, function := req.Value, "SetDynamicValue"
+	if req.Kind == "input" {
+		value, function = ResolveInputAlias(host, value), "SetDynamicInput"
+	}
+	function += strconv.Itoa(req.Slot)
+	return FunctionCall{Function: function, Params: map[string]string{"Value": value}}, nil
+}
+
+// GetDynamicHandler returns dynamic input and value slots for [GET] /api/dynamic?host=... .
+func GetDynamicHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	s, err := hostState(host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"dynamic": s.Dynamic,
+	})
+}
+
+// PutDynamicHandler sets dynamic input or value slot for [PUT] /api/dynamic?host=... .
+func PutDynamicHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	req := DynamicRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	call, err := DynamicFunction(host, req)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	poller.Touch()
+	if err := SendFunctionTo(host, call.Function, call.Params); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, host, call.Function)
+	c.JSON(http.StatusOK, gin.H{
+		"host":     host,
+		"function": call,
+	})
+}
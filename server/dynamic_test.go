@@ -0,0 +1,34 @@
+package main
+
+import "testing"
+
+func TestDynamicSlots(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	if err := config.Update(func(cfg *Config) {
+		cfg.InputAliases[addr] = map[string]string{"CAM 2": "3"}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) { delete(cfg.InputAliases, addr) })
+
+	for _, req := range []DynamicRequest{{Kind: "input", Slot: 2, Value: "CAM 2"}, {Kind: "value", Slot: 4, Value: "Goal"}} {
+		call, err := DynamicFunction(addr, req)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if err := SendFunctionTo(addr, call.Function, call.Params); err != nil {
+			t.Fatal(err)
+		}
+	}
+	s, err := StreamState(addr)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if s.Dynamic.Input2 != "3" || s.Dynamic.Value4 != "Goal" {
+		t.Fatalf("unexpected dynamic slots %+v", s.Dynamic)
+	}
+	if _, err := DynamicFunction(addr, DynamicRequest{Kind: "input", Slot: 5}); err == nil {
+		t.Fatal("expected slot 5 to be rejected")
+	}
+}
@@ -0,0 +1,106 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+)
+
+// ErrorKind classifies AppError so clients can branch on it without parsing messages.
+type ErrorKind string
+
+// Available error kinds.
+const (
+	ErrKindNotLoaded      ErrorKind = "not_loaded"      // vMix state has not been loaded yet.
+	ErrKindNotFound       ErrorKind = "not_found"       // requested resource does not exist.
+	ErrKindInvalidRequest ErrorKind = "invalid_request" // request body or query is malformed.
+	ErrKindUnreachable    ErrorKind = "unreachable"     // vMix host did not respond.
+	ErrKindFunctionFailed ErrorKind = "function_failed" // vMix rejected or failed a function call.
+	ErrKindUnsupported    ErrorKind = "unsupported"     // feature is not available on vMix edition or version.
+	ErrKindLocked         ErrorKind = "locked"          // vMix host is locked for maintenance.
+	ErrKindProtected      ErrorKind = "protected"       // destructive function refused while host is on air.
+	ErrKindBlocked        ErrorKind = "blocked"         // function refused by plugin hook.
+	ErrKindConflict       ErrorKind = "conflict"        // settings changed on other machine since last sync.
+	ErrKindInternal       ErrorKind = "internal"        // anything else.
+)
+
+// AppError is the JSON error body returned by every API handler.
+type AppError struct {
+	Kind       ErrorKind `json:"kind"`           // error classification.
+	Host       string    `json:"host,omitempty"` // vMix host related to the error, if any.
+	Retryable  bool      `json:"retryable"`      // whether retrying the same request may succeed.
+	MessageKey string    `json:"message_key"`    // localization key for frontend. e.g. "error.unreachable" .
+	Message    string    `json:"message"`        // human readable message, for logs and fallback display.
+	cause      error
+}
+
+// Error implements error interface.
+func (e *AppError) Error() string {
+	if e.Host != "" {
+		return fmt.Sprintf("%s (%s): %s", e.Kind, e.Host, e.Message)
+	}
+	return fmt.Sprintf("%s: %s", e.Kind, e.Message)
+}
+
+// Unwrap returns underlying error.
+func (e *AppError) Unwrap() error {
+	return e.cause
+}
+
+// Status returns HTTP status code for the error kind.
+func (e *AppError) Status() int {
+	switch e.Kind {
+	case ErrKindNotLoaded:
+		return http.StatusServiceUnavailable
+	case ErrKindNotFound:
+		return http.StatusNotFound
+	case ErrKindInvalidRequest:
+		return http.StatusBadRequest
+	case ErrKindUnreachable, ErrKindFunctionFailed:
+		return http.StatusBadGateway
+	case ErrKindUnsupported:
+		return http.StatusUnprocessableEntity
+	case ErrKindLocked:
+		return http.StatusLocked
+	case ErrKindProtected, ErrKindConflict:
+		return http.StatusConflict
+	case ErrKindBlocked:
+		return http.StatusForbidden
+	default:
+		return http.StatusInternalServerError
+	}
+}
+
+// NewAppError creates AppError wrapping err. err may be nil.
+func NewAppError(kind ErrorKind, host string, err error) *AppError {
+	e := &AppError{
+		Kind:       kind,
+		Host:       host,
+		Retryable:  kind == ErrKindUnreachable || kind == ErrKindNotLoaded,
+		MessageKey: "error." + string(kind),
+		cause:      err,
+	}
+	if err != nil {
+		e.Message = err.Error()
+	}
+	return e
+}
+
+// AsAppError converts any error into AppError. Non-AppError values are treated as internal errors.
+func AsAppError(err error) *AppError {
+	var appErr *AppError
+	if errors.As(err, &appErr) {
+		return appErr
+	}
+	return NewAppError(ErrKindInternal, "", err)
+}
+
+// AbortWithAppError aborts request with JSON AppError body.
+func AbortWithAppError(c *gin.Context, err error) {
+	appErr := AsAppError(err)
+	c.AbortWithStatusJSON(appErr.Status(), gin.H{
+		"error": appErr,
+	})
+}
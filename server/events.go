@@ -0,0 +1,21 @@
+package main
+
+import (
+	"net/http"
+
+	"github.com/FlowingSPDG/vmix-utility/server/core"
+	"github.com/gin-gonic/gin"
+)
+
+// Event bus lives in core package. Aliases keep features publishing and subscribing as before.
+type (
+	Event    = core.Event
+	EventHub = core.EventHub
+)
+
+// GetEventStatsHandler returns event delivery statistics for [GET] /api/events/stats .
+func GetEventStatsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"stats": events.Stats(),
+	})
+}
@@ -0,0 +1,195 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strconv"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Long-poll wait bounds. Default stays below common proxy idle timeouts.
+const (
+	longPollDefaultWait = 25 * time.Second
+	longPollMaxWait     = 60 * time.Second
+)
+
+// eventCursor returns cursor client resumes from, given as Last-Event-ID header on SSE reconnect or cursor query.
+// ok is false when client gave none, and starts from now.
+func eventCursor(c *gin.Context) (uint64, bool, error) {
+	v := c.GetHeader("Last-Event-ID")
+	if v == "" {
+		v = c.Query("cursor")
+	}
+	if v == "" {
+		return 0, false, nil
+	}
+	gen, err := strconv.ParseUint(v, 10, 64)
+	if err != nil {
+		return 0, false, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid cursor %q", v))
+	}
+	return gen, true, nil
+}
+
+// writeSSE writes event in text/event-stream format with generation as event ID, so browser EventSource resumes by itself.
+func writeSSE(c *gin.Context, ev Event) error {
+	b, err := json.Marshal(ev)
+	if err != nil {
+		return err
+	}
+	if ev.Gen > 0 {
+		if _, err := fmt.Fprintf(c.Writer, "id: %d\n", ev.Gen); err != nil {
+			return err
+		}
+	}
+	if _, err := fmt.Fprintf(c.Writer, "event: %s\ndata: %s\n\n", ev.Topic, b); err != nil {
+		return err
+	}
+	c.Writer.Flush()
+	return nil
+}
+
+// EventStreamHandler streams events as Server-Sent Events for [GET] /api/events?topics=...&hosts=...&cursor=... ,
+// for integrations that cannot use WebSocket. Events are the same as WebSocket receives.
+// Events after cursor or Last-Event-ID are replayed first. "reset" event is sent when they were already discarded,
+// and client should fetch state again.
+func EventStreamHandler(c *gin.Context) {
+	cursor, resume, err := eventCursor(c)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	defer operators.Connect(operatorName(c), c.ClientIP())()
+
+	filter := wsFilterOf(c)
+	ch, unsubscribe := events.SubscribeTo(64, c.DefaultQuery("client", c.ClientIP()), filter.WantsTopic)
+	defer unsubscribe()
+
+	c.Header("Content-Type", "text/event-stream")
+	c.Header("Cache-Control", "no-cache")
+	c.Header("X-Accel-Buffering", "no") // disables buffering of nginx reverse proxy.
+	c.Status(http.StatusOK)
+
+	// Subscribed before reading backlog, so events published meanwhile are in channel and skipped by generation.
+	last := events.Generation()
+	if resume {
+		backlog, complete := events.Since(cursor)
+		if !complete {
+			if err := writeSSE(c, Event{Topic: "reset"}); err != nil {
+				return
+			}
+		}
+		for _, ev := range backlog {
+			if ev.Gen > last {
+				break
+			}
+			if filter.Match(ev) {
+				if err := writeSSE(c, ev); err != nil {
+					return
+				}
+			}
+		}
+	}
+	if err := writeSSE(c, Event{Topic: "subscribed", Data: filter.Subscription(), Gen: last}); err != nil {
+		return
+	}
+
+	ping := time.NewTicker(wsPingInterval)
+	defer ping.Stop()
+	for {
+		select {
+		case <-c.Request.Context().Done():
+			return
+		case <-ping.C:
+			// Comment line keeps idle connection open through proxies.
+			if _, err := fmt.Fprint(c.Writer, ": ping\n\n"); err != nil {
+				return
+			}
+			c.Writer.Flush()
+		case ev, ok := <-ch:
+			if !ok {
+				// Evicted as slow subscriber. Client reconnects with Last-Event-ID and resumes from backlog.
+				writeSSE(c, Event{Topic: "evicted"})
+				return
+			}
+			if ev.Gen <= last || !filter.Match(ev) {
+				continue
+			}
+			if err := writeSSE(c, ev); err != nil {
+				return
+			}
+		}
+	}
+}
+
+// LongPollEventsHandler returns events after cursor for [GET] /api/events/poll?cursor=...&wait=25&topics=...&hosts=... ,
+// waiting up to wait seconds for first one. Response cursor is passed to next poll, so no event is missed between polls
+// as long as it is still in backlog. "reset" is true when some were already discarded, and client should fetch state again.
+// Request without cursor returns current cursor immediately.
+func LongPollEventsHandler(c *gin.Context) {
+	cursor, resume, err := eventCursor(c)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	wait := longPollDefaultWait
+	if v := c.Query("wait"); v != "" {
+		sec, err := strconv.ParseFloat(v, 64)
+		if err != nil || sec < 0 {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid wait %q", v)))
+			return
+		}
+		if wait = time.Duration(sec * float64(time.Second)); wait > longPollMaxWait {
+			wait = longPollMaxWait
+		}
+	}
+	if !resume {
+		c.JSON(http.StatusOK, gin.H{"events": []Event{}, "cursor": events.Generation(), "reset": false})
+		return
+	}
+
+	filter := wsFilterOf(c)
+	ch, unsubscribe := events.SubscribeTo(64, c.DefaultQuery("client", c.ClientIP()), filter.WantsTopic)
+	defer unsubscribe()
+
+	backlog, complete := events.Since(cursor)
+	matched := []Event{}
+	for _, ev := range backlog {
+		if filter.Match(ev) {
+			matched = append(matched, ev)
+		}
+	}
+	// Cursor advances past events filtered out too, so they are not scanned again.
+	next := cursor
+	if len(backlog) > 0 {
+		next = backlog[len(backlog)-1].Gen
+	}
+	if len(matched) == 0 && complete {
+		timeout := time.NewTimer(wait)
+		defer timeout.Stop()
+	waiting:
+		for {
+			select {
+			case <-c.Request.Context().Done():
+				return
+			case <-timeout.C:
+				break waiting
+			case ev, ok := <-ch:
+				if !ok {
+					break waiting
+				}
+				if ev.Gen <= next {
+					continue
+				}
+				next = ev.Gen
+				if filter.Match(ev) {
+					matched = append(matched, ev)
+					break waiting
+				}
+			}
+		}
+	}
+	c.JSON(http.StatusOK, gin.H{"events": matched, "cursor": next, "reset": !complete})
+}
@@ -0,0 +1,75 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestLongPollEvents(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/events/poll", LongPollEventsHandler)
+	poll := func(query string) (res struct {
+		Events []Event `json:"events"`
+		Cursor uint64  `json:"cursor"`
+		Reset  bool    `json:"reset"`
+	}) {
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/events/poll?"+query, nil))
+		if w.Code != http.StatusOK {
+			t.Fatalf("unexpected status %d : %s", w.Code, w.Body)
+		}
+		json.Unmarshal(w.Body.Bytes(), &res)
+		return res
+	}
+
+	start := poll("").Cursor
+	events.Publish(Event{Topic: "other_test", Data: "skipped"})
+	events.Publish(Event{Topic: "poll_test", Data: "first"})
+	res := poll(fmt.Sprintf("cursor=%d&topics=poll_test", start))
+	if len(res.Events) != 1 || res.Events[0].Data != "first" || res.Cursor < start+2 || res.Reset {
+		t.Fatalf("expected backlog event after cursor, got %+v", res)
+	}
+
+	go func() {
+		time.Sleep(50 * time.Millisecond)
+		events.Publish(Event{Topic: "poll_test", Data: "second"})
+	}()
+	res = poll(fmt.Sprintf("cursor=%d&topics=poll_test&wait=3", res.Cursor))
+	if len(res.Events) != 1 || res.Events[0].Data != "second" {
+		t.Fatalf("expected poll to wait for next event, got %+v", res)
+	}
+	if res = poll(fmt.Sprintf("cursor=%d&topics=poll_test&wait=0.05", res.Cursor)); len(res.Events) != 0 {
+		t.Fatalf("expected empty poll after timeout, got %+v", res)
+	}
+}
+
+func TestEventFilterActsPerClient(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	filterOf := func(query string) *wsFilter {
+		c, _ := gin.CreateTestContext(httptest.NewRecorder())
+		c.Request = httptest.NewRequest(http.MethodGet, "/api/ws?"+query, nil)
+		return wsFilterOf(c)
+	}
+	input := Event{Topic: "acts", Host: "primary", Data: ActsEvent{Name: "Input", Input: "2", Value: "1"}}
+	audio := Event{Topic: "acts", Host: "primary", Data: ActsEvent{Name: "InputVolume", Input: "2", Value: "0.5"}}
+
+	tally := filterOf("topics=acts&acts=Input")
+	all := filterOf("topics=acts&acts=")
+	if !tally.Match(input) || tally.Match(audio) || !all.Match(audio) {
+		t.Fatal("expected ACTS filtered per client")
+	}
+	tally.apply(wsRequest{Type: "subscribe", Acts: []string{"InputVolume"}})
+	if !tally.Match(audio) || !all.Match(input) {
+		t.Fatal("expected ACTS subscription changed by request of client only")
+	}
+	if !tally.Match(Event{Topic: "acts", Data: "not activator"}) {
+		t.Fatal("expected events other than activators not filtered by ACTS")
+	}
+}
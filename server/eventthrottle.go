@@ -0,0 +1,38 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+)
+
+// GetEventRatesHandler returns maximum events per second by topic for [GET] /api/events/rates .
+func GetEventRatesHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"rates": events.Rates(),
+	})
+}
+
+// PutEventRatesHandler saves maximum events per second by topic for [PUT] /api/events/rates .
+func PutEventRatesHandler(c *gin.Context) {
+	rates := map[string]float64{}
+	if err := c.ShouldBindJSON(&rates); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for topic, rate := range rates {
+		if rate < 0 {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid rate of topic %q", topic)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.EventRates = rates
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	events.SetRates(rates)
+	GetEventRatesHandler(c)
+}
@@ -0,0 +1,106 @@
+package main
+
+import (
+	"encoding/csv"
+	"fmt"
+	"net/http"
+	"strconv"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// FleetHost is inventory entry of vMix host.
+type FleetHost struct {
+	Name          string          `json:"name"`
+	Addr          string          `json:"addr"`
+	Online        bool            `json:"online"`
+	Connection    ConnectionState `json:"connection"`
+	Version       string          `json:"version"`
+	Edition       string          `json:"edition"`
+	Outdated      bool            `json:"outdated"`
+	Preset        string          `json:"preset"`
+	UptimeSeconds float64         `json:"uptime_seconds"` // time since host came online, as observed by utility.
+	Inputs        int             `json:"inputs"`
+	MaxInputs     int             `json:"max_inputs"`
+	LastSeen      time.Time       `json:"last_seen"`
+}
+
+// FleetReport is inventory of every configured vMix host.
+type FleetReport struct {
+	GeneratedAt time.Time   `json:"generated_at"`
+	Latest      string      `json:"latest_version"`
+	Hosts       []FleetHost `json:"hosts"`
+}
+
+// GenerateFleetReport builds inventory from latest polling results.
+func GenerateFleetReport(now time.Time) FleetReport {
+	latest := config.Get().LatestVersion.Version
+	report := FleetReport{GeneratedAt: now, Latest: latest, Hosts: []FleetHost{}}
+	for _, conn := range Connections() {
+		host := FleetHost{Name: conn.Name, Addr: conn.Addr, Connection: ConnectionDisconnected}
+		if s, ok := poller.Status(conn.Addr); ok {
+			host.Online = s.Online
+			host.Connection = s.Connection
+			host.LastSeen = s.LastSeen
+			if s.Version != "" {
+				host.Version = s.Version
+				host.Edition = s.Edition
+				host.Outdated = adviseUpgrade(s, latest).Outdated
+				host.MaxInputs = CapabilitiesFor(s.Edition, s.Version).MaxInputs
+			}
+			if s.Online && !s.Since.IsZero() {
+				host.UptimeSeconds = now.Sub(s.Since).Seconds()
+			}
+			if s.State != nil {
+				host.Preset = s.State.Preset
+				host.Inputs = len(s.State.Inputs)
+			}
+		}
+		report.Hosts = append(report.Hosts, host)
+	}
+	return report
+}
+
+// CSV returns report as CSV rows including header.
+func (r FleetReport) CSV() [][]string {
+	rows := [][]string{{"name", "addr", "online", "version", "edition", "outdated", "preset", "uptime_seconds", "inputs", "max_inputs", "last_seen"}}
+	for _, h := range r.Hosts {
+		lastSeen := ""
+		if !h.LastSeen.IsZero() {
+			lastSeen = h.LastSeen.Format(time.RFC3339)
+		}
+		rows = append(rows, []string{
+			h.Name,
+			h.Addr,
+			strconv.FormatBool(h.Online),
+			h.Version,
+			h.Edition,
+			strconv.FormatBool(h.Outdated),
+			h.Preset,
+			strconv.FormatFloat(h.UptimeSeconds, 'f', 0, 64),
+			strconv.Itoa(h.Inputs),
+			strconv.Itoa(h.MaxInputs),
+			lastSeen,
+		})
+	}
+	return rows
+}
+
+// GetFleetReportHandler returns inventory report for [GET] /api/fleet/report?format=json|csv .
+func GetFleetReportHandler(c *gin.Context) {
+	report := GenerateFleetReport(time.Now())
+	switch format := c.DefaultQuery("format", "json"); format {
+	case "json":
+		c.JSON(http.StatusOK, gin.H{
+			"report": report,
+		})
+	case "csv":
+		c.Header("Content-Disposition", fmt.Sprintf(`attachment; filename="vmix_fleet_%s.csv"`, report.GeneratedAt.Format("20060102_150405")))
+		c.Header("Content-Type", "text/csv; charset=utf-8")
+		w := csv.NewWriter(c.Writer)
+		w.WriteAll(report.CSV())
+	default:
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown format %q", format)))
+	}
+}
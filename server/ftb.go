@@ -0,0 +1,201 @@
+package main
+
+import (
+	"crypto/rand"
+	"encoding/hex"
+	"fmt"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// ftbTokenTTL is how long armed fade to black can be confirmed.
+const ftbTokenTTL = 10 * time.Second
+
+// armedFTB is fade to black waiting for confirmation.
+type armedFTB struct {
+	Hosts   []string
+	Expires time.Time
+}
+
+// FTBInterlock guards fade to black with two-step confirmation. Grouped hosts fade all together or not at all.
+type FTBInterlock struct {
+	mu    sync.Mutex
+	armed map[string]armedFTB // token -> armed hosts.
+}
+
+// NewFTBInterlock creates FTBInterlock.
+func NewFTBInterlock() *FTBInterlock {
+	return &FTBInterlock{armed: make(map[string]armedFTB)}
+}
+
+// ResolveHosts returns hosts of group, or host itself. Primary vMix host is used if both are empty.
+func ResolveHosts(host, group string) ([]string, error) {
+	if group != "" {
+		hosts, ok := config.Get().HostGroups[group]
+		if !ok || len(hosts) == 0 {
+			return nil, NewAppError(ErrKindNotFound, "", fmt.Errorf("host group %q not found", group))
+		}
+		return hosts, nil
+	}
+	if host == "" {
+		host = *vmixaddr
+	}
+	return []string{host}, nil
+}
+
+// Arm returns one-time token to confirm fade to black of hosts.
+func (f *FTBInterlock) Arm(hosts []string, now time.Time) (string, time.Time, error) {
+	b := make([]byte, 16)
+	if _, err := rand.Read(b); err != nil {
+		return "", time.Time{}, NewAppError(ErrKindInternal, "", err)
+	}
+	token := hex.EncodeToString(b)
+	expires := now.Add(ftbTokenTTL)
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	for t, a := range f.armed {
+		if now.After(a.Expires) {
+			delete(f.armed, t)
+		}
+	}
+	f.armed[token] = armedFTB{Hosts: hosts, Expires: expires}
+	return token, expires, nil
+}
+
+// take consumes token.
+func (f *FTBInterlock) take(token string, now time.Time) ([]string, error) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	a, ok := f.armed[token]
+	delete(f.armed, token)
+	if !ok || now.After(a.Expires) {
+		return nil, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("confirmation token invalid or expired"))
+	}
+	return a.Hosts, nil
+}
+
+// checkInterlock verifies every host is online, not locked for maintenance, not protected while on air,
+// and in same fade to black state, since FadeToBlack toggles and mixed states would leave hosts out of sync.
+func checkInterlock(hosts []string) error {
+	var first *bool
+	for _, addr := range hosts {
+		if IsOfflineAddr(addr) {
+			return NewAppError(ErrKindUnsupported, addr, fmt.Errorf("offline connection is read-only, fade to black cancelled for all hosts"))
+		}
+		if err := locks.Check(addr); err != nil {
+			return err
+		}
+		if err := CheckOnAirProtect(addr, "FadeToBlack"); err != nil {
+			return err
+		}
+		s, ok := poller.Status(addr)
+		if !ok || !s.Online || s.State == nil {
+			return NewAppError(ErrKindUnreachable, addr, fmt.Errorf("host offline, fade to black cancelled for all hosts"))
+		}
+		ftb := bool(s.State.FadeToBlack)
+		if first == nil {
+			first = &ftb
+		} else if *first != ftb {
+			return NewAppError(ErrKindInvalidRequest, addr, fmt.Errorf("hosts have different fade to black states, fade to black cancelled for all hosts"))
+		}
+	}
+	return nil
+}
+
+// Confirm consumes token and fades hosts to black. Interlock is checked again, and function is sent only if
+// every host accepts it, including plugin hooks, so grouped hosts never fade partially.
+func (f *FTBInterlock) Confirm(token string, now time.Time) ([]string, error) {
+	hosts, err := f.take(token, now)
+	if err != nil {
+		return nil, err
+	}
+	if err := checkInterlock(hosts); err != nil {
+		return hosts, err
+	}
+	poller.Touch()
+	return hosts, SendFunctionAllOrNone(hosts, "FadeToBlack", nil)
+}
+
+// ArmFadeToBlackRequest is request body of ArmFadeToBlackHandler.
+type ArmFadeToBlackRequest struct {
+	Host  string `json:"host"`  // vMix host. Primary vMix host is used if host and group are empty.
+	Group string `json:"group"` // host group in settings.
+}
+
+// ArmFadeToBlackHandler arms fade to black and returns confirmation token for [POST] /api/ftb/arm .
+func ArmFadeToBlackHandler(c *gin.Context) {
+	req := ArmFadeToBlackRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	hosts, err := ResolveHosts(req.Host, req.Group)
+	if err == nil {
+		err = checkInterlock(hosts)
+	}
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	token, expires, err := ftb.Arm(hosts, time.Now())
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"token":   token,
+		"hosts":   hosts,
+		"expires": expires,
+	})
+}
+
+// ConfirmFadeToBlackRequest is request body of ConfirmFadeToBlackHandler.
+type ConfirmFadeToBlackRequest struct {
+	Token string `json:"token" binding:"required"`
+}
+
+// ConfirmFadeToBlackHandler fades armed hosts to black for [POST] /api/ftb/confirm .
+func ConfirmFadeToBlackHandler(c *gin.Context) {
+	req := ConfirmFadeToBlackRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	hosts, err := ftb.Confirm(req.Token, time.Now())
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	for _, host := range hosts {
+		announceAction(c, host, "FadeToBlack")
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"hosts": hosts,
+	})
+}
+
+// GetHostGroupsHandler returns host groups for [GET] /api/hosts/groups .
+func GetHostGroupsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"groups": config.Get().HostGroups,
+	})
+}
+
+// PutHostGroupsHandler saves host groups for [PUT] /api/hosts/groups .
+func PutHostGroupsHandler(c *gin.Context) {
+	groups := map[string][]string{}
+	if err := c.ShouldBindJSON(&groups); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.HostGroups = groups
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetHostGroupsHandler(c)
+}
@@ -0,0 +1,52 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestFTBInterlockTokens(t *testing.T) {
+	f := NewFTBInterlock()
+	now := time.Now()
+	token, _, err := f.Arm([]string{"http://a:8088"}, now)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := f.take(token, now.Add(ftbTokenTTL+time.Second)); err == nil {
+		t.Fatal("expired token accepted")
+	}
+	token, _, _ = f.Arm([]string{"http://a:8088"}, now)
+	if hosts, err := f.take(token, now); err != nil || len(hosts) != 1 {
+		t.Fatalf("token rejected: %v", err)
+	}
+	if _, err := f.take(token, now); err == nil {
+		t.Fatal("token accepted twice")
+	}
+}
+
+func TestFTBInterlockLockedHostCancelsAll(t *testing.T) {
+	a, b := startMock(t), startMock(t)
+	hosts := []string{"http://" + a.HTTPAddr, "http://" + b.HTTPAddr}
+	p := NewPoller()
+	for _, addr := range hosts {
+		p.statuses[addr] = &HostStatus{Name: "mock", Addr: addr, Online: true, State: DefaultMockState()}
+	}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+	locks.Lock(HostLock{Host: hosts[1], Reason: "reconfiguring", LockedBy: "op1"})
+	defer locks.Unlock(hosts[1])
+
+	f := NewFTBInterlock()
+	now := time.Now()
+	token, _, err := f.Arm(hosts, now)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := f.Confirm(token, now); err == nil || AsAppError(err).Kind != ErrKindLocked {
+		t.Fatalf("expected locked error, got %v", err)
+	}
+	if calls := append(a.Calls(), b.Calls()...); len(calls) != 0 {
+		t.Fatalf("expected fade to black sent to no host, got %+v", calls)
+	}
+}
@@ -0,0 +1,208 @@
+package main
+
+import (
+	"fmt"
+	"io/ioutil"
+	"net/http"
+	"net/url"
+	"regexp"
+	"sort"
+	"strings"
+	"sync"
+	"unicode/utf8"
+)
+
+// SendFunctionTo sends vMix function to any vMix host by HTTP API. e.g. "http://192.168.0.20:8088" .
+// Every function sent by utility goes through here. In rehearsal mode function is only logged and broadcast.
+// Standby secondary refuses every function, so it never switches vMix alongside primary.
+// Input parameter may be input alias of host.
+func SendFunctionTo(addr, function string, params map[string]string) error {
+	return sendFunction(addr, function, params, false, true)
+}
+
+// SendFunctionOverride sends vMix function same as SendFunctionTo, bypassing on-air protection.
+// Used only when operator explicitly confirmed destructive function.
+func SendFunctionOverride(addr, function string, params map[string]string) error {
+	return sendFunction(addr, function, params, true, true)
+}
+
+// SendTitleUpdate sends periodic title update, such as show clock sent every second. Plugin hooks and show
+// statistics are skipped, so they see operator functions only. Standby, lock and on-air checks still apply.
+func SendTitleUpdate(addr, function string, params map[string]string) error {
+	f, err := prepareFunction(addr, function, params, false, false)
+	if err != nil {
+		return err
+	}
+	return deliverFunction(f.addr, f.function, f.params)
+}
+
+// sendFunction sends function. Plugin hooks may rewrite or block it unless hooks is false,
+// which is for functions chained by hooks themselves.
+func sendFunction(addr, function string, params map[string]string, override, hooks bool) error {
+	f, err := prepareFunction(addr, function, params, override, hooks)
+	if err != nil {
+		return err
+	}
+	return f.send()
+}
+
+// preparedFunction is function that passed every check and pre-send hook, ready to be delivered.
+type preparedFunction struct {
+	addr     string
+	function string
+	params   map[string]string
+	hooks    bool
+	chain    []TriggerAction // functions chained by pre-send hooks.
+}
+
+// prepareFunction runs every check refusing function and pre-send hooks, without sending anything.
+func prepareFunction(addr, function string, params map[string]string, override, hooks bool) (preparedFunction, error) {
+	if IsOfflineAddr(addr) {
+		return preparedFunction{}, NewAppError(ErrKindUnsupported, addr, fmt.Errorf("offline connection is read-only"))
+	}
+	if !pairing.IsActive() {
+		return preparedFunction{}, standbyError(addr)
+	}
+	if err := locks.Check(addr); err != nil {
+		return preparedFunction{}, err
+	}
+	params = resolveAliasParams(addr, params)
+	var chain []TriggerAction
+	if hooks {
+		var err error
+		function, params, chain, err = runPreSendHooks(addr, function, params)
+		if err != nil {
+			return preparedFunction{}, err
+		}
+	}
+	if !override {
+		if err := CheckOnAirProtect(addr, function); err != nil {
+			return preparedFunction{}, err
+		}
+	}
+	return preparedFunction{addr: addr, function: function, params: params, hooks: hooks, chain: chain}, nil
+}
+
+// send delivers prepared function and runs post-send hooks.
+func (f preparedFunction) send() error {
+	err := deliverFunction(f.addr, f.function, f.params)
+	if !Rehearsal() {
+		showStats.RecordFunction(f.addr, f.function, err)
+	}
+	if f.hooks && (len(f.chain) > 0 || plugins.HasHook(PluginHookPostSend)) {
+		go runPostSendHooks(f.addr, f.function, f.params, err, f.chain)
+	}
+	return err
+}
+
+// SendFunctionAllOrNone sends function to every host concurrently, such as mirrored main and backup machines.
+// Every host is checked first, including maintenance locks, on-air protection and plugin hooks,
+// so function refused for any host is sent to none of them. Delivery itself may still fail on some hosts.
+func SendFunctionAllOrNone(addrs []string, function string, params map[string]string) error {
+	prepared := make([]preparedFunction, len(addrs))
+	for i, addr := range addrs {
+		f, err := prepareFunction(addr, function, params, false, true)
+		if err != nil {
+			return err
+		}
+		prepared[i] = f
+	}
+	errs := make([]error, len(prepared))
+	wg := &sync.WaitGroup{}
+	for i, f := range prepared {
+		wg.Add(1)
+		go func(i int, f preparedFunction) {
+			defer wg.Done()
+			errs[i] = f.send()
+		}(i, f)
+	}
+	wg.Wait()
+	for _, err := range errs {
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// functionName matches vMix function names. e.g. "Cut", "OverlayInput1In" .
+var functionName = regexp.MustCompile(`^[A-Za-z][A-Za-z0-9]*$`)
+
+// escapeFunctionParam percent-encodes UTF-8 bytes of key or value. Spaces are encoded as %20 rather than "+",
+// so values decode the same whether receiver treats "+" as space or not.
+func escapeFunctionParam(s string) string {
+	return strings.ReplaceAll(url.QueryEscape(s), "+", "%20")
+}
+
+// EncodeFunctionParams encodes parameters as query string for HTTP and TCP API, sorted by key.
+// Japanese and other non-ASCII text is sent as percent-encoded UTF-8, and "&", "=" and "+" in values are escaped.
+// Parameters that are not valid UTF-8, such as Shift_JIS text, are refused instead of reaching vMix garbled.
+func EncodeFunctionParams(params map[string]string) (string, error) {
+	keys := make([]string, 0, len(params))
+	for k, v := range params {
+		if k == "" {
+			return "", fmt.Errorf("parameter name is empty")
+		}
+		if !utf8.ValidString(k) || !utf8.ValidString(v) {
+			return "", fmt.Errorf("parameter %q is not valid UTF-8", k)
+		}
+		keys = append(keys, k)
+	}
+	sort.Strings(keys)
+	pairs := make([]string, 0, len(keys))
+	for _, k := range keys {
+		pairs = append(pairs, escapeFunctionParam(k)+"="+escapeFunctionParam(params[k]))
+	}
+	return strings.Join(pairs, "&"), nil
+}
+
+// EncodeFunctionCommand returns argument of TCP API FUNCTION command. e.g. "SetText Input=5&Value=Hello%20World" .
+// Function name is sent unescaped, so names with spaces, "&" or line breaks, which would split command, are refused.
+func EncodeFunctionCommand(function string, params map[string]string) (string, error) {
+	if !functionName.MatchString(function) {
+		return "", fmt.Errorf("invalid function name %q", function)
+	}
+	query, err := EncodeFunctionParams(params)
+	if err != nil {
+		return "", err
+	}
+	if query == "" {
+		return function, nil
+	}
+	return function + " " + query, nil
+}
+
+// deliverFunction sends function to vMix by HTTP API. In rehearsal mode function is only logged and broadcast.
+func deliverFunction(addr, function string, params map[string]string) error {
+	if !functionName.MatchString(function) {
+		return NewAppError(ErrKindInvalidRequest, addr, fmt.Errorf("invalid function name %q", function))
+	}
+	query, err := EncodeFunctionParams(params)
+	if err != nil {
+		return NewAppError(ErrKindInvalidRequest, addr, err)
+	}
+	if Rehearsal() {
+		rehearse(addr, function, params)
+		return nil
+	}
+	u := strings.TrimSuffix(addr, "/") + "/api?Function=" + escapeFunctionParam(function)
+	if query != "" {
+		u += "&" + query
+	}
+	resp, err := hostClients.Get(addr).Get(u)
+	if err != nil {
+		return NewAppError(ErrKindUnreachable, addr, err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		b, _ := ioutil.ReadAll(resp.Body)
+		return NewAppError(ErrKindFunctionFailed, addr, fmt.Errorf("%s %s : %s", function, resp.Status, strings.TrimSpace(string(b))))
+	}
+	return nil
+}
+
+// FunctionCall is vMix function with parameters.
+type FunctionCall struct {
+	Function string            `json:"function"` // e.g. "Cut" .
+	Params   map[string]string `json:"params"`   // e.g. {"Input":"1"} .
+}
@@ -0,0 +1,79 @@
+package main
+
+import (
+	"net/url"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestEncodeFunctionParams(t *testing.T) {
+	params := map[string]string{"SelectedName": "見出し.Text", "Value": "日本語 & A=B+C"}
+	query, err := EncodeFunctionParams(params)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if strings.ContainsAny(query, " +") || !strings.HasPrefix(query, "SelectedName=") {
+		t.Fatalf("unexpected query %q", query)
+	}
+	q, err := url.ParseQuery(query)
+	if err != nil || q.Get("SelectedName") != params["SelectedName"] || q.Get("Value") != params["Value"] || len(q) != 2 {
+		t.Fatalf("query %q did not round trip: %v %v", query, q, err)
+	}
+	if _, err := EncodeFunctionParams(map[string]string{"Value": "\x93\xfa\x96\x7b"}); err == nil {
+		t.Fatal("expected Shift_JIS value refused")
+	}
+}
+
+func TestFunctionParamsAgainstMock(t *testing.T) {
+	m := startMock(t)
+	params := map[string]string{"Input": "5", "SelectedName": "Headline.Text", "Value": "こんにちは & ようこそ = 100%"}
+	if err := SendFunctionTo("http://"+m.HTTPAddr, "SetText", params); err != nil {
+		t.Fatal(err)
+	}
+
+	client := NewTCPClient(m.TCPAddr)
+	go client.Run()
+	for deadline := time.Now().Add(2 * time.Second); !client.Connected(); time.Sleep(10 * time.Millisecond) {
+		if time.Now().After(deadline) {
+			t.Fatal("timed out connecting TCP API")
+		}
+	}
+	if err := client.Function("SetText", params, 2*time.Second); err != nil {
+		t.Fatal(err)
+	}
+
+	calls := m.Calls()
+	if len(calls) != 2 {
+		t.Fatalf("expected 2 calls, got %+v", calls)
+	}
+	for _, call := range calls {
+		if call.Function != "SetText" || call.Params["Value"] != params["Value"] || call.Params["SelectedName"] != "Headline.Text" {
+			t.Fatalf("parameters garbled via %s : %+v", call.Via, call.Params)
+		}
+	}
+}
+
+func TestEncodeFunctionCommand(t *testing.T) {
+	arg, err := EncodeFunctionCommand("SetText", map[string]string{"Input": "5", "Value": "line 1\r\nline 2 & more"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if arg != "SetText Input=5&Value=line%201%0D%0Aline%202%20%26%20more" {
+		t.Fatalf("unexpected command %q", arg)
+	}
+	if arg, err := EncodeFunctionCommand("Cut", nil); err != nil || arg != "Cut" {
+		t.Fatalf("unexpected command %q %v", arg, err)
+	}
+	for _, function := range []string{"", "Set Text", "Cut\r\nFUNCTION Fade", "Cut&Input=1"} {
+		if _, err := EncodeFunctionCommand(function, nil); err == nil {
+			t.Fatalf("expected function %q refused", function)
+		}
+	}
+	if _, err := EncodeFunctionCommand("SetText", map[string]string{"": "1"}); err == nil {
+		t.Fatal("expected empty parameter name refused")
+	}
+	if _, err := NewTCPClient("127.0.0.1:0").Request("XMLTEXT", "vmix/inputs\r\nFUNCTION Cut", time.Second); err == nil || AsAppError(err).Kind != ErrKindInvalidRequest {
+		t.Fatalf("expected line break refused, got %v", err)
+	}
+}
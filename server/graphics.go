@@ -0,0 +1,149 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"sort"
+	"sync"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Graphic templates rendered by web graphics output.
+const (
+	GraphicTemplateLowerThird = "lower_third"
+	GraphicTemplateScoreboard = "scoreboard"
+)
+
+// Graphic is overlay graphic rendered as HTML, to be used as vMix Web Browser input.
+type Graphic struct {
+	Name     string            `json:"name"`
+	Template string            `json:"template"` // GraphicTemplateLowerThird, GraphicTemplateScoreboard or user-defined template name.
+	Visible  bool              `json:"visible"`
+	Fields   map[string]string `json:"fields"` // template fields. e.g. "title", "subtitle" .
+}
+
+// GraphicsStore keeps graphics and publishes "graphics" events on change.
+type GraphicsStore struct {
+	mu       sync.Mutex
+	graphics map[string]*Graphic
+}
+
+// NewGraphicsStore creates GraphicsStore.
+func NewGraphicsStore() *GraphicsStore {
+	return &GraphicsStore{
+		graphics: make(map[string]*Graphic),
+	}
+}
+
+// Get returns copy of graphic.
+func (s *GraphicsStore) Get(name string) (Graphic, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	g, ok := s.graphics[name]
+	if !ok {
+		return Graphic{}, false
+	}
+	return copyGraphic(g), true
+}
+
+// List returns copies of all graphics ordered by name.
+func (s *GraphicsStore) List() []Graphic {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	list := make([]Graphic, 0, len(s.graphics))
+	for _, g := range s.graphics {
+		list = append(list, copyGraphic(g))
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].Name < list[j].Name })
+	return list
+}
+
+// Update modifies graphic with fn, creating it if missing, and publishes change.
+func (s *GraphicsStore) Update(name string, fn func(g *Graphic)) Graphic {
+	s.mu.Lock()
+	g, ok := s.graphics[name]
+	if !ok {
+		g = &Graphic{Name: name, Template: GraphicTemplateLowerThird, Fields: map[string]string{}}
+		s.graphics[name] = g
+	}
+	fn(g)
+	g.Name = name
+	if g.Fields == nil {
+		g.Fields = map[string]string{}
+	}
+	updated := copyGraphic(g)
+	s.mu.Unlock()
+	events.Publish(Event{Topic: "graphics", Data: updated})
+	return updated
+}
+
+func copyGraphic(g *Graphic) Graphic {
+	c := *g
+	c.Fields = make(map[string]string, len(g.Fields))
+	for k, v := range g.Fields {
+		c.Fields[k] = v
+	}
+	return c
+}
+
+// GetGraphicsHandler returns all graphics for [GET] /api/graphics .
+func GetGraphicsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"graphics": graphics.List(),
+	})
+}
+
+// GetGraphicHandler returns graphic for [GET] /api/graphics/:name .
+func GetGraphicHandler(c *gin.Context) {
+	g, ok := graphics.Get(c.Param("name"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("graphic %q not found", c.Param("name"))))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"graphic": g,
+	})
+}
+
+// PutGraphicHandler creates or replaces graphic for [PUT] /api/graphics/:name .
+func PutGraphicHandler(c *gin.Context) {
+	req := Graphic{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if !graphicTemplateExists(req.Template) {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown template %q", req.Template)))
+		return
+	}
+	g := graphics.Update(c.Param("name"), func(g *Graphic) {
+		*g = req
+	})
+	c.JSON(http.StatusOK, gin.H{
+		"graphic": g,
+	})
+}
+
+// ShowGraphicHandler shows graphic for [POST] /api/graphics/:name/show .
+func ShowGraphicHandler(c *gin.Context) {
+	setGraphicVisible(c, true)
+}
+
+// HideGraphicHandler hides graphic for [POST] /api/graphics/:name/hide .
+func HideGraphicHandler(c *gin.Context) {
+	setGraphicVisible(c, false)
+}
+
+func setGraphicVisible(c *gin.Context, visible bool) {
+	if _, ok := graphics.Get(c.Param("name")); !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("graphic %q not found", c.Param("name"))))
+		return
+	}
+	g := graphics.Update(c.Param("name"), func(g *Graphic) {
+		g.Visible = visible
+	})
+	c.JSON(http.StatusOK, gin.H{
+		"graphic": g,
+	})
+}
@@ -0,0 +1,144 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"html/template"
+	"net/http"
+	"sort"
+
+	"github.com/gin-gonic/gin"
+)
+
+// builtinGraphicTemplates are rendered by web graphics output itself.
+var builtinGraphicTemplates = []string{GraphicTemplateLowerThird, GraphicTemplateScoreboard}
+
+// isBuiltinGraphicTemplate reports whether template is rendered by web graphics output.
+func isBuiltinGraphicTemplate(name string) bool {
+	for _, t := range builtinGraphicTemplates {
+		if t == name {
+			return true
+		}
+	}
+	return false
+}
+
+// parseGraphicTemplate parses user-defined HTML template. Fields of graphic are referenced as {{.title}} ,
+// and are escaped for HTML, so field values never inject markup into overlay.
+func parseGraphicTemplate(name, src string) (*template.Template, error) {
+	return template.New(name).Option("missingkey=zero").Parse(src)
+}
+
+// graphicTemplateExists reports whether template is built-in or defined in settings.
+func graphicTemplateExists(name string) bool {
+	if isBuiltinGraphicTemplate(name) {
+		return true
+	}
+	_, ok := config.Get().GraphicTemplates[name]
+	return ok
+}
+
+// RenderGraphic fills user-defined template of graphic with its fields.
+func RenderGraphic(g Graphic) (string, error) {
+	if isBuiltinGraphicTemplate(g.Template) {
+		return "", NewAppError(ErrKindUnsupported, "", fmt.Errorf("built-in template %q is rendered by graphics page", g.Template))
+	}
+	src, ok := config.Get().GraphicTemplates[g.Template]
+	if !ok {
+		return "", NewAppError(ErrKindNotFound, "", fmt.Errorf("template %q not found", g.Template))
+	}
+	t, err := parseGraphicTemplate(g.Template, src)
+	if err != nil {
+		return "", NewAppError(ErrKindInvalidRequest, "", err)
+	}
+	b := &bytes.Buffer{}
+	if err := t.Execute(b, g.Fields); err != nil {
+		return "", NewAppError(ErrKindInvalidRequest, "", err)
+	}
+	return b.String(), nil
+}
+
+// GetGraphicTemplatesHandler returns built-in template names and user-defined templates for [GET] /api/graphic-templates .
+func GetGraphicTemplatesHandler(c *gin.Context) {
+	templates := config.Get().GraphicTemplates
+	names := make([]string, 0, len(templates))
+	for name := range templates {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	c.JSON(http.StatusOK, gin.H{
+		"builtin":   builtinGraphicTemplates,
+		"names":     names,
+		"templates": templates,
+	})
+}
+
+// PutGraphicTemplateRequest is request body of PutGraphicTemplateHandler.
+type PutGraphicTemplateRequest struct {
+	HTML string `json:"html" binding:"required"` // e.g. `<div class="name">{{.name}}</div>` .
+}
+
+// PutGraphicTemplateHandler saves user-defined template for [PUT] /api/graphic-templates/:name .
+// Graphics using template are re-published, so overlays pick up edited template without reloading.
+func PutGraphicTemplateHandler(c *gin.Context) {
+	name := c.Param("name")
+	req := PutGraphicTemplateRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if isBuiltinGraphicTemplate(name) {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("template %q is built-in", name)))
+		return
+	}
+	if _, err := parseGraphicTemplate(name, req.HTML); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.GraphicTemplates[name] = req.HTML
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	for _, g := range graphics.List() {
+		if g.Template == name {
+			graphics.Update(g.Name, func(*Graphic) {})
+		}
+	}
+	GetGraphicTemplatesHandler(c)
+}
+
+// DeleteGraphicTemplateHandler deletes user-defined template for [DELETE] /api/graphic-templates/:name .
+func DeleteGraphicTemplateHandler(c *gin.Context) {
+	name := c.Param("name")
+	if _, ok := config.Get().GraphicTemplates[name]; !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("template %q not found", name)))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		delete(cfg.GraphicTemplates, name)
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetGraphicTemplatesHandler(c)
+}
+
+// RenderGraphicHandler returns graphic filled into its user-defined template for [GET] /api/graphics/:name/render .
+func RenderGraphicHandler(c *gin.Context) {
+	g, ok := graphics.Get(c.Param("name"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("graphic %q not found", c.Param("name"))))
+		return
+	}
+	html, err := RenderGraphic(g)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"graphic": g,
+		"html":    html,
+	})
+}
@@ -0,0 +1,50 @@
+package main
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestUserGraphicTemplate(t *testing.T) {
+	defer config.Update(func(cfg *Config) {
+		delete(cfg.GraphicTemplates, "name_card")
+	})
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/graphic-templates/:name", PutGraphicTemplateHandler)
+	r.PUT("/api/graphics/:name", PutGraphicHandler)
+	r.GET("/api/graphics/:name/render", RenderGraphicHandler)
+	do := func(method, path, body string) *httptest.ResponseRecorder {
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(method, path, strings.NewReader(body)))
+		return w
+	}
+
+	if w := do(http.MethodPut, "/api/graphics/guest", `{"template":"name_card"}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected unknown template refused, got %d %s", w.Code, w.Body)
+	}
+	if w := do(http.MethodPut, "/api/graphic-templates/name_card", `{"html":"<div>{{.name</div>"}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected broken template refused, got %d %s", w.Code, w.Body)
+	}
+	if w := do(http.MethodPut, "/api/graphic-templates/scoreboard", `{"html":"<div></div>"}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected built-in template kept, got %d %s", w.Code, w.Body)
+	}
+	if w := do(http.MethodPut, "/api/graphic-templates/name_card", `{"html":"<div class=\"name\">{{.name}}</div><div>{{.role}}</div>"}`); w.Code != http.StatusOK {
+		t.Fatalf("expected template saved, got %d %s", w.Code, w.Body)
+	}
+	if w := do(http.MethodPut, "/api/graphics/guest", `{"template":"name_card","fields":{"name":"<b>Ann</b>"}}`); w.Code != http.StatusOK {
+		t.Fatalf("expected graphic with user template saved, got %d %s", w.Code, w.Body)
+	}
+	w := do(http.MethodGet, "/api/graphics/guest/render", "")
+	res := struct {
+		HTML string `json:"html"`
+	}{}
+	if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || res.HTML != `<div class="name">&lt;b&gt;Ann&lt;/b&gt;</div><div></div>` {
+		t.Fatalf("expected fields filled and escaped, got %d %s", w.Code, w.Body)
+	}
+}
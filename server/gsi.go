@@ -0,0 +1,250 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"log"
+	"net"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// gsiDefaultListen is listen address of Game State Integration listener, matching "uri" of game config.
+// e.g. "uri" "http://127.0.0.1:3000" in gamestate_integration_vmix.cfg .
+const gsiDefaultListen = ":3000"
+
+// GSISettings is Game State Integration listener configuration, for CS2 and Dota 2 casting setups.
+type GSISettings struct {
+	Enabled bool      `json:"enabled"`
+	Listen  string    `json:"listen"` // HTTP listen address. Default ":3000".
+	Token   string    `json:"token"`  // "auth" "token" of game config. Posts with other token are refused. Empty accepts any.
+	Feed    string    `json:"feed"`   // push data feed every post is ingested into, updating titles. Empty to disable.
+	Rules   []GSIRule `json:"rules"`
+}
+
+// GSIRule fires action when value at path of game state changes. e.g. {"path":"round.bomb","value":"planted"}
+// runs replay when bomb is planted, {"path":"map.team_ct.score"} on every CT score change.
+type GSIRule struct {
+	Path   string        `json:"path"`  // dot separated path in game state, as in data feed mappings.
+	Value  string        `json:"value"` // fires only when value changes to this. Empty fires on every change.
+	Action TriggerAction `json:"action"`
+}
+
+// GSIState is summary of game state parsed from last post. Fields missing from post are empty.
+type GSIState struct {
+	Game       string    `json:"game"`        // provider name. e.g. "Counter-Strike 2" .
+	Map        string    `json:"map"`         // e.g. "de_mirage" .
+	MapPhase   string    `json:"map_phase"`   // e.g. "live", "intermission" or "gameover". game_state for Dota 2.
+	Round      int       `json:"round"`       // rounds played.
+	RoundPhase string    `json:"round_phase"` // e.g. "freezetime", "live" or "over".
+	Bomb       string    `json:"bomb"`        // e.g. "planted", "defused" or "exploded".
+	WinTeam    string    `json:"win_team"`    // winner of round just over. e.g. "CT" .
+	ScoreCT    int       `json:"score_ct"`    // CT or Radiant score.
+	ScoreT     int       `json:"score_t"`     // T or Dire score.
+	Clock      float64   `json:"clock"`       // seconds left in phase, or game clock of Dota 2.
+	Updated    time.Time `json:"updated"`
+}
+
+// gsiString returns value at path of game state, or empty string.
+func gsiString(doc interface{}, path string) string {
+	v, _ := FeedValue(doc, path)
+	return v
+}
+
+// gsiNumber returns number at path of game state, or 0.
+func gsiNumber(doc interface{}, path string) float64 {
+	f, _ := strconv.ParseFloat(gsiString(doc, path), 64)
+	return f
+}
+
+// ParseGSIState summarizes round, clock and score of CS2 or Dota 2 game state.
+func ParseGSIState(doc interface{}, now time.Time) GSIState {
+	s := GSIState{
+		Game:       gsiString(doc, "provider.name"),
+		Map:        gsiString(doc, "map.name"),
+		MapPhase:   gsiString(doc, "map.phase"),
+		Round:      int(gsiNumber(doc, "map.round")),
+		RoundPhase: gsiString(doc, "round.phase"),
+		Bomb:       gsiString(doc, "round.bomb"),
+		WinTeam:    gsiString(doc, "round.win_team"),
+		ScoreCT:    int(gsiNumber(doc, "map.team_ct.score")),
+		ScoreT:     int(gsiNumber(doc, "map.team_t.score")),
+		Clock:      gsiNumber(doc, "phase_countdowns.phase_ends_in"),
+		Updated:    now,
+	}
+	if s.MapPhase == "" {
+		// Dota 2.
+		s.MapPhase = gsiString(doc, "map.game_state")
+		s.ScoreCT = int(gsiNumber(doc, "map.radiant_score"))
+		s.ScoreT = int(gsiNumber(doc, "map.dire_score"))
+		s.Clock = gsiNumber(doc, "map.clock_time")
+	}
+	return s
+}
+
+// GSIListener receives Game State Integration posts, fires rules and feeds title updater.
+type GSIListener struct {
+	mu       sync.Mutex
+	settings GSISettings
+	server   *http.Server
+	values   map[string]string // rule path -> last value.
+	state    GSIState
+	lastErr  string
+}
+
+// NewGSIListener creates GSIListener.
+func NewGSIListener() *GSIListener {
+	return &GSIListener{values: make(map[string]string)}
+}
+
+// Settings returns current settings.
+func (g *GSIListener) Settings() GSISettings {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	return g.settings
+}
+
+// Status returns settings, last game state and last error.
+func (g *GSIListener) Status() gin.H {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	return gin.H{
+		"settings":  g.settings,
+		"listening": g.server != nil,
+		"state":     g.state,
+		"error":     g.lastErr,
+	}
+}
+
+// Configure replaces settings and restarts listener.
+func (g *GSIListener) Configure(settings GSISettings) error {
+	if settings.Listen == "" {
+		settings.Listen = gsiDefaultListen
+	}
+	for _, r := range settings.Rules {
+		if r.Path == "" || r.Action.Function == "" {
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("GSI rule needs path and function"))
+		}
+	}
+	g.mu.Lock()
+	if g.server != nil {
+		g.server.Close()
+		g.server = nil
+	}
+	g.settings = settings
+	g.values = make(map[string]string)
+	g.lastErr = ""
+	if !settings.Enabled {
+		g.mu.Unlock()
+		return nil
+	}
+	ln, err := net.Listen("tcp", settings.Listen)
+	if err != nil {
+		g.lastErr = err.Error()
+		g.mu.Unlock()
+		return NewAppError(ErrKindInternal, "", err)
+	}
+	g.server = &http.Server{Handler: http.HandlerFunc(g.serveHTTP), ReadTimeout: 5 * time.Second}
+	server := g.server
+	g.mu.Unlock()
+	log.Printf("Game State Integration listening on %s\n", settings.Listen)
+	go server.Serve(ln)
+	return nil
+}
+
+// serveHTTP handles post from game client. Game retries failed posts, so errors are only answered by status code.
+func (g *GSIListener) serveHTTP(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPost {
+		http.Error(w, "POST only", http.StatusMethodNotAllowed)
+		return
+	}
+	b, err := ioutil.ReadAll(http.MaxBytesReader(w, r.Body, maxDataFeedSize))
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	if err := g.Handle(b, time.Now()); err != nil {
+		code := http.StatusBadRequest
+		if AsAppError(err).Kind == ErrKindBlocked {
+			code = http.StatusForbidden
+		}
+		http.Error(w, err.Error(), code)
+		return
+	}
+	w.WriteHeader(http.StatusOK)
+}
+
+// Handle applies posted game state. Rules whose value changed fire, and post is ingested into data feed.
+func (g *GSIListener) Handle(b []byte, now time.Time) error {
+	var doc interface{}
+	dec := json.NewDecoder(bytes.NewReader(b))
+	dec.UseNumber()
+	if err := dec.Decode(&doc); err != nil {
+		return NewAppError(ErrKindInvalidRequest, "", err)
+	}
+	g.mu.Lock()
+	settings := g.settings
+	if settings.Token != "" && gsiString(doc, "auth.token") != settings.Token {
+		g.mu.Unlock()
+		return NewAppError(ErrKindBlocked, "", fmt.Errorf("invalid GSI auth token"))
+	}
+	var fire []TriggerAction
+	values := map[string]string{}
+	for _, r := range settings.Rules {
+		value := gsiString(doc, r.Path) // missing is empty. e.g. "round.bomb" until bomb is planted.
+		values[r.Path] = value
+		prev, seen := g.values[r.Path]
+		if !seen || prev == value || (r.Value != "" && value != r.Value) {
+			continue
+		}
+		fire = append(fire, r.Action)
+	}
+	for path, value := range values {
+		g.values[path] = value
+	}
+	g.state = ParseGSIState(doc, now)
+	state := g.state
+	g.mu.Unlock()
+
+	events.Publish(Event{Topic: "gsi", Data: state})
+	for _, a := range fire {
+		go a.Fire("gsi")
+	}
+	if settings.Feed != "" {
+		if err := dataFeeds.Ingest(settings.Feed, b); err != nil {
+			log.Printf("Failed to ingest game state into data feed %s : %v\n", settings.Feed, err)
+		}
+	}
+	return nil
+}
+
+// GetGSIHandler returns Game State Integration settings and last game state for [GET] /api/triggers/gsi .
+func GetGSIHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gsiListener.Status())
+}
+
+// PutGSIHandler saves Game State Integration settings for [PUT] /api/triggers/gsi .
+func PutGSIHandler(c *gin.Context) {
+	settings := GSISettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := gsiListener.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.GSI = gsiListener.Settings()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetGSIHandler(c)
+}
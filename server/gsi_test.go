@@ -0,0 +1,57 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestGSIListener(t *testing.T) {
+	SetRehearsal(true)
+	defer SetRehearsal(false)
+	ch, unsubscribe := events.Subscribe(16)
+	defer unsubscribe()
+
+	g := NewGSIListener()
+	g.settings = GSISettings{Token: "secret", Rules: []GSIRule{
+		{Path: "round.bomb", Value: "planted", Action: TriggerAction{Host: "http://a:8088", FunctionCall: FunctionCall{Function: "ReplayPlayLastEvent"}}},
+	}}
+	post := func(body string) error {
+		return g.Handle([]byte(body), time.Now())
+	}
+	if err := post(`{"auth":{"token":"wrong"}}`); err == nil || AsAppError(err).Kind != ErrKindBlocked {
+		t.Fatalf("expected wrong token refused, got %v", err)
+	}
+	if err := post(`{"auth":{"token":"secret"},"provider":{"name":"Counter-Strike 2"},"map":{"name":"de_mirage","phase":"live","round":7,"team_ct":{"score":4},"team_t":{"score":3}},"round":{"phase":"live"},"phase_countdowns":{"phase_ends_in":"84.3"}}`); err != nil {
+		t.Fatal(err)
+	}
+	if err := post(`{"auth":{"token":"secret"},"map":{"phase":"live"},"round":{"phase":"live","bomb":"planted"}}`); err != nil {
+		t.Fatal(err)
+	}
+	if err := post(`{"auth":{"token":"secret"},"map":{"phase":"live"},"round":{"phase":"live","bomb":"planted"}}`); err != nil {
+		t.Fatal(err)
+	}
+
+	fired := 0
+	timeout := time.After(500 * time.Millisecond)
+	for done := false; !done; {
+		select {
+		case ev := <-ch:
+			if ev.Topic == "trigger" {
+				fired++
+			}
+		case <-timeout:
+			done = true
+		}
+	}
+	if fired != 1 {
+		t.Fatalf("expected bomb plant rule fired once, got %d", fired)
+	}
+
+	s := ParseGSIState(map[string]interface{}{"map": map[string]interface{}{"game_state": "DOTA_GAMERULES_STATE_GAME_IN_PROGRESS", "radiant_score": 12.0, "dire_score": 9.0, "clock_time": 1500.0}}, time.Now())
+	if s.MapPhase != "DOTA_GAMERULES_STATE_GAME_IN_PROGRESS" || s.ScoreCT != 12 || s.ScoreT != 9 || s.Clock != 1500 {
+		t.Fatalf("unexpected Dota 2 state %+v", s)
+	}
+	if st := g.Status()["state"].(GSIState); st.Bomb != "planted" {
+		t.Fatalf("expected last state kept, got %+v", st)
+	}
+}
@@ -0,0 +1,196 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// taskStallGrace is added to three periods of background task before it is reported stalled,
+// covering slow iterations such as poll cycle waiting for unreachable hosts.
+const taskStallGrace = 10 * time.Second
+
+// Health check statuses, in increasing severity.
+const (
+	HealthOK       = "ok"
+	HealthWarning  = "warning"
+	HealthCritical = "critical"
+)
+
+// HealthCheck is result of single health check. e.g. {"name":"host:primary","status":"critical","detail":"disconnected"} .
+type HealthCheck struct {
+	Name   string `json:"name"`
+	Status string `json:"status"`
+	Detail string `json:"detail,omitempty"`
+}
+
+// HealthReport is summary for monitoring such as Zabbix or Nagios. Status is "ok", "degraded" with any warning,
+// or "down" with any critical check.
+type HealthReport struct {
+	Status string        `json:"status"`
+	Time   time.Time     `json:"time"`
+	Checks []HealthCheck `json:"checks"`
+}
+
+// newHealthReport summarizes checks.
+func newHealthReport(checks []HealthCheck, now time.Time) HealthReport {
+	r := HealthReport{Status: "ok", Time: now, Checks: checks}
+	for _, c := range checks {
+		switch {
+		case c.Status == HealthCritical:
+			r.Status = "down"
+		case c.Status == HealthWarning && r.Status == "ok":
+			r.Status = "degraded"
+		}
+	}
+	return r
+}
+
+// taskBeat is last heartbeat of background task.
+type taskBeat struct {
+	last   time.Time
+	period time.Duration
+}
+
+// TaskMonitor records heartbeats of background loops, so stalled or crashed loop is reported by health check.
+type TaskMonitor struct {
+	mu    sync.Mutex
+	beats map[string]taskBeat
+}
+
+// NewTaskMonitor creates TaskMonitor.
+func NewTaskMonitor() *TaskMonitor {
+	return &TaskMonitor{beats: make(map[string]taskBeat)}
+}
+
+// Beat records that task named name is alive and beats again within period.
+func (m *TaskMonitor) Beat(name string, period time.Duration) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.beats[name] = taskBeat{last: time.Now(), period: period}
+}
+
+// Checks returns check of every task, ordered by name. Task is critical after missing three beats.
+func (m *TaskMonitor) Checks(now time.Time) []HealthCheck {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	checks := make([]HealthCheck, 0, len(m.beats))
+	for name, beat := range m.beats {
+		check := HealthCheck{Name: "task:" + name, Status: HealthOK}
+		if since := now.Sub(beat.last); since > 3*beat.period+taskStallGrace {
+			check.Status = HealthCritical
+			check.Detail = fmt.Sprintf("stalled for %s", since.Round(time.Second))
+		}
+		checks = append(checks, check)
+	}
+	sort.Slice(checks, func(i, j int) bool { return checks[i].Name < checks[j].Name })
+	return checks
+}
+
+// hostHealthChecks checks every polled vMix host. Disconnected primary host is critical, and secondary host warning.
+func hostHealthChecks() []HealthCheck {
+	checks := []HealthCheck{}
+	for _, conn := range Connections() {
+		if IsOfflineAddr(conn.Addr) {
+			continue
+		}
+		check := HealthCheck{Name: "host:" + conn.Name, Status: HealthOK}
+		status, ok := poller.Status(conn.Addr)
+		switch {
+		case !ok:
+			check.Status = HealthWarning
+			check.Detail = "not polled yet"
+		case status.Connection == ConnectionReconnecting:
+			check.Status = HealthWarning
+			check.Detail = string(status.Connection)
+		case status.Connection == ConnectionDisconnected:
+			check.Status = HealthWarning
+			if conn.Priority == ConnectionPrimary {
+				check.Status = HealthCritical
+			}
+			check.Detail = string(status.Connection)
+			if status.Error != nil && status.Error.Message != "" {
+				check.Detail += " : " + status.Error.Message
+			}
+		}
+		checks = append(checks, check)
+	}
+	return checks
+}
+
+// serviceHealthChecks checks vMix TCP API connection and configured triggers and bridges.
+func serviceHealthChecks() []HealthCheck {
+	checks := []HealthCheck{}
+	if vmixTCP != nil {
+		check := HealthCheck{Name: "tcp:" + vmixTCP.Addr, Status: HealthOK}
+		if !vmixTCP.Connected() {
+			check.Status = HealthWarning
+			check.Detail = "disconnected"
+		}
+		checks = append(checks, check)
+	}
+	for _, svc := range []struct {
+		name   string
+		status gin.H
+	}{
+		{"serial", serialTrigger.Status()},
+		{"artnet", artNetTrigger.Status()},
+		{"mqtt", mqttBridge.Status()},
+		{"gsi", gsiListener.Status()},
+		{"chat", chatBridge.Status()},
+	} {
+		check := HealthCheck{Name: "service:" + svc.name, Status: HealthOK}
+		if err, _ := svc.status["error"].(string); err != "" {
+			check.Status = HealthWarning
+			check.Detail = err
+		}
+		checks = append(checks, check)
+	}
+	return checks
+}
+
+// writeHealthReport writes report as JSON, or as single Nagios plugin style line when format=text.
+// Status code is 503 when report is down, so plain HTTP checks page without parsing body.
+func writeHealthReport(c *gin.Context, r HealthReport) {
+	code := http.StatusOK
+	if r.Status == "down" {
+		code = http.StatusServiceUnavailable
+	}
+	if c.Query("format") != "text" {
+		c.JSON(code, r)
+		return
+	}
+	label := map[string]string{"ok": "OK", "degraded": "WARNING", "down": "CRITICAL"}[r.Status]
+	problems := []string{}
+	for _, check := range r.Checks {
+		if check.Status != HealthOK {
+			problems = append(problems, strings.TrimSpace(check.Name+" "+check.Detail))
+		}
+	}
+	line := fmt.Sprintf("%s - %d checks ok", label, len(r.Checks))
+	if len(problems) > 0 {
+		line = fmt.Sprintf("%s - %s", label, strings.Join(problems, "; "))
+	}
+	c.String(code, "%s\n", line)
+}
+
+// HealthzHandler reports liveness of utility for [GET] /api/healthz?format=text .
+// Utility is live while its background tasks keep running.
+func HealthzHandler(c *gin.Context) {
+	now := time.Now()
+	writeHealthReport(c, newHealthReport(tasks.Checks(now), now))
+}
+
+// ReadyzHandler reports readiness of utility and every vMix host for [GET] /api/readyz?format=text .
+func ReadyzHandler(c *gin.Context) {
+	now := time.Now()
+	checks := tasks.Checks(now)
+	checks = append(checks, hostHealthChecks()...)
+	checks = append(checks, serviceHealthChecks()...)
+	writeHealthReport(c, newHealthReport(checks, now))
+}
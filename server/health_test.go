@@ -0,0 +1,48 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestTaskMonitorReportsStalledTask(t *testing.T) {
+	m := NewTaskMonitor()
+	m.Beat("poller", time.Second)
+	now := time.Now()
+	if r := newHealthReport(m.Checks(now), now); r.Status != "ok" {
+		t.Fatalf("expected live task ok, got %+v", r)
+	}
+	later := now.Add(3*time.Second + taskStallGrace + time.Second)
+	if r := newHealthReport(m.Checks(later), later); r.Status != "down" || r.Checks[0].Name != "task:poller" {
+		t.Fatalf("expected stalled task down, got %+v", r)
+	}
+}
+
+func TestReadyzReportsHosts(t *testing.T) {
+	p := NewPoller()
+	p.statuses[*vmixaddr] = &HostStatus{Name: "primary", Addr: *vmixaddr, Connection: ConnectionDisconnected, Error: NewAppError(ErrKindUnreachable, *vmixaddr, nil)}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/readyz", ReadyzHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/readyz?format=text", nil))
+	if w.Code != http.StatusServiceUnavailable || !strings.HasPrefix(w.Body.String(), "CRITICAL - ") || !strings.Contains(w.Body.String(), "host:primary disconnected") {
+		t.Fatalf("expected disconnected primary host critical, got %d %s", w.Code, w.Body)
+	}
+
+	p.statuses[*vmixaddr] = &HostStatus{Name: "primary", Addr: *vmixaddr, Connection: ConnectionConnected}
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/readyz", nil))
+	if w.Code != http.StatusOK || !strings.Contains(w.Body.String(), `{"name":"host:primary","status":"ok"}`) {
+		t.Fatalf("expected connected primary host ok, got %d %s", w.Code, w.Body)
+	}
+}
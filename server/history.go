@@ -0,0 +1,142 @@
+package main
+
+import (
+	"bytes"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// historyTopics are event topics recorded in action history.
+var historyTopics = map[string]bool{
+	"operator_action":  true,
+	"rehearsal_action": true,
+	"trigger":          true,
+	"status":           true,
+	"outputs":          true,
+	"autopilot":        true,
+	"stream_health":    true,
+	"upgrade":          true,
+	"chat":             true,
+}
+
+// HistoryEntry is recorded event.
+type HistoryEntry struct {
+	At time.Time `json:"at"`
+	Event
+}
+
+// ActionHistory records recent actions and host changes from EventHub, so they can be reviewed or archived after show.
+type ActionHistory struct {
+	mu      sync.Mutex
+	limit   int
+	entries []HistoryEntry
+}
+
+// NewActionHistory creates ActionHistory keeping last limit entries.
+func NewActionHistory(limit int) *ActionHistory {
+	return &ActionHistory{limit: limit}
+}
+
+// Record appends event if its topic is recorded.
+func (h *ActionHistory) Record(ev Event, now time.Time) {
+	if !historyTopics[ev.Topic] {
+		return
+	}
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	h.entries = append(h.entries, HistoryEntry{At: now, Event: ev})
+	if over := len(h.entries) - h.limit; over > 0 {
+		h.entries = append(h.entries[:0], h.entries[over:]...)
+	}
+}
+
+// Entries returns entries recorded at or after since.
+func (h *ActionHistory) Entries(since time.Time) []HistoryEntry {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	entries := []HistoryEntry{}
+	for _, e := range h.entries {
+		if !e.At.Before(since) {
+			entries = append(entries, e)
+		}
+	}
+	return entries
+}
+
+// Stats returns size of history.
+func (h *ActionHistory) Stats() CacheStats {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	return CacheStats{Name: "history", Entries: len(h.entries), Limit: h.limit}
+}
+
+// Run records events until hub evicts subscriber, then subscribes again.
+func (h *ActionHistory) Run() {
+	for {
+		ch, unsubscribe := events.SubscribeTo(256, "history", func(topic string) bool { return historyTopics[topic] })
+		for ev := range ch {
+			h.Record(ev, time.Now())
+		}
+		unsubscribe()
+	}
+}
+
+// LogBuffer keeps last lines written by standard logger, for show reports.
+type LogBuffer struct {
+	mu    sync.Mutex
+	limit int
+	lines [][]byte
+}
+
+// NewLogBuffer creates LogBuffer keeping last limit lines.
+func NewLogBuffer(limit int) *LogBuffer {
+	return &LogBuffer{limit: limit}
+}
+
+// Write implements io.Writer. Standard logger writes single line per call.
+func (b *LogBuffer) Write(p []byte) (int, error) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	b.lines = append(b.lines, append([]byte(nil), p...))
+	if over := len(b.lines) - b.limit; over > 0 {
+		b.lines = append(b.lines[:0], b.lines[over:]...)
+	}
+	return len(p), nil
+}
+
+// Bytes returns buffered lines.
+func (b *LogBuffer) Bytes() []byte {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return bytes.Join(b.lines, nil)
+}
+
+// Stats returns size of buffered lines.
+func (b *LogBuffer) Stats() CacheStats {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	stats := CacheStats{Name: "log", Entries: len(b.lines), Limit: b.limit}
+	for _, line := range b.lines {
+		stats.Bytes += int64(len(line))
+	}
+	return stats
+}
+
+// GetHistoryHandler returns action history for [GET] /api/history?since=RFC3339 .
+func GetHistoryHandler(c *gin.Context) {
+	var since time.Time
+	if q := c.Query("since"); q != "" {
+		t, err := time.Parse(time.RFC3339, q)
+		if err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+			return
+		}
+		since = t
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"history": history.Entries(since),
+	})
+}
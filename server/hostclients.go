@@ -0,0 +1,272 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"net/http"
+	"net/http/httptrace"
+	"sort"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// hostClientTimeout is default timeout of HTTP requests to vMix hosts, so wedged connection fails instead of hanging poll forever.
+const hostClientTimeout = 5 * time.Second
+
+// HTTPPoolSettings is keep-alive and connection pooling of HTTP client to vMix host.
+// vMix serves HTTP/1.1 without pipelining, so concurrent requests need their own connections,
+// and keeping few of them idle avoids dialing new connection on every poll.
+type HTTPPoolSettings struct {
+	DisableKeepAlive bool `json:"disable_keep_alive"` // close connection after each request.
+	KeepAliveSec     int  `json:"keep_alive_sec"`     // TCP keep-alive probe interval. 0 is 30.
+	MaxIdlePerHost   int  `json:"max_idle_per_host"`  // idle connections kept for reuse. 0 is 4.
+	MaxConnsPerHost  int  `json:"max_conns_per_host"` // connections including active. 0 is unlimited.
+	IdleTimeoutSec   int  `json:"idle_timeout_sec"`   // idle connection closed after this. 0 is 90.
+	TimeoutMs        int  `json:"timeout_ms"`         // request timeout, bounding how long poll of unreachable host takes. 0 is 5000.
+}
+
+// DefaultHTTPPool is pooling of hosts without settings. Polling and functions rarely run more than
+// few requests at once, so few idle connections are enough to avoid TCP churn.
+var DefaultHTTPPool = HTTPPoolSettings{KeepAliveSec: 30, MaxIdlePerHost: 4, IdleTimeoutSec: 90, TimeoutMs: int(hostClientTimeout / time.Millisecond)}
+
+// withDefaults fills zero settings by DefaultHTTPPool.
+func (s HTTPPoolSettings) withDefaults() HTTPPoolSettings {
+	if s.KeepAliveSec <= 0 {
+		s.KeepAliveSec = DefaultHTTPPool.KeepAliveSec
+	}
+	if s.MaxIdlePerHost <= 0 {
+		s.MaxIdlePerHost = DefaultHTTPPool.MaxIdlePerHost
+	}
+	if s.IdleTimeoutSec <= 0 {
+		s.IdleTimeoutSec = DefaultHTTPPool.IdleTimeoutSec
+	}
+	if s.TimeoutMs <= 0 {
+		s.TimeoutMs = DefaultHTTPPool.TimeoutMs
+	}
+	return s
+}
+
+// HTTPPoolStats is connection reuse of host client since start.
+type HTTPPoolStats struct {
+	Addr     string           `json:"addr"`
+	Settings HTTPPoolSettings `json:"settings"`
+	Requests int64            `json:"requests"`
+	Reused   int64            `json:"reused"` // requests sent on connection kept alive.
+	Dials    int64            `json:"dials"`  // new TCP connections.
+	Open     int64            `json:"open"`   // connections currently open, both active and idle.
+	Resets   int64            `json:"resets"` // clients recreated by watchdog.
+}
+
+// poolCounters is counters of HTTPPoolStats updated atomically. Kept separate so 64-bit fields are aligned.
+type poolCounters struct {
+	requests, reused, dials, open, resets int64
+}
+
+// hostPool is HTTP client of host with its counters.
+type hostPool struct {
+	client   *http.Client
+	settings HTTPPoolSettings
+	counters *poolCounters
+}
+
+// HostClients holds HTTP client per vMix host. Client can be recreated when connection got wedged.
+type HostClients struct {
+	mu       sync.Mutex
+	clients  map[string]*hostPool        // addr -> client.
+	counters map[string]*poolCounters    // addr -> counters, kept across resets.
+	settings map[string]HTTPPoolSettings // addr -> pooling. Missing host uses DefaultHTTPPool.
+}
+
+// NewHostClients creates HostClients.
+func NewHostClients() *HostClients {
+	return &HostClients{
+		clients:  make(map[string]*hostPool),
+		counters: make(map[string]*poolCounters),
+		settings: make(map[string]HTTPPoolSettings),
+	}
+}
+
+// Get returns client of host.
+func (h *HostClients) Get(addr string) *http.Client {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	p, ok := h.clients[addr]
+	if !ok {
+		p = h.newPool(addr)
+		h.clients[addr] = p
+	}
+	return p.client
+}
+
+// newPool creates client of host by its pooling settings. Caller must hold mu.
+func (h *HostClients) newPool(addr string) *hostPool {
+	settings, ok := h.settings[addr]
+	if !ok {
+		settings = DefaultHTTPPool
+	}
+	settings = settings.withDefaults()
+	counters, ok := h.counters[addr]
+	if !ok {
+		counters = &poolCounters{}
+		h.counters[addr] = counters
+	}
+	timeout := time.Duration(settings.TimeoutMs) * time.Millisecond
+	dialer := &net.Dialer{Timeout: timeout, KeepAlive: time.Duration(settings.KeepAliveSec) * time.Second}
+	transport := &http.Transport{
+		Proxy: http.ProxyFromEnvironment,
+		DialContext: func(ctx context.Context, network, address string) (net.Conn, error) {
+			conn, err := dialer.DialContext(ctx, network, address)
+			if err != nil {
+				return nil, err
+			}
+			atomic.AddInt64(&counters.dials, 1)
+			atomic.AddInt64(&counters.open, 1)
+			return &countedConn{Conn: conn, open: &counters.open}, nil
+		},
+		DisableKeepAlives:   settings.DisableKeepAlive,
+		MaxIdleConns:        settings.MaxIdlePerHost,
+		MaxIdleConnsPerHost: settings.MaxIdlePerHost,
+		MaxConnsPerHost:     settings.MaxConnsPerHost,
+		IdleConnTimeout:     time.Duration(settings.IdleTimeoutSec) * time.Second,
+	}
+	return &hostPool{
+		client:   &http.Client{Timeout: timeout, Transport: &tracedTransport{base: transport, counters: counters}},
+		settings: settings,
+		counters: counters,
+	}
+}
+
+// Reset drops client of host and closes its connections. Next request dials new connection.
+func (h *HostClients) Reset(addr string) {
+	h.mu.Lock()
+	p, ok := h.clients[addr]
+	delete(h.clients, addr)
+	h.mu.Unlock()
+	if ok {
+		atomic.AddInt64(&p.counters.resets, 1)
+		p.client.CloseIdleConnections()
+	}
+}
+
+// SetPools sets pooling settings by host and recreates clients of changed hosts.
+func (h *HostClients) SetPools(pools map[string]HTTPPoolSettings) {
+	h.mu.Lock()
+	h.settings = make(map[string]HTTPPoolSettings, len(pools))
+	for addr, settings := range pools {
+		h.settings[addr] = settings
+	}
+	stale := []*hostPool{}
+	for addr, p := range h.clients {
+		settings, ok := h.settings[addr]
+		if !ok {
+			settings = DefaultHTTPPool
+		}
+		if settings.withDefaults() != p.settings {
+			stale = append(stale, p)
+			delete(h.clients, addr)
+		}
+	}
+	h.mu.Unlock()
+	for _, p := range stale {
+		p.client.CloseIdleConnections()
+	}
+}
+
+// Stats returns connection reuse of every host ordered by address.
+func (h *HostClients) Stats() []HTTPPoolStats {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	list := make([]HTTPPoolStats, 0, len(h.counters))
+	for addr, counters := range h.counters {
+		settings, ok := h.settings[addr]
+		if !ok {
+			settings = DefaultHTTPPool
+		}
+		list = append(list, HTTPPoolStats{
+			Addr:     addr,
+			Settings: settings.withDefaults(),
+			Requests: atomic.LoadInt64(&counters.requests),
+			Reused:   atomic.LoadInt64(&counters.reused),
+			Dials:    atomic.LoadInt64(&counters.dials),
+			Open:     atomic.LoadInt64(&counters.open),
+			Resets:   atomic.LoadInt64(&counters.resets),
+		})
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].Addr < list[j].Addr })
+	return list
+}
+
+// tracedTransport counts requests and whether they reused kept alive connection.
+type tracedTransport struct {
+	base     http.RoundTripper
+	counters *poolCounters
+}
+
+func (t *tracedTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	atomic.AddInt64(&t.counters.requests, 1)
+	trace := &httptrace.ClientTrace{
+		GotConn: func(info httptrace.GotConnInfo) {
+			if info.Reused {
+				atomic.AddInt64(&t.counters.reused, 1)
+			}
+		},
+	}
+	return t.base.RoundTrip(req.WithContext(httptrace.WithClientTrace(req.Context(), trace)))
+}
+
+func (t *tracedTransport) CloseIdleConnections() {
+	if c, ok := t.base.(interface{ CloseIdleConnections() }); ok {
+		c.CloseIdleConnections()
+	}
+}
+
+// countedConn decrements open connections once closed.
+type countedConn struct {
+	net.Conn
+	open   *int64
+	closed int32
+}
+
+func (c *countedConn) Close() error {
+	if atomic.CompareAndSwapInt32(&c.closed, 0, 1) {
+		atomic.AddInt64(c.open, -1)
+	}
+	return c.Conn.Close()
+}
+
+// GetHTTPPoolHandler returns pooling settings and connection reuse of vMix hosts for [GET] /api/http/pool .
+func GetHTTPPoolHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"default": DefaultHTTPPool,
+		"pools":   config.Get().HTTPPools,
+		"stats":   hostClients.Stats(),
+	})
+}
+
+// PutHTTPPoolHandler saves pooling settings by vMix host for [PUT] /api/http/pool .
+// Clients of changed hosts are recreated, so new settings apply from next request.
+func PutHTTPPoolHandler(c *gin.Context) {
+	pools := map[string]HTTPPoolSettings{}
+	if err := c.ShouldBindJSON(&pools); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for addr, settings := range pools {
+		if settings.KeepAliveSec < 0 || settings.MaxIdlePerHost < 0 || settings.MaxConnsPerHost < 0 || settings.IdleTimeoutSec < 0 {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, addr, fmt.Errorf("negative pool settings")))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.HTTPPools = pools
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	hostClients.SetPools(pools)
+	GetHTTPPoolHandler(c)
+}
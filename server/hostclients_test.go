@@ -0,0 +1,40 @@
+package main
+
+import (
+	"io"
+	"io/ioutil"
+	"testing"
+)
+
+func TestHostClientsReuseConnections(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	h := NewHostClients()
+	for i := 0; i < 3; i++ {
+		resp, err := h.Get(addr).Get(addr + "/api")
+		if err != nil {
+			t.Fatalf("request failed : %v", err)
+		}
+		io.Copy(ioutil.Discard, resp.Body)
+		resp.Body.Close()
+	}
+	stats := h.Stats()
+	if len(stats) != 1 || stats[0].Requests != 3 || stats[0].Dials != 1 || stats[0].Reused != 2 {
+		t.Fatalf("expected 3 requests on 1 kept alive connection, got %+v", stats)
+	}
+
+	// Changed settings recreate client, and counters are kept.
+	h.SetPools(map[string]HTTPPoolSettings{addr: {DisableKeepAlive: true}})
+	for i := 0; i < 2; i++ {
+		resp, err := h.Get(addr).Get(addr + "/api")
+		if err != nil {
+			t.Fatalf("request failed : %v", err)
+		}
+		io.Copy(ioutil.Discard, resp.Body)
+		resp.Body.Close()
+	}
+	stats = h.Stats()
+	if stats[0].Requests != 5 || stats[0].Dials != 3 || stats[0].Reused != 2 || !stats[0].Settings.DisableKeepAlive {
+		t.Fatalf("expected new connection per request without keep-alive, got %+v", stats)
+	}
+}
@@ -0,0 +1,161 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"reflect"
+	"sort"
+	"strconv"
+	"sync"
+
+	"github.com/gin-gonic/gin"
+)
+
+// CachedInput is immutable input entry. Entries are replaced, never modified,
+// so readers can share pointers without copying.
+type CachedInput struct {
+	Input      StateInput `json:"input"`
+	Generation uint64     `json:"generation"` // cache generation when this entry was last changed.
+}
+
+// maxRemovedInputs limits remembered removed keys. Oldest are forgotten, and clients behind them must fetch again from scratch.
+const maxRemovedInputs = 1000
+
+// InputCache keeps latest inputs by key with generation counters, so clients can fetch only changed inputs.
+type InputCache struct {
+	mu         sync.RWMutex
+	generation uint64
+	inputs     map[string]*CachedInput
+	removed    map[string]uint64 // key -> generation when removed.
+	floor      uint64            // newest generation of forgotten removed keys.
+	evicted    uint64
+}
+
+// NewInputCache creates InputCache.
+func NewInputCache() *InputCache {
+	return &InputCache{
+		inputs:  make(map[string]*CachedInput),
+		removed: make(map[string]uint64),
+	}
+}
+
+// Update merges inputs of state. Generation is incremented only if something changed.
+func (c *InputCache) Update(s *State) uint64 {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	next := c.generation + 1
+	changed := false
+	seen := make(map[string]bool, len(s.Inputs))
+	for _, in := range s.Inputs {
+		seen[in.Key] = true
+		if cur, ok := c.inputs[in.Key]; ok && reflect.DeepEqual(cur.Input, in) {
+			continue
+		}
+		c.inputs[in.Key] = &CachedInput{Input: in, Generation: next}
+		delete(c.removed, in.Key)
+		changed = true
+	}
+	for key := range c.inputs {
+		if !seen[key] {
+			delete(c.inputs, key)
+			c.removed[key] = next
+			changed = true
+		}
+	}
+	if changed {
+		c.generation = next
+	}
+	c.evictRemoved()
+	return c.generation
+}
+
+// evictRemoved forgets oldest removed keys over maxRemovedInputs. Caller must hold lock.
+func (c *InputCache) evictRemoved() {
+	if len(c.removed) <= maxRemovedInputs {
+		return
+	}
+	keys := make([]string, 0, len(c.removed))
+	for key := range c.removed {
+		keys = append(keys, key)
+	}
+	sort.Slice(keys, func(i, j int) bool { return c.removed[keys[i]] < c.removed[keys[j]] })
+	for _, key := range keys[:len(keys)-maxRemovedInputs] {
+		if gen := c.removed[key]; gen > c.floor {
+			c.floor = gen
+		}
+		delete(c.removed, key)
+		c.evicted++
+	}
+}
+
+// Stale reports whether removed keys after generation may have been forgotten, so client must fetch from scratch.
+func (c *InputCache) Stale(generation uint64) bool {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	return generation < c.floor
+}
+
+// Stats returns size of cache.
+func (c *InputCache) Stats() CacheStats {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	return CacheStats{Name: "inputs", Entries: len(c.inputs) + len(c.removed), Limit: maxRemovedInputs, Evicted: c.evicted}
+}
+
+// Get returns cached input by key.
+func (c *InputCache) Get(key string) (*CachedInput, bool) {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	in, ok := c.inputs[key]
+	return in, ok
+}
+
+// Since returns entries changed after generation, keys removed after generation and current generation.
+func (c *InputCache) Since(generation uint64) ([]*CachedInput, []string, uint64) {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	changed := []*CachedInput{}
+	for _, in := range c.inputs {
+		if in.Generation > generation {
+			changed = append(changed, in)
+		}
+	}
+	removed := []string{}
+	for key, gen := range c.removed {
+		if gen > generation {
+			removed = append(removed, key)
+		}
+	}
+	return changed, removed, c.generation
+}
+
+// Generation returns current generation.
+func (c *InputCache) Generation() uint64 {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	return c.generation
+}
+
+// GetInputCacheHandler returns inputs changed since generation for [GET] /api/inputs/cache?since=N .
+func GetInputCacheHandler(c *gin.Context) {
+	var since uint64
+	if q := c.Query("since"); q != "" {
+		n, err := strconv.ParseUint(q, 10, 64)
+		if err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid since %q", q)))
+			return
+		}
+		since = n
+	}
+	reset := since > 0 && inputCache.Stale(since)
+	if reset {
+		since = 0
+	}
+	changed, removed, generation := inputCache.Since(since)
+	c.JSON(http.StatusOK, gin.H{
+		"generation": generation,
+		"changed":    changed,
+		"removed":    removed,
+		"reset":      reset, // client must drop inputs not in changed.
+	})
+}
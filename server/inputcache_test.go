@@ -0,0 +1,48 @@
+package main
+
+import (
+	"fmt"
+	"testing"
+)
+
+func TestInputCacheGenerations(t *testing.T) {
+	c := NewInputCache()
+	s := DefaultMockState()
+	g1 := c.Update(s)
+	if changed, _, _ := c.Since(0); len(changed) != len(s.Inputs) {
+		t.Fatalf("expected %d changed inputs, got %d", len(s.Inputs), len(changed))
+	}
+
+	if g := c.Update(s); g != g1 {
+		t.Fatalf("generation changed without changes: %d -> %d", g1, g)
+	}
+
+	before, _ := c.Get(s.Inputs[1].Key)
+	s.Inputs[0].Title = "Renamed"
+	s.Inputs = s.Inputs[:5]
+	g2 := c.Update(s)
+	changed, removed, gen := c.Since(g1)
+	if g2 != g1+1 || gen != g2 || len(changed) != 1 || changed[0].Input.Title != "Renamed" || len(removed) != 1 {
+		t.Fatalf("unexpected diff: gen=%d changed=%+v removed=%v", gen, changed, removed)
+	}
+	if after, _ := c.Get(s.Inputs[1].Key); after != before {
+		t.Fatal("unchanged input entry was replaced")
+	}
+}
+
+func TestInputCacheForgetsOldRemovedKeys(t *testing.T) {
+	c := NewInputCache()
+	s := &State{}
+	for i := 1; i <= maxRemovedInputs+10; i++ {
+		s.Inputs = append(s.Inputs, StateInput{Key: fmt.Sprintf("key-%d", i), Number: i})
+	}
+	g1 := c.Update(s)
+	s.Inputs = nil
+	g2 := c.Update(s)
+	if stats := c.Stats(); stats.Entries != maxRemovedInputs || stats.Evicted != 10 {
+		t.Fatalf("unexpected stats %+v", stats)
+	}
+	if !c.Stale(g1) || c.Stale(g2) {
+		t.Fatalf("expected only generations before eviction to be stale")
+	}
+}
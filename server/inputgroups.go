@@ -0,0 +1,159 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"sort"
+
+	"github.com/gin-gonic/gin"
+)
+
+// groupFunctions maps group action to vMix function applied to every input of group.
+var groupFunctions = map[string]string{
+	"play":     "Play",
+	"pause":    "Pause",
+	"restart":  "Restart",
+	"loop_on":  "LoopOn",
+	"loop_off": "LoopOff",
+}
+
+// groupActions returns actions applicable to input groups, "preview_next" first.
+func groupActions() []string {
+	actions := []string{"preview_next"}
+	for action := range groupFunctions {
+		actions = append(actions, action)
+	}
+	sort.Strings(actions[1:])
+	return actions
+}
+
+// InputGroups returns input groups of host. Inputs are referenced by key, number or title.
+func InputGroups(host string) map[string][]string {
+	groups := config.Get().InputGroups[host]
+	if groups == nil {
+		groups = map[string][]string{}
+	}
+	return groups
+}
+
+// hostState returns latest polled state of host.
+func hostState(host string) (*State, error) {
+	status, ok := poller.Status(host)
+	if !ok || status.State == nil {
+		return nil, NewAppError(ErrKindNotLoaded, host, fmt.Errorf("vMix state not loaded"))
+	}
+	return status.State, nil
+}
+
+// resolveGroup returns inputs of group that exist in state, in group order.
+func resolveGroup(s *State, refs []string) []*StateInput {
+	inputs := []*StateInput{}
+	for _, ref := range refs {
+		if in := s.FindInput(ref); in != nil {
+			inputs = append(inputs, in)
+		}
+	}
+	return inputs
+}
+
+// NextInGroup returns input following current preview in group, or program if preview is not in group.
+// First input is returned if neither is in group.
+func NextInGroup(s *State, inputs []*StateInput) *StateInput {
+	if len(inputs) == 0 {
+		return nil
+	}
+	for _, current := range []int{s.Preview, s.Active} {
+		for i, in := range inputs {
+			if in.Number == current {
+				return inputs[(i+1)%len(inputs)]
+			}
+		}
+	}
+	return inputs[0]
+}
+
+// ApplyGroupAction applies action to input group of host and returns keys of affected inputs.
+// "preview_next" previews next input in group, other actions send function to every input.
+func ApplyGroupAction(host, group, action string) ([]string, error) {
+	refs, ok := InputGroups(host)[group]
+	if !ok {
+		return nil, NewAppError(ErrKindNotFound, host, fmt.Errorf("input group %q not found", group))
+	}
+	s, err := hostState(host)
+	if err != nil {
+		return nil, err
+	}
+	inputs := resolveGroup(s, refs)
+	if len(inputs) == 0 {
+		return nil, NewAppError(ErrKindNotFound, host, fmt.Errorf("no inputs of group %q found", group))
+	}
+	poller.Touch()
+	if action == "preview_next" {
+		next := NextInGroup(s, inputs)
+		return []string{next.Key}, SendFunctionTo(host, "PreviewInput", map[string]string{"Input": next.Key})
+	}
+	function, ok := groupFunctions[action]
+	if !ok {
+		return nil, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("unknown group action %q", action))
+	}
+	keys := make([]string, 0, len(inputs))
+	for _, in := range inputs {
+		if err := SendFunctionTo(host, function, map[string]string{"Input": in.Key}); err != nil {
+			return keys, err
+		}
+		keys = append(keys, in.Key)
+	}
+	return keys, nil
+}
+
+// GetInputGroupsHandler returns input groups of host for [GET] /api/groups/inputs?host=... .
+func GetInputGroupsHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"groups":  InputGroups(host),
+		"actions": groupActions(),
+	})
+}
+
+// PutInputGroupsHandler saves input groups of host for [PUT] /api/groups/inputs?host=... .
+func PutInputGroupsHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	groups := map[string][]string{}
+	if err := c.ShouldBindJSON(&groups); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.InputGroups[host] = groups
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetInputGroupsHandler(c)
+}
+
+// InputGroupActionRequest is request body of InputGroupActionHandler.
+type InputGroupActionRequest struct {
+	Action string `json:"action" binding:"required"` // e.g. "preview_next", "loop_on" .
+}
+
+// InputGroupActionHandler applies action to input group for [POST] /api/groups/inputs/:name/action?host=... .
+func InputGroupActionHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	req := InputGroupActionRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	keys, err := ApplyGroupAction(host, c.Param("name"), req.Action)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, host, fmt.Sprintf("%s %s", req.Action, c.Param("name")))
+	c.JSON(http.StatusOK, gin.H{
+		"host":   host,
+		"inputs": keys,
+	})
+}
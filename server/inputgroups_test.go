@@ -0,0 +1,23 @@
+package main
+
+import "testing"
+
+func TestNextInGroup(t *testing.T) {
+	s := DefaultMockState()
+	s.Active, s.Preview = 2, 3
+	inputs := resolveGroup(s, []string{"2", "3", "4", "NotExist"})
+	if len(inputs) != 3 {
+		t.Fatalf("expected 3 inputs, got %d", len(inputs))
+	}
+	if next := NextInGroup(s, inputs); next.Number != 4 {
+		t.Fatalf("expected input 4, got %d", next.Number)
+	}
+	s.Preview = 4
+	if next := NextInGroup(s, inputs); next.Number != 2 {
+		t.Fatalf("expected wrap to input 2, got %d", next.Number)
+	}
+	s.Active, s.Preview = 1, 6
+	if next := NextInGroup(s, inputs); next.Number != 2 {
+		t.Fatalf("expected first input, got %d", next.Number)
+	}
+}
@@ -0,0 +1,89 @@
+package main
+
+import (
+	"encoding/json"
+	"reflect"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// PatchOp is JSON Patch (RFC 6902) operation. Only "add", "remove" and "replace" are produced.
+type PatchOp struct {
+	Op    string      `json:"op"`
+	Path  string      `json:"path"` // JSON Pointer (RFC 6901). e.g. "/states/primary/active" .
+	Value interface{} `json:"value"`
+}
+
+// toJSONValue converts v into generic JSON value (map, slice, string, float64, bool or nil), as seen by clients.
+func toJSONValue(v interface{}) (interface{}, error) {
+	b, err := json.Marshal(v)
+	if err != nil {
+		return nil, err
+	}
+	var out interface{}
+	err = json.Unmarshal(b, &out)
+	return out, err
+}
+
+// escapePointer escapes JSON Pointer reference token.
+func escapePointer(token string) string {
+	return strings.ReplaceAll(strings.ReplaceAll(token, "~", "~0"), "/", "~1")
+}
+
+// DiffJSON returns operations turning generic JSON value a into b.
+// Objects are compared key by key. Arrays of same length are compared element by element, otherwise replaced,
+// except appended elements which are added, such as new inputs.
+func DiffJSON(a, b interface{}) []PatchOp {
+	return diffJSON("", a, b, []PatchOp{})
+}
+
+func diffJSON(path string, a, b interface{}, ops []PatchOp) []PatchOp {
+	switch av := a.(type) {
+	case map[string]interface{}:
+		bv, ok := b.(map[string]interface{})
+		if !ok {
+			break
+		}
+		keys := make([]string, 0, len(av)+len(bv))
+		for k := range av {
+			keys = append(keys, k)
+		}
+		for k := range bv {
+			if _, ok := av[k]; !ok {
+				keys = append(keys, k)
+			}
+		}
+		sort.Strings(keys)
+		for _, k := range keys {
+			p := path + "/" + escapePointer(k)
+			old, inA := av[k]
+			cur, inB := bv[k]
+			switch {
+			case !inB:
+				ops = append(ops, PatchOp{Op: "remove", Path: p})
+			case !inA:
+				ops = append(ops, PatchOp{Op: "add", Path: p, Value: cur})
+			default:
+				ops = diffJSON(p, old, cur, ops)
+			}
+		}
+		return ops
+	case []interface{}:
+		bv, ok := b.([]interface{})
+		if !ok || len(bv) < len(av) {
+			break
+		}
+		for i := range av {
+			ops = diffJSON(path+"/"+strconv.Itoa(i), av[i], bv[i], ops)
+		}
+		for i := len(av); i < len(bv); i++ {
+			ops = append(ops, PatchOp{Op: "add", Path: path + "/-", Value: bv[i]})
+		}
+		return ops
+	}
+	if reflect.DeepEqual(a, b) {
+		return ops
+	}
+	return append(ops, PatchOp{Op: "replace", Path: path, Value: b})
+}
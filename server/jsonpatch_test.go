@@ -0,0 +1,21 @@
+package main
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestDiffJSON(t *testing.T) {
+	var a, b interface{}
+	json.Unmarshal([]byte(`{"active":1,"inputs":[{"title":"A"}],"a/b":true,"gone":1}`), &a)
+	json.Unmarshal([]byte(`{"active":2,"inputs":[{"title":"A"},{"title":"B"}],"a/b":true,"new":null}`), &b)
+
+	got, _ := json.Marshal(DiffJSON(a, b))
+	want := `[{"op":"replace","path":"/active","value":2},{"op":"remove","path":"/gone","value":null},{"op":"add","path":"/inputs/-","value":{"title":"B"}},{"op":"add","path":"/new","value":null}]`
+	if string(got) != want {
+		t.Fatalf("unexpected patch\n got %s\nwant %s", got, want)
+	}
+	if ops := DiffJSON(a, a); len(ops) != 0 {
+		t.Fatalf("expected no operations, got %+v", ops)
+	}
+}
@@ -0,0 +1,17 @@
+package main
+
+import (
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func durationMs(d time.Duration) float64 {
+	return float64(d) / float64(time.Millisecond)
+}
+
+// GetLatencyReportHandler returns tally path latency report for [GET] /api/latency .
+func GetLatencyReportHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, latency.Report())
+}
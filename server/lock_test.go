@@ -0,0 +1,18 @@
+package main
+
+import "testing"
+
+func TestHostLocks(t *testing.T) {
+	l := NewHostLocks()
+	l.Lock(HostLock{Host: "http://a:8088", Reason: "reconfiguring", LockedBy: "op1"})
+	if err := l.Check("http://a:8088"); err == nil || AsAppError(err).Kind != ErrKindLocked {
+		t.Fatalf("expected locked error, got %v", err)
+	}
+	if err := l.Check("http://b:8088"); err != nil {
+		t.Fatalf("unlocked host rejected: %v", err)
+	}
+	l.Unlock("http://a:8088")
+	if err := l.Check("http://a:8088"); err != nil {
+		t.Fatalf("host still locked: %v", err)
+	}
+}
@@ -0,0 +1,229 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// lookFrameInterval is interval between incremental pan/zoom updates while animating looks.
+const lookFrameInterval = 40 * time.Millisecond
+
+// defaultTransform is pan and zoom of input or layer without <position> element.
+var defaultTransform = StateTransform{ZoomX: 1, ZoomY: 1}
+
+// LayerLook is saved pan and zoom of input and its layers, such as camera angles of virtual set.
+type LayerLook struct {
+	Layers     map[int]StateTransform `json:"layers"`      // layer number 1-10 -> transform. 0 is input itself.
+	DurationMs int                    `json:"duration_ms"` // default animation duration of recall.
+}
+
+// InputLooks is saved looks of vMix host. input key -> look name -> look.
+type InputLooks map[string]map[string]LayerLook
+
+// CurrentLook returns pan and zoom of input and its layers in state.
+func CurrentLook(in *StateInput) LayerLook {
+	look := LayerLook{Layers: map[int]StateTransform{0: defaultTransform}}
+	if in.Transform != nil {
+		look.Layers[0] = *in.Transform
+	}
+	for _, ov := range in.Overlays {
+		t := defaultTransform
+		if ov.Transform != nil {
+			t = *ov.Transform
+		}
+		// Layer index in vMix XML is zero based.
+		look.Layers[ov.Index+1] = t
+	}
+	return look
+}
+
+// lookFunction returns vMix function setting property of layer. Layer 0 is input itself.
+func lookFunction(layer int, property string) string {
+	if layer == 0 {
+		return "Set" + property
+	}
+	return "SetLayer" + strconv.Itoa(layer) + property
+}
+
+// LookFrames returns functions of each animation frame from current look to target look.
+// Only values that change between frames are sent. Layers missing in current look start from default.
+func LookFrames(inputKey string, from, to LayerLook, frames int) [][]FunctionCall {
+	if frames < 1 {
+		frames = 1
+	}
+	format := func(v float64) string { return strconv.FormatFloat(v, 'f', 4, 64) }
+	result := make([][]FunctionCall, frames)
+	last := map[string]string{}
+	for i := 1; i <= frames; i++ {
+		t := float64(i) / float64(frames)
+		calls := []FunctionCall{}
+		for layer, target := range to.Layers {
+			start, ok := from.Layers[layer]
+			if !ok {
+				start = defaultTransform
+			}
+			for _, p := range []struct {
+				property string
+				from, to float64
+			}{
+				{"Zoom", start.ZoomX, target.ZoomX},
+				{"PanX", start.PanX, target.PanX},
+				{"PanY", start.PanY, target.PanY},
+			} {
+				if p.from == p.to {
+					continue
+				}
+				function := lookFunction(layer, p.property)
+				value := format(p.from + (p.to-p.from)*t)
+				if last[function] == value {
+					continue
+				}
+				last[function] = value
+				calls = append(calls, FunctionCall{Function: function, Params: map[string]string{"Input": inputKey, "Value": value}})
+			}
+		}
+		result[i-1] = calls
+	}
+	return result
+}
+
+// lookInput returns state and input of host resolved by reference or alias.
+func lookInput(host, ref string) (*State, *StateInput, error) {
+	s, err := hostState(host)
+	if err != nil {
+		return nil, nil, err
+	}
+	in := s.FindInput(ResolveInputAlias(host, ref))
+	if in == nil {
+		return nil, nil, NewAppError(ErrKindNotFound, host, fmt.Errorf("input %q not found", ref))
+	}
+	return s, in, nil
+}
+
+// SaveLayerLook saves current pan and zoom of input as named look.
+func SaveLayerLook(host, input, name string, durationMs int) (LayerLook, error) {
+	_, in, err := lookInput(host, input)
+	if err != nil {
+		return LayerLook{}, err
+	}
+	look := CurrentLook(in)
+	look.DurationMs = durationMs
+	err = config.Update(func(cfg *Config) {
+		if cfg.LayerLooks[host] == nil {
+			cfg.LayerLooks[host] = InputLooks{}
+		}
+		if cfg.LayerLooks[host][in.Key] == nil {
+			cfg.LayerLooks[host][in.Key] = map[string]LayerLook{}
+		}
+		cfg.LayerLooks[host][in.Key][name] = look
+	})
+	return look, err
+}
+
+// RecallLayerLook animates input from current pan and zoom to saved look over duration.
+// Negative duration uses duration saved with look.
+func RecallLayerLook(host, input, name string, duration time.Duration) error {
+	_, in, err := lookInput(host, input)
+	if err != nil {
+		return err
+	}
+	look, ok := config.Get().LayerLooks[host][in.Key][name]
+	if !ok {
+		return NewAppError(ErrKindNotFound, host, fmt.Errorf("look %q of input %q not found", name, input))
+	}
+	if duration < 0 {
+		duration = time.Duration(look.DurationMs) * time.Millisecond
+	}
+	frames := LookFrames(in.Key, CurrentLook(in), look, int(duration/lookFrameInterval))
+	t := time.NewTicker(lookFrameInterval)
+	defer t.Stop()
+	for i, calls := range frames {
+		if i > 0 {
+			<-t.C
+		}
+		for _, call := range calls {
+			if err := SendFunctionTo(host, call.Function, call.Params); err != nil {
+				return err
+			}
+		}
+	}
+	poller.Touch()
+	return nil
+}
+
+// GetLayerLooksHandler returns saved looks of input for [GET] /api/looks?host=...&input=... .
+func GetLayerLooksHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	_, in, err := lookInput(host, c.Query("input"))
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	looks := config.Get().LayerLooks[host][in.Key]
+	if looks == nil {
+		looks = map[string]LayerLook{}
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"input":   in.Key,
+		"current": CurrentLook(in),
+		"looks":   looks,
+	})
+}
+
+// LayerLookRequest is request body of SaveLayerLookHandler and RecallLayerLookHandler.
+type LayerLookRequest struct {
+	Input      string `json:"input"`
+	DurationMs *int   `json:"duration_ms"` // animation duration. Saved duration is used if omitted on recall.
+}
+
+// SaveLayerLookHandler saves current pan and zoom as look for [POST] /api/looks/:name/save?host=... .
+func SaveLayerLookHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	req := LayerLookRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	durationMs := 0
+	if req.DurationMs != nil {
+		durationMs = *req.DurationMs
+	}
+	look, err := SaveLayerLook(host, req.Input, c.Param("name"), durationMs)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host": host,
+		"look": look,
+	})
+}
+
+// RecallLayerLookHandler animates input to look for [POST] /api/looks/:name/recall?host=... .
+// Response is returned after animation finished.
+func RecallLayerLookHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	req := LayerLookRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	duration := time.Duration(-1)
+	if req.DurationMs != nil {
+		duration = time.Duration(*req.DurationMs) * time.Millisecond
+	}
+	if err := RecallLayerLook(host, req.Input, c.Param("name"), duration); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, host, fmt.Sprintf("look %s on %s", c.Param("name"), req.Input))
+	c.JSON(http.StatusOK, gin.H{
+		"host": host,
+		"look": c.Param("name"),
+	})
+}
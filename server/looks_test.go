@@ -0,0 +1,27 @@
+package main
+
+import "testing"
+
+func TestLookFrames(t *testing.T) {
+	s, err := ParseState([]byte(`<vmix><inputs><input key="k1" number="1" type="Mix" title="Set">Set<position panX="0.5" panY="0" zoomX="2" zoomY="2"/><overlay index="1" key="k2"><position panX="-0.5" panY="0.5" zoomX="0.5" zoomY="0.5"/></overlay></input></inputs></vmix>`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	from := CurrentLook(&s.Inputs[0])
+	if from.Layers[0].ZoomX != 2 || from.Layers[2].PanY != 0.5 || s.Inputs[0].Text != "Set" {
+		t.Fatalf("unexpected look %+v", from)
+	}
+
+	to := LayerLook{Layers: map[int]StateTransform{0: {PanX: 0.5, ZoomX: 1, ZoomY: 1}, 2: {PanX: 0.5, PanY: 0.5, ZoomX: 0.5, ZoomY: 0.5}}}
+	frames := LookFrames("k1", from, to, 2)
+	if len(frames) != 2 || len(frames[0]) != 2 {
+		t.Fatalf("unexpected frames %+v", frames)
+	}
+	values := map[string]string{}
+	for _, call := range frames[1] {
+		values[call.Function] = call.Params["Value"]
+	}
+	if values["SetZoom"] != "1.0000" || values["SetLayer2PanX"] != "0.5000" {
+		t.Fatalf("unexpected last frame %+v", frames[1])
+	}
+}
@@ -4,12 +4,16 @@ import (
 	"embed"
 	"flag"
 	"fmt"
+	"io"
 	"log"
 	"net/http"
+	"os"
 	"os/exec"
 	"strings"
 	"sync"
+	"time"
 
+	"github.com/FlowingSPDG/vmix-utility/server/core"
 	"github.com/gin-gonic/gin"
 
 	vmixgo "github.com/FlowingSPDG/vmix-go"
@@ -23,12 +27,80 @@ type vMixFunction struct {
 	Options        map[string]string `json:"options"`         // other options types, such as "Duration":"int" .
 }
 
+// appVersion is utility version, set by -ldflags "-X main.appVersion=..." at release build.
+var appVersion = "dev"
+
 // vMix variables
 var (
 	hostaddr      *string        // API Listen host
 	vmixaddr      *string        // Target vMix host address
 	vMixFunctions []vMixFunction // vMix functions slice. TODO!
 	vmix          *vmixgo.Vmix
+	vmixTCPAddr   *string           // Target vMix TCP API address
+	vmixTCP       *TCPClient        // vMix TCP API client
+	tallyRefresh  *ProgramRefresher // Refreshes program and preview of primary host on tally
+	app           = core.New()
+	events        = app.Events
+	actsCache     = NewActsCache()
+	latency       = app.Latency
+	snapshots     = NewSnapshotStore()
+	inputCache    = NewInputCache()
+	poller        = NewPoller()
+	pollInterval  *time.Duration // vMix status polling interval while active
+	pollIdle      *time.Duration // vMix status polling interval while idle
+	pollFocus     *time.Duration // vMix status polling interval while user interface is focused
+	pollBudget    *float64       // Maximum vMix polls per second
+	graphics      = NewGraphicsStore()
+	scoreboards   = NewScoreboardStore()
+	showClock     = NewShowClock()
+	ftb           = NewFTBInterlock()
+	locks         = NewHostLocks()
+	operators     = NewOperators()
+	autoPilot     = NewAutoPilot()
+	afv           = NewAFV()
+	serialTrigger = NewSerialTrigger()
+	artNetTrigger = NewArtNetTrigger()
+	timecode      = NewTimecodeChase()
+	cueWarnings   = NewCueWarnings()
+	streamHealth  = NewStreamHealth()
+	history       = NewActionHistory(5000)
+	startup       = NewStartupActions()
+	logBuffer     = NewLogBuffer(5000)
+	hostClients   = NewHostClients()
+	watchdog      = NewWatchdog()
+	audition      = NewAudition()
+	talkback      = NewTalkback()
+	offline       = NewOfflineHosts()
+	rawXML        = NewRawXMLHistory()
+	plugins       = NewPluginManager()
+	mqttBridge    = NewMQTTBridge()
+	tasks         = NewTaskMonitor()
+	showStats     = NewShowStatsStore(20, time.Now())
+	timeOnAir     = NewTimeOnAir()
+	dataFeeds     = NewDataFeeds()
+	gsiListener   = NewGSIListener()
+	chatBridge    = NewChatBridge()
+	appUpdates    = NewAppUpdates()
+	syncFeed      = NewSyncFeed()
+	pairingRole   *string      // Hot-standby role
+	pairingPeer   *string      // Paired vmix-utility instance URL
+	pairing       *Pairing     // Hot-standby pairing
+	basePath      *string      // URL path prefix when served behind reverse proxy
+	proxies       *string      // Trusted reverse proxy addresses
+	configPath    *string      // Settings file path
+	configSync    *string      // Shared folder or WebDAV URL to sync settings
+	config        *ConfigStore // Settings
+
+	rehearsal        *bool   // Start in rehearsal mode
+	headless         *bool   // Do not open browser on start
+	multiviewerToken *string // Token for multiviewer click-to-cut commands
+	pairingToken     *string // Token shared by paired instances
+	mockEnabled      *bool   // Use built-in mock vMix instead of real vMix
+	mockHTTP         *string // Mock vMix HTTP API listen address
+	mockTCP          *string // Mock vMix TCP API listen address
+	mockState        *string // Mock vMix initial XML state file
+	offlineStates    *string // vMix XML files loaded as offline connections
+	pluginsDir       *string // Plugins directory
 )
 
 // Static files
@@ -50,9 +122,7 @@ func RefreshInputHandler(c *gin.Context) {
 	var err error
 	vmix, err = vmix.Refresh()
 	if err != nil {
-		c.AbortWithStatusJSON(http.StatusInternalServerError, gin.H{
-			"err": err.Error(),
-		})
+		AbortWithAppError(c, NewAppError(ErrKindUnreachable, *vmixaddr, err))
 		return
 	}
 	c.JSON(http.StatusOK, gin.H{
@@ -61,17 +131,27 @@ func RefreshInputHandler(c *gin.Context) {
 }
 
 // GetInputsHandler returns available vmix inputs for [GET] /api/inputs as JSON.
+// With detail=full, inputs of polled state of host are returned with position, audio, text fields and layers.
 func GetInputsHandler(c *gin.Context) {
-	if vmix == nil {
-		c.AbortWithStatusJSON(http.StatusInternalServerError, gin.H{
-			"error": "vmix instance not loaded",
+	if c.Query("detail") == "full" {
+		host := c.DefaultQuery("host", *vmixaddr)
+		s, err := hostState(host)
+		if err != nil {
+			AbortWithAppError(c, err)
+			return
+		}
+		c.JSON(http.StatusOK, gin.H{
+			"host":   host,
+			"inputs": s.Inputs,
 		})
 		return
 	}
+	if vmix == nil {
+		AbortWithAppError(c, NewAppError(ErrKindNotLoaded, *vmixaddr, fmt.Errorf("vmix instance not loaded")))
+		return
+	}
 	if vmix.Inputs.Input == nil {
-		c.AbortWithStatusJSON(http.StatusNotFound, gin.H{
-			"error": "Input not loaded",
-		})
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, *vmixaddr, fmt.Errorf("Input not loaded")))
 		return
 	}
 	c.JSON(http.StatusOK, gin.H{
@@ -95,7 +175,9 @@ type DoMultipleFunctionsRequest struct {
 		Key   string `json:"key"`   // Key.
 		Value string `json:"value"` // Value.
 	} `json:"queries"` // Key-Value queries.
-	Num int `json:"num"`
+	Num        int         `json:"num"`
+	Conditions []Condition `json:"conditions"` // functions are sent only if every condition holds.
+	Override   bool        `json:"override"`   // send even if function is refused by on-air protection.
 }
 
 // Validate form
@@ -117,9 +199,34 @@ func (r *DoMultipleFunctionsRequest) Validate() error {
 // DoMultipleFunctionsHandler Sends multiple functions to vMix.
 func DoMultipleFunctionsHandler(c *gin.Context) {
 	req := DoMultipleFunctionsRequest{}
-	c.BindJSON(&req)
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
 	if err := req.Validate(); err != nil {
-		c.AbortWithError(http.StatusBadRequest, err)
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := RequireFunction(*vmixaddr, req.Function); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := locks.Check(*vmixaddr); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	send := SendFunctionTo
+	if req.Override {
+		send = SendFunctionOverride
+	} else if err := CheckOnAirProtect(*vmixaddr, req.Function); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if cond, err := CheckConditions(*vmixaddr, req.Conditions); err != nil {
+		AbortWithAppError(c, err)
+		return
+	} else if cond != nil {
+		c.String(http.StatusOK, fmt.Sprintf("Skipped since condition %s is not met", cond))
 		return
 	}
 	params := make(map[string]string)
@@ -127,12 +234,14 @@ func DoMultipleFunctionsHandler(c *gin.Context) {
 		params[v.Key] = v.Value
 	}
 
+	poller.Touch()
+	announceAction(c, *vmixaddr, req.Function)
 	wg := &sync.WaitGroup{}
 	numerrors := 0
 	for i := 0; i < req.Num; i++ {
 		wg.Add(1)
 		go func() {
-			if err := vmix.SendFunction(req.Function, params); err != nil {
+			if err := send(*vmixaddr, req.Function, params); err != nil {
 				numerrors++
 				log.Printf("Error sending function %s with %v queries. ERR : %v\n", req.Function, params, err)
 			}
@@ -147,25 +256,207 @@ func DoMultipleFunctionsHandler(c *gin.Context) {
 	}
 }
 
+// applyConfig configures services from settings, at startup and again when settings are replaced as whole,
+// such as by replication from primary. Invalid settings of one service are logged, and other services are still configured.
+func applyConfig(cfg Config) {
+	events.SetRates(cfg.EventRates)
+	hostClients.SetPools(cfg.HTTPPools)
+	if vmixTCP != nil {
+		ApplyTCPSubscription(vmixTCP, TCPSubscriptionFor(*vmixaddr))
+	}
+	if err := showClock.Configure(cfg.Clock); err != nil {
+		log.Printf("Invalid show clock settings : %v\n", err)
+	}
+	scoreboards.Restore(cfg.Scoreboards)
+	dataFeeds.Configure(cfg.DataFeeds)
+	if err := gsiListener.Configure(cfg.GSI); err != nil {
+		log.Printf("Failed to start Game State Integration listener : %v\n", err)
+	}
+	if err := serialTrigger.Configure(cfg.SerialTrigger); err != nil {
+		log.Printf("Invalid serial trigger settings : %v\n", err)
+	}
+	if err := mqttBridge.Configure(cfg.MQTT); err != nil {
+		log.Printf("Invalid MQTT settings : %v\n", err)
+	}
+	if err := chatBridge.Configure(cfg.Chat); err != nil {
+		log.Printf("Invalid chat settings : %v\n", err)
+	}
+	if err := timecode.Configure(cfg.Timecode); err != nil {
+		log.Printf("Failed to start timecode source : %v\n", err)
+	}
+	if err := timecode.SetCues(cfg.TimecodeCues); err != nil {
+		log.Printf("Invalid timecode cues : %v\n", err)
+	}
+	if err := cueWarnings.Configure(cfg.CueWarnings); err != nil {
+		log.Printf("Invalid cue warning settings : %v\n", err)
+	}
+	if err := artNetTrigger.Configure(cfg.ArtNetTrigger); err != nil {
+		log.Printf("Failed to start Art-Net trigger : %v\n", err)
+	}
+}
+
 func init() {
 	vmixaddr = flag.String("vmix", "http://localhost:8088", "vMix API Address")
+	vmixTCPAddr = flag.String("vmix-tcp", "", "vMix TCP API Address. Port 8099 of -vmix host is used if empty")
 	hostaddr = flag.String("host", ":8080", "Server listen port")
-	flag.Parse()
+	basePath = flag.String("base-path", "", "URL path prefix when served behind reverse proxy. e.g. /vmix")
+	proxies = flag.String("trusted-proxies", "127.0.0.1,::1", "Comma separated reverse proxy IPs or CIDRs whose X-Forwarded-* headers are trusted")
+	pollInterval = flag.Duration("poll-interval", time.Second, "vMix status polling interval while active")
+	pollIdle = flag.Duration("poll-interval-idle", 5*time.Second, "vMix status polling interval while idle")
+	pollFocus = flag.Duration("poll-interval-focus", 250*time.Millisecond, "vMix status polling interval while user interface is focused or shortly after function was sent")
+	pollBudget = flag.Float64("poll-budget", 0, "Maximum vMix polls per second across connections. Primary connections are always polled. 0 for unlimited")
+	configPath = flag.String("config", "vmix_utility.json", "Settings file path")
+	configSync = flag.String("config-sync", "", "Shared folder (cloud storage or file share) or WebDAV URL to sync settings between machines")
+	pairingRole = flag.String("role", RoleStandalone, "Hot-standby role. standalone, primary or secondary")
+	pairingPeer = flag.String("peer", "", "Paired vmix-utility URL. e.g. http://192.168.0.10:8080")
+	multiviewerToken = flag.String("multiviewer-token", "", "Token required by multiviewer click-to-cut WebSocket. Disabled if empty")
+	pairingToken = flag.String("peer-token", "", "Token shared by paired instances to replicate settings. Peer is identified by address of -peer if empty")
+	headless = flag.Bool("headless", false, "Start without opening browser, such as when started at login on venue machines")
+	rehearsal = flag.Bool("rehearsal", false, "Start in rehearsal mode. Functions are logged and broadcast without being sent to vMix")
+	mockEnabled = flag.Bool("mock", false, "Use built-in mock vMix instead of connecting to real vMix")
+	mockHTTP = flag.String("mock-http", "127.0.0.1:18088", "Mock vMix HTTP API listen address")
+	mockTCP = flag.String("mock-tcp", "127.0.0.1:18099", "Mock vMix TCP API listen address")
+	mockState = flag.String("mock-state", "", "Mock vMix initial state XML file. Built-in preset is used if empty")
+	offlineStates = flag.String("offline", "", "Comma separated saved vMix XML files loaded as read-only offline connections")
+	pluginsDir = flag.String("plugins", "plugins", "Plugins directory. Each subdirectory with plugin.json is started as plugin")
 }
 
 func main() {
+	flag.Parse()
+	log.SetOutput(io.MultiWriter(os.Stderr, logBuffer))
 	log.Println("STARTING...")
 
-	// Init vMix
+	// Load settings
 	var err error
+	config, err = LoadConfigStore(*configPath)
+	if err != nil {
+		panic(err)
+	}
+	if *configSync != "" {
+		if err := config.EnableSync(*configSync); err != nil {
+			log.Fatalf("Invalid settings sync target : %v\n", err)
+		}
+		go config.WatchSync(10 * time.Second)
+	}
+
+	// Init hot-standby pairing
+	switch *pairingRole {
+	case RoleStandalone, RolePrimary, RoleSecondary:
+	default:
+		log.Fatalf("Unknown role %q\n", *pairingRole)
+	}
+	pairing = NewPairing(*pairingRole, *pairingPeer, *pairingToken)
+	go pairing.Run(2 * time.Second)
+
+	// Init mock vMix
+	var mock *MockServer
+	if *mockEnabled {
+		var state *State
+		if *mockState != "" {
+			state, err = LoadMockState(*mockState)
+			if err != nil {
+				panic(err)
+			}
+		}
+		mock = NewMockServer(state)
+		if err := mock.Start(*mockHTTP, *mockTCP); err != nil {
+			panic(err)
+		}
+		*vmixaddr = "http://" + *mockHTTP
+		*vmixTCPAddr = *mockTCP
+		log.Printf("Using mock vMix. HTTP: %s TCP: %s\n", *mockHTTP, *mockTCP)
+	}
+
+	// Load offline connections
+	for _, path := range strings.Split(*offlineStates, ",") {
+		if path = strings.TrimSpace(path); path == "" {
+			continue
+		}
+		h, err := offline.LoadFile(path)
+		if err != nil {
+			panic(err)
+		}
+		log.Printf("Loaded offline connection %s (%d inputs)\n", h.Addr, h.Inputs)
+	}
+
+	SetRehearsal(*rehearsal)
+
+	// Init vMix
 	vmix, err = vmixgo.NewVmix(*vmixaddr)
 	if err != nil {
 		panic(err)
 	}
 
+	// Init vMix TCP API for ACTS
+	if *vmixTCPAddr == "" {
+		*vmixTCPAddr = DefaultTCPAddr(*vmixaddr)
+	}
+	vmixTCP = NewTCPClient(*vmixTCPAddr)
+	vmixTCP.OnMessage = HandleTCPMessage
+	tallyRefresh = &ProgramRefresher{Host: *vmixaddr, Query: vmixTCP.XMLText}
+
+	// Configure services from settings
+	applyConfig(config.Get())
+	go vmixTCP.Run()
+
+	// Start action history
+	go history.Run()
+
+	// Start status polling
+	poller.SetBudget(*pollBudget)
+	go poller.Run(*pollFocus, *pollInterval, *pollIdle)
+
+	// Start stale operator cleanup
+	go operators.Run(time.Minute)
+
+	// Start idle connection watchdog
+	go watchdog.Run(time.Second)
+
+	// Start show clock ticker
+	go showClock.Run(200 * time.Millisecond)
+
+	// Start state sync feed
+	go syncFeed.Run(syncInterval)
+
+	// Start auto-pilot
+	go autoPilot.Run(250 * time.Millisecond)
+
+	// Start audition un-solo on program
+	go audition.Run(250 * time.Millisecond)
+
+	// Start stream health monitor
+	go streamHealth.Run(5 * time.Second)
+
+	// Start time on air tracking
+	go timeOnAir.Run()
+
+	// Start serial GPIO trigger
+	go serialTrigger.Run()
+
+	// Start MQTT bridge
+	go mqttBridge.Run()
+
+	// Start chat commands
+	go chatBridge.Run()
+
+	// Start cue warnings
+	go cueWarnings.Run(100 * time.Millisecond)
+
+	// Start plugins
+	if err := plugins.Load(*pluginsDir); err != nil {
+		log.Printf("Failed to load plugins : %v\n", err)
+	}
+
 	// Init Gin router
+	trusted, err := ParseTrustedProxies(*proxies)
+	if err != nil {
+		log.Fatalln(err)
+	}
+	*basePath = NormalizeBasePath(*basePath)
 	gin.SetMode(gin.ReleaseMode)
 	r := gin.Default()
+	r.Use(TrustedProxyMiddleware(trusted))
+	root := r.Group(*basePath)
 
 	// Cache files
 	index, err := staticFS.ReadFile("static/index.html")
@@ -178,13 +469,13 @@ func main() {
 		panic(err)
 	}
 	// serve static files
-	r.GET("/", func(c *gin.Context) {
+	root.GET("/", func(c *gin.Context) {
 		c.Writer.WriteString(string(index))
 	})
-	r.GET("/favicon.ico", func(c *gin.Context) {
+	root.GET("/favicon.ico", func(c *gin.Context) {
 		c.Data(http.StatusOK, "image/x-icon", favicon)
 	})
-	r.GET("/css/*file", func(c *gin.Context) {
+	root.GET("/css/*file", func(c *gin.Context) {
 		file := c.Param("file")
 		b, err := staticFS.ReadFile("static/css" + file)
 		if err != nil {
@@ -193,7 +484,7 @@ func main() {
 		}
 		c.Data(http.StatusOK, "text/css", b)
 	})
-	r.GET("/js/*file", func(c *gin.Context) {
+	root.GET("/js/*file", func(c *gin.Context) {
 		file := c.Param("file")
 		b, err := staticFS.ReadFile("static/js" + file)
 		if err != nil {
@@ -202,7 +493,7 @@ func main() {
 		}
 		c.Data(http.StatusOK, "text/css", b)
 	})
-	r.GET("/img/*file", func(c *gin.Context) {
+	root.GET("/img/*file", func(c *gin.Context) {
 		file := c.Param("file")
 		b, err := staticFS.ReadFile("static/img" + file)
 		if err != nil {
@@ -211,7 +502,7 @@ func main() {
 		}
 		c.Data(http.StatusOK, "text/css", b)
 	})
-	r.GET("/fonts/*file", func(c *gin.Context) {
+	root.GET("/fonts/*file", func(c *gin.Context) {
 		file := c.Param("file")
 		b, err := staticFS.ReadFile("static/fonts" + file)
 		if err != nil {
@@ -220,30 +511,217 @@ func main() {
 		}
 		c.Data(http.StatusOK, "text/css", b)
 	})
-	r.GET("/multiviewer/*file", func(c *gin.Context) {
-		file := c.Param("file")
-		b, err := multiviewFS.ReadFile("vMixMultiview" + file)
-		if err != nil {
-			c.AbortWithError(http.StatusNotFound, err)
-			return
-		}
-		c.Data(http.StatusOK, "", b)
-	})
+	root.GET("/multiviewer/*file", MultiviewerFileHandler)
 
-	api := r.Group("/api")
+	// Deep links. e.g. /open/output/program or /open/?url=vmix-utility://shortcut/Intro
+	root.GET("/open/*link", OperatorMiddleware, OpenDeepLinkHandler)
+
+	api := root.Group("/api")
+	api.Use(OperatorMiddleware)
 	{
 		api.GET("/vmix", GetvMixURLHandler)
 		api.GET("/inputs", GetInputsHandler)
+		api.GET("/inputs/cache", GetInputCacheHandler)
+		api.GET("/inputs/sources/validate", ValidateInputSourcesHandler)
+		api.GET("/inputs/functions", GetApplicableFunctionsHandler)
 		api.GET("/functions", GetFunctionsHandler)
+		api.GET("/state", GetStateHandler)
+		api.GET("/snapshot", GetAppSnapshotHandler)
 		api.POST("/refresh", RefreshInputHandler)
 		api.POST("/multiple", DoMultipleFunctionsHandler)
+		api.GET("/acts", GetActsHandler)
+		api.GET("/latency", GetLatencyReportHandler)
+		api.GET("/ws", WebSocketHandler)
+		api.GET("/sync", SyncWebSocketHandler)
+		api.GET("/events", EventStreamHandler)
+		api.GET("/events/poll", LongPollEventsHandler)
+		api.GET("/events/stats", GetEventStatsHandler)
+		api.GET("/cache/stats", GetCacheStatsHandler)
+		api.GET("/http/pool", GetHTTPPoolHandler)
+		api.GET("/plugins", GetPluginsHandler)
+		api.POST("/plugins/:name/reload", ReloadPluginHandler)
+		api.POST("/plugins/:name/commands/:command", RunPluginCommandHandler)
+		api.Any("/plugins/:name/http/*path", PluginHTTPHandler)
+		api.PUT("/http/pool", PutHTTPPoolHandler)
+		api.GET("/events/rates", GetEventRatesHandler)
+		api.PUT("/events/rates", PutEventRatesHandler)
+		api.GET("/multiviewer/image", GetMultiviewImageHandler)
+		api.GET("/snapshots", GetSnapshotsHandler)
+		api.POST("/snapshots", CaptureSnapshotHandler)
+		api.GET("/snapshots/diff", DiffSnapshotsHandler)
+		api.DELETE("/snapshots/:label", DeleteSnapshotHandler)
+		api.GET("/preset", GetPresetHandler)
+		api.GET("/preset/inputs", GetPresetInputsHandler)
+		api.GET("/preset/datasources", GetPresetDataSourcesHandler)
+		api.GET("/preset/shortcuts", GetPresetShortcutsHandler)
+		api.GET("/shortcuts", GetShortcutsHandler)
+		api.PUT("/shortcuts", PutShortcutsHandler)
+		api.POST("/shortcuts/import", ImportShortcutsHandler)
+		api.GET("/shortcuts/export", ExportShortcutsHandler)
+		api.POST("/shortcuts/run/:name", RunShortcutHandler)
+		api.POST("/preset/validate", ValidatePresetHandler)
+		api.GET("/profile", GetHardwareProfileHandler)
+		api.PUT("/profile", PutHardwareProfileHandler)
+		api.GET("/assets/folders", GetMediaFoldersHandler)
+		api.PUT("/assets/folders", PutMediaFoldersHandler)
+		api.POST("/assets/verify", VerifyAssetsHandler)
+		api.GET("/graphics", GetGraphicsHandler)
+		api.GET("/graphics/:name", GetGraphicHandler)
+		api.PUT("/graphics/:name", PutGraphicHandler)
+		api.POST("/graphics/:name/show", ShowGraphicHandler)
+		api.POST("/graphics/:name/hide", HideGraphicHandler)
+		api.GET("/graphics/:name/render", RenderGraphicHandler)
+		api.GET("/graphic-templates", GetGraphicTemplatesHandler)
+		api.PUT("/graphic-templates/:name", PutGraphicTemplateHandler)
+		api.DELETE("/graphic-templates/:name", DeleteGraphicTemplateHandler)
+		api.GET("/scoreboards/:name", GetScoreboardHandler)
+		api.PUT("/scoreboards/:name", PutScoreboardHandler)
+		api.POST("/scoreboards/:name/command", ScoreboardCommandHandler)
+		api.GET("/clock", GetClockHandler)
+		api.POST("/clock/command", ClockCommandHandler)
+		api.PUT("/clock/title", PutClockTitleHandler)
+		api.GET("/clock/settings", GetClockSettingsHandler)
+		api.PUT("/clock/settings", PutClockSettingsHandler)
+		api.GET("/health", HealthHandler)
+		api.GET("/healthz", HealthzHandler)
+		api.GET("/readyz", ReadyzHandler)
+		api.GET("/pairing", GetPairingHandler)
+		api.GET("/pairing/config", GetPairingConfigHandler)
+		api.GET("/config/sync", GetConfigSyncHandler)
+		api.POST("/config/sync/push", PushConfigSyncHandler)
+		api.POST("/config/sync/pull", PullConfigSyncHandler)
+		api.GET("/config/sync/conflict", GetConfigConflictHandler)
+		api.GET("/config/backups", GetConfigBackupsHandler)
+		api.POST("/config/backups", CreateConfigBackupHandler)
+		api.POST("/config/rollback", RollbackConfigHandler)
+		api.GET("/tcp/subscription", GetTCPSubscriptionHandler)
+		api.PUT("/tcp/subscription", PutTCPSubscriptionHandler)
+		api.GET("/xmltext", GetXMLTextHandler)
+		api.GET("/xml/diff", GetRawXMLDiffHandler)
+		api.GET("/xml/raw", GetRawXMLHandler)
+		api.GET("/connections", GetConnectionsHandler)
+		api.GET("/diagnose", DiagnoseConnectionHandler)
+		api.GET("/diagnose/ports", ScanPortsHandler)
+		api.GET("/setup", GetSetupHandler)
+		api.GET("/setup/scan", ScanSetupHandler)
+		api.POST("/setup", RunSetupHandler)
+		api.GET("/offline", GetOfflineHandler)
+		api.PUT("/offline/:name", PutOfflineHandler)
+		api.DELETE("/offline/:name", DeleteOfflineHandler)
+		api.GET("/quick", GetQuickHandler)
+		api.POST("/quick/cut", QuickCutHandler)
+		api.GET("/watchdog", GetWatchdogHandler)
+		api.PUT("/watchdog", PutWatchdogHandler)
+		api.GET("/startup", GetStartupActionsHandler)
+		api.PUT("/startup", PutStartupActionsHandler)
+		api.POST("/startup/run", RerunStartupActionsHandler)
+		api.GET("/capabilities", GetCapabilitiesHandler)
+		api.GET("/upgrades", GetUpgradeAdviceHandler)
+		api.GET("/fleet/report", GetFleetReportHandler)
+		api.GET("/stats/shows", GetShowStatsListHandler)
+		api.POST("/stats/shows", StartShowStatsHandler)
+		api.GET("/stats/shows/:id", GetShowStatsHandler)
+		api.GET("/feeds", GetDataFeedsHandler)
+		api.PUT("/feeds", PutDataFeedsHandler)
+		api.POST("/feeds/ingest/:name", IngestDataFeedHandler)
+		api.GET("/timeonair", GetTimeOnAirHandler)
+		api.DELETE("/timeonair", ResetTimeOnAirHandler)
+		api.GET("/timeonair/overlay", GetTimeOnAirOverlayHandler)
+		api.GET("/history", GetHistoryHandler)
+		api.POST("/reports/show", CollectShowReportHandler)
+		api.GET("/docs/show", GetShowDocHandler)
+		api.POST("/browser/navigate", BrowserNavigateHandler)
+		api.GET("/dynamic", GetDynamicHandler)
+		api.PUT("/dynamic", PutDynamicHandler)
+		api.GET("/aliases", GetInputAliasesHandler)
+		api.PUT("/aliases", PutInputAliasesHandler)
+		api.GET("/groups/inputs", GetInputGroupsHandler)
+		api.PUT("/groups/inputs", PutInputGroupsHandler)
+		api.POST("/groups/inputs/:name/action", InputGroupActionHandler)
+		api.GET("/triggers/serial", GetSerialTriggerHandler)
+		api.PUT("/triggers/serial", PutSerialTriggerHandler)
+		api.GET("/triggers/artnet", GetArtNetTriggerHandler)
+		api.PUT("/triggers/artnet", PutArtNetTriggerHandler)
+		api.GET("/triggers/gsi", GetGSIHandler)
+		api.PUT("/triggers/gsi", PutGSIHandler)
+		api.GET("/triggers/chat", GetChatHandler)
+		api.PUT("/triggers/chat", PutChatHandler)
+		api.GET("/mqtt", GetMQTTHandler)
+		api.PUT("/mqtt", PutMQTTHandler)
+		api.GET("/timecode", GetTimecodeHandler)
+		api.PUT("/timecode/settings", PutTimecodeSettingsHandler)
+		api.PUT("/timecode/cues", PutTimecodeCuesHandler)
+		api.GET("/timecode/warnings", GetCueWarningsHandler)
+		api.PUT("/timecode/warnings", PutCueWarningsHandler)
+		api.GET("/looks", GetLayerLooksHandler)
+		api.POST("/looks/:name/save", SaveLayerLookHandler)
+		api.POST("/looks/:name/recall", RecallLayerLookHandler)
+		api.POST("/transitions/macro", TransitionMacroHandler)
+		api.GET("/pip/presets", GetPiPPresetsHandler)
+		api.PUT("/pip/presets", PutPiPPresetsHandler)
+		api.POST("/pip", ApplyPiPHandler)
+		api.GET("/replay/presets", GetReplayPresetsHandler)
+		api.PUT("/replay/presets", PutReplayPresetsHandler)
+		api.POST("/replay/moment", ReplayMomentHandler)
+		api.GET("/talkback", GetTalkbackHandler)
+		api.PUT("/talkback", PutTalkbackHandler)
+		api.POST("/talkback/press/:name", PressTalkbackHandler)
+		api.POST("/talkback/release/:name", ReleaseTalkbackHandler)
+		api.GET("/audition", GetAuditionHandler)
+		api.POST("/audition", AuditionHandler)
+		api.GET("/afv", GetAFVRulesHandler)
+		api.PUT("/afv", PutAFVRulesHandler)
+		api.GET("/autopilot", GetAutoPilotHandler)
+		api.PUT("/autopilot", PutAutoPilotHandler)
+		api.GET("/hosts/groups", GetHostGroupsHandler)
+		api.GET("/companion/manifest", GetCompanionManifestHandler)
+		api.GET("/streamdeck/ws", StreamDeckWebSocketHandler)
+		api.PUT("/hosts/groups", PutHostGroupsHandler)
+		api.POST("/ftb/arm", ArmFadeToBlackHandler)
+		api.POST("/ftb/confirm", ConfirmFadeToBlackHandler)
+		api.GET("/operators", GetOperatorsHandler)
+		api.GET("/session", GetSessionHandler)
+		api.PUT("/session", PutSessionHandler)
+		api.GET("/protect", GetOnAirProtectHandler)
+		api.PUT("/protect", PutOnAirProtectHandler)
+		api.GET("/locks", GetLocksHandler)
+		api.POST("/locks", LockHostHandler)
+		api.DELETE("/locks", UnlockHostHandler)
+		api.GET("/background", GetBackgroundHandler)
+		api.PUT("/background", PutBackgroundHandler)
+		api.GET("/autostart", GetAutoStartHandler)
+		api.PUT("/autostart", PutAutoStartHandler)
+		api.GET("/deeplink", GetDeepLinkHandler)
+		api.PUT("/deeplink", PutDeepLinkHandler)
+		api.GET("/rehearsal", GetRehearsalHandler)
+		api.PUT("/rehearsal", PutRehearsalHandler)
+		api.GET("/outputs", GetOutputsHandler)
+		api.GET("/streams/health", GetStreamHealthHandler)
+		api.PUT("/streams/health", PutStreamHealthHandler)
+		api.POST("/outputs/:output/:action", SetOutputHandler)
+		api.PUT("/upgrades/latest", PutLatestVersionHandler)
+		api.POST("/upgrades/latest/fetch", FetchLatestVersionHandler)
+		api.GET("/update", GetAppUpdateHandler)
+		api.GET("/update/channel", GetUpdateChannelHandler)
+		api.PUT("/update/channel", SetUpdateChannelHandler)
+		api.GET("/update/changelog", GetChangelogHandler)
+		api.PUT("/connections", PutConnectionsHandler)
+	}
+	if mock != nil {
+		m := api.Group("/mock")
+		m.GET("/state", GetMockStateHandler(mock))
+		m.PUT("/state", PutMockStateHandler(mock))
+		m.GET("/calls", GetMockCallsHandler(mock))
+		m.DELETE("/calls", DeleteMockCallsHandler(mock))
 	}
 
-	url := fmt.Sprintf("http://localhost%s/", *hostaddr)
-	err = exec.Command("rundll32.exe", "url.dll,FileProtocolHandler", url).Start()
-	if err != nil {
-		log.Println("Failed to open link. ignoring...")
-		log.Printf("ERR : %v\n", err)
+	if !*headless {
+		url := fmt.Sprintf("http://localhost%s%s/", *hostaddr, *basePath)
+		err = exec.Command("rundll32.exe", "url.dll,FileProtocolHandler", url).Start()
+		if err != nil {
+			log.Println("Failed to open link. ignoring...")
+			log.Printf("ERR : %v\n", err)
+		}
 	}
 	log.Panicf("Failed to listen port %s : %v\n", *hostaddr, r.Run(*hostaddr))
 }
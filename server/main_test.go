@@ -0,0 +1,26 @@
+package main
+
+import (
+	"flag"
+	"io/ioutil"
+	"log"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+// TestMain loads empty settings, since handlers and background services read global config.
+func TestMain(m *testing.M) {
+	flag.Parse()
+	dir, err := ioutil.TempDir("", "vmix_utility_test")
+	if err != nil {
+		log.Fatalln(err)
+	}
+	config, err = LoadConfigStore(filepath.Join(dir, "vmix_utility.json"))
+	if err != nil {
+		log.Fatalln(err)
+	}
+	code := m.Run()
+	os.RemoveAll(dir)
+	os.Exit(code)
+}
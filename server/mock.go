@@ -0,0 +1,563 @@
+package main
+
+import (
+	"bufio"
+	"encoding/xml"
+	"fmt"
+	"io/ioutil"
+	"log"
+	"net"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// MockCall is function call received by MockServer.
+type MockCall struct {
+	Function string            `json:"function"`
+	Params   map[string]string `json:"params"`
+	Via      string            `json:"via"` // "http" or "tcp".
+	At       time.Time         `json:"at"`
+}
+
+// mockCallHistory is number of latest function calls kept by MockServer.
+const mockCallHistory = 1000
+
+// mockConn is TCP API connection of MockServer. Responses and subscribed messages are written from different
+// goroutines, so writes are serialized to keep multi-line responses such as XML intact.
+type mockConn struct {
+	net.Conn
+	mu sync.Mutex
+}
+
+// MockServer emulates vMix HTTP and TCP API with in-memory XML state.
+type MockServer struct {
+	mu    sync.Mutex
+	state *State
+	calls []MockCall // latest mockCallHistory calls.
+
+	subMu       sync.Mutex
+	subscribers map[*mockConn]map[string]bool // conn -> subscribed topics ("TALLY", "ACTS").
+
+	HTTPAddr string // bound HTTP API address, set by Start.
+	TCPAddr  string // bound TCP API address, set by Start.
+
+	listeners []net.Listener
+}
+
+// NewMockServer creates MockServer. If state is nil, DefaultMockState is used.
+func NewMockServer(state *State) *MockServer {
+	if state == nil {
+		state = DefaultMockState()
+	}
+	return &MockServer{
+		state:       state,
+		subscribers: make(map[*mockConn]map[string]bool),
+	}
+}
+
+// DefaultMockState returns small preset used when no state script is specified.
+func DefaultMockState() *State {
+	return &State{
+		Version: "24.0.0.72",
+		Edition: "4K",
+		Preset:  `C:\Users\vmix\Documents\vMixStorage\mock.vmix`,
+		Inputs: []StateInput{
+			{Key: "b8b4e2ad-0001-4c1a-9a51-000000000001", Number: 1, Type: "Colour", Title: "Colour", ShortTitle: "Colour", State: "Paused", Text: "Colour"},
+			{Key: "b8b4e2ad-0002-4c1a-9a51-000000000002", Number: 2, Type: "Capture", Title: "Camera 1", ShortTitle: "Camera 1", State: "Running", Text: "Camera 1"},
+			{Key: "b8b4e2ad-0003-4c1a-9a51-000000000003", Number: 3, Type: "Capture", Title: "Camera 2", ShortTitle: "Camera 2", State: "Running", Text: "Camera 2"},
+			{Key: "b8b4e2ad-0004-4c1a-9a51-000000000004", Number: 4, Type: "Video", Title: "Opening.mp4", ShortTitle: "Opening.mp4", State: "Paused", Duration: 30000, Text: "Opening.mp4"},
+			{Key: "b8b4e2ad-0005-4c1a-9a51-000000000005", Number: 5, Type: "GT", Title: "Lower Third.gtzip", ShortTitle: "Lower Third.gtzip", State: "Paused", Text: "Lower Third.gtzip", Fields: []StateInputField{
+				{Index: 0, Name: "Headline.Text", Value: "Headline"},
+				{Index: 1, Name: "Description.Text", Value: "Description"},
+			}},
+			{Key: "b8b4e2ad-0006-4c1a-9a51-000000000006", Number: 6, Type: "Mix", Title: "PiP", ShortTitle: "PiP", State: "Paused", Text: "PiP", Overlays: []StateInputOverlay{
+				{Index: 0, Key: "b8b4e2ad-0002-4c1a-9a51-000000000002"},
+				{Index: 1, Key: "b8b4e2ad-0003-4c1a-9a51-000000000003"},
+			}},
+		},
+		Overlays: []StateOverlay{{Number: 1}, {Number: 2}, {Number: 3}, {Number: 4}, {Number: 5}, {Number: 6}},
+		Preview:  3,
+		Active:   2,
+		Transitions: []StateTransition{
+			{Number: 1, Effect: "Fade", Duration: 500},
+			{Number: 2, Effect: "Merge", Duration: 1000},
+			{Number: 3, Effect: "Wipe", Duration: 1000},
+			{Number: 4, Effect: "CubeZoom", Duration: 1000},
+		},
+	}
+}
+
+// LoadMockState reads state script (vMix XML) from file.
+func LoadMockState(path string) (*State, error) {
+	b, err := ioutil.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	return ParseState(b)
+}
+
+// XML returns current state as XML.
+func (m *MockServer) XML() ([]byte, error) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	return xml.Marshal(m.state)
+}
+
+// SetState replaces whole state.
+func (m *MockServer) SetState(s *State) {
+	m.mu.Lock()
+	m.state = s
+	tally := s.Tally()
+	m.mu.Unlock()
+	m.publish("TALLY", "TALLY OK "+tally)
+}
+
+// Calls returns function call history.
+func (m *MockServer) Calls() []MockCall {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	calls := make([]MockCall, len(m.calls))
+	copy(calls, m.calls)
+	return calls
+}
+
+// ResetCalls clears function call history, such as between test scenarios.
+func (m *MockServer) ResetCalls() {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	m.calls = nil
+}
+
+// Apply executes function against state, as vMix does. Unknown functions are accepted and only recorded.
+func (m *MockServer) Apply(via, function string, params map[string]string) error {
+	m.mu.Lock()
+	m.calls = append(m.calls, MockCall{Function: function, Params: params, Via: via, At: time.Now()})
+	if len(m.calls) > mockCallHistory {
+		m.calls = append([]MockCall{}, m.calls[len(m.calls)-mockCallHistory:]...)
+	}
+	s := m.state
+	prevActive, prevPreview := s.Active, s.Preview
+	var acts []string
+
+	input := func() (*StateInput, error) {
+		ref, ok := params["Input"]
+		if !ok {
+			return nil, nil
+		}
+		in := s.FindInput(ref)
+		if in == nil {
+			return nil, fmt.Errorf("Input %q not found", ref)
+		}
+		return in, nil
+	}
+
+	in, err := input()
+	if err != nil {
+		m.mu.Unlock()
+		return err
+	}
+
+	switch {
+	case function == "PreviewInput":
+		if in == nil {
+			m.mu.Unlock()
+			return fmt.Errorf("Input required")
+		}
+		s.Preview = in.Number
+	case function == "ActiveInput":
+		if in == nil {
+			m.mu.Unlock()
+			return fmt.Errorf("Input required")
+		}
+		s.Active = in.Number
+	case isTransitionFunction(function):
+		if in != nil {
+			s.Preview, s.Active = s.Active, in.Number
+		} else {
+			s.Preview, s.Active = s.Active, s.Preview
+		}
+	case function == "SetInputName":
+		if in == nil {
+			m.mu.Unlock()
+			return fmt.Errorf("Input required")
+		}
+		in.Title = params["Value"]
+		in.ShortTitle = params["Value"]
+		in.Text = params["Value"]
+	case function == "FadeToBlack":
+		s.FadeToBlack = !s.FadeToBlack
+		acts = append(acts, fmt.Sprintf("FadeToBlack %d", boolToInt(bool(s.FadeToBlack))))
+	case function == "StartRecording", function == "StopRecording", function == "StartStopRecording":
+		s.Recording = Bool(toggleOrSet(function, bool(s.Recording)))
+		acts = append(acts, fmt.Sprintf("Recording %d", boolToInt(bool(s.Recording))))
+	case function == "StartStreaming", function == "StopStreaming", function == "StartStopStreaming":
+		s.Streaming = Bool(toggleOrSet(function, bool(s.Streaming)))
+		acts = append(acts, fmt.Sprintf("Streaming %d", boolToInt(bool(s.Streaming))))
+	case function == "StartExternal", function == "StopExternal", function == "StartStopExternal":
+		s.External = Bool(toggleOrSet(function, bool(s.External)))
+		acts = append(acts, fmt.Sprintf("External %d", boolToInt(bool(s.External))))
+	case function == "StartMultiCorder", function == "StopMultiCorder", function == "StartStopMultiCorder":
+		s.MultiCorder = Bool(toggleOrSet(function, bool(s.MultiCorder)))
+		acts = append(acts, fmt.Sprintf("MultiCorder %d", boolToInt(bool(s.MultiCorder))))
+	case strings.HasPrefix(function, "SetDynamicInput"), strings.HasPrefix(function, "SetDynamicValue"):
+		kind := strings.ToLower(strings.TrimPrefix(function, "SetDynamic")[:5])
+		n, _ := strconv.Atoi(function[len(function)-1:])
+		if slot := s.Dynamic.Slot(kind, n); slot != nil {
+			*slot = params["Value"]
+		}
+	case function == "SetVolume", function == "AudioOn", function == "AudioOff", function == "SetText":
+		if in == nil {
+			m.mu.Unlock()
+			return fmt.Errorf("Input required")
+		}
+		applyInputDetail(in, function, params)
+	case strings.HasPrefix(function, "OverlayInput"):
+		if a, ok := applyOverlay(s, function, in); ok {
+			acts = append(acts, a)
+		}
+	}
+
+	if s.Active != prevActive {
+		acts = append(acts, fmt.Sprintf("Input %d 0", prevActive), fmt.Sprintf("Input %d 1", s.Active))
+	}
+	if s.Preview != prevPreview {
+		acts = append(acts, fmt.Sprintf("InputPreview %d 0", prevPreview), fmt.Sprintf("InputPreview %d 1", s.Preview))
+	}
+	tallyChanged := s.Active != prevActive || s.Preview != prevPreview
+	tally := s.Tally()
+	m.mu.Unlock()
+
+	for _, a := range acts {
+		m.publish("ACTS", "ACTS OK "+a)
+	}
+	if tallyChanged {
+		m.publish("TALLY", "TALLY OK "+tally)
+	}
+	return nil
+}
+
+// applyInputDetail handles audio and text functions of input.
+func applyInputDetail(in *StateInput, function string, params map[string]string) {
+	switch function {
+	case "SetVolume":
+		if v, err := strconv.ParseFloat(params["Value"], 64); err == nil {
+			in.Volume = v
+		}
+	case "AudioOn", "AudioOff":
+		in.Muted = function == "AudioOff"
+	case "SetText":
+		for i := range in.Fields {
+			f := &in.Fields[i]
+			if f.Name == params["SelectedName"] || (params["SelectedName"] == "" && strconv.Itoa(f.Index) == params["SelectedIndex"]) {
+				f.Value = params["Value"]
+			}
+		}
+	}
+}
+
+// applyOverlay handles OverlayInput{N}, OverlayInput{N}In/Out/On/Off and OverlayInputAllOff.
+func applyOverlay(s *State, function string, in *StateInput) (string, bool) {
+	if function == "OverlayInputAllOff" {
+		for i := range s.Overlays {
+			s.Overlays[i].Text = ""
+		}
+		return "", false
+	}
+	rest := strings.TrimPrefix(function, "OverlayInput")
+	if len(rest) == 0 {
+		return "", false
+	}
+	n, err := strconv.Atoi(rest[:1])
+	if err != nil || n < 1 || n > len(s.Overlays) {
+		return "", false
+	}
+	ov := &s.Overlays[n-1]
+	op := rest[1:]
+	switch op {
+	case "", "In", "On":
+		if in == nil {
+			return "", false
+		}
+		if op == "" && ov.Text == strconv.Itoa(in.Number) {
+			ov.Text = ""
+			return fmt.Sprintf("Overlay%d %d 0", n, in.Number), true
+		}
+		ov.Text = strconv.Itoa(in.Number)
+		return fmt.Sprintf("Overlay%d %d 1", n, in.Number), true
+	case "Out", "Off":
+		prev := ov.Text
+		ov.Text = ""
+		return fmt.Sprintf("Overlay%d %s 0", n, prev), prev != ""
+	}
+	return "", false
+}
+
+// isTransitionFunction returns true for functions that transition preview to program.
+func isTransitionFunction(function string) bool {
+	switch function {
+	case "Cut", "CutDirect", "Fade", "Merge", "Wipe", "Zoom", "Stinger1", "Stinger2", "Transition1", "Transition2", "Transition3", "Transition4", "QuickPlay":
+		return true
+	}
+	return false
+}
+
+func toggleOrSet(function string, cur bool) bool {
+	switch {
+	case strings.HasPrefix(function, "StartStop"):
+		return !cur
+	case strings.HasPrefix(function, "Start"):
+		return true
+	default:
+		return false
+	}
+}
+
+func boolToInt(b bool) int {
+	if b {
+		return 1
+	}
+	return 0
+}
+
+// ServeHTTP implements vMix HTTP API. GET /api returns XML, GET /api?Function=... executes function.
+func (m *MockServer) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if strings.TrimSuffix(r.URL.Path, "/") != "/api" {
+		http.NotFound(w, r)
+		return
+	}
+	q := r.URL.Query()
+	function := q.Get("Function")
+	if function == "" {
+		b, err := m.XML()
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		w.Header().Set("Content-Type", "text/xml")
+		w.Write(b)
+		return
+	}
+	params := make(map[string]string)
+	for k := range q {
+		if k != "Function" {
+			params[k] = q.Get(k)
+		}
+	}
+	if err := m.Apply("http", function, params); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	w.Write([]byte("Function completed successfully."))
+}
+
+// Start binds HTTP and TCP API listeners synchronously and serves them in background.
+// Port 0 may be used to pick free ports. bound addresses are stored in HTTPAddr and TCPAddr.
+func (m *MockServer) Start(httpAddr, tcpAddr string) error {
+	hl, err := net.Listen("tcp", httpAddr)
+	if err != nil {
+		return err
+	}
+	tl, err := net.Listen("tcp", tcpAddr)
+	if err != nil {
+		hl.Close()
+		return err
+	}
+	m.HTTPAddr = hl.Addr().String()
+	m.TCPAddr = tl.Addr().String()
+	m.listeners = []net.Listener{hl, tl}
+	go func() {
+		log.Printf("Mock vMix HTTP API stopped : %v\n", http.Serve(hl, m))
+	}()
+	go func() {
+		log.Printf("Mock vMix TCP API stopped : %v\n", m.ServeTCP(tl))
+	}()
+	return nil
+}
+
+// Close stops listeners started by Start.
+func (m *MockServer) Close() {
+	for _, l := range m.listeners {
+		l.Close()
+	}
+}
+
+// ServeTCP accepts TCP API connections from l.
+func (m *MockServer) ServeTCP(l net.Listener) error {
+	for {
+		conn, err := l.Accept()
+		if err != nil {
+			return err
+		}
+		go m.handleTCP(&mockConn{Conn: conn})
+	}
+}
+
+func (m *MockServer) handleTCP(conn *mockConn) {
+	m.subMu.Lock()
+	m.subscribers[conn] = make(map[string]bool)
+	m.subMu.Unlock()
+	defer func() {
+		m.subMu.Lock()
+		delete(m.subscribers, conn)
+		m.subMu.Unlock()
+		conn.Close()
+	}()
+
+	m.mu.Lock()
+	version := m.state.Version
+	m.mu.Unlock()
+	m.write(conn, "VERSION OK "+version)
+
+	sc := bufio.NewScanner(conn)
+	for sc.Scan() {
+		line := strings.TrimSpace(sc.Text())
+		if line == "" {
+			continue
+		}
+		cmd, arg := line, ""
+		if i := strings.IndexByte(line, ' '); i >= 0 {
+			cmd, arg = line[:i], strings.TrimSpace(line[i+1:])
+		}
+		switch strings.ToUpper(cmd) {
+		case "XML":
+			b, err := m.XML()
+			if err != nil {
+				m.write(conn, "XML ER "+err.Error())
+				continue
+			}
+			m.write(conn, fmt.Sprintf("XML %d", len(b)+2), string(b))
+		case "XMLTEXT":
+			b, err := m.XML()
+			if err != nil {
+				m.write(conn, "XMLTEXT ER "+err.Error())
+				continue
+			}
+			root, err := parseXMLNode(b)
+			if err != nil {
+				m.write(conn, "XMLTEXT ER "+err.Error())
+				continue
+			}
+			v, err := EvalXMLText(root, arg)
+			if err != nil {
+				m.write(conn, "XMLTEXT ER "+err.Error())
+				continue
+			}
+			m.write(conn, "XMLTEXT OK "+v)
+		case "TALLY":
+			m.mu.Lock()
+			tally := m.state.Tally()
+			m.mu.Unlock()
+			m.write(conn, "TALLY OK "+tally)
+		case "FUNCTION":
+			function, rawQuery := arg, ""
+			if i := strings.IndexByte(arg, ' '); i >= 0 {
+				function, rawQuery = arg[:i], arg[i+1:]
+			}
+			params := make(map[string]string)
+			if q, err := url.ParseQuery(rawQuery); err == nil {
+				for k := range q {
+					params[k] = q.Get(k)
+				}
+			}
+			if err := m.Apply("tcp", function, params); err != nil {
+				m.write(conn, "FUNCTION ER "+err.Error())
+				continue
+			}
+			m.write(conn, "FUNCTION OK Completed")
+		case "SUBSCRIBE", "UNSUBSCRIBE":
+			topic := strings.ToUpper(arg)
+			if topic != "TALLY" && topic != "ACTS" {
+				m.write(conn, strings.ToUpper(cmd)+" ER Unknown subscription")
+				continue
+			}
+			m.subMu.Lock()
+			m.subscribers[conn][topic] = strings.ToUpper(cmd) == "SUBSCRIBE"
+			m.subMu.Unlock()
+			m.write(conn, strings.ToUpper(cmd)+" OK "+topic)
+		case "QUIT":
+			m.write(conn, "QUIT OK Bye")
+			return
+		default:
+			m.write(conn, strings.ToUpper(cmd)+" ER Unknown command")
+		}
+	}
+}
+
+// write writes lines at once, so messages published by other goroutines never interleave with them.
+func (m *MockServer) write(conn *mockConn, lines ...string) {
+	b := &strings.Builder{}
+	for _, line := range lines {
+		b.WriteString(line + "\r\n")
+	}
+	conn.mu.Lock()
+	defer conn.mu.Unlock()
+	if _, err := conn.Write([]byte(b.String())); err != nil {
+		log.Printf("Mock vMix: failed to write to %s : %v\n", conn.RemoteAddr(), err)
+	}
+}
+
+// publish sends line to every TCP connection subscribed to topic.
+func (m *MockServer) publish(topic, line string) {
+	m.subMu.Lock()
+	defer m.subMu.Unlock()
+	for conn, topics := range m.subscribers {
+		if topics[topic] {
+			m.write(conn, line)
+		}
+	}
+}
+
+// GetMockCallsHandler returns function calls received by mock server for [GET] /api/mock/calls .
+func GetMockCallsHandler(mock *MockServer) gin.HandlerFunc {
+	return func(c *gin.Context) {
+		c.JSON(http.StatusOK, gin.H{
+			"calls": mock.Calls(),
+		})
+	}
+}
+
+// DeleteMockCallsHandler clears function calls received by mock server for [DELETE] /api/mock/calls .
+func DeleteMockCallsHandler(mock *MockServer) gin.HandlerFunc {
+	return func(c *gin.Context) {
+		mock.ResetCalls()
+		c.Status(http.StatusNoContent)
+	}
+}
+
+// PutMockStateHandler replaces mock state with XML request body for [PUT] /api/mock/state .
+func PutMockStateHandler(mock *MockServer) gin.HandlerFunc {
+	return func(c *gin.Context) {
+		b, err := ioutil.ReadAll(c.Request.Body)
+		if err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+			return
+		}
+		s, err := ParseState(b)
+		if err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+			return
+		}
+		mock.SetState(s)
+		c.JSON(http.StatusOK, gin.H{
+			"inputs": len(s.Inputs),
+		})
+	}
+}
+
+// GetMockStateHandler returns current mock state XML for [GET] /api/mock/state .
+func GetMockStateHandler(mock *MockServer) gin.HandlerFunc {
+	return func(c *gin.Context) {
+		b, err := mock.XML()
+		if err != nil {
+			AbortWithAppError(c, err)
+			return
+		}
+		c.Data(http.StatusOK, "text/xml", b)
+	}
+}
@@ -0,0 +1,488 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"strconv"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/FlowingSPDG/vmix-utility/server/core"
+	"github.com/gin-gonic/gin"
+
+	vmixgo "github.com/FlowingSPDG/vmix-go"
+)
+
+func startMock(t *testing.T) *MockServer {
+	t.Helper()
+	m := NewMockServer(nil)
+	if err := m.Start("127.0.0.1:0", "127.0.0.1:0"); err != nil {
+		t.Fatalf("failed to start mock vMix : %v", err)
+	}
+	t.Cleanup(m.Close)
+	return m
+}
+
+// tcpClient is minimal vMix TCP API client for tests.
+type tcpClient struct {
+	t    *testing.T
+	conn net.Conn
+	r    *bufio.Reader
+}
+
+func dialMock(t *testing.T, m *MockServer) *tcpClient {
+	t.Helper()
+	conn, err := net.Dial("tcp", m.TCPAddr)
+	if err != nil {
+		t.Fatalf("failed to dial mock TCP API : %v", err)
+	}
+	t.Cleanup(func() { conn.Close() })
+	c := &tcpClient{t: t, conn: conn, r: bufio.NewReader(conn)}
+	c.expect("VERSION OK 24.0.0.72")
+	return c
+}
+
+func (c *tcpClient) send(line string) {
+	c.t.Helper()
+	if _, err := c.conn.Write([]byte(line + "\r\n")); err != nil {
+		c.t.Fatalf("failed to send %q : %v", line, err)
+	}
+}
+
+func (c *tcpClient) readLine() string {
+	c.t.Helper()
+	c.conn.SetReadDeadline(time.Now().Add(2 * time.Second))
+	line, err := c.r.ReadString('\n')
+	if err != nil {
+		c.t.Fatalf("failed to read line : %v", err)
+	}
+	return strings.TrimRight(line, "\r\n")
+}
+
+func (c *tcpClient) expect(want string) {
+	c.t.Helper()
+	if got := c.readLine(); got != want {
+		c.t.Fatalf("expected %q, got %q", want, got)
+	}
+}
+
+func TestMockHTTPState(t *testing.T) {
+	m := startMock(t)
+	s, _, err := FetchState("http://" + m.HTTPAddr)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(s.Inputs) != 6 || s.Active != 2 || s.Preview != 3 {
+		t.Fatalf("unexpected state: inputs=%d active=%d preview=%d", len(s.Inputs), s.Active, s.Preview)
+	}
+}
+
+func TestMockHTTPFunction(t *testing.T) {
+	m := startMock(t)
+	resp, err := http.Get("http://" + m.HTTPAddr + "/api?Function=Cut")
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		t.Fatalf("unexpected status %d", resp.StatusCode)
+	}
+
+	s, _, err := FetchState("http://" + m.HTTPAddr)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if s.Active != 3 || s.Preview != 2 {
+		t.Fatalf("Cut did not swap preview/program: active=%d preview=%d", s.Active, s.Preview)
+	}
+	calls := m.Calls()
+	if len(calls) != 1 || calls[0].Function != "Cut" || calls[0].Via != "http" {
+		t.Fatalf("unexpected calls: %+v", calls)
+	}
+
+	resp, err = http.Get("http://" + m.HTTPAddr + "/api?Function=PreviewInput&Input=NotExist")
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusInternalServerError {
+		t.Fatalf("expected error for unknown input, got %d", resp.StatusCode)
+	}
+}
+
+func TestMockTCPSubscriptions(t *testing.T) {
+	m := startMock(t)
+	c := dialMock(t, m)
+
+	c.send("SUBSCRIBE ACTS")
+	c.expect("SUBSCRIBE OK ACTS")
+	c.send("SUBSCRIBE TALLY")
+	c.expect("SUBSCRIBE OK TALLY")
+
+	c.send("FUNCTION PreviewInput Input=4")
+	c.expect("ACTS OK InputPreview 3 0")
+	c.expect("ACTS OK InputPreview 4 1")
+	c.expect("TALLY OK 010200")
+	c.expect("FUNCTION OK Completed")
+
+	// Functions sent over HTTP are delivered to TCP subscribers too.
+	resp, err := http.Get("http://" + m.HTTPAddr + "/api?Function=OverlayInput1In&Input=5")
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	c.expect("ACTS OK Overlay1 5 1")
+
+	c.send("UNSUBSCRIBE ACTS")
+	c.expect("UNSUBSCRIBE OK ACTS")
+	c.send("TALLY")
+	c.expect("TALLY OK 010200")
+}
+
+func TestMockTCPXML(t *testing.T) {
+	m := startMock(t)
+	c := dialMock(t, m)
+
+	c.send("XML")
+	header := c.readLine()
+	if !strings.HasPrefix(header, "XML ") {
+		t.Fatalf("unexpected XML header %q", header)
+	}
+	s, err := ParseState([]byte(c.readLine()))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(s.Inputs) != 6 {
+		t.Fatalf("expected 6 inputs, got %d", len(s.Inputs))
+	}
+
+	c.send("FUNCTION SetInputName Input=1&Value=Renamed")
+	c.expect("FUNCTION OK Completed")
+	c.send("NOPE")
+	c.expect("NOPE ER Unknown command")
+}
+
+// TestMockTCPConcurrentWrites checks XML response is not split by subscribed messages published meanwhile.
+func TestMockTCPConcurrentWrites(t *testing.T) {
+	m := startMock(t)
+	c := dialMock(t, m)
+	c.send("SUBSCRIBE ACTS")
+	c.expect("SUBSCRIBE OK ACTS")
+	c.send("SUBSCRIBE TALLY")
+	c.expect("SUBSCRIBE OK TALLY")
+
+	done := make(chan struct{})
+	defer close(done)
+	go func() {
+		for i := 0; ; i++ {
+			select {
+			case <-done:
+				return
+			default:
+			}
+			if resp, err := http.Get("http://" + m.HTTPAddr + "/api?Function=PreviewInput&Input=" + []string{"3", "4"}[i%2]); err == nil {
+				resp.Body.Close()
+			}
+		}
+	}()
+
+	const requests = 50
+	for i := 0; i < requests; i++ {
+		c.send("XML")
+	}
+	for got := 0; got < requests; {
+		line := c.readLine()
+		switch {
+		case strings.HasPrefix(line, "ACTS OK "), strings.HasPrefix(line, "TALLY OK "):
+		case strings.HasPrefix(line, "XML "):
+			if _, err := ParseState([]byte(c.readLine())); err != nil {
+				t.Fatalf("expected XML right after header, got %v", err)
+			}
+			got++
+		default:
+			t.Fatalf("unexpected line %q", line)
+		}
+	}
+}
+
+func TestMockCallHistory(t *testing.T) {
+	m := startMock(t)
+	for i := 0; i < mockCallHistory+10; i++ {
+		if err := m.Apply("test", "SetText", map[string]string{"Input": "1", "Value": strconv.Itoa(i)}); err != nil {
+			t.Fatal(err)
+		}
+	}
+	calls := m.Calls()
+	if len(calls) != mockCallHistory || calls[0].Params["Value"] != "10" {
+		t.Fatalf("expected latest %d calls kept, got %d from %v", mockCallHistory, len(calls), calls[0].Params)
+	}
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.DELETE("/api/mock/calls", DeleteMockCallsHandler(m))
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodDelete, "/api/mock/calls", nil))
+	if w.Code != http.StatusNoContent || len(m.Calls()) != 0 {
+		t.Fatalf("expected calls reset, got %d %d", w.Code, len(m.Calls()))
+	}
+}
+
+// TestHandlersAgainstMock exercises API handlers with vmix-go client connected to mock vMix.
+func TestHandlersAgainstMock(t *testing.T) {
+	m := startMock(t)
+	*vmixaddr = "http://" + m.HTTPAddr
+	var err error
+	vmix, err = vmixgo.NewVmix(*vmixaddr)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/inputs", GetInputsHandler)
+	r.POST("/api/refresh", RefreshInputHandler)
+	r.POST("/api/multiple", DoMultipleFunctionsHandler)
+
+	do := func(method, path string, body interface{}) *httptest.ResponseRecorder {
+		var b []byte
+		if body != nil {
+			b, _ = json.Marshal(body)
+		}
+		req := httptest.NewRequest(method, path, bytes.NewReader(b))
+		req.Header.Set("Content-Type", "application/json")
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, req)
+		return w
+	}
+
+	w := do(http.MethodGet, "/api/inputs", nil)
+	var inputs struct {
+		Inputs []json.RawMessage `json:"inputs"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &inputs); err != nil || len(inputs.Inputs) != 6 {
+		t.Fatalf("unexpected /api/inputs response %d %s", w.Code, w.Body.String())
+	}
+
+	w = do(http.MethodPost, "/api/multiple", gin.H{"function": "Cut", "queries": []gin.H{}, "num": 1})
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected /api/multiple response %d %s", w.Code, w.Body.String())
+	}
+	if calls := m.Calls(); len(calls) != 1 || calls[0].Function != "Cut" {
+		t.Fatalf("function was not delivered to vMix: %+v", calls)
+	}
+
+	w = do(http.MethodPost, "/api/multiple", gin.H{"function": "Cut", "num": 0})
+	var errBody struct {
+		Error AppError `json:"error"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &errBody); err != nil || w.Code != http.StatusBadRequest || errBody.Error.Kind != ErrKindInvalidRequest {
+		t.Fatalf("expected invalid_request error, got %d %s", w.Code, w.Body.String())
+	}
+
+	s := DefaultMockState()
+	s.Inputs = s.Inputs[:2]
+	m.SetState(s)
+	w = do(http.MethodPost, "/api/refresh", nil)
+	if err := json.Unmarshal(w.Body.Bytes(), &inputs); err != nil || len(inputs.Inputs) != 2 {
+		t.Fatalf("refresh did not pick up new state %d %s", w.Code, w.Body.String())
+	}
+}
+
+func TestTCPClientAgainstMock(t *testing.T) {
+	m := startMock(t)
+	received := make(chan TCPMessage, 16)
+	client := NewTCPClient(m.TCPAddr)
+	client.OnMessage = func(msg TCPMessage) { received <- msg }
+	client.Subscribe("ACTS")
+	go client.Run()
+
+	next := func() TCPMessage {
+		t.Helper()
+		select {
+		case msg := <-received:
+			return msg
+		case <-time.After(2 * time.Second):
+			t.Fatal("timed out waiting for TCP message")
+		}
+		return TCPMessage{}
+	}
+	for msg := next(); msg.Command != "SUBSCRIBE"; msg = next() {
+	}
+
+	if _, err := client.Request("FUNCTION", "Cut", 2*time.Second); err != nil {
+		t.Fatal(err)
+	}
+	if msg := next(); msg.Command != "ACTS" || msg.Body != "Input 2 0" {
+		t.Fatalf("unexpected message %+v", msg)
+	}
+	if msg := next(); msg.Command != "ACTS" || msg.Body != "Input 3 1" {
+		t.Fatalf("unexpected message %+v", msg)
+	}
+
+	msg, err := client.Request("XML", "", 2*time.Second)
+	if err != nil {
+		t.Fatal(err)
+	}
+	s, err := ParseState([]byte(msg.Body))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if s.Active != 3 {
+		t.Fatalf("expected active input 3, got %d", s.Active)
+	}
+
+	if _, err := client.Request("FUNCTION", "PreviewInput Input=99", 2*time.Second); err == nil {
+		t.Fatal("expected error for unknown input")
+	}
+}
+
+func TestHandleTCPMessageRecordsLatency(t *testing.T) {
+	ch, unsubscribe := events.Subscribe(1)
+	defer unsubscribe()
+
+	HandleTCPMessage(TCPMessage{Command: "ACTS", Status: "OK", Body: "Input 7 1", ReceivedAt: time.Now()})
+	ev := <-ch
+	if a, ok := ev.Data.(ActsEvent); !ok || a.Input != "7" || a.Value != "1" || ev.Trace == nil {
+		t.Fatalf("unexpected event %+v", ev)
+	}
+	if v := actsCache.Snapshot()["Input"]["7"]; v != "1" {
+		t.Fatalf("ACTS cache not updated: %q", v)
+	}
+	report := latency.Report()
+	if report.Stages[0].Stage != core.LatencyStageReceiveToCache || report.Stages[0].Count == 0 {
+		t.Fatalf("latency not recorded: %+v", report)
+	}
+}
+
+func TestMockXMLText(t *testing.T) {
+	m := startMock(t)
+	c := dialMock(t, m)
+
+	c.send("XMLTEXT vmix/inputs/input[2]/@title")
+	c.expect("XMLTEXT OK Camera 1")
+	c.send("XMLTEXT vmix/inputs/input[@number='4']/@type")
+	c.expect("XMLTEXT OK Video")
+	c.send("XMLTEXT vmix/active")
+	c.expect("XMLTEXT OK 2")
+	c.send("XMLTEXT vmix/inputs/input[99]")
+	if line := c.readLine(); !strings.HasPrefix(line, "XMLTEXT ER ") {
+		t.Fatalf("expected error, got %q", line)
+	}
+}
+
+func TestProgramRefresherXMLText(t *testing.T) {
+	m := startMock(t)
+	client := NewTCPClient(m.TCPAddr)
+	go client.Run()
+	for deadline := time.Now().Add(2 * time.Second); !client.Connected(); time.Sleep(10 * time.Millisecond) {
+		if time.Now().After(deadline) {
+			t.Fatal("timed out connecting TCP API")
+		}
+	}
+	addr := "http://" + m.HTTPAddr
+	stale := DefaultMockState()
+	stale.Active, stale.Preview = 1, 1
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "mock", Addr: addr, Online: true, State: stale}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+	ch, unsubscribe := events.Subscribe(16)
+	defer unsubscribe()
+
+	r := &ProgramRefresher{Host: addr, Query: client.XMLText}
+	if err := r.refresh(); err != nil {
+		t.Fatal(err)
+	}
+	if s, _ := p.Status(addr); s.State.Active != 2 || s.State.Preview != 3 || stale.Active != 1 {
+		t.Fatalf("expected program and preview patched into copy of state, got %d %d", s.State.Active, s.State.Preview)
+	}
+	for ev := range ch {
+		if ev.Topic == "program" {
+			if d := ev.Data.(map[string]int); d["active"] != 2 || d["preview"] != 3 {
+				t.Fatalf("unexpected program event %+v", d)
+			}
+			break
+		}
+	}
+}
+
+// waitEvent returns next event of topic, skipping other topics.
+func waitEvent(t *testing.T, ch <-chan Event, topic string) Event {
+	t.Helper()
+	timeout := time.After(2 * time.Second)
+	for {
+		select {
+		case ev := <-ch:
+			if ev.Topic == topic {
+				return ev
+			}
+		case <-timeout:
+			t.Fatalf("no %q event", topic)
+		}
+	}
+}
+
+// TestPollerAgainstMock drives poller, input cache and multiviewer against mock vMix and checks what clients receive.
+func TestPollerAgainstMock(t *testing.T) {
+	m := startMock(t)
+	prevAddr, prevPoller := *vmixaddr, poller
+	*vmixaddr = "http://" + m.HTTPAddr
+	poller = NewPoller()
+	defer func() { *vmixaddr, poller = prevAddr, prevPoller }()
+	ch, unsubscribe := events.Subscribe(64)
+	defer unsubscribe()
+
+	conn := Connection{Name: "primary", Addr: *vmixaddr, Priority: ConnectionPrimary}
+	if !poller.poll(conn) {
+		t.Fatal("expected first poll to report change")
+	}
+	status, ok := waitEvent(t, ch, "status").Data.(*HostStatus)
+	if !ok || status.Name != "primary" || !status.Online || status.Connection != ConnectionConnected || status.State.Active != 2 {
+		t.Fatalf("unexpected status event %+v", status)
+	}
+	if outputs := waitEvent(t, ch, "outputs"); outputs.Host != "primary" {
+		t.Fatalf("unexpected outputs event %+v", outputs)
+	}
+	gen := inputCache.Generation()
+	if poller.poll(conn) || inputCache.Generation() != gen {
+		t.Fatal("expected unchanged mock state to keep state and input cache")
+	}
+
+	// Clicking layer 2 of PiP in multiviewer cuts to Camera 2, and sync clients get patch of program.
+	before, _ := toJSONValue(BuildAppSnapshot())
+	key, err := ExecMultiviewerCommand(MultiviewerCommand{Action: "cut", Input: "6", Layer: 2})
+	if err != nil || key != "b8b4e2ad-0003-4c1a-9a51-000000000003" {
+		t.Fatalf("unexpected multiviewer result %q %v", key, err)
+	}
+	if calls := m.Calls(); len(calls) != 1 || calls[0].Function != "Cut" || calls[0].Params["Input"] != key {
+		t.Fatalf("expected cut delivered to mock, got %+v", calls)
+	}
+	if !poller.poll(conn) {
+		t.Fatal("expected poll after cut to report change")
+	}
+	after, _ := toJSONValue(BuildAppSnapshot())
+	patched := map[string]string{}
+	for _, op := range DiffJSON(before, after) {
+		patched[op.Path] = fmt.Sprint(op.Value)
+	}
+	if patched["/states/primary/active"] != "3" || patched["/states/primary/preview"] != "2" {
+		t.Fatalf("expected program and preview patched, got %v", patched)
+	}
+
+	// Only renamed input is handed to multiviewer fetching inputs since last generation.
+	if err := m.Apply("test", "SetInputName", map[string]string{"Input": "1", "Value": "Renamed"}); err != nil {
+		t.Fatal(err)
+	}
+	poller.poll(conn)
+	changed, removed, next := inputCache.Since(gen)
+	if next <= gen || len(changed) != 1 || changed[0].Input.Title != "Renamed" || len(removed) != 0 {
+		t.Fatalf("unexpected input cache diff %d %+v %v", next, changed, removed)
+	}
+}
@@ -0,0 +1,457 @@
+package main
+
+import (
+	"bufio"
+	"encoding/binary"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log"
+	"net"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// MQTT client constants. Only QoS 0 of MQTT 3.1.1 is used, which is enough for state that is republished on change.
+const (
+	mqttDefaultPrefix     = "vmix-utility"
+	mqttKeepAlive         = 30 * time.Second
+	mqttReconnectInterval = 5 * time.Second
+	mqttStateInterval     = 250 * time.Millisecond // states are compared this often and published on change.
+)
+
+// MQTT control packet types, as high nibble of fixed header.
+const (
+	mqttConnect   = 0x10
+	mqttConnAck   = 0x20
+	mqttPublish   = 0x30
+	mqttSubscribe = 0x82 // includes reserved flags 0b0010.
+	mqttPingReq   = 0xC0
+)
+
+// mqttMaxRemaining is largest remaining length of control packet.
+const mqttMaxRemaining = 268435455
+
+// MQTTSettings is MQTT client configuration, for venue automation such as Home Assistant or Node-RED.
+//
+// States are published retained as "<prefix>/<connection>/<state>", e.g. "vmix-utility/primary/program" -> "3".
+// Functions are sent by publishing parameters as JSON or query string to "<prefix>/<connection>/function/<Function>",
+// e.g. "vmix-utility/primary/function/Cut" <- "Input=3", and shortcuts by publishing to "<prefix>/shortcut/<name>".
+type MQTTSettings struct {
+	Broker   string `json:"broker"` // e.g. "192.168.0.5:1883". Empty to disable.
+	ClientID string `json:"client_id"`
+	Username string `json:"username"`
+	Password string `json:"password"`
+	Prefix   string `json:"prefix"`   // topic prefix. Default "vmix-utility".
+	Commands bool   `json:"commands"` // subscribe function and shortcut topics. Off by default, since broker may be shared.
+}
+
+// mqttPacket is received control packet.
+type mqttPacket struct {
+	Type    byte
+	Flags   byte
+	Payload []byte // variable header and payload.
+}
+
+// writeMQTTString appends length-prefixed UTF-8 string.
+func writeMQTTString(b []byte, s string) []byte {
+	b = append(b, byte(len(s)>>8), byte(len(s)))
+	return append(b, s...)
+}
+
+// encodeMQTTPacket encodes control packet with remaining length.
+func encodeMQTTPacket(header byte, body []byte) []byte {
+	b := []byte{header}
+	n := len(body)
+	for {
+		digit := byte(n % 128)
+		n /= 128
+		if n > 0 {
+			digit |= 0x80
+		}
+		b = append(b, digit)
+		if n == 0 {
+			break
+		}
+	}
+	return append(b, body...)
+}
+
+// EncodeMQTTConnect encodes CONNECT packet with clean session.
+func EncodeMQTTConnect(settings MQTTSettings) []byte {
+	flags := byte(0x02)
+	if settings.Username != "" {
+		flags |= 0x80
+	}
+	if settings.Password != "" {
+		flags |= 0x40
+	}
+	body := writeMQTTString(nil, "MQTT")
+	body = append(body, 4, flags, byte(mqttKeepAlive/time.Second>>8), byte(mqttKeepAlive/time.Second))
+	body = writeMQTTString(body, settings.ClientID)
+	if settings.Username != "" {
+		body = writeMQTTString(body, settings.Username)
+	}
+	if settings.Password != "" {
+		body = writeMQTTString(body, settings.Password)
+	}
+	return encodeMQTTPacket(mqttConnect, body)
+}
+
+// EncodeMQTTPublish encodes QoS 0 PUBLISH packet.
+func EncodeMQTTPublish(topic string, payload []byte, retain bool) []byte {
+	header := byte(mqttPublish)
+	if retain {
+		header |= 0x01
+	}
+	return encodeMQTTPacket(header, append(writeMQTTString(nil, topic), payload...))
+}
+
+// EncodeMQTTSubscribe encodes SUBSCRIBE packet of QoS 0 topic filters.
+func EncodeMQTTSubscribe(id uint16, filters ...string) []byte {
+	body := []byte{byte(id >> 8), byte(id)}
+	for _, f := range filters {
+		body = append(writeMQTTString(body, f), 0)
+	}
+	return encodeMQTTPacket(mqttSubscribe, body)
+}
+
+// ReadMQTTPacket reads single control packet.
+func ReadMQTTPacket(r *bufio.Reader) (mqttPacket, error) {
+	header, err := r.ReadByte()
+	if err != nil {
+		return mqttPacket{}, err
+	}
+	n, mul := 0, 1
+	for i := 0; ; i++ {
+		digit, err := r.ReadByte()
+		if err != nil {
+			return mqttPacket{}, err
+		}
+		n += int(digit&0x7F) * mul
+		if digit&0x80 == 0 {
+			break
+		}
+		if mul *= 128; i >= 3 || n > mqttMaxRemaining {
+			return mqttPacket{}, fmt.Errorf("malformed remaining length")
+		}
+	}
+	payload := make([]byte, n)
+	if _, err := io.ReadFull(r, payload); err != nil {
+		return mqttPacket{}, err
+	}
+	return mqttPacket{Type: header & 0xF0, Flags: header & 0x0F, Payload: payload}, nil
+}
+
+// DecodeMQTTPublish returns topic and payload of received PUBLISH packet.
+func DecodeMQTTPublish(p mqttPacket) (string, []byte, error) {
+	if len(p.Payload) < 2 {
+		return "", nil, fmt.Errorf("PUBLISH too short")
+	}
+	n := int(binary.BigEndian.Uint16(p.Payload))
+	rest := p.Payload[2:]
+	if len(rest) < n {
+		return "", nil, fmt.Errorf("PUBLISH topic too long")
+	}
+	topic, rest := string(rest[:n]), rest[n:]
+	if qos := p.Flags >> 1 & 0x03; qos > 0 {
+		// Packet identifier. Subscriptions are QoS 0, so broker should not send it.
+		if len(rest) < 2 {
+			return "", nil, fmt.Errorf("PUBLISH packet identifier missing")
+		}
+		rest = rest[2:]
+	}
+	return topic, rest, nil
+}
+
+// MQTTStates returns state topics and values of host status, relative to prefix.
+func MQTTStates(status *HostStatus) map[string]string {
+	host := mqttTopicLevel(status.Name)
+	states := map[string]string{
+		host + "/connection": string(status.Connection),
+	}
+	if status.State == nil {
+		return states
+	}
+	s := status.State
+	states[host+"/program"] = strconv.Itoa(s.Active)
+	states[host+"/preview"] = strconv.Itoa(s.Preview)
+	if in := s.FindInput(strconv.Itoa(s.Active)); in != nil {
+		states[host+"/program_title"] = in.Title
+	}
+	if in := s.FindInput(strconv.Itoa(s.Preview)); in != nil {
+		states[host+"/preview_title"] = in.Title
+	}
+	states[host+"/recording"] = strconv.FormatBool(bool(s.Recording))
+	states[host+"/streaming"] = strconv.FormatBool(bool(s.Streaming))
+	states[host+"/fade_to_black"] = strconv.FormatBool(bool(s.FadeToBlack))
+	return states
+}
+
+// mqttTopicLevel makes name usable as single topic level.
+func mqttTopicLevel(name string) string {
+	return strings.NewReplacer("/", "_", "+", "_", "#", "_").Replace(name)
+}
+
+// ParseMQTTCommand converts message on command topic to action. ok is false for topics not commands.
+func ParseMQTTCommand(prefix, topic string, payload []byte) (TriggerAction, bool, error) {
+	rest := strings.TrimPrefix(topic, prefix+"/")
+	if rest == topic {
+		return TriggerAction{}, false, nil
+	}
+	parts := strings.Split(rest, "/")
+	switch {
+	case len(parts) == 2 && parts[0] == "shortcut":
+		sc, ok := findShortcut(parts[1])
+		if !ok {
+			return TriggerAction{}, true, fmt.Errorf("shortcut %q not found", parts[1])
+		}
+		return TriggerAction{Host: sc.Host, FunctionCall: sc.FunctionCall}, true, nil
+	case len(parts) == 3 && parts[1] == "function":
+		host := ""
+		for _, conn := range Connections() {
+			if mqttTopicLevel(conn.Name) == parts[0] {
+				host = conn.Addr
+			}
+		}
+		if host == "" {
+			return TriggerAction{}, true, fmt.Errorf("connection %q not found", parts[0])
+		}
+		params := map[string]string{}
+		if p := strings.TrimSpace(string(payload)); strings.HasPrefix(p, "{") {
+			if err := json.Unmarshal(payload, &params); err != nil {
+				return TriggerAction{}, true, err
+			}
+		} else if p != "" {
+			q, err := url.ParseQuery(p)
+			if err != nil {
+				return TriggerAction{}, true, err
+			}
+			for k := range q {
+				params[k] = q.Get(k)
+			}
+		}
+		return TriggerAction{Host: host, FunctionCall: FunctionCall{Function: parts[2], Params: params}}, true, nil
+	}
+	return TriggerAction{}, false, nil
+}
+
+// MQTTBridge publishes production state to MQTT broker and fires functions published to command topics.
+type MQTTBridge struct {
+	mu        sync.Mutex
+	settings  MQTTSettings
+	conn      net.Conn
+	restart   chan struct{}
+	published map[string]string // topic -> last published value.
+	lastErr   string
+}
+
+// NewMQTTBridge creates MQTTBridge. Call Run to connect.
+func NewMQTTBridge() *MQTTBridge {
+	return &MQTTBridge{restart: make(chan struct{}, 1)}
+}
+
+// Configure replaces settings and reconnects.
+func (b *MQTTBridge) Configure(settings MQTTSettings) error {
+	if settings.Prefix == "" {
+		settings.Prefix = mqttDefaultPrefix
+	}
+	settings.Prefix = strings.TrimSuffix(settings.Prefix, "/")
+	if strings.ContainsAny(settings.Prefix, "+#") {
+		return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("prefix must not contain wildcards"))
+	}
+	if settings.ClientID == "" {
+		settings.ClientID = "vmix-utility"
+	}
+	b.mu.Lock()
+	b.settings = settings
+	conn := b.conn
+	b.mu.Unlock()
+	if conn != nil {
+		conn.Close() // unblocks read loop.
+	}
+	select {
+	case b.restart <- struct{}{}:
+	default:
+	}
+	return nil
+}
+
+// Settings returns current settings.
+func (b *MQTTBridge) Settings() MQTTSettings {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return b.settings
+}
+
+// Status returns settings and connection state. Password is not returned.
+func (b *MQTTBridge) Status() gin.H {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	settings := b.settings
+	settings.Password = maskSecret(settings.Password)
+	return gin.H{
+		"settings":  settings,
+		"connected": b.conn != nil,
+		"error":     b.lastErr,
+	}
+}
+
+// Run connects to broker forever, reconnecting on failure or configuration change.
+func (b *MQTTBridge) Run() {
+	for {
+		settings := b.Settings()
+		if settings.Broker != "" {
+			if err := b.runOnce(settings); err != nil {
+				log.Printf("MQTT %s : %v\n", settings.Broker, err)
+				b.mu.Lock()
+				b.lastErr = err.Error()
+				b.mu.Unlock()
+			}
+		}
+		select {
+		case <-b.restart:
+		case <-time.After(mqttReconnectInterval):
+		}
+	}
+}
+
+// write writes packet to current connection.
+func (b *MQTTBridge) write(conn net.Conn, packet []byte) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	conn.SetWriteDeadline(time.Now().Add(5 * time.Second))
+	_, err := conn.Write(packet)
+	return err
+}
+
+func (b *MQTTBridge) runOnce(settings MQTTSettings) error {
+	conn, err := net.DialTimeout("tcp", strings.TrimPrefix(settings.Broker, "tcp://"), 5*time.Second)
+	if err != nil {
+		return err
+	}
+	defer conn.Close()
+	r := bufio.NewReader(conn)
+	if err := b.write(conn, EncodeMQTTConnect(settings)); err != nil {
+		return err
+	}
+	conn.SetReadDeadline(time.Now().Add(5 * time.Second))
+	ack, err := ReadMQTTPacket(r)
+	if err != nil {
+		return err
+	}
+	if ack.Type != mqttConnAck || len(ack.Payload) < 2 || ack.Payload[1] != 0 {
+		return fmt.Errorf("connection refused by broker : %v", ack.Payload)
+	}
+	if settings.Commands {
+		if err := b.write(conn, EncodeMQTTSubscribe(1, settings.Prefix+"/+/function/+", settings.Prefix+"/shortcut/+")); err != nil {
+			return err
+		}
+	}
+	b.mu.Lock()
+	b.conn = conn
+	b.lastErr = ""
+	b.published = make(map[string]string) // broker may have lost retained states, so everything is published again.
+	b.mu.Unlock()
+	defer func() {
+		b.mu.Lock()
+		b.conn = nil
+		b.mu.Unlock()
+	}()
+	log.Printf("MQTT connected to %s\n", settings.Broker)
+
+	done := make(chan struct{})
+	defer close(done)
+	go b.publishStates(conn, settings.Prefix, done)
+
+	for {
+		conn.SetReadDeadline(time.Now().Add(mqttKeepAlive * 2))
+		p, err := ReadMQTTPacket(r)
+		if err != nil {
+			return err
+		}
+		if p.Type != mqttPublish {
+			continue
+		}
+		topic, payload, err := DecodeMQTTPublish(p)
+		if err != nil {
+			return err
+		}
+		action, ok, err := ParseMQTTCommand(settings.Prefix, topic, payload)
+		if err != nil {
+			log.Printf("MQTT command %s : %v\n", topic, err)
+			continue
+		}
+		if ok && pairing.IsActive() {
+			go action.Fire("mqtt " + topic)
+		}
+	}
+}
+
+// publishStates publishes changed states and pings broker until done.
+func (b *MQTTBridge) publishStates(conn net.Conn, prefix string, done chan struct{}) {
+	t := time.NewTicker(mqttStateInterval)
+	defer t.Stop()
+	lastPing := time.Now()
+	for {
+		select {
+		case <-done:
+			return
+		case now := <-t.C:
+			for _, status := range poller.Statuses() {
+				for topic, value := range MQTTStates(status) {
+					b.mu.Lock()
+					changed := b.published[topic] != value
+					b.published[topic] = value
+					b.mu.Unlock()
+					if !changed {
+						continue
+					}
+					if err := b.write(conn, EncodeMQTTPublish(prefix+"/"+topic, []byte(value), true)); err != nil {
+						conn.Close()
+						return
+					}
+				}
+			}
+			if now.Sub(lastPing) >= mqttKeepAlive/2 {
+				lastPing = now
+				if err := b.write(conn, []byte{mqttPingReq, 0}); err != nil {
+					conn.Close()
+					return
+				}
+			}
+		}
+	}
+}
+
+// GetMQTTHandler returns MQTT settings and connection state for [GET] /api/mqtt .
+func GetMQTTHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, mqttBridge.Status())
+}
+
+// PutMQTTHandler saves MQTT settings and reconnects for [PUT] /api/mqtt .
+func PutMQTTHandler(c *gin.Context) {
+	settings := MQTTSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	settings.Password = keepSecret(settings.Password, mqttBridge.Settings().Password)
+	if err := mqttBridge.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.MQTT = mqttBridge.Settings()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetMQTTHandler(c)
+}
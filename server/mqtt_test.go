@@ -0,0 +1,97 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestMQTTPacketRoundTrip(t *testing.T) {
+	payload := []byte(strings.Repeat("x", 300)) // remaining length needs two bytes.
+	b := EncodeMQTTPublish("vmix-utility/primary/program", payload, true)
+	if b[0] != mqttPublish|0x01 {
+		t.Fatalf("expected retained PUBLISH header, got %x", b[0])
+	}
+	p, err := ReadMQTTPacket(bufio.NewReader(bytes.NewReader(b)))
+	if err != nil {
+		t.Fatal(err)
+	}
+	topic, got, err := DecodeMQTTPublish(p)
+	if err != nil || topic != "vmix-utility/primary/program" || !bytes.Equal(got, payload) {
+		t.Fatalf("expected PUBLISH decoded, got %q %d bytes %v", topic, len(got), err)
+	}
+
+	connect := EncodeMQTTConnect(MQTTSettings{ClientID: "booth", Username: "user", Password: "pass"})
+	if !bytes.Contains(connect, []byte("\x00\x04MQTT\x04\xc2")) || !bytes.HasSuffix(connect, []byte("\x00\x04pass")) {
+		t.Fatalf("expected CONNECT with credentials, got %q", connect)
+	}
+}
+
+func TestMQTTStatesAndCommands(t *testing.T) {
+	states := MQTTStates(&HostStatus{Name: "primary", Connection: ConnectionConnected, State: DefaultMockState()})
+	if states["primary/connection"] != "connected" || states["primary/program"] != "2" || states["primary/preview"] != "3" || states["primary/recording"] != "false" {
+		t.Fatalf("unexpected states %v", states)
+	}
+
+	config.Update(func(cfg *Config) {
+		cfg.Shortcuts = append(cfg.Shortcuts, Shortcut{Name: "mqtt_test", FunctionCall: FunctionCall{Function: "Fade"}})
+	})
+	defer config.Update(func(cfg *Config) {
+		cfg.Shortcuts = cfg.Shortcuts[:len(cfg.Shortcuts)-1]
+	})
+
+	action, ok, err := ParseMQTTCommand("venue", "venue/primary/function/Cut", []byte("Input=3&Duration=500"))
+	if !ok || err != nil || action.Host != *vmixaddr || action.Function != "Cut" || action.Params["Input"] != "3" || action.Params["Duration"] != "500" {
+		t.Fatalf("expected query string function command, got %+v %v %v", action, ok, err)
+	}
+	action, ok, err = ParseMQTTCommand("venue", "venue/primary/function/Fade", []byte(`{"Input":"CAM 1"}`))
+	if !ok || err != nil || action.Params["Input"] != "CAM 1" {
+		t.Fatalf("expected JSON function command, got %+v %v %v", action, ok, err)
+	}
+	action, ok, err = ParseMQTTCommand("venue", "venue/shortcut/mqtt_test", nil)
+	if !ok || err != nil || action.Function != "Fade" {
+		t.Fatalf("expected shortcut command, got %+v %v %v", action, ok, err)
+	}
+	if _, ok, err = ParseMQTTCommand("venue", "venue/unknown/function/Cut", nil); !ok || err == nil {
+		t.Fatalf("expected unknown connection refused, got %v %v", ok, err)
+	}
+	if _, ok, _ = ParseMQTTCommand("venue", "venue/primary/program", []byte("2")); ok {
+		t.Fatal("expected state topic not to be command")
+	}
+}
+
+func TestPutMQTTKeepsMaskedPassword(t *testing.T) {
+	defer config.Update(func(cfg *Config) { cfg.MQTT = MQTTSettings{} })
+	defer mqttBridge.Configure(MQTTSettings{})
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/mqtt", PutMQTTHandler)
+	put := func(body string) MQTTSettings {
+		t.Helper()
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/mqtt", strings.NewReader(body)))
+		res := struct {
+			Settings MQTTSettings `json:"settings"`
+		}{}
+		if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || w.Code != http.StatusOK {
+			t.Fatalf("unexpected response %d %s", w.Code, w.Body.String())
+		}
+		return res.Settings
+	}
+
+	got := put(`{"username":"vmix","password":"secret"}`)
+	if got.Password != secretMask {
+		t.Fatalf("expected password masked, got %q", got.Password)
+	}
+	b, _ := json.Marshal(got)
+	put(string(b))
+	if pw := config.Get().MQTT.Password; pw != "secret" || mqttBridge.Settings().Password != "secret" {
+		t.Fatalf("expected password kept when mask is saved back, got %q", pw)
+	}
+}
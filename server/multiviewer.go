@@ -0,0 +1,153 @@
+package main
+
+import (
+	"crypto/subtle"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// multiviewerActions maps multiviewer command action to vMix function.
+var multiviewerActions = map[string]string{
+	"preview": "PreviewInput",
+	"cut":     "Cut",
+	"fade":    "Fade",
+}
+
+// MultiviewerCommand is command sent by multiviewer page over WebSocket.
+// e.g. {"action":"cut","input":"Multiview","layer":3} cuts to input on layer 3 of "Multiview" input.
+type MultiviewerCommand struct {
+	Action string `json:"action"` // "preview", "cut" or "fade".
+	Host   string `json:"host"`   // vMix host. Primary vMix host is used if empty.
+	Input  string `json:"input"`  // clicked input, or input containing clicked layer.
+	Layer  int    `json:"layer"`  // clicked layer 1-10. 0 if input itself was clicked.
+}
+
+// MultiviewerResult is reply to MultiviewerCommand.
+type MultiviewerResult struct {
+	Action string    `json:"action"`
+	Input  string    `json:"input,omitempty"` // key of input function was sent for.
+	Error  *AppError `json:"error,omitempty"`
+}
+
+// multiviewerAuthorized reports whether request has multiviewer token, by "token" query or Authorization bearer header.
+// Channel is disabled when token is not configured.
+func multiviewerAuthorized(r *http.Request, token string) bool {
+	if token == "" {
+		return false
+	}
+	got := r.URL.Query().Get("token")
+	if got == "" {
+		got = strings.TrimPrefix(r.Header.Get("Authorization"), "Bearer ")
+	}
+	return subtle.ConstantTimeCompare([]byte(got), []byte(token)) == 1
+}
+
+// resolveLayerInput returns input shown on layer of input, or input itself when layer is 0.
+func resolveLayerInput(s *State, input string, layer int) (*StateInput, error) {
+	in := s.FindInput(input)
+	if in == nil {
+		return nil, fmt.Errorf("input %q not found", input)
+	}
+	if layer == 0 {
+		return in, nil
+	}
+	for _, ov := range in.Overlays {
+		// Layer index in vMix XML is zero based.
+		if ov.Index == layer-1 {
+			if layerInput := s.FindInput(ov.Key); layerInput != nil {
+				return layerInput, nil
+			}
+		}
+	}
+	return nil, fmt.Errorf("layer %d of input %q is empty", layer, input)
+}
+
+// ExecMultiviewerCommand sends function for command and returns key of target input.
+func ExecMultiviewerCommand(cmd MultiviewerCommand) (string, error) {
+	host := cmd.Host
+	if host == "" {
+		host = *vmixaddr
+	}
+	function, ok := multiviewerActions[cmd.Action]
+	if !ok {
+		return "", NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("unknown action %q", cmd.Action))
+	}
+	s, err := hostState(host)
+	if err != nil {
+		return "", err
+	}
+	in, err := resolveLayerInput(s, ResolveInputAlias(host, cmd.Input), cmd.Layer)
+	if err != nil {
+		return "", NewAppError(ErrKindNotFound, host, err)
+	}
+	poller.Touch()
+	if err := SendFunctionTo(host, function, map[string]string{"Input": in.Key}); err != nil {
+		return "", err
+	}
+	return in.Key, nil
+}
+
+// MultiviewerWebSocketHandler accepts click-to-cut commands from multiviewer page for [GET] /multiviewer/ws?token=... .
+// Every command is answered by MultiviewerResult.
+func MultiviewerWebSocketHandler(c *gin.Context) {
+	if !multiviewerAuthorized(c.Request, *multiviewerToken) {
+		c.AbortWithStatus(http.StatusUnauthorized)
+		return
+	}
+	ws, err := upgradeWebSocket(c.Writer, c.Request)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	defer ws.Close()
+	defer operators.Connect(operatorName(c), c.ClientIP())()
+
+	for {
+		b, err := ws.ReadMessage()
+		if err != nil {
+			return
+		}
+		cmd := MultiviewerCommand{}
+		if err := json.Unmarshal(b, &cmd); err != nil {
+			ws.WriteJSON(MultiviewerResult{Error: NewAppError(ErrKindInvalidRequest, "", err)})
+			continue
+		}
+		if cmd.Host == "" {
+			cmd.Host = *vmixaddr
+		}
+		key, err := ExecMultiviewerCommand(cmd)
+		res := MultiviewerResult{Action: cmd.Action, Input: key}
+		if err != nil {
+			res.Error = AsAppError(err)
+		} else {
+			announceAction(c, cmd.Host, fmt.Sprintf("%s %s", multiviewerActions[cmd.Action], key))
+		}
+		if err := ws.WriteJSON(res); err != nil {
+			return
+		}
+	}
+}
+
+// MultiviewerFileHandler serves multiviewer for [GET] /multiviewer/*file . /multiviewer/program and /multiviewer/preview
+// redirect to single full-frame output view, for confidence monitors in vMix or OBS browser sources.
+func MultiviewerFileHandler(c *gin.Context) {
+	file := c.Param("file")
+	switch file {
+	case "/ws":
+		MultiviewerWebSocketHandler(c)
+		return
+	case "/program", "/preview":
+		c.Redirect(http.StatusFound, *basePath+"/#/output"+file)
+		return
+	}
+	b, err := multiviewFS.ReadFile("vMixMultiview" + file)
+	if err != nil {
+		c.AbortWithError(http.StatusNotFound, err)
+		return
+	}
+	c.Data(http.StatusOK, "", b)
+}
@@ -0,0 +1,49 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestResolveLayerInput(t *testing.T) {
+	s := DefaultMockState()
+	s.Inputs[0].Overlays = []StateInputOverlay{{Index: 2, Key: s.Inputs[3].Key}}
+	in, err := resolveLayerInput(s, "1", 3)
+	if err != nil || in.Number != 4 {
+		t.Fatalf("expected input 4, got %+v %v", in, err)
+	}
+	if in, err := resolveLayerInput(s, "2", 0); err != nil || in.Number != 2 {
+		t.Fatalf("expected input 2, got %+v %v", in, err)
+	}
+	if _, err := resolveLayerInput(s, "1", 1); err == nil {
+		t.Fatal("expected empty layer error")
+	}
+}
+
+func TestMultiviewerAuthorized(t *testing.T) {
+	r := httptest.NewRequest("GET", "/multiviewer/ws?token=secret", nil)
+	if !multiviewerAuthorized(r, "secret") || multiviewerAuthorized(r, "other") || multiviewerAuthorized(r, "") {
+		t.Fatal("unexpected query token result")
+	}
+	r = httptest.NewRequest("GET", "/multiviewer/ws", nil)
+	r.Header.Set("Authorization", "Bearer secret")
+	if !multiviewerAuthorized(r, "secret") {
+		t.Fatal("bearer token must be accepted")
+	}
+}
+
+func TestMultiviewerOutputRoutes(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/multiviewer/*file", MultiviewerFileHandler)
+	for _, bus := range []string{"program", "preview"} {
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/multiviewer/"+bus, nil))
+		if w.Code != http.StatusFound || w.Header().Get("Location") != *basePath+"/#/output/"+bus {
+			t.Fatalf("expected %s redirected to output view, got %d %s", bus, w.Code, w.Header().Get("Location"))
+		}
+	}
+}
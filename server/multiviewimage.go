@@ -0,0 +1,196 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"html"
+	"image"
+	"image/color"
+	"image/draw"
+	"image/png"
+	"math"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+const (
+	multiviewWidth  = 1920
+	multiviewHeight = 1080
+	multiviewBorder = 6
+)
+
+// multiviewColors are tile colors by tally.
+var multiviewColors = map[string]color.RGBA{
+	"program": {R: 0xe0, G: 0x20, B: 0x20, A: 0xff},
+	"preview": {R: 0x20, G: 0xc0, B: 0x40, A: 0xff},
+	"":        {R: 0x50, G: 0x50, B: 0x50, A: 0xff},
+}
+
+// multiviewDigits is 3x5 bitmap font of digits, drawn as input numbers on PNG since no font is bundled.
+var multiviewDigits = [10][5]string{
+	{"###", "#.#", "#.#", "#.#", "###"},
+	{".#.", "##.", ".#.", ".#.", "###"},
+	{"###", "..#", "###", "#..", "###"},
+	{"###", "..#", "###", "..#", "###"},
+	{"#.#", "#.#", "###", "..#", "..#"},
+	{"###", "#..", "###", "..#", "###"},
+	{"###", "#..", "###", "#.#", "###"},
+	{"###", "..#", "..#", "..#", "..#"},
+	{"###", "#.#", "###", "#.#", "###"},
+	{"###", "#.#", "###", "..#", "###"},
+}
+
+// MultiviewTile is input shown on multiview image.
+type MultiviewTile struct {
+	Number int    `json:"number"`
+	Title  string `json:"title"`
+	Tally  string `json:"tally"` // "program", "preview" or empty.
+	X      int    `json:"x"`
+	Y      int    `json:"y"`
+	W      int    `json:"w"`
+	H      int    `json:"h"`
+}
+
+// MultiviewLayout is multiview layout rendered into image.
+type MultiviewLayout struct {
+	Width  int             `json:"width"`
+	Height int             `json:"height"`
+	Input  string          `json:"input,omitempty"` // multiview input whose layers are shown. Every input is shown as grid if empty.
+	Tiles  []MultiviewTile `json:"tiles"`
+}
+
+func multiviewTally(s *State, number int) string {
+	switch number {
+	case s.Active:
+		return "program"
+	case s.Preview:
+		return "preview"
+	}
+	return ""
+}
+
+// BuildMultiviewLayout returns layout of layers of input ref, placed by their position, or grid of every input if ref is empty.
+func BuildMultiviewLayout(s *State, ref string) (MultiviewLayout, error) {
+	l := MultiviewLayout{Width: multiviewWidth, Height: multiviewHeight, Tiles: []MultiviewTile{}}
+	if ref == "" {
+		cols := int(math.Ceil(math.Sqrt(float64(len(s.Inputs)))))
+		if cols == 0 {
+			return l, nil
+		}
+		rows := (len(s.Inputs) + cols - 1) / cols
+		w, h := l.Width/cols, l.Height/rows
+		for i, in := range s.Inputs {
+			l.Tiles = append(l.Tiles, MultiviewTile{Number: in.Number, Title: in.Title, Tally: multiviewTally(s, in.Number), X: i % cols * w, Y: i / cols * h, W: w, H: h})
+		}
+		return l, nil
+	}
+	in := s.FindInput(ref)
+	if in == nil {
+		return l, fmt.Errorf("input %q not found", ref)
+	}
+	l.Input = in.Title
+	for _, ov := range in.Overlays {
+		layer := s.FindInput(ov.Key)
+		if layer == nil {
+			continue
+		}
+		t := MultiviewTile{Number: layer.Number, Title: layer.Title, Tally: multiviewTally(s, layer.Number), W: l.Width, H: l.Height}
+		if tr := ov.Transform; tr != nil {
+			// vMix pans by half of frame per 1.0, and positive PanY moves up.
+			t.W, t.H = int(float64(l.Width)*tr.ZoomX), int(float64(l.Height)*tr.ZoomY)
+			t.X = int((1+tr.PanX)/2*float64(l.Width)) - t.W/2
+			t.Y = int((1-tr.PanY)/2*float64(l.Height)) - t.H/2
+		}
+		l.Tiles = append(l.Tiles, t)
+	}
+	return l, nil
+}
+
+// RenderMultiviewPNG renders layout as PNG. Tiles are filled by tally color and labelled by input number.
+func RenderMultiviewPNG(l MultiviewLayout) ([]byte, error) {
+	img := image.NewRGBA(image.Rect(0, 0, l.Width, l.Height))
+	draw.Draw(img, img.Bounds(), image.NewUniform(color.Black), image.Point{}, draw.Src)
+	for _, t := range l.Tiles {
+		r := image.Rect(t.X, t.Y, t.X+t.W, t.Y+t.H).Intersect(img.Bounds())
+		draw.Draw(img, r, image.NewUniform(multiviewColors[t.Tally]), image.Point{}, draw.Src)
+		draw.Draw(img, r.Inset(multiviewBorder), image.NewUniform(color.RGBA{R: 0x20, G: 0x20, B: 0x20, A: 0xff}), image.Point{}, draw.Src)
+		scale := t.H / 20
+		if scale < 2 {
+			scale = 2
+		}
+		drawDigits(img, strconv.Itoa(t.Number), t.X+multiviewBorder*2, t.Y+multiviewBorder*2, scale)
+	}
+	buf := &bytes.Buffer{}
+	if err := png.Encode(buf, img); err != nil {
+		return nil, err
+	}
+	return buf.Bytes(), nil
+}
+
+func drawDigits(img *image.RGBA, digits string, x, y, scale int) {
+	for _, d := range digits {
+		glyph := multiviewDigits[d-'0']
+		for row, line := range glyph {
+			for col, px := range line {
+				if px != '#' {
+					continue
+				}
+				r := image.Rect(x+col*scale, y+row*scale, x+(col+1)*scale, y+(row+1)*scale).Intersect(img.Bounds())
+				draw.Draw(img, r, image.NewUniform(color.White), image.Point{}, draw.Src)
+			}
+		}
+		x += 4 * scale
+	}
+}
+
+// RenderMultiviewSVG renders layout as SVG, labelled by input number and title.
+func RenderMultiviewSVG(l MultiviewLayout) []byte {
+	sb := &strings.Builder{}
+	fmt.Fprintf(sb, `<svg xmlns="http://www.w3.org/2000/svg" width="%d" height="%d" viewBox="0 0 %d %d">`, l.Width, l.Height, l.Width, l.Height)
+	fmt.Fprintf(sb, `<rect width="%d" height="%d" fill="#000"/>`, l.Width, l.Height)
+	for _, t := range l.Tiles {
+		c := multiviewColors[t.Tally]
+		fmt.Fprintf(sb, `<rect x="%d" y="%d" width="%d" height="%d" fill="#202020" stroke="#%02x%02x%02x" stroke-width="%d"/>`, t.X, t.Y, t.W, t.H, c.R, c.G, c.B, multiviewBorder*2)
+		fmt.Fprintf(sb, `<text x="%d" y="%d" fill="#fff" font-family="sans-serif" font-size="%d">%d %s</text>`, t.X+multiviewBorder*2, t.Y+t.H-multiviewBorder*3, t.H/10, t.Number, html.EscapeString(t.Title))
+	}
+	sb.WriteString(`</svg>`)
+	return []byte(sb.String())
+}
+
+// GetMultiviewImageHandler renders multiview of host as image for [GET] /api/multiviewer/image?host=...&input=...&format=png|svg .
+// Image is attached as file for fault reports when download=1.
+func GetMultiviewImageHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	s, err := hostState(host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	l, err := BuildMultiviewLayout(s, ResolveInputAlias(host, c.Query("input")))
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, host, err))
+		return
+	}
+	format := c.DefaultQuery("format", "png")
+	var b []byte
+	contentType := "image/png"
+	switch format {
+	case "png":
+		if b, err = RenderMultiviewPNG(l); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInternal, host, err))
+			return
+		}
+	case "svg":
+		b, contentType = RenderMultiviewSVG(l), "image/svg+xml"
+	default:
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("unknown format %q", format)))
+		return
+	}
+	if c.Query("download") == "1" {
+		c.Header("Content-Disposition", fmt.Sprintf(`attachment; filename="multiview.%s"`, format))
+	}
+	c.Data(http.StatusOK, contentType, b)
+}
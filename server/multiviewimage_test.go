@@ -0,0 +1,46 @@
+package main
+
+import (
+	"bytes"
+	"image/png"
+	"strings"
+	"testing"
+)
+
+func TestMultiviewLayout(t *testing.T) {
+	s := DefaultMockState()
+	s.Inputs[5].Overlays[1].Transform = &StateTransform{PanX: 0.5, PanY: -0.5, ZoomX: 0.5, ZoomY: 0.5}
+	l, err := BuildMultiviewLayout(s, "PiP")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(l.Tiles) != 2 || l.Tiles[0].Tally != "program" || l.Tiles[1].Tally != "preview" {
+		t.Fatalf("unexpected tiles %+v", l.Tiles)
+	}
+	// Quarter size layer in bottom right.
+	if tile := l.Tiles[1]; tile.X != 960 || tile.Y != 540 || tile.W != 960 || tile.H != 540 {
+		t.Fatalf("unexpected position %+v", tile)
+	}
+
+	grid, err := BuildMultiviewLayout(s, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(grid.Tiles) != len(s.Inputs) {
+		t.Fatalf("expected every input in grid, got %+v", grid.Tiles)
+	}
+	b, err := RenderMultiviewPNG(grid)
+	if err != nil {
+		t.Fatal(err)
+	}
+	img, err := png.Decode(bytes.NewReader(b))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if img.Bounds().Dx() != multiviewWidth {
+		t.Fatalf("unexpected size %v", img.Bounds())
+	}
+	if svg := string(RenderMultiviewSVG(grid)); !strings.Contains(svg, "Lower Third.gtzip") {
+		t.Fatalf("expected titles in svg, got %s", svg)
+	}
+}
@@ -0,0 +1,164 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"io"
+	"io/ioutil"
+	"net/http"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// offlineScheme is address scheme of offline connections. e.g. "offline://venue_a" .
+const offlineScheme = "offline://"
+
+// IsOfflineAddr reports whether addr is offline connection loaded from XML file.
+func IsOfflineAddr(addr string) bool {
+	return strings.HasPrefix(addr, offlineScheme)
+}
+
+// OfflineHost is saved vMix XML loaded as read-only connection.
+type OfflineHost struct {
+	Name     string    `json:"name"`
+	Addr     string    `json:"addr"`
+	Inputs   int       `json:"inputs"`
+	LoadedAt time.Time `json:"loaded_at"`
+
+	raw []byte
+}
+
+// OfflineHosts holds offline connections. They are polled like any host, so inputs, snapshots and multiviewer
+// work on captured state without original vMix, but functions are rejected.
+type OfflineHosts struct {
+	mu    sync.RWMutex
+	hosts map[string]*OfflineHost // addr -> host.
+}
+
+// NewOfflineHosts creates OfflineHosts.
+func NewOfflineHosts() *OfflineHosts {
+	return &OfflineHosts{hosts: make(map[string]*OfflineHost)}
+}
+
+// Load parses XML and adds or replaces offline connection name.
+func (o *OfflineHosts) Load(name string, raw []byte) (OfflineHost, error) {
+	name = unsafeFileChars.ReplaceAllString(name, "_")
+	if name == "" {
+		return OfflineHost{}, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("name is required"))
+	}
+	addr := offlineScheme + name
+	s, err := ParseStateWithFallback(addr, raw)
+	if err != nil {
+		return OfflineHost{}, NewAppError(ErrKindInvalidRequest, addr, err)
+	}
+	h := &OfflineHost{Name: name, Addr: addr, Inputs: len(s.Inputs), LoadedAt: time.Now(), raw: raw}
+	o.mu.Lock()
+	o.hosts[addr] = h
+	o.mu.Unlock()
+	return *h, nil
+}
+
+// LoadFile loads XML file named by file name without extension.
+func (o *OfflineHosts) LoadFile(path string) (OfflineHost, error) {
+	b, err := ioutil.ReadFile(path)
+	if err != nil {
+		return OfflineHost{}, err
+	}
+	return o.Load(strings.TrimSuffix(filepath.Base(path), filepath.Ext(path)), b)
+}
+
+// Remove removes offline connection.
+func (o *OfflineHosts) Remove(addr string) bool {
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	_, ok := o.hosts[addr]
+	delete(o.hosts, addr)
+	return ok
+}
+
+// List returns offline connections ordered by name.
+func (o *OfflineHosts) List() []OfflineHost {
+	o.mu.RLock()
+	defer o.mu.RUnlock()
+	list := make([]OfflineHost, 0, len(o.hosts))
+	for _, h := range o.hosts {
+		list = append(list, *h)
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].Name < list[j].Name })
+	return list
+}
+
+// Stats returns size of loaded XML. Offline connections are loaded explicitly, so they are never evicted.
+func (o *OfflineHosts) Stats() CacheStats {
+	o.mu.RLock()
+	defer o.mu.RUnlock()
+	stats := CacheStats{Name: "offline", Entries: len(o.hosts)}
+	for _, h := range o.hosts {
+		stats.Bytes += int64(len(h.raw))
+	}
+	return stats
+}
+
+// Connections returns offline connections. They are secondary, since captured state never changes.
+func (o *OfflineHosts) Connections() []Connection {
+	conns := []Connection{}
+	for _, h := range o.List() {
+		conns = append(conns, Connection{Name: h.Name, Addr: h.Addr, Priority: ConnectionSecondary})
+	}
+	return conns
+}
+
+// fetch returns state of offline connection, parsed again each time so callers never share state.
+func (o *OfflineHosts) fetch(addr string, raw io.Writer) (*State, error) {
+	o.mu.RLock()
+	h, ok := o.hosts[addr]
+	o.mu.RUnlock()
+	if !ok {
+		return nil, NewAppError(ErrKindNotFound, addr, fmt.Errorf("offline connection not loaded"))
+	}
+	if raw != nil {
+		if _, err := raw.Write(h.raw); err != nil {
+			return nil, NewAppError(ErrKindInternal, addr, err)
+		}
+	}
+	return ParseStateWithFallback(addr, h.raw)
+}
+
+// GetOfflineHandler returns offline connections for [GET] /api/offline .
+func GetOfflineHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"offline": offline.List(),
+	})
+}
+
+// PutOfflineHandler loads saved vMix XML in request body as offline connection for [PUT] /api/offline/:name .
+func PutOfflineHandler(c *gin.Context) {
+	b, err := ioutil.ReadAll(c.Request.Body)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	h, err := offline.Load(c.Param("name"), bytes.TrimSpace(b))
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	poller.Touch()
+	c.JSON(http.StatusOK, gin.H{
+		"offline": h,
+	})
+}
+
+// DeleteOfflineHandler removes offline connection for [DELETE] /api/offline/:name .
+func DeleteOfflineHandler(c *gin.Context) {
+	if !offline.Remove(offlineScheme + c.Param("name")) {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("offline connection %q not found", c.Param("name"))))
+		return
+	}
+	GetOfflineHandler(c)
+}
@@ -0,0 +1,41 @@
+package main
+
+import (
+	"encoding/xml"
+	"testing"
+)
+
+func TestOfflineConnection(t *testing.T) {
+	b, err := xml.Marshal(DefaultMockState())
+	if err != nil {
+		t.Fatal(err)
+	}
+	h, err := offline.Load("venue a", b)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer offline.Remove(h.Addr)
+	if h.Addr != "offline://venue_a" || h.Inputs != 6 {
+		t.Fatalf("unexpected offline host %+v", h)
+	}
+	conns := Connections()
+	if last := conns[len(conns)-1]; last.Addr != h.Addr {
+		t.Fatalf("expected offline connection last, got %+v", conns)
+	}
+
+	p := NewPoller()
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+	p.poll(conns[len(conns)-1])
+	s, err := hostState(h.Addr)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if s.Active != 2 {
+		t.Fatalf("unexpected state %+v", s)
+	}
+	if err := SendFunctionTo(h.Addr, "Cut", nil); err == nil || AsAppError(err).Kind != ErrKindUnsupported {
+		t.Fatalf("expected offline connection to reject functions, got %v", err)
+	}
+}
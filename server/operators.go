@@ -0,0 +1,142 @@
+package main
+
+import (
+	"net/http"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// operatorHeader is request header identifying operator. e.g. "X-Operator: Director" .
+const operatorHeader = "X-Operator"
+
+// operatorTimeout is inactivity after which operator is no longer active.
+const operatorTimeout = 30 * time.Second
+
+// Operator is frontend window or API client using utility.
+type Operator struct {
+	Name      string    `json:"name"`
+	Addr      string    `json:"addr"` // client IP.
+	LastSeen  time.Time `json:"last_seen"`
+	Connected int       `json:"connected"` // open WebSocket connections.
+}
+
+// OperatorAction is function sent by operator, broadcast to other operators.
+type OperatorAction struct {
+	Operator string    `json:"operator"`
+	Host     string    `json:"host"`
+	Function string    `json:"function"`
+	At       time.Time `json:"at"`
+}
+
+// Operators tracks active operators.
+type Operators struct {
+	mu        sync.Mutex
+	operators map[string]*Operator // name -> operator.
+}
+
+// NewOperators creates Operators.
+func NewOperators() *Operators {
+	return &Operators{operators: make(map[string]*Operator)}
+}
+
+// Seen records activity of operator.
+func (o *Operators) Seen(name, addr string, now time.Time) {
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	op, ok := o.operators[name]
+	if !ok {
+		op = &Operator{Name: name}
+		o.operators[name] = op
+	}
+	op.Addr = addr
+	op.LastSeen = now
+}
+
+// Connect records WebSocket connection of operator. Returned function must be called on disconnect.
+func (o *Operators) Connect(name, addr string) func() {
+	o.Seen(name, addr, time.Now())
+	o.mu.Lock()
+	o.operators[name].Connected++
+	o.mu.Unlock()
+	return func() {
+		o.mu.Lock()
+		defer o.mu.Unlock()
+		if op, ok := o.operators[name]; ok {
+			op.Connected--
+			op.LastSeen = time.Now()
+		}
+	}
+}
+
+// Prune removes operators without connection and not seen within operatorTimeout. Returns number of removed operators.
+func (o *Operators) Prune(now time.Time) int {
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	removed := 0
+	for name, op := range o.operators {
+		if op.Connected == 0 && now.Sub(op.LastSeen) > operatorTimeout {
+			delete(o.operators, name)
+			removed++
+		}
+	}
+	return removed
+}
+
+// Run prunes stale operators every interval, so one-off API clients do not accumulate while nobody lists operators.
+func (o *Operators) Run(interval time.Duration) {
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	for now := range t.C {
+		o.Prune(now)
+	}
+}
+
+// Active returns operators connected or seen recently, ordered by name.
+func (o *Operators) Active(now time.Time) []Operator {
+	o.Prune(now)
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	list := make([]Operator, 0, len(o.operators))
+	for _, op := range o.operators {
+		list = append(list, *op)
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].Name < list[j].Name })
+	return list
+}
+
+// operatorName returns operator of request, or client IP if request has no identity.
+func operatorName(c *gin.Context) string {
+	if name := c.GetHeader(operatorHeader); name != "" {
+		return name
+	}
+	if name := c.Query("operator"); name != "" {
+		return name
+	}
+	return c.ClientIP()
+}
+
+// OperatorMiddleware records presence of operator sending request.
+func OperatorMiddleware(c *gin.Context) {
+	operators.Seen(operatorName(c), c.ClientIP(), time.Now())
+	c.Next()
+}
+
+// announceAction broadcasts function sent by operator, so other operators notice double driving.
+func announceAction(c *gin.Context, host, function string) {
+	events.Publish(Event{Topic: "operator_action", Host: host, Data: OperatorAction{
+		Operator: operatorName(c),
+		Host:     host,
+		Function: function,
+		At:       time.Now(),
+	}})
+}
+
+// GetOperatorsHandler returns active operators for [GET] /api/operators .
+func GetOperatorsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"operators": operators.Active(time.Now()),
+	})
+}
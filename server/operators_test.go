@@ -0,0 +1,22 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestOperatorsPruneKeepsConnected(t *testing.T) {
+	o := NewOperators()
+	now := time.Now()
+	o.Seen("one-off", "192.0.2.1", now.Add(-time.Minute))
+	disconnect := o.Connect("director", "192.0.2.2")
+	defer disconnect()
+	o.operators["director"].LastSeen = now.Add(-time.Hour)
+
+	if n := o.Prune(now); n != 1 {
+		t.Fatalf("expected 1 stale operator removed, got %d", n)
+	}
+	if list := o.Active(now); len(list) != 1 || list[0].Name != "director" {
+		t.Fatalf("unexpected operators %+v", list)
+	}
+}
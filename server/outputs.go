@@ -0,0 +1,98 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// OutputStatus is recording, streaming, external output and MultiCorder states of vMix host.
+type OutputStatus struct {
+	Recording   bool `json:"recording"`
+	Streaming   bool `json:"streaming"`
+	External    bool `json:"external"`
+	MultiCorder bool `json:"multicorder"`
+}
+
+// outputFunctions maps output name to vMix function suffix. e.g. "external" -> "StartExternal"/"StopExternal" .
+var outputFunctions = map[string]string{
+	"recording":   "Recording",
+	"streaming":   "Streaming",
+	"external":    "External",
+	"multicorder": "MultiCorder",
+}
+
+// OutputsOf returns output states of state.
+func OutputsOf(s *State) OutputStatus {
+	if s == nil {
+		return OutputStatus{}
+	}
+	return OutputStatus{
+		Recording:   bool(s.Recording),
+		Streaming:   bool(s.Streaming),
+		External:    bool(s.External),
+		MultiCorder: bool(s.MultiCorder),
+	}
+}
+
+// SetOutput starts or stops output on host.
+func SetOutput(addr, output string, start bool) error {
+	suffix, ok := outputFunctions[strings.ToLower(output)]
+	if !ok {
+		return NewAppError(ErrKindInvalidRequest, addr, fmt.Errorf("unknown output %q", output))
+	}
+	if suffix == "MultiCorder" {
+		if err := RequireFeature(addr, FeatureMultiCorder); err != nil {
+			return err
+		}
+	}
+	function := "Stop" + suffix
+	if start {
+		function = "Start" + suffix
+	}
+	if suffix == "Streaming" && !start {
+		streamHealth.Disarm(addr)
+	}
+	poller.Touch()
+	return SendFunctionTo(addr, function, nil)
+}
+
+// GetOutputsHandler returns output states of every polled host for [GET] /api/outputs .
+func GetOutputsHandler(c *gin.Context) {
+	outputs := make(map[string]OutputStatus)
+	for _, s := range poller.Statuses() {
+		if s.Online {
+			outputs[s.Name] = s.Outputs
+		}
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"outputs": outputs,
+	})
+}
+
+// SetOutputHandler starts or stops output for [POST] /api/outputs/:output/:action?host=... .
+// action is "start" or "stop". Primary vMix host is used if host is empty.
+func SetOutputHandler(c *gin.Context) {
+	addr := c.DefaultQuery("host", *vmixaddr)
+	var start bool
+	switch action := c.Param("action"); action {
+	case "start":
+		start = true
+	case "stop":
+	default:
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, addr, fmt.Errorf("unknown action %q", action)))
+		return
+	}
+	if err := SetOutput(addr, c.Param("output"), start); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, addr, c.Param("action")+" "+c.Param("output"))
+	c.JSON(http.StatusOK, gin.H{
+		"host":   addr,
+		"output": c.Param("output"),
+		"active": start,
+	})
+}
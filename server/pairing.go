@@ -0,0 +1,235 @@
+package main
+
+import (
+	"crypto/subtle"
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"log"
+	"net"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Pairing roles.
+const (
+	RoleStandalone = "standalone"
+	RolePrimary    = "primary"
+	RoleSecondary  = "secondary"
+)
+
+// pairingFailThreshold is number of failed health checks before secondary takes over.
+const pairingFailThreshold = 3
+
+// pairingRecoverThreshold is number of consecutive healthy checks before active secondary returns to standby,
+// so flapping primary does not make pair switch back and forth on every check.
+const pairingRecoverThreshold = 5
+
+// PairingStatus is hot-standby state published as "pairing" event.
+type PairingStatus struct {
+	Role          string    `json:"role"`
+	Peer          string    `json:"peer"`
+	Active        bool      `json:"active"` // this instance owns automation and outgoing actions.
+	PeerHealthy   bool      `json:"peer_healthy"`
+	LastPeerCheck time.Time `json:"last_peer_check"`
+	TakeoverAt    time.Time `json:"takeover_at,omitempty"`
+}
+
+// Pairing pairs primary and secondary instances. Secondary becomes active when primary stops responding.
+// While standing by, secondary sends no functions and runs no automation, and replicates settings of primary
+// and reconfigures services such as triggers and bridges with them, so it takes over with same connections,
+// shortcuts and triggers. vMix state is not replicated, since both instances poll vMix hosts themselves.
+type Pairing struct {
+	mu       sync.Mutex
+	status   PairingStatus
+	failures int
+	healthy  int // consecutive healthy checks.
+	token    string // shared token sent to and required from peer. Peer is identified by its address if empty.
+	client   *http.Client
+}
+
+// NewPairing creates Pairing. Standalone and primary instances are always active.
+func NewPairing(role, peer, token string) *Pairing {
+	return &Pairing{
+		status: PairingStatus{
+			Role:   role,
+			Peer:   strings.TrimSuffix(peer, "/"),
+			Active: role != RoleSecondary,
+		},
+		token:  token,
+		client: &http.Client{Timeout: 2 * time.Second},
+	}
+}
+
+// authorized reports whether request comes from paired instance, by shared token if configured,
+// or by client address resolved from peer URL otherwise.
+func (p *Pairing) authorized(c *gin.Context) bool {
+	if p.token != "" {
+		got := strings.TrimPrefix(c.GetHeader("Authorization"), "Bearer ")
+		return subtle.ConstantTimeCompare([]byte(got), []byte(p.token)) == 1
+	}
+	u, err := url.Parse(p.status.Peer)
+	if p.status.Peer == "" || err != nil {
+		return false
+	}
+	addrs, err := net.LookupHost(u.Hostname())
+	if err != nil {
+		return false
+	}
+	for _, addr := range addrs {
+		if net.ParseIP(addr).Equal(net.ParseIP(c.ClientIP())) {
+			return true
+		}
+	}
+	return false
+}
+
+// Status returns current pairing status.
+func (p *Pairing) Status() PairingStatus {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return p.status
+}
+
+// IsActive returns true if this instance should perform actions. Nil pairing, as in tests, is always active.
+func (p *Pairing) IsActive() bool {
+	if p == nil {
+		return true
+	}
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return p.status.Active
+}
+
+// Run checks peer health every interval. Only secondary changes its active state.
+func (p *Pairing) Run(interval time.Duration) {
+	if p.status.Peer == "" || p.status.Role == RoleStandalone {
+		return
+	}
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	for range t.C {
+		if !BackgroundActive() {
+			continue
+		}
+		p.check()
+	}
+}
+
+func (p *Pairing) check() {
+	healthy := false
+	resp, err := p.client.Get(p.status.Peer + "/api/health")
+	if err == nil {
+		healthy = resp.StatusCode == http.StatusOK
+		resp.Body.Close()
+	}
+
+	p.mu.Lock()
+	changed := p.status.PeerHealthy != healthy
+	p.status.PeerHealthy = healthy
+	p.status.LastPeerCheck = time.Now()
+	if healthy {
+		p.failures = 0
+		p.healthy++
+	} else {
+		p.failures++
+		p.healthy = 0
+	}
+	if p.status.Role == RoleSecondary {
+		switch {
+		case !p.status.Active && p.failures >= pairingFailThreshold:
+			p.status.Active = true
+			p.status.TakeoverAt = time.Now()
+			changed = true
+			log.Printf("Primary %s is not responding, taking over\n", p.status.Peer)
+		case p.status.Active && p.healthy >= pairingRecoverThreshold:
+			p.status.Active = false
+			p.status.TakeoverAt = time.Time{}
+			changed = true
+			log.Printf("Primary %s is back, returning to standby\n", p.status.Peer)
+		}
+	}
+	status := p.status
+	p.mu.Unlock()
+
+	if changed {
+		events.Publish(Event{Topic: "pairing", Data: status})
+	}
+	if healthy && status.Role == RoleSecondary && !status.Active {
+		if err := p.replicateConfig(); err != nil {
+			log.Printf("Failed to replicate settings of primary %s : %v\n", status.Peer, err)
+		}
+	}
+}
+
+// replicateConfig copies settings of primary if they are newer than local settings.
+func (p *Pairing) replicateConfig() error {
+	req, err := http.NewRequest(http.MethodGet, p.status.Peer+"/api/pairing/config", nil)
+	if err != nil {
+		return err
+	}
+	if p.token != "" {
+		req.Header.Set("Authorization", "Bearer "+p.token)
+	}
+	resp, err := p.client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("unexpected status %s", resp.Status)
+	}
+	b, err := ioutil.ReadAll(resp.Body)
+	if err != nil {
+		return err
+	}
+	remote := Config{}
+	if err := json.Unmarshal(b, &remote); err != nil {
+		return err
+	}
+	replaced, err := config.Replicate(remote)
+	if err != nil {
+		return err
+	}
+	if replaced {
+		log.Printf("Settings replicated from primary %s\n", p.status.Peer)
+		applyConfig(config.Get())
+		events.Publish(Event{Topic: "config", Data: config.SyncStatus()})
+	}
+	return nil
+}
+
+// standbyError is returned for actions refused by secondary while primary is active.
+func standbyError(addr string) error {
+	return NewAppError(ErrKindBlocked, addr, fmt.Errorf("secondary instance is standing by while primary %s is active", pairing.Status().Peer))
+}
+
+// HealthHandler responds to health checks from paired instance for [GET] /api/health .
+func HealthHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"status": "ok",
+		"role":   pairing.Status().Role,
+	})
+}
+
+// GetPairingConfigHandler returns settings replicated to secondary for [GET] /api/pairing/config .
+// Settings include secrets, so only paired instance is served, see Pairing.authorized.
+func GetPairingConfigHandler(c *gin.Context) {
+	if !pairing.authorized(c) {
+		c.AbortWithStatus(http.StatusUnauthorized)
+		return
+	}
+	c.JSON(http.StatusOK, config.Get())
+}
+
+// GetPairingHandler returns hot-standby status for [GET] /api/pairing .
+func GetPairingHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"pairing": pairing.Status(),
+	})
+}
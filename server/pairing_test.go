@@ -0,0 +1,152 @@
+package main
+
+import (
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestPairingSecondaryStandsBy(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+
+	dir, err := ioutil.TempDir("", "vmix_utility_pairing")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	local, err := LoadConfigStore(filepath.Join(dir, "vmix_utility.json"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	primaryConfig := Config{
+		UpdatedAt:  time.Now().Add(time.Hour),
+		HostGroups: map[string][]string{"main": {addr}},
+		Clock:      ClockSettings{TitleInput: "Clock", OSCAddress: defaultClockOSCAddress},
+	}
+	defer showClock.Configure(ClockSettings{})
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/health", func(c *gin.Context) { c.JSON(http.StatusOK, gin.H{"status": "ok"}) })
+	r.GET("/api/pairing/config", func(c *gin.Context) { c.JSON(http.StatusOK, primaryConfig) })
+	primary := httptest.NewServer(r)
+	defer primary.Close()
+
+	prevConfig, prevPairing := config, pairing
+	config, pairing = local, NewPairing(RoleSecondary, primary.URL, "")
+	defer func() { config, pairing = prevConfig, prevPairing }()
+
+	pairing.check()
+	if pairing.IsActive() {
+		t.Fatal("expected secondary standing by while primary is healthy")
+	}
+	if got := config.Get().HostGroups["main"]; len(got) != 1 || got[0] != addr {
+		t.Fatalf("expected settings of primary replicated, got %+v", config.Get().HostGroups)
+	}
+	if got := showClock.Settings().TitleInput; got != "Clock" {
+		t.Fatalf("expected show clock reconfigured with replicated settings, got %q", got)
+	}
+	if err := SendFunctionTo(addr, "Cut", nil); err == nil || AsAppError(err).Kind != ErrKindBlocked {
+		t.Fatalf("expected function refused while standing by, got %v", err)
+	}
+	if err := (TriggerAction{Host: addr, FunctionCall: FunctionCall{Function: "Cut"}}).Fire("test"); err == nil {
+		t.Fatal("expected trigger refused while standing by")
+	}
+	if calls := m.Calls(); len(calls) != 0 {
+		t.Fatalf("expected no function sent by standby secondary, got %+v", calls)
+	}
+
+	primary.Close()
+	for i := 0; i < pairingFailThreshold; i++ {
+		pairing.check()
+	}
+	if !pairing.IsActive() {
+		t.Fatal("expected secondary to take over after primary stopped responding")
+	}
+	if err := SendFunctionTo(addr, "Cut", nil); err != nil {
+		t.Fatal(err)
+	}
+	if calls := m.Calls(); len(calls) != 1 {
+		t.Fatalf("expected function sent after takeover, got %+v", calls)
+	}
+}
+
+func TestPairingConfigRequiresPeer(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/pairing/config", GetPairingConfigHandler)
+	get := func(remoteAddr, token string) int {
+		req := httptest.NewRequest(http.MethodGet, "/api/pairing/config", nil)
+		req.RemoteAddr = remoteAddr
+		if token != "" {
+			req.Header.Set("Authorization", "Bearer "+token)
+		}
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, req)
+		return w.Code
+	}
+
+	prev := pairing
+	defer func() { pairing = prev }()
+	pairing = NewPairing(RolePrimary, "http://192.0.2.10:8080", "")
+	if code := get("192.0.2.10:50000", ""); code != http.StatusOK {
+		t.Fatalf("expected peer served, got %d", code)
+	}
+	if code := get("192.0.2.99:50000", ""); code != http.StatusUnauthorized {
+		t.Fatalf("expected other client refused, got %d", code)
+	}
+
+	pairing = NewPairing(RolePrimary, "http://192.0.2.10:8080", "secret")
+	if code := get("192.0.2.10:50000", ""); code != http.StatusUnauthorized {
+		t.Fatalf("expected token required from peer address, got %d", code)
+	}
+	if code := get("192.0.2.99:50000", "secret"); code != http.StatusOK {
+		t.Fatalf("expected token accepted, got %d", code)
+	}
+}
+
+func TestPairingFlappingPrimaryKeepsSecondaryActive(t *testing.T) {
+	var up int32
+	primary := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if atomic.LoadInt32(&up) == 0 {
+			w.WriteHeader(http.StatusServiceUnavailable)
+		}
+	}))
+	defer primary.Close()
+	p := NewPairing(RoleSecondary, primary.URL, "")
+	for i := 0; i < pairingFailThreshold; i++ {
+		p.check()
+	}
+	if !p.IsActive() {
+		t.Fatal("expected secondary to take over")
+	}
+
+	// primary answers every other check.
+	for i := 0; i < 2*pairingRecoverThreshold; i++ {
+		atomic.StoreInt32(&up, int32(i%2))
+		p.check()
+		if !p.IsActive() {
+			t.Fatalf("expected secondary to stay active while primary flaps, check %d", i)
+		}
+	}
+
+	atomic.StoreInt32(&up, 1)
+	for i := 0; i < pairingRecoverThreshold-1; i++ {
+		p.check()
+	}
+	if !p.IsActive() {
+		t.Fatal("expected secondary to stay active until primary is healthy long enough")
+	}
+	p.check()
+	if p.IsActive() {
+		t.Fatal("expected secondary to return to standby once primary is stable")
+	}
+}
@@ -0,0 +1,171 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+
+	"github.com/gin-gonic/gin"
+)
+
+// PiPPreset is named picture-in-picture position. Position is computed from Anchor, or PanX/PanY are used as is if Anchor is empty.
+type PiPPreset struct {
+	Anchor string  `json:"anchor"` // "top_left", "top_right", "bottom_left", "bottom_right", "left", "right" or empty.
+	Zoom   float64 `json:"zoom"`   // 0-5. e.g. 0.25 for quarter width.
+	Margin float64 `json:"margin"` // gap to screen edge for anchored presets, in vMix pan units.
+	PanX   float64 `json:"pan_x"`  // -2 to 2 for presets without anchor.
+	PanY   float64 `json:"pan_y"`  // -2 to 2 for presets without anchor.
+}
+
+// Position returns zoom and pan of preset. vMix pan of ±(1 - zoom) puts input edge on screen edge.
+func (p PiPPreset) Position() (zoom, x, y float64, err error) {
+	zoom = p.Zoom
+	if zoom <= 0 || zoom > 5 {
+		return 0, 0, 0, fmt.Errorf("zoom must be 0-5")
+	}
+	edge := 1 - zoom - p.Margin
+	switch p.Anchor {
+	case "":
+		return zoom, p.PanX, p.PanY, nil
+	case "top_left":
+		return zoom, -edge, edge, nil
+	case "top_right":
+		return zoom, edge, edge, nil
+	case "bottom_left":
+		return zoom, -edge, -edge, nil
+	case "bottom_right":
+		return zoom, edge, -edge, nil
+	case "left":
+		return zoom, -edge, 0, nil
+	case "right":
+		return zoom, edge, 0, nil
+	}
+	return 0, 0, 0, fmt.Errorf("unknown anchor %q", p.Anchor)
+}
+
+// PiPRequest is request body of ApplyPiPHandler.
+type PiPRequest struct {
+	Host   string `json:"host"`   // vMix host. Primary vMix host is used if empty.
+	Input  string `json:"input"`  // input shown as PiP.
+	Preset string `json:"preset"` // PiP preset name.
+	Target string `json:"target"` // input PiP is layered on. Program input is used if empty.
+	Layer  int    `json:"layer"`  // layer of target, 1-10. Default: 1 .
+	Off    bool   `json:"off"`    // turn layer off instead.
+}
+
+// PiPActions returns functions positioning input by preset and turning layer of target on.
+func PiPActions(inputKey, targetKey string, layer int, p PiPPreset) ([]FunctionCall, error) {
+	zoom, x, y, err := p.Position()
+	if err != nil {
+		return nil, err
+	}
+	format := func(v float64) string { return strconv.FormatFloat(v, 'f', -1, 64) }
+	l := strconv.Itoa(layer)
+	return []FunctionCall{
+		{Function: "SetZoom", Params: map[string]string{"Input": inputKey, "Value": format(zoom)}},
+		{Function: "SetPanX", Params: map[string]string{"Input": inputKey, "Value": format(x)}},
+		{Function: "SetPanY", Params: map[string]string{"Input": inputKey, "Value": format(y)}},
+		{Function: "SetMultiViewOverlay", Params: map[string]string{"Input": targetKey, "Value": l + "," + inputKey}},
+		{Function: "MultiViewOverlayOn", Params: map[string]string{"Input": targetKey, "Value": l}},
+	}, nil
+}
+
+// ApplyPiP positions input as PiP on target, or turns PiP layer off.
+func ApplyPiP(req PiPRequest) error {
+	if req.Host == "" {
+		req.Host = *vmixaddr
+	}
+	if req.Layer == 0 {
+		req.Layer = 1
+	}
+	if req.Layer < 1 || req.Layer > 10 {
+		return NewAppError(ErrKindInvalidRequest, req.Host, fmt.Errorf("layer must be 1-10"))
+	}
+	s, err := hostState(req.Host)
+	if err != nil {
+		return err
+	}
+	target := s.FindInput(strconv.Itoa(s.Active))
+	if req.Target != "" {
+		target = s.FindInput(ResolveInputAlias(req.Host, req.Target))
+	}
+	if target == nil {
+		return NewAppError(ErrKindNotFound, req.Host, fmt.Errorf("target input %q not found", req.Target))
+	}
+	var calls []FunctionCall
+	if req.Off {
+		calls = []FunctionCall{{Function: "MultiViewOverlayOff", Params: map[string]string{"Input": target.Key, "Value": strconv.Itoa(req.Layer)}}}
+	} else {
+		in := s.FindInput(ResolveInputAlias(req.Host, req.Input))
+		if in == nil {
+			return NewAppError(ErrKindNotFound, req.Host, fmt.Errorf("input %q not found", req.Input))
+		}
+		p, ok := config.Get().PiPPresets[req.Preset]
+		if !ok {
+			return NewAppError(ErrKindNotFound, req.Host, fmt.Errorf("PiP preset %q not found", req.Preset))
+		}
+		if calls, err = PiPActions(in.Key, target.Key, req.Layer, p); err != nil {
+			return NewAppError(ErrKindInvalidRequest, req.Host, err)
+		}
+	}
+	poller.Touch()
+	for _, call := range calls {
+		if err := SendFunctionTo(req.Host, call.Function, call.Params); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// GetPiPPresetsHandler returns PiP presets for [GET] /api/pip/presets .
+func GetPiPPresetsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"presets": config.Get().PiPPresets,
+	})
+}
+
+// PutPiPPresetsHandler saves PiP presets for [PUT] /api/pip/presets .
+func PutPiPPresetsHandler(c *gin.Context) {
+	presets := map[string]PiPPreset{}
+	if err := c.ShouldBindJSON(&presets); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for name, p := range presets {
+		if _, _, _, err := p.Position(); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("preset %s : %v", name, err)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.PiPPresets = presets
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetPiPPresetsHandler(c)
+}
+
+// ApplyPiPHandler positions input as PiP for [POST] /api/pip .
+func ApplyPiPHandler(c *gin.Context) {
+	req := PiPRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := ApplyPiP(req); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if req.Host == "" {
+		req.Host = *vmixaddr
+	}
+	action := fmt.Sprintf("PiP %s %s", req.Input, req.Preset)
+	if req.Off {
+		action = fmt.Sprintf("PiP layer %d off", req.Layer)
+	}
+	announceAction(c, req.Host, action)
+	c.JSON(http.StatusOK, gin.H{
+		"pip": req,
+	})
+}
@@ -0,0 +1,20 @@
+package main
+
+import "testing"
+
+func TestPiPPresetPosition(t *testing.T) {
+	zoom, x, y, err := PiPPreset{Anchor: "top_right", Zoom: 0.25, Margin: 0.05}.Position()
+	if err != nil || zoom != 0.25 || x != 0.7 || y != 0.7 {
+		t.Fatalf("unexpected position %v %v %v %v", zoom, x, y, err)
+	}
+	if _, x, y, _ = (PiPPreset{Anchor: "left", Zoom: 0.5}).Position(); x != -0.5 || y != 0 {
+		t.Fatalf("unexpected side-by-side position %v %v", x, y)
+	}
+	if _, _, _, err = (PiPPreset{Anchor: "middle", Zoom: 0.5}).Position(); err == nil {
+		t.Fatal("expected unknown anchor error")
+	}
+	calls, err := PiPActions("key-2", "key-1", 2, PiPPreset{Zoom: 0.5, PanX: 0.1, PanY: -0.1})
+	if err != nil || len(calls) != 5 || calls[3].Params["Value"] != "2,key-2" || calls[4].Params["Input"] != "key-1" {
+		t.Fatalf("unexpected calls %+v %v", calls, err)
+	}
+}
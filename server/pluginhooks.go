@@ -0,0 +1,123 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"log"
+	"sort"
+	"time"
+)
+
+// Plugin hooks around every function sent by utility.
+const (
+	PluginHookPreSend  = "pre_send"  // may rewrite, block or chain function before it is sent.
+	PluginHookPostSend = "post_send" // may chain functions after function was sent or failed.
+)
+
+// pluginHookTimeout is how long function waits for each pre_send hook. Every function goes through hooks,
+// so slow or crashed plugin is skipped instead of holding up switching.
+const pluginHookTimeout = time.Second
+
+// PluginHookResult is body of plugin response to "hook" message. Empty result keeps function as is.
+// e.g. {"params":{"Duration":"500"}} forces minimum transition duration of venue.
+type PluginHookResult struct {
+	Function string            `json:"function"` // replaces function. pre_send only.
+	Params   map[string]string `json:"params"`   // replaces parameters. pre_send only.
+	Block    string            `json:"block"`    // reason refusing function. pre_send only.
+	Chain    []TriggerAction   `json:"chain"`    // sent after function succeeded, without hooks. Empty host is host of function.
+}
+
+// HasHook reports whether any running plugin registered hook, so sending skips hooks cheaply when none does.
+func (m *PluginManager) HasHook(hook string) bool {
+	return len(m.Hooked(hook)) > 0
+}
+
+// Hooked returns running plugins registering hook, ordered by name.
+func (m *PluginManager) Hooked(hook string) []*Plugin {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	hooked := []*Plugin{}
+	for _, p := range m.plugins {
+		info := p.Info()
+		if !info.Running {
+			continue
+		}
+		for _, h := range info.Manifest.Hooks {
+			if h == hook {
+				hooked = append(hooked, p)
+				break
+			}
+		}
+	}
+	sort.Slice(hooked, func(i, j int) bool { return hooked[i].Info().Name < hooked[j].Info().Name })
+	return hooked
+}
+
+// callHook sends hook message to plugin and decodes its result.
+func callHook(p *Plugin, hook string, action TriggerAction, sendErr error) (PluginHookResult, error) {
+	msg := PluginMessage{Type: "hook", Name: hook, Action: &action}
+	if sendErr != nil {
+		msg.Error = sendErr.Error()
+	}
+	result := PluginHookResult{}
+	res, err := p.CallTimeout(msg, pluginHookTimeout)
+	if err != nil {
+		return result, err
+	}
+	if len(res.Body) > 0 {
+		if err := json.Unmarshal(res.Body, &result); err != nil {
+			return result, fmt.Errorf("invalid hook result : %w", err)
+		}
+	}
+	return result, nil
+}
+
+// runPreSendHooks passes function through pre_send hooks in plugin name order, each seeing function rewritten by previous one.
+// Failing hook is logged and skipped, so broken plugin never stops production.
+func runPreSendHooks(addr, function string, params map[string]string) (string, map[string]string, []TriggerAction, error) {
+	chain := []TriggerAction{}
+	for _, p := range plugins.Hooked(PluginHookPreSend) {
+		name := p.Info().Name
+		result, err := callHook(p, PluginHookPreSend, TriggerAction{Host: addr, FunctionCall: FunctionCall{Function: function, Params: params}}, nil)
+		if err != nil {
+			log.Printf("Plugin %s pre_send hook failed, sending %s unchanged : %v\n", name, function, err)
+			continue
+		}
+		if result.Block != "" {
+			return function, params, nil, NewAppError(ErrKindBlocked, addr, fmt.Errorf("%s blocked by plugin %s : %s", function, name, result.Block))
+		}
+		if result.Function != "" {
+			function = result.Function
+		}
+		if result.Params != nil {
+			params = result.Params
+		}
+		chain = append(chain, result.Chain...)
+	}
+	return function, params, chain, nil
+}
+
+// runPostSendHooks sends functions chained by pre_send hooks if function succeeded, then runs post_send hooks
+// and sends functions they chain.
+func runPostSendHooks(addr, function string, params map[string]string, sendErr error, chain []TriggerAction) {
+	if sendErr != nil {
+		chain = nil
+	}
+	for _, p := range plugins.Hooked(PluginHookPostSend) {
+		result, err := callHook(p, PluginHookPostSend, TriggerAction{Host: addr, FunctionCall: FunctionCall{Function: function, Params: params}}, sendErr)
+		if err != nil {
+			log.Printf("Plugin %s post_send hook failed : %v\n", p.Info().Name, err)
+			continue
+		}
+		chain = append(chain, result.Chain...)
+	}
+	for _, action := range chain {
+		host := action.Host
+		if host == "" {
+			host = addr
+		}
+		if err := sendFunction(host, action.Function, action.Params, false, false); err != nil {
+			log.Printf("Failed to send %s chained by plugin hook after %s : %v\n", action.Function, function, err)
+		}
+	}
+}
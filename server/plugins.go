@@ -0,0 +1,482 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io"
+	"io/ioutil"
+	"log"
+	"net/http"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// pluginManifestFile is manifest file name in each plugin directory.
+const pluginManifestFile = "plugin.json"
+
+// pluginCallTimeout is how long command or HTTP request waits for plugin response.
+const pluginCallTimeout = 5 * time.Second
+
+// PluginManifest describes plugin, read from plugin.json in its directory under plugins directory.
+type PluginManifest struct {
+	Name     string            `json:"name"`     // display name. Plugin is addressed by directory name.
+	Command  string            `json:"command"`  // executable, relative to plugin directory. e.g. "./plugin.exe" .
+	Args     []string          `json:"args"`     // e.g. ["main.py"] .
+	Env      map[string]string `json:"env"`      // environment added to utility's.
+	Topics   []string          `json:"topics"`   // event topics forwarded to plugin. e.g. ["acts", "status"] .
+	Commands []string          `json:"commands"` // commands served at /api/plugins/<name>/commands/<command> .
+	Routes   bool              `json:"routes"`   // serve HTTP requests under /api/plugins/<name>/http/ .
+	Hooks    []string          `json:"hooks"`    // PluginHookPreSend and PluginHookPostSend.
+}
+
+// PluginMessage is single line of JSON exchanged with plugin over stdin and stdout.
+//
+// Utility sends "event" with Event, "command" with Name and Args, and "request" with Method, Path, Query and Body.
+// Utility sends "hook" with Name, Action and Error of send, see PluginHookResult.
+// Plugin answers commands, requests and hooks with "response" of same ID, with Status, Body or Error.
+// Plugin may send "function" with Action to send vMix function, and "log" with Message.
+type PluginMessage struct {
+	Type    string          `json:"type"`
+	ID      uint64          `json:"id,omitempty"`
+	Event   *Event          `json:"event,omitempty"`
+	Name    string          `json:"name,omitempty"`
+	Args    json.RawMessage `json:"args,omitempty"`
+	Method  string          `json:"method,omitempty"`
+	Path    string          `json:"path,omitempty"`
+	Query   string          `json:"query,omitempty"`
+	Body    json.RawMessage `json:"body,omitempty"` // request body is JSON string when not JSON.
+	Status  int             `json:"status,omitempty"`
+	Error   string          `json:"error,omitempty"`
+	Action  *TriggerAction  `json:"action,omitempty"`
+	Message string          `json:"message,omitempty"`
+}
+
+// PluginInfo is state of plugin.
+type PluginInfo struct {
+	Name      string         `json:"name"`
+	Dir       string         `json:"dir"`
+	Manifest  PluginManifest `json:"manifest"`
+	Running   bool           `json:"running"`
+	StartedAt time.Time      `json:"started_at"`
+	Error     string         `json:"error,omitempty"` // why plugin stopped or failed to start.
+}
+
+// Plugin is plugin subprocess.
+type Plugin struct {
+	mu       sync.Mutex
+	wmu      sync.Mutex // serializes writes, held without mu so plugin not reading stdin blocks only writers.
+	info     PluginInfo
+	cmd      *exec.Cmd
+	stdin    io.WriteCloser
+	pending  map[uint64]chan PluginMessage // request ID -> response.
+	nextID   uint64
+	stopSubs func()
+}
+
+// PluginManager runs plugins found in plugins directory. Plugins are subprocesses speaking JSON lines,
+// so they can be written in any language and crash without taking utility down. Plugin should exit when its stdin is closed,
+// which happens when utility exits.
+type PluginManager struct {
+	mu      sync.Mutex
+	dir     string
+	plugins map[string]*Plugin // name -> plugin.
+}
+
+// NewPluginManager creates PluginManager.
+func NewPluginManager() *PluginManager {
+	return &PluginManager{plugins: make(map[string]*Plugin)}
+}
+
+// Load reads manifests under dir and starts plugins. Missing dir is not error, as plugins are optional.
+func (m *PluginManager) Load(dir string) error {
+	m.mu.Lock()
+	m.dir = dir
+	m.mu.Unlock()
+	entries, err := ioutil.ReadDir(dir)
+	if os.IsNotExist(err) {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+	for _, e := range entries {
+		if !e.IsDir() {
+			continue
+		}
+		if _, err := m.Start(e.Name()); err != nil {
+			log.Printf("Failed to start plugin %s : %v\n", e.Name(), err)
+		}
+	}
+	return nil
+}
+
+// readPluginManifest reads manifest of plugin directory.
+func readPluginManifest(dir string) (PluginManifest, error) {
+	manifest := PluginManifest{}
+	b, err := ioutil.ReadFile(filepath.Join(dir, pluginManifestFile))
+	if err != nil {
+		return manifest, err
+	}
+	if err := json.Unmarshal(b, &manifest); err != nil {
+		return manifest, fmt.Errorf("invalid %s : %w", pluginManifestFile, err)
+	}
+	if manifest.Name == "" {
+		manifest.Name = filepath.Base(dir)
+	}
+	if manifest.Command == "" {
+		return manifest, fmt.Errorf("command is required")
+	}
+	return manifest, nil
+}
+
+// Start starts plugin in directory name under plugins directory, stopping running one first.
+func (m *PluginManager) Start(name string) (PluginInfo, error) {
+	if name == "" || name == "." || name == ".." || strings.ContainsAny(name, `/\`) {
+		return PluginInfo{}, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid plugin name %q", name))
+	}
+	m.mu.Lock()
+	dir := filepath.Join(m.dir, name)
+	prev := m.plugins[name]
+	m.mu.Unlock()
+	if prev != nil {
+		prev.Stop()
+	}
+	manifest, err := readPluginManifest(dir)
+	if err != nil {
+		return PluginInfo{}, NewAppError(ErrKindInvalidRequest, "", err)
+	}
+	p := &Plugin{info: PluginInfo{Name: name, Dir: dir, Manifest: manifest}, pending: make(map[uint64]chan PluginMessage)}
+	m.mu.Lock()
+	m.plugins[name] = p
+	m.mu.Unlock()
+	if err := p.start(); err != nil {
+		p.mu.Lock()
+		p.info.Error = err.Error()
+		p.mu.Unlock()
+		return p.Info(), NewAppError(ErrKindInternal, "", err)
+	}
+	log.Printf("Started plugin %s\n", name)
+	return p.Info(), nil
+}
+
+// Get returns plugin by name.
+func (m *PluginManager) Get(name string) (*Plugin, bool) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	p, ok := m.plugins[name]
+	return p, ok
+}
+
+// List returns plugins ordered by name.
+func (m *PluginManager) List() []PluginInfo {
+	m.mu.Lock()
+	list := make([]PluginInfo, 0, len(m.plugins))
+	for _, p := range m.plugins {
+		list = append(list, p.Info())
+	}
+	m.mu.Unlock()
+	sort.Slice(list, func(i, j int) bool { return list[i].Name < list[j].Name })
+	return list
+}
+
+// Info returns state of plugin.
+func (p *Plugin) Info() PluginInfo {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return p.info
+}
+
+func (p *Plugin) start() error {
+	manifest := p.info.Manifest
+	command := manifest.Command
+	if !filepath.IsAbs(command) && filepath.Base(command) != command {
+		command = filepath.Join(p.info.Dir, command)
+	}
+	cmd := exec.Command(command, manifest.Args...)
+	cmd.Dir = p.info.Dir
+	cmd.Env = os.Environ()
+	for k, v := range manifest.Env {
+		cmd.Env = append(cmd.Env, k+"="+v)
+	}
+	stdin, err := cmd.StdinPipe()
+	if err != nil {
+		return err
+	}
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return err
+	}
+	stderr, err := cmd.StderrPipe()
+	if err != nil {
+		return err
+	}
+	if err := cmd.Start(); err != nil {
+		return err
+	}
+	p.mu.Lock()
+	p.cmd, p.stdin = cmd, stdin
+	p.info.Running, p.info.StartedAt, p.info.Error = true, time.Now(), ""
+	p.mu.Unlock()
+
+	go p.logStderr(stderr)
+	go p.read(stdout)
+	if len(manifest.Topics) > 0 {
+		topics := make(map[string]bool, len(manifest.Topics))
+		for _, topic := range manifest.Topics {
+			topics[topic] = true
+		}
+		ch, stop := events.SubscribeTo(256, "plugin "+p.info.Name, func(topic string) bool { return topics[topic] })
+		p.mu.Lock()
+		p.stopSubs = stop
+		p.mu.Unlock()
+		go func() {
+			for ev := range ch {
+				ev := ev
+				if err := p.send(PluginMessage{Type: "event", Event: &ev}); err != nil {
+					return
+				}
+			}
+		}()
+	}
+	go p.wait()
+	return nil
+}
+
+// wait marks plugin stopped once process exits, failing calls waiting for response.
+func (p *Plugin) wait() {
+	err := p.cmd.Wait()
+	p.mu.Lock()
+	p.info.Running = false
+	if err != nil && p.info.Error == "" {
+		p.info.Error = err.Error()
+	}
+	for id, ch := range p.pending {
+		ch <- PluginMessage{Type: "response", ID: id, Error: "plugin stopped"}
+		delete(p.pending, id)
+	}
+	stop := p.stopSubs
+	p.stopSubs = nil
+	p.mu.Unlock()
+	if stop != nil {
+		stop()
+	}
+	log.Printf("Plugin %s stopped : %v\n", p.info.Name, err)
+}
+
+// Stop kills plugin process.
+func (p *Plugin) Stop() {
+	p.mu.Lock()
+	cmd, running := p.cmd, p.info.Running
+	if running {
+		p.info.Error = "stopped"
+	}
+	p.mu.Unlock()
+	if running && cmd.Process != nil {
+		cmd.Process.Kill()
+	}
+}
+
+func (p *Plugin) logStderr(r io.Reader) {
+	sc := bufio.NewScanner(r)
+	for sc.Scan() {
+		log.Printf("Plugin %s : %s\n", p.info.Name, sc.Text())
+	}
+}
+
+// read handles messages from plugin until its stdout is closed.
+func (p *Plugin) read(r io.Reader) {
+	sc := bufio.NewScanner(r)
+	sc.Buffer(make([]byte, 64*1024), 4*1024*1024)
+	for sc.Scan() {
+		msg := PluginMessage{}
+		if err := json.Unmarshal(sc.Bytes(), &msg); err != nil {
+			log.Printf("Plugin %s sent invalid message : %v\n", p.info.Name, err)
+			continue
+		}
+		switch msg.Type {
+		case "response":
+			p.mu.Lock()
+			ch, ok := p.pending[msg.ID]
+			delete(p.pending, msg.ID)
+			p.mu.Unlock()
+			if ok {
+				ch <- msg
+			}
+		case "function":
+			if msg.Action == nil {
+				continue
+			}
+			go func(id uint64, action TriggerAction) {
+				res := PluginMessage{Type: "response", ID: id}
+				if err := action.Fire("plugin " + p.info.Name); err != nil {
+					res.Error = err.Error()
+				}
+				if id != 0 {
+					p.send(res)
+				}
+			}(msg.ID, *msg.Action)
+		case "log":
+			log.Printf("Plugin %s : %s\n", p.info.Name, msg.Message)
+		}
+	}
+}
+
+// send writes message to plugin stdin.
+func (p *Plugin) send(msg PluginMessage) error {
+	b, err := json.Marshal(msg)
+	if err != nil {
+		return err
+	}
+	p.mu.Lock()
+	stdin, running := p.stdin, p.info.Running
+	p.mu.Unlock()
+	if !running {
+		return fmt.Errorf("plugin %s is not running", p.info.Name)
+	}
+	p.wmu.Lock()
+	defer p.wmu.Unlock()
+	_, err = stdin.Write(append(b, '\n'))
+	return err
+}
+
+// Call sends command or request to plugin and waits for response.
+func (p *Plugin) Call(msg PluginMessage) (PluginMessage, error) {
+	return p.CallTimeout(msg, pluginCallTimeout)
+}
+
+// CallTimeout sends message to plugin and waits for response up to timeout.
+func (p *Plugin) CallTimeout(msg PluginMessage, timeout time.Duration) (PluginMessage, error) {
+	ch := make(chan PluginMessage, 1)
+	p.mu.Lock()
+	p.nextID++
+	msg.ID = p.nextID
+	p.pending[msg.ID] = ch
+	p.mu.Unlock()
+	if err := p.send(msg); err != nil {
+		p.mu.Lock()
+		delete(p.pending, msg.ID)
+		p.mu.Unlock()
+		return PluginMessage{}, NewAppError(ErrKindUnsupported, "", err)
+	}
+	select {
+	case res := <-ch:
+		if res.Error != "" {
+			return res, NewAppError(ErrKindInternal, "", fmt.Errorf("plugin %s : %s", p.info.Name, res.Error))
+		}
+		return res, nil
+	case <-time.After(timeout):
+		p.mu.Lock()
+		delete(p.pending, msg.ID)
+		p.mu.Unlock()
+		return PluginMessage{}, NewAppError(ErrKindInternal, "", fmt.Errorf("plugin %s did not respond", p.info.Name))
+	}
+}
+
+// pluginOf returns plugin of :name parameter, aborting request if not found.
+func pluginOf(c *gin.Context) (*Plugin, bool) {
+	p, ok := plugins.Get(c.Param("name"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("plugin %q not found", c.Param("name"))))
+	}
+	return p, ok
+}
+
+// writePluginResponse writes plugin response body as JSON.
+func writePluginResponse(c *gin.Context, res PluginMessage) {
+	status := res.Status
+	if status == 0 {
+		status = http.StatusOK
+	}
+	// net/http panics on status codes outside 100-999.
+	if status < 100 || status > 999 {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", fmt.Errorf("plugin responded with invalid status %d", res.Status)))
+		return
+	}
+	body := res.Body
+	if len(body) == 0 {
+		body = json.RawMessage("null")
+	}
+	c.Data(status, "application/json; charset=utf-8", body)
+}
+
+// GetPluginsHandler returns plugins for [GET] /api/plugins .
+func GetPluginsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"plugins": plugins.List(),
+	})
+}
+
+// ReloadPluginHandler restarts plugin with its manifest read again for [POST] /api/plugins/:name/reload .
+// Plugin added to plugins directory after start is started too.
+func ReloadPluginHandler(c *gin.Context) {
+	info, err := plugins.Start(c.Param("name"))
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"plugin": info,
+	})
+}
+
+// RunPluginCommandHandler runs plugin command with request body as args for [POST] /api/plugins/:name/commands/:command .
+func RunPluginCommandHandler(c *gin.Context) {
+	p, ok := pluginOf(c)
+	if !ok {
+		return
+	}
+	known := false
+	for _, command := range p.Info().Manifest.Commands {
+		known = known || command == c.Param("command")
+	}
+	if !known {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("plugin %s has no command %q", p.Info().Name, c.Param("command"))))
+		return
+	}
+	args := json.RawMessage{}
+	if err := c.ShouldBindJSON(&args); err != nil && err != io.EOF {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	res, err := p.Call(PluginMessage{Type: "command", Name: c.Param("command"), Args: args})
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	writePluginResponse(c, res)
+}
+
+// PluginHTTPHandler forwards request to plugin serving routes for [ANY] /api/plugins/:name/http/*path .
+func PluginHTTPHandler(c *gin.Context) {
+	p, ok := pluginOf(c)
+	if !ok {
+		return
+	}
+	if !p.Info().Manifest.Routes {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("plugin %s serves no routes", p.Info().Name)))
+		return
+	}
+	b, err := ioutil.ReadAll(c.Request.Body)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	body := json.RawMessage(b)
+	if len(b) > 0 && !json.Valid(b) {
+		body, _ = json.Marshal(string(b))
+	}
+	res, err := p.Call(PluginMessage{Type: "request", Method: c.Request.Method, Path: c.Param("path"), Query: c.Request.URL.RawQuery, Body: body})
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	writePluginResponse(c, res)
+}
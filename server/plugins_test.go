@@ -0,0 +1,148 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strconv"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// TestPluginHelperProcess is plugin started by TestPluginManager, running test binary itself as subprocess.
+func TestPluginHelperProcess(t *testing.T) {
+	if os.Getenv("VMIX_UTILITY_TEST_PLUGIN") != "1" {
+		return
+	}
+	enc := json.NewEncoder(os.Stdout)
+	sc := bufio.NewScanner(os.Stdin)
+	for sc.Scan() {
+		msg := PluginMessage{}
+		if err := json.Unmarshal(sc.Bytes(), &msg); err != nil {
+			continue
+		}
+		switch msg.Type {
+		case "command":
+			enc.Encode(PluginMessage{Type: "response", ID: msg.ID, Body: msg.Args})
+		case "request":
+			body, _ := json.Marshal(map[string]string{"method": msg.Method, "path": msg.Path})
+			enc.Encode(PluginMessage{Type: "response", ID: msg.ID, Status: 201, Body: body})
+		case "hook":
+			result := PluginHookResult{}
+			duration, _ := strconv.Atoi(msg.Action.Params["Duration"])
+			switch {
+			case msg.Action.Function == "Fade" && duration < 500:
+				result.Params = map[string]string{"Duration": "500"}
+			case msg.Action.Function == "Forbidden":
+				result.Block = "not at this venue"
+			}
+			body, _ := json.Marshal(result)
+			enc.Encode(PluginMessage{Type: "response", ID: msg.ID, Body: body})
+		case "event":
+			host, _ := msg.Event.Data.(string)
+			enc.Encode(PluginMessage{Type: "function", Action: &TriggerAction{Host: host, FunctionCall: FunctionCall{Function: "Cut"}}})
+		}
+	}
+	os.Exit(0)
+}
+
+// startTestPlugin starts TestPluginHelperProcess as plugin name with manifest.
+func startTestPlugin(t *testing.T, name string, manifest PluginManifest) (*PluginManager, *Plugin) {
+	t.Helper()
+	dir, err := ioutil.TempDir("", "vmix_utility_plugins")
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { os.RemoveAll(dir) })
+	manifest.Command = os.Args[0]
+	manifest.Args = []string{"-test.run=TestPluginHelperProcess"}
+	manifest.Env = map[string]string{"VMIX_UTILITY_TEST_PLUGIN": "1"}
+	b, _ := json.Marshal(manifest)
+	os.Mkdir(filepath.Join(dir, name), 0755)
+	if err := ioutil.WriteFile(filepath.Join(dir, name, pluginManifestFile), b, 0644); err != nil {
+		t.Fatal(err)
+	}
+	pm := NewPluginManager()
+	if err := pm.Load(dir); err != nil {
+		t.Fatal(err)
+	}
+	p, ok := pm.Get(name)
+	if !ok || !p.Info().Running {
+		t.Fatalf("expected plugin running, got %+v", pm.List())
+	}
+	t.Cleanup(p.Stop)
+	return pm, p
+}
+
+func TestPluginManager(t *testing.T) {
+	m := startMock(t)
+	_, p := startTestPlugin(t, "echo", PluginManifest{Topics: []string{"plugin_test"}, Commands: []string{"echo"}, Routes: true})
+
+	res, err := p.Call(PluginMessage{Type: "command", Name: "echo", Args: json.RawMessage(`{"a":1}`)})
+	if err != nil || string(res.Body) != `{"a":1}` {
+		t.Fatalf("expected command echoed, got %s %v", res.Body, err)
+	}
+	res, err = p.Call(PluginMessage{Type: "request", Method: "GET", Path: "/hello"})
+	if err != nil || res.Status != 201 || string(res.Body) != `{"method":"GET","path":"/hello"}` {
+		t.Fatalf("expected request served, got %d %s %v", res.Status, res.Body, err)
+	}
+
+	events.Publish(Event{Topic: "plugin_test", Data: "http://" + m.HTTPAddr})
+	deadline := time.Now().Add(3 * time.Second)
+	for time.Now().Before(deadline) {
+		for _, call := range m.Calls() {
+			if call.Function == "Cut" {
+				return
+			}
+		}
+		time.Sleep(20 * time.Millisecond)
+	}
+	t.Fatalf("expected plugin to send Cut on event, got %+v", m.Calls())
+}
+
+func TestPluginHooks(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	pm, _ := startTestPlugin(t, "venue", PluginManifest{Hooks: []string{PluginHookPreSend}})
+	prev := plugins
+	plugins = pm
+	defer func() { plugins = prev }()
+
+	if err := SendFunctionTo(addr, "Fade", map[string]string{"Duration": "100"}); err != nil {
+		t.Fatal(err)
+	}
+	calls := m.Calls()
+	if len(calls) != 1 || calls[0].Function != "Fade" || calls[0].Params["Duration"] != "500" {
+		t.Fatalf("expected hook to force minimum duration, got %+v", calls)
+	}
+	if err := SendFunctionTo(addr, "Forbidden", nil); err == nil || AsAppError(err).Kind != ErrKindBlocked {
+		t.Fatalf("expected function blocked by hook, got %v", err)
+	}
+	if len(m.Calls()) != 1 {
+		t.Fatalf("expected blocked function not sent, got %+v", m.Calls())
+	}
+}
+
+func TestPluginUntrustedInput(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	for _, status := range []int{-1, 42, 1000} {
+		w := httptest.NewRecorder()
+		c, _ := gin.CreateTestContext(w)
+		writePluginResponse(c, PluginMessage{Status: status})
+		if w.Code != http.StatusInternalServerError {
+			t.Fatalf("expected invalid status %d refused, got %d", status, w.Code)
+		}
+	}
+	m := NewPluginManager()
+	for _, name := range []string{"..", "../other", `..\other`, "a/b"} {
+		if _, err := m.Start(name); AsAppError(err).Kind != ErrKindInvalidRequest {
+			t.Fatalf("expected plugin name %q refused, got %v", name, err)
+		}
+	}
+}
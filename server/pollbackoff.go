@@ -0,0 +1,82 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"log"
+	"net"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Poll backoff of host answering with errors or slowly, which vMix does when its machine is overloaded.
+// Polls are delayed pollBackoffBase, doubling while it continues up to maxPollBackoff,
+// rather than piling requests onto already struggling machine.
+const (
+	slowPollThreshold = 2 * time.Second
+	pollBackoffBase   = 2 * time.Second
+	maxPollBackoff    = 16 * time.Second
+)
+
+// PollBackoff is temporary delay of polling host, published as "poll_backoff" event.
+type PollBackoff struct {
+	Level  int       `json:"level"`  // consecutive struggling polls.
+	Reason string    `json:"reason"` // e.g. "vMix responded 503 Service Unavailable" .
+	Until  time.Time `json:"until"`  // host is not polled before this.
+}
+
+// VMixStatusError is non-OK HTTP status returned by vMix.
+type VMixStatusError struct {
+	Code   int
+	Status string
+}
+
+func (e *VMixStatusError) Error() string {
+	return fmt.Sprintf("unexpected status %s", e.Status)
+}
+
+// pollBusyReason returns why poll shows host struggling, or empty. Refused connection is not struggling,
+// since vMix is simply not running, and watchdog handles it.
+func pollBusyReason(err error, latency time.Duration) string {
+	statusErr := &VMixStatusError{}
+	var netErr net.Error
+	switch {
+	case errors.As(err, &statusErr):
+		return "vMix responded " + statusErr.Status
+	case errors.As(err, &netErr) && netErr.Timeout():
+		return "vMix did not respond in time"
+	case err == nil && latency > slowPollThreshold:
+		return fmt.Sprintf("vMix responded in %.1fs", latency.Seconds())
+	}
+	return ""
+}
+
+// nextPollBackoff returns backoff after poll at now. nil when host is healthy.
+func nextPollBackoff(prev *PollBackoff, reason string, now time.Time) *PollBackoff {
+	if reason == "" {
+		return nil
+	}
+	level := 1
+	if prev != nil {
+		level = prev.Level + 1
+	}
+	delay := maxPollBackoff
+	if shift := level - 1; shift < 8 && pollBackoffBase<<shift < maxPollBackoff {
+		delay = pollBackoffBase << shift
+	}
+	return &PollBackoff{Level: level, Reason: reason, Until: now.Add(delay)}
+}
+
+// publishPollBackoff publishes "poll_backoff" event when backoff of host starts, grows or ends.
+func publishPollBackoff(status *HostStatus, prev *PollBackoff) {
+	if prev == nil && status.Backoff == nil {
+		return
+	}
+	if status.Backoff == nil {
+		log.Printf("Polling %s (%s) recovered from backoff\n", status.Name, status.Addr)
+	} else {
+		log.Printf("Polling %s (%s) backed off until %s : %s\n", status.Name, status.Addr, status.Backoff.Until.Format("15:04:05"), status.Backoff.Reason)
+	}
+	events.Publish(Event{Topic: "poll_backoff", Host: status.Name, Data: gin.H{"addr": status.Addr, "backoff": status.Backoff}})
+}
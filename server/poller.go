@@ -0,0 +1,511 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Connection is additional vMix host polled by utility.
+type Connection struct {
+	Name     string `json:"name"`
+	Addr     string `json:"addr"`     // vMix HTTP API URL. e.g. "http://192.168.0.20:8088" .
+	Priority string `json:"priority"` // ConnectionPrimary or ConnectionSecondary. Empty is primary.
+}
+
+// Connection priorities. Primary hosts, such as on-air machine, are polled every interval.
+// Secondary hosts, such as utility machines, are polled slower and only with polling budget left.
+const (
+	ConnectionPrimary   = "primary"
+	ConnectionSecondary = "secondary"
+)
+
+// secondaryPollFactor is interval multiplier of secondary connections.
+const secondaryPollFactor = 3
+
+// ConnectionState is connection state of vMix host, serialized as enum for clients.
+type ConnectionState string
+
+// Connection states. Host is reconnecting while offline shorter than watchdog alert threshold after it was seen.
+const (
+	ConnectionConnected    ConnectionState = "connected"
+	ConnectionReconnecting ConnectionState = "reconnecting"
+	ConnectionDisconnected ConnectionState = "disconnected"
+)
+
+// connectionStateOf returns connection state of status polled at now.
+func connectionStateOf(status *HostStatus, now time.Time) ConnectionState {
+	if status.Online {
+		return ConnectionConnected
+	}
+	_, _, alert := config.Get().Watchdog.thresholds()
+	if !status.LastSeen.IsZero() && now.Sub(status.LastSeen) < alert {
+		return ConnectionReconnecting
+	}
+	return ConnectionDisconnected
+}
+
+// HostStatus is latest polling result of vMix host.
+type HostStatus struct {
+	Name        string          `json:"name"`
+	Addr        string          `json:"addr"`
+	Online      bool            `json:"online"`
+	Connection  ConnectionState `json:"connection"`
+	LastPoll    time.Time       `json:"last_poll"`
+	LastSeen    time.Time       `json:"last_seen"`    // last successful poll.
+	Since       time.Time       `json:"online_since"` // start of current online period. Zero while offline.
+	LatencyMs   float64         `json:"latency_ms"`
+	Error       *AppError       `json:"error,omitempty"`
+	Version     string          `json:"version"`
+	Edition     string          `json:"edition"`
+	Outputs     OutputStatus    `json:"outputs"`
+	LastOutputs OutputStatus    `json:"last_outputs"`      // outputs as of LastSeen, kept while offline.
+	Backoff     *PollBackoff    `json:"backoff,omitempty"` // polling delayed while host struggles.
+	State       *State          `json:"-"`
+}
+
+// Poller polls every vMix host in its own loop, so slow or unreachable host delays only its own status.
+// Interval of each host adapts to activity: it tightens to fast interval while any user interface is focused
+// or shortly after activity is reported by Touch, is reset to minimum when state of host changes,
+// and grows up to maximum while nothing happens.
+type Poller struct {
+	mu       sync.RWMutex
+	statuses map[string]*HostStatus // addr -> status.
+	loops    map[string]*hostLoop   // addr -> polling loop.
+	focus    map[string]bool        // user interfaces currently focused, such as WebSocket clients.
+
+	fast, min, max time.Duration
+	budget         float64   // maximum polls per second across connections. 0 is unlimited.
+	tokens         float64   // polls left in budget.
+	refilled       time.Time // last refill of tokens.
+}
+
+// hostLoop is polling loop of single vMix host.
+type hostLoop struct {
+	conn        Connection
+	interval    time.Duration
+	touched     time.Time // last touch accepted, to debounce touches.
+	activeUntil time.Time // host is polled at fast interval until this.
+	touch       chan struct{}
+	stop        chan struct{}
+}
+
+// pollBackoff is interval multiplier applied while idle.
+const pollBackoff = 1.5
+
+// pollActivityWindow is how long host is polled at fast interval after activity, such as cut sent by operator.
+const pollActivityWindow = 3 * time.Second
+
+// NewPoller creates Poller.
+func NewPoller() *Poller {
+	return &Poller{
+		statuses: make(map[string]*HostStatus),
+		loops:    make(map[string]*hostLoop),
+		focus:    make(map[string]bool),
+		fast:     250 * time.Millisecond,
+		min:      time.Second,
+		max:      5 * time.Second,
+	}
+}
+
+// Touch reports activity on every host, such as sent functions, so they are polled at fast interval for a while.
+func (p *Poller) Touch() {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	now := time.Now()
+	for _, l := range p.loops {
+		p.touchLoop(l, now)
+	}
+}
+
+// TouchHost reports activity on host, such as ACTS it emitted or function sent to it.
+// Touches are debounced, so bursts such as transition or audio ACTS storms poll host at most once per fast interval.
+func (p *Poller) TouchHost(addr string) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if l, ok := p.loops[addr]; ok {
+		p.touchLoop(l, time.Now())
+	}
+}
+
+// touchLoop extends activity window of l and wakes it, unless it was touched within fast interval. Caller must hold mu.
+func (p *Poller) touchLoop(l *hostLoop, now time.Time) {
+	l.activeUntil = now.Add(pollActivityWindow)
+	if now.Sub(l.touched) < p.fast {
+		return
+	}
+	l.touched = now
+	select {
+	case l.touch <- struct{}{}:
+	default:
+	}
+}
+
+// SetFocus reports whether user interface of client is focused. Hosts are polled at fast interval while any is focused.
+func (p *Poller) SetFocus(client string, focused bool) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if !focused {
+		delete(p.focus, client)
+		return
+	}
+	p.focus[client] = true
+	// wake idle loops, so focused interface does not wait for interval grown while idle.
+	for _, l := range p.loops {
+		select {
+		case l.touch <- struct{}{}:
+		default:
+		}
+	}
+}
+
+// Focused reports whether any user interface is focused.
+func (p *Poller) Focused() bool {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	return len(p.focus) > 0
+}
+
+// SetBudget sets maximum polls per second across connections. Primary connections are always polled. 0 is unlimited.
+func (p *Poller) SetBudget(perSecond float64) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.budget = perSecond
+	p.tokens = perSecond
+}
+
+// Budget returns maximum polls per second. 0 is unlimited.
+func (p *Poller) Budget() float64 {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	return p.budget
+}
+
+// Interval returns shortest current polling interval among hosts.
+func (p *Poller) Interval() time.Duration {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	interval := time.Duration(0)
+	for _, l := range p.loops {
+		if interval == 0 || l.interval < interval {
+			interval = l.interval
+		}
+	}
+	return interval
+}
+
+// stateFingerprint summarizes state to detect activity cheaply.
+func stateFingerprint(s *State) string {
+	if s == nil {
+		return ""
+	}
+	return fmt.Sprintf("%d/%d/%d/%s/%v/%v/%v", s.Active, s.Preview, len(s.Inputs), s.Tally(), s.Recording, s.Streaming, s.FadeToBlack)
+}
+
+// Connections returns hosts to poll. Primary vMix host is always first, and offline connections are last.
+func Connections() []Connection {
+	conns := []Connection{{Name: "primary", Addr: *vmixaddr, Priority: ConnectionPrimary}}
+	for _, conn := range config.Get().Connections {
+		if conn.Addr != *vmixaddr {
+			conns = append(conns, conn)
+		}
+	}
+	return append(conns, offline.Connections()...)
+}
+
+// due reports whether host should be polled now at interval. Primary connections are always polled, unless backed off.
+// Secondary connections are polled once secondaryPollFactor intervals passed, within budget shared by every host.
+func (p *Poller) due(conn Connection, interval time.Duration, now time.Time) bool {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	s, ok := p.statuses[conn.Addr]
+	if ok && s.Backoff != nil && now.Before(s.Backoff.Until) {
+		return false
+	}
+	if conn.Priority == ConnectionSecondary && ok && now.Sub(s.LastPoll) < interval*secondaryPollFactor {
+		return false
+	}
+	if p.budget <= 0 {
+		return true
+	}
+	// tokens refill at budget per second, up to one second worth of polls.
+	if !p.refilled.IsZero() {
+		p.tokens += now.Sub(p.refilled).Seconds() * p.budget
+		if p.tokens > p.budget {
+			p.tokens = p.budget
+		}
+	}
+	p.refilled = now
+	if conn.Priority == ConnectionSecondary && p.tokens < 1 {
+		return false
+	}
+	// primary connections spend budget too, even beyond it, so secondaries get only what is left.
+	if p.tokens -= 1; p.tokens < -p.budget {
+		p.tokens = -p.budget
+	}
+	return true
+}
+
+// poll polls host once and reports whether its state changed.
+func (p *Poller) poll(conn Connection) bool {
+	start := time.Now()
+	s, err := StreamState(conn.Addr)
+	status := &HostStatus{
+		Name:      conn.Name,
+		Addr:      conn.Addr,
+		Online:    err == nil,
+		LastPoll:  start,
+		LatencyMs: durationMs(time.Since(start)),
+		State:     s,
+	}
+	if err != nil {
+		status.Error = AsAppError(err)
+	} else {
+		status.Version = s.Version
+		status.Edition = s.Edition
+		status.Outputs = OutputsOf(s)
+		status.LastOutputs = status.Outputs
+		if conn.Addr == *vmixaddr {
+			inputCache.Update(s)
+		}
+	}
+
+	p.mu.Lock()
+	prev, ok := p.statuses[conn.Addr]
+	switch {
+	case status.Online && ok && prev.Online:
+		status.LastSeen = start
+		status.Since = prev.Since
+	case status.Online:
+		status.LastSeen = start
+		status.Since = start
+	case ok:
+		status.LastSeen = prev.LastSeen
+		status.LastOutputs = prev.LastOutputs
+	}
+	status.Connection = connectionStateOf(status, start)
+	var prevBackoff *PollBackoff
+	if ok {
+		prevBackoff = prev.Backoff
+	}
+	status.Backoff = nextPollBackoff(prevBackoff, pollBusyReason(err, time.Since(start)), time.Now())
+	p.statuses[conn.Addr] = status
+	changed := !ok || stateFingerprint(prev.State) != stateFingerprint(status.State)
+	p.mu.Unlock()
+	if ok {
+		showStats.Observe(prev, status, start)
+	} else {
+		showStats.Observe(nil, status, start)
+	}
+	publishPollBackoff(status, prevBackoff)
+	if !ok || prev.Connection != status.Connection {
+		events.Publish(Event{Topic: "status", Host: status.Name, Data: status})
+	}
+	if status.Online && ok && prev.State != nil && prev.State.Dynamic != status.State.Dynamic {
+		events.Publish(Event{Topic: "dynamic", Host: status.Name, Data: status.State.Dynamic})
+	}
+	if status.Online && (!ok || prev.Outputs != status.Outputs) {
+		events.Publish(Event{Topic: "outputs", Host: status.Name, Data: status.Outputs})
+	}
+	if status.Online && (!ok || prev.Version != status.Version) {
+		checkUpgrade(status)
+	}
+	if status.Online && (!ok || !prev.Online) && !IsOfflineAddr(conn.Addr) {
+		go startup.OnOnline(conn.Addr, conn.Name)
+	}
+	return changed
+}
+
+// reconcile starts loops of new connections and stops loops and removes statuses of hosts no longer configured.
+func (p *Poller) reconcile(conns []Connection) {
+	keep := make(map[string]bool, len(conns))
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	for _, conn := range conns {
+		keep[conn.Addr] = true
+		if l, ok := p.loops[conn.Addr]; ok {
+			l.conn = conn
+			continue
+		}
+		l := &hostLoop{conn: conn, interval: p.min, touch: make(chan struct{}, 1), stop: make(chan struct{})}
+		p.loops[conn.Addr] = l
+		go p.runHost(l)
+	}
+	for addr, l := range p.loops {
+		if !keep[addr] {
+			close(l.stop)
+			delete(p.loops, addr)
+		}
+	}
+	for addr := range p.statuses {
+		if !keep[addr] {
+			delete(p.statuses, addr)
+		}
+	}
+}
+
+// runHost polls host of l until stopped, adapting its interval.
+func (p *Poller) runHost(l *hostLoop) {
+	p.mu.RLock()
+	interval := p.min
+	p.mu.RUnlock()
+	for {
+		p.mu.RLock()
+		conn := l.conn
+		p.mu.RUnlock()
+		start := time.Now()
+		changed := false
+		if BackgroundActive() && p.due(conn, interval, start) {
+			changed = p.poll(conn)
+		}
+		select {
+		case <-l.stop:
+			// host was removed while polling. Drop status written by this poll, unless host was added again.
+			p.mu.Lock()
+			if _, ok := p.loops[conn.Addr]; !ok {
+				delete(p.statuses, conn.Addr)
+			}
+			p.mu.Unlock()
+			return
+		default:
+		}
+		interval = p.nextInterval(l, interval, changed, time.Now())
+
+		timer := time.NewTimer(interval)
+		select {
+		case <-timer.C:
+		case <-l.touch:
+			timer.Stop()
+			// touch only shortens wait, so host is never polled more often than fast interval.
+			p.mu.RLock()
+			fast := p.fast
+			p.mu.RUnlock()
+			interval = fast
+			if wait := fast - time.Since(start); wait > 0 {
+				select {
+				case <-time.After(wait):
+				case <-l.stop:
+					return
+				}
+			}
+		case <-l.stop:
+			timer.Stop()
+			return
+		}
+	}
+}
+
+// nextInterval returns interval of l after poll: fast while focused or active, minimum when state changed,
+// and growing up to maximum otherwise.
+func (p *Poller) nextInterval(l *hostLoop, interval time.Duration, changed bool, now time.Time) time.Duration {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	switch {
+	case len(p.focus) > 0 || now.Before(l.activeUntil):
+		interval = p.fast
+	case changed:
+		interval = p.min
+	default:
+		if interval = time.Duration(float64(interval) * pollBackoff); interval > p.max {
+			interval = p.max
+		}
+	}
+	l.interval = interval
+	return interval
+}
+
+// Status returns latest status of host.
+func (p *Poller) Status(addr string) (*HostStatus, bool) {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	s, ok := p.statuses[addr]
+	return s, ok
+}
+
+// PatchProgram replaces program and preview in latest state of host, such as fetched by XMLTEXT after tally changed,
+// and publishes "program" event if they changed. Rest of state is updated by next poll.
+func (p *Poller) PatchProgram(addr string, active, preview int) {
+	p.mu.Lock()
+	prev, ok := p.statuses[addr]
+	if !ok || !prev.Online || prev.State == nil || (prev.State.Active == active && prev.State.Preview == preview) {
+		p.mu.Unlock()
+		return
+	}
+	// statuses and states are shared with readers, so patch copies.
+	s := *prev.State
+	s.Active, s.Preview = active, preview
+	status := *prev
+	status.State = &s
+	p.statuses[addr] = &status
+	p.mu.Unlock()
+	events.Publish(Event{Topic: "program", Host: status.Name, Data: map[string]int{"active": active, "preview": preview}})
+}
+
+// Statuses returns latest statuses ordered by name.
+func (p *Poller) Statuses() []*HostStatus {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	list := make([]*HostStatus, 0, len(p.statuses))
+	for _, s := range p.statuses {
+		list = append(list, s)
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].Name < list[j].Name })
+	return list
+}
+
+// Run keeps polling loop of every connection running, with interval adapting between fast, min and max.
+// Connections added or removed in settings are picked up every min.
+func (p *Poller) Run(fast, min, max time.Duration) {
+	if fast > min {
+		fast = min
+	}
+	p.mu.Lock()
+	p.fast, p.min, p.max = fast, min, max
+	p.mu.Unlock()
+	t := time.NewTicker(min)
+	defer t.Stop()
+	for {
+		tasks.Beat("poller", max)
+		p.reconcile(Connections())
+		<-t.C
+	}
+}
+
+// GetConnectionsHandler returns connections and their statuses for [GET] /api/connections .
+func GetConnectionsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"connections":      Connections(),
+		"statuses":         poller.Statuses(),
+		"poll_interval_ms": durationMs(poller.Interval()),
+		"poll_focused":     poller.Focused(),
+		"poll_budget":      poller.Budget(),
+	})
+}
+
+// PutConnectionsHandler saves additional connections for [PUT] /api/connections .
+func PutConnectionsHandler(c *gin.Context) {
+	conns := []Connection{}
+	if err := c.ShouldBindJSON(&conns); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for _, conn := range conns {
+		if conn.Priority != "" && conn.Priority != ConnectionPrimary && conn.Priority != ConnectionSecondary {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, conn.Addr, fmt.Errorf("unknown priority %q", conn.Priority)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Connections = conns
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"connections": Connections(),
+	})
+}
@@ -0,0 +1,181 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+)
+
+func TestPollerDuePrefersPrimary(t *testing.T) {
+	now := time.Now()
+	p := NewPoller()
+	p.SetBudget(3)
+	conns := []Connection{
+		{Name: "air", Addr: "http://air", Priority: ConnectionPrimary},
+		{Name: "backup", Addr: "http://backup"},
+		{Name: "gfx", Addr: "http://gfx", Priority: ConnectionSecondary},
+		{Name: "replay", Addr: "http://replay", Priority: ConnectionSecondary},
+	}
+	p.statuses["http://replay"] = &HostStatus{LastPoll: now.Add(-5 * time.Second)}
+	p.statuses["http://gfx"] = &HostStatus{LastPoll: now.Add(-10 * time.Second)}
+
+	names := func(conns []Connection, now time.Time) (s string) {
+		for _, c := range conns {
+			if p.due(c, time.Second, now) {
+				s += c.Name + " "
+			}
+		}
+		return s
+	}
+	if got := names(conns, now); got != "air backup gfx " {
+		t.Fatalf("expected primaries and secondary within budget, got %q", got)
+	}
+	if got := names(conns[2:], now.Add(time.Second)); got != "gfx replay " {
+		t.Fatalf("expected budget refilled after one second, got %q", got)
+	}
+
+	p.statuses["http://gfx"].LastPoll = now.Add(-time.Second)
+	p.SetBudget(0)
+	if got := names(conns, now); got != "air backup replay " {
+		t.Fatalf("expected secondary polled only when due, got %q", got)
+	}
+}
+
+func TestPollerSlowHostDoesNotStallOthers(t *testing.T) {
+	m := startMock(t)
+	release := make(chan struct{})
+	slow := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		<-release
+	}))
+	defer slow.Close()
+	defer close(release)
+
+	p := NewPoller()
+	p.min, p.max = 10*time.Millisecond, 20*time.Millisecond
+	fast := Connection{Name: "fast", Addr: "http://" + m.HTTPAddr}
+	p.reconcile([]Connection{{Name: "slow", Addr: slow.URL}, fast})
+	defer p.reconcile(nil)
+
+	var first time.Time
+	for deadline := time.Now().Add(2 * time.Second); ; time.Sleep(5 * time.Millisecond) {
+		if time.Now().After(deadline) {
+			t.Fatal("fast host was not polled repeatedly while slow host hangs")
+		}
+		s, ok := p.Status(fast.Addr)
+		if !ok {
+			continue
+		}
+		if !s.Online {
+			t.Fatalf("expected fast host online, got %+v", s)
+		}
+		if first.IsZero() {
+			first = s.LastPoll
+		} else if s.LastPoll.After(first) {
+			break
+		}
+	}
+	if _, ok := p.Status(slow.URL); ok {
+		t.Fatal("expected slow host still polling")
+	}
+}
+
+func TestConnectionStateOf(t *testing.T) {
+	now := time.Now()
+	for _, c := range []struct {
+		status *HostStatus
+		want   ConnectionState
+	}{
+		{&HostStatus{Online: true, LastSeen: now}, ConnectionConnected},
+		{&HostStatus{LastSeen: now.Add(-5 * time.Second)}, ConnectionReconnecting},
+		{&HostStatus{LastSeen: now.Add(-10 * time.Minute)}, ConnectionDisconnected},
+		{&HostStatus{}, ConnectionDisconnected},
+	} {
+		if got := connectionStateOf(c.status, now); got != c.want {
+			t.Errorf("expected %s for %+v, got %s", c.want, c.status, got)
+		}
+	}
+}
+
+func TestPollBackoff(t *testing.T) {
+	now := time.Now()
+	err := NewAppError(ErrKindUnreachable, "http://air", &VMixStatusError{Code: 503, Status: "503 Service Unavailable"})
+	reason := pollBusyReason(err, time.Second)
+	if reason != "vMix responded 503 Service Unavailable" {
+		t.Fatalf("unexpected reason %q", reason)
+	}
+	if r := pollBusyReason(nil, 3*time.Second); r == "" {
+		t.Fatalf("expected slow response to back off")
+	}
+	if r := pollBusyReason(NewAppError(ErrKindUnreachable, "http://air", fmt.Errorf("connection refused")), 0); r != "" {
+		t.Fatalf("expected refused connection not to back off, got %q", r)
+	}
+
+	var b *PollBackoff
+	for _, want := range []time.Duration{2 * time.Second, 4 * time.Second, 8 * time.Second, 16 * time.Second, 16 * time.Second} {
+		b = nextPollBackoff(b, reason, now)
+		if got := b.Until.Sub(now); got != want {
+			t.Fatalf("level %d expected delay %v, got %v", b.Level, want, got)
+		}
+	}
+	if nextPollBackoff(b, "", now) != nil {
+		t.Fatalf("expected healthy poll to clear backoff")
+	}
+
+	p := NewPoller()
+	p.statuses["http://air"] = &HostStatus{Backoff: &PollBackoff{Level: 1, Until: now.Add(time.Second)}}
+	conn := Connection{Name: "air", Addr: "http://air", Priority: ConnectionPrimary}
+	if p.due(conn, time.Second, now) {
+		t.Fatal("expected backed off host to be skipped")
+	}
+	if !p.due(conn, time.Second, now.Add(2*time.Second)) {
+		t.Fatal("expected host polled after backoff")
+	}
+}
+
+func TestPollerTouchHostDebounced(t *testing.T) {
+	p := NewPoller()
+	p.fast, p.min, p.max = 100*time.Millisecond, time.Second, 5*time.Second
+	newLoop := func() *hostLoop {
+		return &hostLoop{touch: make(chan struct{}, 1), stop: make(chan struct{})}
+	}
+	a, b := newLoop(), newLoop()
+	p.loops["http://a"], p.loops["http://b"] = a, b
+
+	p.TouchHost("http://a")
+	select {
+	case <-a.touch:
+	default:
+		t.Fatal("expected touched host woken")
+	}
+	for i := 0; i < 100; i++ {
+		p.TouchHost("http://a")
+	}
+	select {
+	case <-a.touch:
+		t.Fatal("expected touch storm debounced")
+	default:
+	}
+	select {
+	case <-b.touch:
+		t.Fatal("expected other host untouched")
+	default:
+	}
+
+	now := time.Now()
+	if got := p.nextInterval(a, p.max, false, now); got != p.fast {
+		t.Fatalf("expected active host polled at fast interval, got %v", got)
+	}
+	if got := p.nextInterval(b, p.min, false, now); got != 1500*time.Millisecond {
+		t.Fatalf("expected idle host to back off, got %v", got)
+	}
+	p.SetFocus("ui", true)
+	if got := p.nextInterval(b, p.max, false, now); got != p.fast {
+		t.Fatalf("expected fast interval while focused, got %v", got)
+	}
+	p.SetFocus("ui", false)
+	if got := p.nextInterval(b, p.min, true, now); got != p.min {
+		t.Fatalf("expected minimum interval on change after blur, got %v", got)
+	}
+}
@@ -0,0 +1,162 @@
+package main
+
+import (
+	"fmt"
+	"net"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Port probe results.
+const (
+	PortOpen     = "open"     // connection accepted.
+	PortClosed   = "closed"   // host refused connection, so host is up but nothing listens.
+	PortFiltered = "filtered" // no answer, typically dropped by Windows Firewall.
+)
+
+// PortProbe is result of probing single TCP port of vMix host.
+type PortProbe struct {
+	Port    int    `json:"port"`
+	Service string `json:"service"` // "http_api", "tcp_api", "web_controller_alt" or "ndi".
+	Status  string `json:"status"`
+	Detail  string `json:"detail,omitempty"`
+}
+
+// vMixPorts returns common vMix ports to probe. port and tcpPort are ports of HTTP and TCP API.
+func vMixPorts(port, tcpPort int) []PortProbe {
+	return []PortProbe{
+		{Port: port, Service: "http_api"},         // HTTP API and Web Controller.
+		{Port: tcpPort, Service: "tcp_api"},       // TCP API, vMix 21 or later.
+		{Port: 80, Service: "web_controller_alt"}, // Web Controller moved to port 80.
+		{Port: 5960, Service: "ndi"},              // first NDI stream port.
+	}
+}
+
+// classifyDial returns port status of dial error.
+func classifyDial(err error) string {
+	if err == nil {
+		return PortOpen
+	}
+	if strings.Contains(err.Error(), "refused") {
+		return PortClosed
+	}
+	return PortFiltered
+}
+
+// ScanPorts probes ports of host concurrently.
+func ScanPorts(host string, ports []PortProbe, timeout time.Duration) []PortProbe {
+	wg := &sync.WaitGroup{}
+	for i := range ports {
+		wg.Add(1)
+		go func(p *PortProbe) {
+			defer wg.Done()
+			conn, err := net.DialTimeout("tcp", net.JoinHostPort(host, strconv.Itoa(p.Port)), timeout)
+			p.Status = classifyDial(err)
+			if err != nil {
+				p.Detail = err.Error()
+				return
+			}
+			conn.Close()
+		}(&ports[i])
+	}
+	wg.Wait()
+	return ports
+}
+
+// PortHints explains probe results of vMix host with likely setup causes, such as Windows Firewall.
+func PortHints(probes []PortProbe) []string {
+	status := map[string]PortProbe{}
+	filtered := 0
+	for _, p := range probes {
+		status[p.Service] = p
+		if p.Status == PortFiltered {
+			filtered++
+		}
+	}
+	hints := []string{}
+	if filtered == len(probes) {
+		return append(hints, "host does not answer on any port. Check host is powered on and IP address is correct. "+
+			"If it is, Windows Firewall drops connections: allow vMix in Windows Defender Firewall, "+
+			"or change network profile from Public to Private if vMix is only allowed on private networks")
+	}
+	httpAPI, tcpAPI := status["http_api"], status["tcp_api"]
+	switch httpAPI.Status {
+	case PortFiltered:
+		hints = append(hints, fmt.Sprintf("port %d is dropped while other ports answer. Add inbound Windows Firewall rule for TCP %d, "+
+			"or allow vMix for current network profile", httpAPI.Port, httpAPI.Port))
+	case PortClosed:
+		if status["web_controller_alt"].Status == PortOpen {
+			hints = append(hints, "nothing listens on HTTP API port but port 80 is open. Web Controller may be set to port 80 in vMix Settings > Web Controller")
+		} else {
+			hints = append(hints, fmt.Sprintf("nothing listens on port %d. Check vMix is running and Web Controller is enabled in vMix Settings > Web Controller", httpAPI.Port))
+		}
+	}
+	switch tcpAPI.Status {
+	case PortFiltered:
+		hints = append(hints, fmt.Sprintf("TCP API port %d is dropped. Add inbound Windows Firewall rule for TCP %d", tcpAPI.Port, tcpAPI.Port))
+	case PortClosed:
+		if httpAPI.Status == PortOpen {
+			hints = append(hints, "HTTP API answers but TCP API does not. TCP API needs vMix 21 or later, otherwise utility falls back to HTTP polling")
+		}
+	}
+	if status["ndi"].Status == PortFiltered && httpAPI.Status == PortOpen {
+		hints = append(hints, "NDI port 5960 is dropped. NDI outputs of this host will not be visible on other machines until firewall allows NDI")
+	}
+	return hints
+}
+
+// HostPortScan is port probe results of vMix host.
+type HostPortScan struct {
+	Name  string      `json:"name"`
+	Addr  string      `json:"addr"`
+	Ports []PortProbe `json:"ports"`
+	Hints []string    `json:"hints"`
+	Error string      `json:"error,omitempty"`
+}
+
+// scanConnection probes common ports of connection.
+func scanConnection(name, addr, port, tcpPort string) HostPortScan {
+	scan := HostPortScan{Name: name, Addr: addr, Ports: []PortProbe{}, Hints: []string{}}
+	host, p, tp, err := parseDiagnoseTarget(addr, port, tcpPort)
+	if err != nil {
+		scan.Error = err.Error()
+		return scan
+	}
+	scan.Ports = ScanPorts(host, vMixPorts(p, tp), diagnoseTimeout)
+	scan.Hints = PortHints(scan.Ports)
+	return scan
+}
+
+// ScanPortsHandler probes common vMix ports for [GET] /api/diagnose/ports?host=...&port=8088&tcp_port=8099 .
+// Every connection is probed concurrently if host is empty.
+func ScanPortsHandler(c *gin.Context) {
+	var targets []Connection
+	if host := c.Query("host"); host != "" {
+		targets = []Connection{{Name: host, Addr: host}}
+	} else {
+		for _, conn := range Connections() {
+			if !IsOfflineAddr(conn.Addr) {
+				targets = append(targets, conn)
+			}
+		}
+	}
+	port, tcpPort := c.Query("port"), c.Query("tcp_port")
+	scans := make([]HostPortScan, len(targets))
+	wg := &sync.WaitGroup{}
+	for i, conn := range targets {
+		wg.Add(1)
+		go func(i int, conn Connection) {
+			defer wg.Done()
+			scans[i] = scanConnection(conn.Name, conn.Addr, port, tcpPort)
+		}(i, conn)
+	}
+	wg.Wait()
+	c.JSON(http.StatusOK, gin.H{
+		"hosts": scans,
+	})
+}
@@ -0,0 +1,46 @@
+package main
+
+import (
+	"net"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestScanPorts(t *testing.T) {
+	ln, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer ln.Close()
+	open := ln.Addr().(*net.TCPAddr).Port
+	ln2, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	closed := ln2.Addr().(*net.TCPAddr).Port
+	ln2.Close()
+
+	probes := ScanPorts("127.0.0.1", []PortProbe{{Port: open, Service: "http_api"}, {Port: closed, Service: "tcp_api"}}, time.Second)
+	if probes[0].Status != PortOpen || probes[1].Status != PortClosed {
+		t.Fatalf("unexpected probes %+v", probes)
+	}
+	if hints := PortHints(probes); len(hints) != 1 || !strings.Contains(hints[0], "TCP API needs vMix 21") {
+		t.Fatalf("unexpected hints %v", hints)
+	}
+}
+
+func TestPortHints(t *testing.T) {
+	all := []PortProbe{{Port: 8088, Service: "http_api", Status: PortFiltered}, {Port: 8099, Service: "tcp_api", Status: PortFiltered}}
+	if hints := PortHints(all); len(hints) != 1 || !strings.Contains(hints[0], "Windows Firewall") {
+		t.Fatalf("expected firewall hint, got %v", hints)
+	}
+	moved := []PortProbe{
+		{Port: 8088, Service: "http_api", Status: PortClosed},
+		{Port: 8099, Service: "tcp_api", Status: PortOpen},
+		{Port: 80, Service: "web_controller_alt", Status: PortOpen},
+	}
+	if hints := PortHints(moved); len(hints) != 1 || !strings.Contains(hints[0], "port 80") {
+		t.Fatalf("expected moved Web Controller hint, got %v", hints)
+	}
+}
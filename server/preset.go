@@ -0,0 +1,221 @@
+package main
+
+import (
+	"encoding/xml"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Preset is parsed vMix preset file (.vmix).
+type Preset struct {
+	Path        string             `json:"path"`
+	Version     string             `json:"version"`
+	Inputs      []PresetInput      `json:"inputs"`
+	DataSources []PresetDataSource `json:"data_sources"`
+	Shortcuts   []PresetShortcut   `json:"shortcuts"`
+}
+
+// PresetInput is input saved in preset.
+type PresetInput struct {
+	Key    string            `json:"key"`
+	Title  string            `json:"title"`
+	Type   string            `json:"type"`
+	Source string            `json:"source"` // file path, device name or URL.
+	Attrs  map[string]string `json:"attrs"`  // all attributes as saved by vMix.
+}
+
+// PresetDataSource is data source saved in preset.
+type PresetDataSource struct {
+	Name  string            `json:"name"`
+	Type  string            `json:"type"`
+	Attrs map[string]string `json:"attrs"`
+}
+
+// PresetShortcut is shortcut saved in preset.
+type PresetShortcut struct {
+	Key      string            `json:"key"`
+	Function string            `json:"function"`
+	Input    string            `json:"input"`
+	Value    string            `json:"value"`
+	Attrs    map[string]string `json:"attrs"`
+}
+
+// LoadPreset reads and parses preset file.
+func LoadPreset(path string) (*Preset, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	p, err := ParsePreset(f)
+	if err != nil {
+		return nil, err
+	}
+	p.Path = path
+	return p, nil
+}
+
+// ParsePreset parses preset XML. Element names are matched case-insensitively
+// since format differs between vMix versions.
+func ParsePreset(r io.Reader) (*Preset, error) {
+	p := &Preset{
+		Inputs:      []PresetInput{},
+		DataSources: []PresetDataSource{},
+		Shortcuts:   []PresetShortcut{},
+	}
+	dec := xml.NewDecoder(r)
+	dec.Strict = false
+
+	var stack []string
+	var input *PresetInput
+	var text strings.Builder
+	for {
+		tok, err := dec.Token()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return nil, err
+		}
+		switch t := tok.(type) {
+		case xml.StartElement:
+			name := strings.ToLower(t.Name.Local)
+			parent := ""
+			if len(stack) > 0 {
+				parent = stack[len(stack)-1]
+			}
+			stack = append(stack, name)
+			attrs := presetAttrs(t.Attr)
+			switch {
+			case name == "input" && len(stack) == 2:
+				input = &PresetInput{
+					Key:   attrs["key"],
+					Title: firstNonEmpty(attrs["title"], attrs["originaltitle"]),
+					Type:  attrs["type"],
+					Attrs: attrs,
+				}
+				text.Reset()
+			case name == "datasource" || parent == "datasources":
+				p.DataSources = append(p.DataSources, PresetDataSource{
+					Name:  firstNonEmpty(attrs["name"], attrs["friendlyname"]),
+					Type:  firstNonEmpty(attrs["type"], name),
+					Attrs: attrs,
+				})
+			case name == "shortcut":
+				p.Shortcuts = append(p.Shortcuts, PresetShortcut{
+					Key:      attrs["key"],
+					Function: attrs["function"],
+					Input:    attrs["input"],
+					Value:    attrs["value"],
+					Attrs:    attrs,
+				})
+			}
+		case xml.CharData:
+			if len(stack) == 2 && stack[1] == "version" {
+				p.Version = strings.TrimSpace(string(t))
+			}
+			if input != nil && len(stack) == 2 {
+				text.Write(t)
+			}
+		case xml.EndElement:
+			if input != nil && len(stack) == 2 {
+				input.Source = strings.TrimSpace(text.String())
+				p.Inputs = append(p.Inputs, *input)
+				input = nil
+			}
+			if len(stack) > 0 {
+				stack = stack[:len(stack)-1]
+			}
+		}
+	}
+	if len(stack) != 0 {
+		return nil, fmt.Errorf("unexpected end of preset")
+	}
+	return p, nil
+}
+
+// presetAttrs converts attributes into map with lower-cased names.
+func presetAttrs(attrs []xml.Attr) map[string]string {
+	m := make(map[string]string, len(attrs))
+	for _, a := range attrs {
+		m[strings.ToLower(a.Name.Local)] = a.Value
+	}
+	return m
+}
+
+func firstNonEmpty(values ...string) string {
+	for _, v := range values {
+		if v != "" {
+			return v
+		}
+	}
+	return ""
+}
+
+// loadPresetFromQuery loads preset specified by "path" query.
+func loadPresetFromQuery(c *gin.Context) (*Preset, bool) {
+	path := c.Query("path")
+	if path == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("path required")))
+		return nil, false
+	}
+	p, err := LoadPreset(path)
+	if os.IsNotExist(err) {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", err))
+		return nil, false
+	}
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return nil, false
+	}
+	return p, true
+}
+
+// GetPresetHandler returns parsed preset for [GET] /api/preset?path=... .
+func GetPresetHandler(c *gin.Context) {
+	p, ok := loadPresetFromQuery(c)
+	if !ok {
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"preset": p,
+	})
+}
+
+// GetPresetInputsHandler returns inputs in preset for [GET] /api/preset/inputs?path=... .
+func GetPresetInputsHandler(c *gin.Context) {
+	p, ok := loadPresetFromQuery(c)
+	if !ok {
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"inputs": p.Inputs,
+	})
+}
+
+// GetPresetDataSourcesHandler returns data sources in preset for [GET] /api/preset/datasources?path=... .
+func GetPresetDataSourcesHandler(c *gin.Context) {
+	p, ok := loadPresetFromQuery(c)
+	if !ok {
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"data_sources": p.DataSources,
+	})
+}
+
+// GetPresetShortcutsHandler returns shortcuts in preset for [GET] /api/preset/shortcuts?path=... .
+func GetPresetShortcutsHandler(c *gin.Context) {
+	p, ok := loadPresetFromQuery(c)
+	if !ok {
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"shortcuts": p.Shortcuts,
+	})
+}
@@ -0,0 +1,146 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"os"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// HardwareProfile describes devices and assets available on venue machine running vMix.
+type HardwareProfile struct {
+	CaptureDevices []string          `json:"capture_devices"` // capture device names.
+	NDISources     []string          `json:"ndi_sources"`     // NDI source names. e.g. "CAMERA1 (NDI HX)" .
+	PathMappings   map[string]string `json:"path_mappings"`   // preset path prefix -> local path prefix, to check files from this machine.
+}
+
+// PresetIssue is missing dependency found in preset.
+type PresetIssue struct {
+	InputKey  string `json:"input_key"`
+	Title     string `json:"title"`
+	Kind      string `json:"kind"`      // "capture_device", "ndi_source" or "file".
+	Reference string `json:"reference"` // device name, NDI source or file path.
+	Message   string `json:"message"`
+}
+
+// ValidatePreset checks devices, NDI sources and files referenced by preset against profile.
+func ValidatePreset(p *Preset, profile HardwareProfile) []PresetIssue {
+	issues := []PresetIssue{}
+	for _, in := range p.Inputs {
+		typ := strings.ToLower(in.Type)
+		switch {
+		case strings.Contains(typ, "ndi") || in.Attrs["ndisource"] != "":
+			src := firstNonEmpty(in.Attrs["ndisource"], in.Source)
+			if !containsFold(profile.NDISources, src) {
+				issues = append(issues, PresetIssue{InputKey: in.Key, Title: in.Title, Kind: "ndi_source", Reference: src, Message: "NDI source is not in hardware profile"})
+			}
+		case strings.Contains(typ, "capture") || in.Attrs["videodevice"] != "" || in.Attrs["devicename"] != "":
+			dev := firstNonEmpty(in.Attrs["videodevice"], in.Attrs["devicename"], in.Source)
+			if !containsFold(profile.CaptureDevices, dev) {
+				issues = append(issues, PresetIssue{InputKey: in.Key, Title: in.Title, Kind: "capture_device", Reference: dev, Message: "capture device is not in hardware profile"})
+			}
+		case isFilePath(in.Source):
+			local := mapPath(in.Source, profile.PathMappings)
+			if _, err := os.Stat(local); err != nil {
+				issues = append(issues, PresetIssue{InputKey: in.Key, Title: in.Title, Kind: "file", Reference: in.Source, Message: fmt.Sprintf("file not accessible at %s", local)})
+			}
+		}
+	}
+	return issues
+}
+
+// isFilePath returns true for absolute Windows, UNC or Unix paths.
+func isFilePath(s string) bool {
+	if strings.HasPrefix(s, `\\`) || strings.HasPrefix(s, "/") {
+		return true
+	}
+	return len(s) > 2 && s[1] == ':' && (s[2] == '\\' || s[2] == '/')
+}
+
+// mapPath replaces longest matching prefix in mappings.
+func mapPath(path string, mappings map[string]string) string {
+	best := ""
+	for from := range mappings {
+		if strings.HasPrefix(strings.ToLower(path), strings.ToLower(from)) && len(from) > len(best) {
+			best = from
+		}
+	}
+	if best == "" {
+		return path
+	}
+	return mappings[best] + path[len(best):]
+}
+
+func containsFold(list []string, s string) bool {
+	for _, v := range list {
+		if strings.EqualFold(v, s) {
+			return true
+		}
+	}
+	return false
+}
+
+// GetHardwareProfileHandler returns hardware profile of vMix host for [GET] /api/profile?host= .
+// Primary vMix host is used if host is empty.
+func GetHardwareProfileHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"profile": config.Get().HardwareProfiles[host],
+	})
+}
+
+// PutHardwareProfileHandler saves hardware profile of vMix host for [PUT] /api/profile?host= .
+func PutHardwareProfileHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	profile := HardwareProfile{}
+	if err := c.ShouldBindJSON(&profile); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.HardwareProfiles[host] = profile
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":    host,
+		"profile": profile,
+	})
+}
+
+// ValidatePresetRequest Request JSON for ValidatePresetHandler
+type ValidatePresetRequest struct {
+	Path string `json:"path"`
+	Host string `json:"host"` // vMix host whose hardware profile is used. Primary vMix host is used if empty.
+}
+
+// ValidatePresetHandler validates preset against hardware profile of host for [POST] /api/preset/validate .
+func ValidatePresetHandler(c *gin.Context) {
+	req := ValidatePresetRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil || req.Path == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("path required")))
+		return
+	}
+	p, err := LoadPreset(req.Path)
+	if os.IsNotExist(err) {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", err))
+		return
+	}
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if req.Host == "" {
+		req.Host = *vmixaddr
+	}
+	issues := ValidatePreset(p, config.Get().HardwareProfiles[req.Host])
+	c.JSON(http.StatusOK, gin.H{
+		"host":   req.Host,
+		"valid":  len(issues) == 0,
+		"issues": issues,
+	})
+}
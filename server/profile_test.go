@@ -0,0 +1,83 @@
+package main
+
+import (
+	"io/ioutil"
+	"net/http"
+	"net/http/httptest"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestValidatePreset(t *testing.T) {
+	dir := t.TempDir()
+	if err := ioutil.WriteFile(filepath.Join(dir, "opening.mp4"), nil, 0644); err != nil {
+		t.Fatal(err)
+	}
+	profile := HardwareProfile{
+		CaptureDevices: []string{"Blackmagic DeckLink 1"},
+		NDISources:     []string{"CAMERA1 (NDI HX)"},
+		PathMappings:   map[string]string{`D:\Show`: dir},
+	}
+	for _, c := range []struct {
+		name  string
+		input PresetInput
+		kind  string // kind of expected issue. Empty if input is valid.
+	}{
+		{"capture device in profile", PresetInput{Type: "Capture", Attrs: map[string]string{"videodevice": "blackmagic decklink 1"}}, ""},
+		{"capture device missing", PresetInput{Type: "Capture", Attrs: map[string]string{"videodevice": "Blackmagic DeckLink 2"}}, "capture_device"},
+		{"capture device by device name", PresetInput{Type: "Audio", Attrs: map[string]string{"devicename": "USB Mic"}}, "capture_device"},
+		{"NDI source in profile", PresetInput{Type: "NDI", Source: "CAMERA1 (NDI HX)", Attrs: map[string]string{}}, ""},
+		{"NDI source missing", PresetInput{Type: "Capture", Attrs: map[string]string{"ndisource": "CAMERA2 (NDI HX)"}}, "ndi_source"},
+		{"mapped file exists", PresetInput{Type: "Video", Source: `D:\Show/opening.mp4`, Attrs: map[string]string{}}, ""},
+		{"mapped file missing", PresetInput{Type: "Video", Source: `D:\Show/closing.mp4`, Attrs: map[string]string{}}, "file"},
+		{"unmapped file missing", PresetInput{Type: "Image", Source: `E:\Logos\logo.png`, Attrs: map[string]string{}}, "file"},
+		{"URL is not checked", PresetInput{Type: "Browser", Source: "https://example.com", Attrs: map[string]string{}}, ""},
+	} {
+		issues := ValidatePreset(&Preset{Inputs: []PresetInput{c.input}}, profile)
+		switch {
+		case c.kind == "" && len(issues) != 0:
+			t.Errorf("%s : expected no issue, got %+v", c.name, issues)
+		case c.kind != "" && (len(issues) != 1 || issues[0].Kind != c.kind):
+			t.Errorf("%s : expected %s issue, got %+v", c.name, c.kind, issues)
+		}
+	}
+}
+
+func TestHardwareProfileOfHost(t *testing.T) {
+	const host = "http://profile-test:8088"
+	defer config.Update(func(cfg *Config) {
+		delete(cfg.HardwareProfiles, host)
+	})
+	preset := filepath.Join(t.TempDir(), "show.vmix")
+	if err := ioutil.WriteFile(preset, []byte(`<XML><Input Key="k1" Title="Cam" Type="Capture" VideoDevice="DeckLink 1" /></XML>`), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/profile", GetHardwareProfileHandler)
+	r.PUT("/api/profile", PutHardwareProfileHandler)
+	r.POST("/api/preset/validate", ValidatePresetHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/profile?host="+host, strings.NewReader(`{"capture_devices":["DeckLink 1"]}`)))
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected profile saved, got %d %s", w.Code, w.Body)
+	}
+	if _, ok := config.Get().HardwareProfiles[*vmixaddr]; ok {
+		t.Fatal("expected profile of other host not saved as primary host profile")
+	}
+
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPost, "/api/preset/validate", strings.NewReader(`{"path":"`+filepath.ToSlash(preset)+`","host":"`+host+`"}`)))
+	if w.Code != http.StatusOK || !strings.Contains(w.Body.String(), `"valid":true`) {
+		t.Fatalf("expected preset valid against profile of host, got %d %s", w.Code, w.Body)
+	}
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPost, "/api/preset/validate", strings.NewReader(`{"path":"`+filepath.ToSlash(preset)+`missing"}`)))
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("expected missing preset not found, got %d %s", w.Code, w.Body)
+	}
+}
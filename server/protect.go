@@ -0,0 +1,115 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// defaultProtectedFunctions are refused while on air if settings do not list functions.
+var defaultProtectedFunctions = []string{"CloseInput", "RemoveInput", "OpenPreset"}
+
+// defaultOnAirStaleAfter is how long last known outputs of offline host are trusted if settings do not set it.
+const defaultOnAirStaleAfter = 5 * time.Minute
+
+// OnAirProtectSettings is on-air protection. Destructive functions are refused while host is recording or streaming.
+type OnAirProtectSettings struct {
+	Enabled       bool     `json:"enabled"`
+	Functions     []string `json:"functions"`       // protected functions. Default: CloseInput, RemoveInput, OpenPreset .
+	StaleAfterSec int      `json:"stale_after_sec"` // how long outputs of offline host are trusted. Default: 300 .
+}
+
+// staleAfter returns how long last known outputs of offline host are trusted.
+func (p OnAirProtectSettings) staleAfter() time.Duration {
+	if p.StaleAfterSec <= 0 {
+		return defaultOnAirStaleAfter
+	}
+	return time.Duration(p.StaleAfterSec) * time.Second
+}
+
+// protected reports whether function is protected by settings.
+func (p OnAirProtectSettings) protected(function string) bool {
+	functions := p.Functions
+	if len(functions) == 0 {
+		functions = defaultProtectedFunctions
+	}
+	for _, f := range functions {
+		if strings.EqualFold(f, function) {
+			return true
+		}
+	}
+	return false
+}
+
+// onAir returns description of live outputs, or empty string if nothing is live.
+func onAir(outputs OutputStatus) string {
+	live := []string{}
+	if outputs.Recording {
+		live = append(live, "recording")
+	}
+	if outputs.Streaming {
+		live = append(live, "streaming")
+	}
+	return strings.Join(live, " and ")
+}
+
+// CheckOnAirProtect returns protected error if function is destructive and host is recording or streaming.
+// Offline host is judged by outputs when it was last seen, since it likely keeps recording while unreachable,
+// until they are older than staleness window. Hosts never seen are allowed, since their outputs are unknown.
+func CheckOnAirProtect(addr, function string) error {
+	return checkOnAirProtect(addr, function, time.Now())
+}
+
+func checkOnAirProtect(addr, function string, now time.Time) error {
+	settings := config.Get().OnAirProtect
+	if !settings.Enabled || !settings.protected(function) {
+		return nil
+	}
+	status, ok := poller.Status(addr)
+	if !ok {
+		return nil
+	}
+	if status.Online {
+		if live := onAir(status.Outputs); live != "" {
+			return NewAppError(ErrKindProtected, addr, fmt.Errorf("%s refused while %s. Send with override to force", function, live))
+		}
+		return nil
+	}
+	if age := now.Sub(status.LastSeen); !status.LastSeen.IsZero() && age < settings.staleAfter() {
+		if live := onAir(status.LastOutputs); live != "" {
+			return NewAppError(ErrKindProtected, addr, fmt.Errorf("%s refused since host was %s when last seen %s ago. Send with override to force",
+				function, live, age.Round(time.Second)))
+		}
+	}
+	return nil
+}
+
+// GetOnAirProtectHandler returns on-air protection settings for [GET] /api/protect .
+func GetOnAirProtectHandler(c *gin.Context) {
+	settings := config.Get().OnAirProtect
+	if len(settings.Functions) == 0 {
+		settings.Functions = defaultProtectedFunctions
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"protect": settings,
+	})
+}
+
+// PutOnAirProtectHandler saves on-air protection settings for [PUT] /api/protect .
+func PutOnAirProtectHandler(c *gin.Context) {
+	settings := OnAirProtectSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.OnAirProtect = settings
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetOnAirProtectHandler(c)
+}
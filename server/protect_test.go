@@ -0,0 +1,46 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestOnAirProtect(t *testing.T) {
+	settings := OnAirProtectSettings{Enabled: true}
+	if !settings.protected("closeinput") || settings.protected("Cut") {
+		t.Fatal("unexpected default protected functions")
+	}
+	settings.Functions = []string{"Cut"}
+	if !settings.protected("Cut") || settings.protected("CloseInput") {
+		t.Fatal("unexpected custom protected functions")
+	}
+	if live := onAir(OutputStatus{Recording: true, Streaming: true, External: true}); live != "recording and streaming" {
+		t.Fatalf("unexpected on air %q", live)
+	}
+	if live := onAir(OutputStatus{External: true}); live != "" {
+		t.Fatalf("external output alone must not be on air, got %q", live)
+	}
+}
+
+func TestOnAirProtectOfflineHost(t *testing.T) {
+	addr := "http://protect-test:8088"
+	now := time.Now()
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "protect", Addr: addr, Online: false, LastSeen: now.Add(-time.Minute), LastOutputs: OutputStatus{Recording: true}}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+	config.Update(func(cfg *Config) { cfg.OnAirProtect = OnAirProtectSettings{Enabled: true, StaleAfterSec: 120} })
+	defer config.Update(func(cfg *Config) { cfg.OnAirProtect = OnAirProtectSettings{} })
+
+	if err := checkOnAirProtect(addr, "CloseInput", now); AsAppError(err).Kind != ErrKindProtected {
+		t.Fatalf("expected offline host recording when last seen protected, got %v", err)
+	}
+	if err := checkOnAirProtect(addr, "CloseInput", now.Add(2*time.Minute)); err != nil {
+		t.Fatalf("expected stale outputs ignored, got %v", err)
+	}
+	p.statuses[addr] = &HostStatus{Name: "protect", Addr: addr, Online: false}
+	if err := checkOnAirProtect(addr, "CloseInput", now); err != nil {
+		t.Fatalf("expected host never seen allowed, got %v", err)
+	}
+}
@@ -0,0 +1,103 @@
+package main
+
+import (
+	"fmt"
+	"net"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// forwardedHeaders are headers set by reverse proxies. They are honored only from trusted proxies.
+var forwardedHeaders = []string{
+	"X-Forwarded-For",
+	"X-Real-IP",
+	"X-Forwarded-Proto",
+	"X-Forwarded-Host",
+	"X-Forwarded-Prefix",
+}
+
+// NormalizeBasePath returns URL path prefix with leading slash and without trailing slash. e.g. "vmix/" -> "/vmix" .
+func NormalizeBasePath(p string) string {
+	p = strings.Trim(strings.TrimSpace(p), "/")
+	if p == "" {
+		return ""
+	}
+	return "/" + p
+}
+
+// RequestBaseURL returns URL of utility as reached by client, including base path. e.g. "http://192.168.0.10:8080/vmix" .
+// Forwarded headers are used when present, since TrustedProxyMiddleware has removed them from untrusted clients.
+func RequestBaseURL(c *gin.Context) string {
+	scheme := "http"
+	if c.Request.TLS != nil {
+		scheme = "https"
+	}
+	if proto := c.GetHeader("X-Forwarded-Proto"); proto != "" {
+		scheme = strings.TrimSpace(strings.Split(proto, ",")[0])
+	}
+	host := c.Request.Host
+	if fwd := c.GetHeader("X-Forwarded-Host"); fwd != "" {
+		host = strings.TrimSpace(strings.Split(fwd, ",")[0])
+	}
+	return scheme + "://" + host + *basePath
+}
+
+// ParseTrustedProxies parses comma separated IP addresses or CIDRs. e.g. "127.0.0.1,10.0.0.0/8" .
+func ParseTrustedProxies(list string) ([]*net.IPNet, error) {
+	var nets []*net.IPNet
+	for _, v := range strings.Split(list, ",") {
+		v = strings.TrimSpace(v)
+		if v == "" {
+			continue
+		}
+		if !strings.Contains(v, "/") {
+			ip := net.ParseIP(v)
+			if ip == nil {
+				return nil, fmt.Errorf("invalid trusted proxy %q", v)
+			}
+			bits := 128
+			if ip.To4() != nil {
+				ip, bits = ip.To4(), 32
+			}
+			nets = append(nets, &net.IPNet{IP: ip, Mask: net.CIDRMask(bits, bits)})
+			continue
+		}
+		_, n, err := net.ParseCIDR(v)
+		if err != nil {
+			return nil, fmt.Errorf("invalid trusted proxy %q : %v", v, err)
+		}
+		nets = append(nets, n)
+	}
+	return nets, nil
+}
+
+// TrustedProxyMiddleware removes forwarded headers from requests not coming from trusted proxies,
+// so clients cannot spoof their address by sending X-Forwarded-For directly.
+func TrustedProxyMiddleware(trusted []*net.IPNet) gin.HandlerFunc {
+	return func(c *gin.Context) {
+		if !isTrustedProxy(trusted, c.Request.RemoteAddr) {
+			for _, h := range forwardedHeaders {
+				c.Request.Header.Del(h)
+			}
+		}
+		c.Next()
+	}
+}
+
+func isTrustedProxy(trusted []*net.IPNet, remoteAddr string) bool {
+	host, _, err := net.SplitHostPort(remoteAddr)
+	if err != nil {
+		host = remoteAddr
+	}
+	ip := net.ParseIP(host)
+	if ip == nil {
+		return false
+	}
+	for _, n := range trusted {
+		if n.Contains(ip) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,31 @@
+package main
+
+import "testing"
+
+func TestNormalizeBasePath(t *testing.T) {
+	for in, want := range map[string]string{"": "", "/": "", "vmix": "/vmix", "/vmix/": "/vmix", "/a/b/": "/a/b"} {
+		if got := NormalizeBasePath(in); got != want {
+			t.Errorf("NormalizeBasePath(%q) = %q, want %q", in, got, want)
+		}
+	}
+}
+
+func TestTrustedProxies(t *testing.T) {
+	trusted, err := ParseTrustedProxies("127.0.0.1, 10.0.0.0/8,::1")
+	if err != nil {
+		t.Fatal(err)
+	}
+	for addr, want := range map[string]bool{
+		"127.0.0.1:50000": true,
+		"10.1.2.3:80":     true,
+		"[::1]:8080":      true,
+		"192.168.0.5:80":  false,
+	} {
+		if got := isTrustedProxy(trusted, addr); got != want {
+			t.Errorf("isTrustedProxy(%q) = %v, want %v", addr, got, want)
+		}
+	}
+	if _, err := ParseTrustedProxies("not-an-ip"); err == nil {
+		t.Fatal("expected error for invalid proxy")
+	}
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"net/http"
+	"net/url"
+
+	"github.com/gin-gonic/gin"
+)
+
+// QuickAction is action offered by compact clients such as tray menu or launcher, described as request to utility API.
+type QuickAction struct {
+	ID     string `json:"id"` // "cut", "ftb" or "multiviewer".
+	Label  string `json:"label"`
+	Method string `json:"method"` // HTTP method. "GET" actions are links to open.
+	Path   string `json:"path"`
+}
+
+// QuickEntry is compact status and quick actions of connection.
+type QuickEntry struct {
+	Name        string          `json:"name"`
+	Addr        string          `json:"addr"`
+	Online      bool            `json:"online"`
+	Connection  ConnectionState `json:"connection"`
+	Active      string          `json:"active"`  // title of program input.
+	Preview     string          `json:"preview"` // title of preview input.
+	Recording   bool            `json:"recording"`
+	Streaming   bool            `json:"streaming"`
+	FadeToBlack bool            `json:"fade_to_black"`
+	Actions     []QuickAction   `json:"actions"`
+}
+
+// inputTitle returns title of input number, or empty if not found.
+func inputTitle(s *State, number int) string {
+	for _, in := range s.Inputs {
+		if in.Number == number {
+			return in.Title
+		}
+	}
+	return ""
+}
+
+// QuickEntries returns compact status of every connection. Clients rebuild menu on "status" and "acts" events.
+// Fade to black goes through FTBInterlock, so it is armed here and confirmed by second request.
+func QuickEntries() []QuickEntry {
+	entries := []QuickEntry{}
+	for _, conn := range Connections() {
+		host := url.QueryEscape(conn.Addr)
+		e := QuickEntry{
+			Name: conn.Name,
+			Addr: conn.Addr,
+			Actions: []QuickAction{
+				{ID: "cut", Label: "Cut", Method: http.MethodPost, Path: *basePath + "/api/quick/cut?host=" + host},
+				{ID: "ftb", Label: "Fade to black", Method: http.MethodPost, Path: *basePath + "/api/ftb/arm"},
+				{ID: "multiviewer", Label: "Open multiviewer", Method: http.MethodGet, Path: *basePath + "/multiviewer/"},
+			},
+			Connection: ConnectionDisconnected,
+		}
+		if status, ok := poller.Status(conn.Addr); ok && status.State != nil {
+			s := status.State
+			e.Online = status.Online
+			e.Connection = status.Connection
+			e.Active = inputTitle(s, s.Active)
+			e.Preview = inputTitle(s, s.Preview)
+			e.Recording = s.Recording
+			e.Streaming = s.Streaming
+			e.FadeToBlack = s.FadeToBlack
+		}
+		entries = append(entries, e)
+	}
+	return entries
+}
+
+// GetQuickHandler returns compact status and quick actions of connections for [GET] /api/quick .
+func GetQuickHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"connections": QuickEntries(),
+	})
+}
+
+// QuickCutHandler cuts preview to program for [POST] /api/quick/cut?host=... .
+func QuickCutHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	poller.Touch()
+	if err := SendFunctionTo(host, "Cut", nil); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, host, "Cut")
+	c.JSON(http.StatusOK, gin.H{
+		"host": host,
+	})
+}
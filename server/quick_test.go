@@ -0,0 +1,19 @@
+package main
+
+import "testing"
+
+func TestQuickEntries(t *testing.T) {
+	p := NewPoller()
+	p.statuses[*vmixaddr] = &HostStatus{Name: "primary", Addr: *vmixaddr, Online: true, State: DefaultMockState()}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	entries := QuickEntries()
+	if len(entries) == 0 || !entries[0].Online || entries[0].Active != "Camera 1" {
+		t.Fatalf("unexpected entries %+v", entries)
+	}
+	if len(entries[0].Actions) != 3 || entries[0].Actions[0].ID != "cut" {
+		t.Fatalf("unexpected actions %+v", entries[0].Actions)
+	}
+}
@@ -0,0 +1,231 @@
+package main
+
+import (
+	"bytes"
+	"encoding/xml"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// rawXMLDiffMaxCells limits line diff work. Larger changed regions are reported as replaced as whole.
+const rawXMLDiffMaxCells = 4000000
+
+// RawXMLCapture is raw XML fetched from host.
+type RawXMLCapture struct {
+	TakenAt time.Time `json:"taken_at"`
+	XML     []byte    `json:"-"`
+	State   *State    `json:"-"`
+}
+
+// maxRawXMLHosts limits hosts with raw XML captures. Host captured least recently is evicted when exceeded.
+const maxRawXMLHosts = 32
+
+// RawXMLHistory keeps last two raw XML captures per host, to see exactly what vMix sent between them.
+type RawXMLHistory struct {
+	mu       sync.Mutex
+	captures map[string][2]*RawXMLCapture // host -> previous, latest.
+	evicted  uint64
+}
+
+// NewRawXMLHistory creates RawXMLHistory.
+func NewRawXMLHistory() *RawXMLHistory {
+	return &RawXMLHistory{captures: make(map[string][2]*RawXMLCapture)}
+}
+
+// Capture fetches raw XML from host and returns previous and new capture. Previous is nil on first capture.
+func (h *RawXMLHistory) Capture(host string) (*RawXMLCapture, *RawXMLCapture, error) {
+	s, raw, err := FetchState(host)
+	if err != nil {
+		return nil, nil, err
+	}
+	latest := &RawXMLCapture{TakenAt: time.Now(), XML: raw, State: s}
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	prev := h.captures[host][1]
+	h.captures[host] = [2]*RawXMLCapture{prev, latest}
+	for len(h.captures) > maxRawXMLHosts {
+		oldest := ""
+		for other, captures := range h.captures {
+			if oldest == "" || captures[1].TakenAt.Before(h.captures[oldest][1].TakenAt) {
+				oldest = other
+			}
+		}
+		delete(h.captures, oldest)
+		h.evicted++
+	}
+	return prev, latest, nil
+}
+
+// Stats returns size of history.
+func (h *RawXMLHistory) Stats() CacheStats {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	stats := CacheStats{Name: "raw_xml", Entries: len(h.captures), Limit: maxRawXMLHosts, Evicted: h.evicted}
+	for _, captures := range h.captures {
+		for _, capture := range captures {
+			if capture != nil {
+				stats.Bytes += int64(len(capture.XML))
+			}
+		}
+	}
+	return stats
+}
+
+// Get returns latest or previous capture of host, or nil if not captured.
+func (h *RawXMLHistory) Get(host string, previous bool) *RawXMLCapture {
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	if previous {
+		return h.captures[host][0]
+	}
+	return h.captures[host][1]
+}
+
+// XMLDiffLine is line added or removed between captures. Line numbers are of indented XML.
+type XMLDiffLine struct {
+	Op   string `json:"op"` // "+" or "-".
+	Line int    `json:"line"`
+	Text string `json:"text"`
+}
+
+// xmlLines formats XML as one element or text per line, since vMix sends whole document in single line.
+func xmlLines(raw []byte) ([]string, error) {
+	dec := xml.NewDecoder(bytes.NewReader(raw))
+	lines := []string{}
+	depth := 0
+	for {
+		tok, err := dec.Token()
+		if err == io.EOF {
+			return lines, nil
+		}
+		if err != nil {
+			return nil, err
+		}
+		indent := strings.Repeat("  ", depth)
+		switch t := tok.(type) {
+		case xml.StartElement:
+			sb := &strings.Builder{}
+			sb.WriteString(indent + "<" + t.Name.Local)
+			for _, attr := range t.Attr {
+				fmt.Fprintf(sb, " %s=%q", attr.Name.Local, attr.Value)
+			}
+			lines = append(lines, sb.String()+">")
+			depth++
+		case xml.EndElement:
+			depth--
+			lines = append(lines, strings.Repeat("  ", depth)+"</"+t.Name.Local+">")
+		case xml.CharData:
+			if text := strings.TrimSpace(string(t)); text != "" {
+				lines = append(lines, indent+text)
+			}
+		}
+	}
+}
+
+// DiffLines returns lines removed from a and added in b, by longest common subsequence after common prefix and suffix.
+func DiffLines(a, b []string) []XMLDiffLine {
+	start := 0
+	for start < len(a) && start < len(b) && a[start] == b[start] {
+		start++
+	}
+	endA, endB := len(a), len(b)
+	for endA > start && endB > start && a[endA-1] == b[endB-1] {
+		endA--
+		endB--
+	}
+	ma, mb := a[start:endA], b[start:endB]
+	diff := []XMLDiffLine{}
+	if len(ma)*len(mb) > rawXMLDiffMaxCells {
+		for i, line := range ma {
+			diff = append(diff, XMLDiffLine{Op: "-", Line: start + i + 1, Text: line})
+		}
+		for i, line := range mb {
+			diff = append(diff, XMLDiffLine{Op: "+", Line: start + i + 1, Text: line})
+		}
+		return diff
+	}
+	// lcs[i][j] is length of common subsequence of ma[i:] and mb[j:].
+	lcs := make([][]int, len(ma)+1)
+	for i := range lcs {
+		lcs[i] = make([]int, len(mb)+1)
+	}
+	for i := len(ma) - 1; i >= 0; i-- {
+		for j := len(mb) - 1; j >= 0; j-- {
+			switch {
+			case ma[i] == mb[j]:
+				lcs[i][j] = lcs[i+1][j+1] + 1
+			case lcs[i+1][j] >= lcs[i][j+1]:
+				lcs[i][j] = lcs[i+1][j]
+			default:
+				lcs[i][j] = lcs[i][j+1]
+			}
+		}
+	}
+	i, j := 0, 0
+	for i < len(ma) || j < len(mb) {
+		switch {
+		case i < len(ma) && j < len(mb) && ma[i] == mb[j]:
+			i++
+			j++
+		case j == len(mb) || (i < len(ma) && lcs[i+1][j] >= lcs[i][j+1]):
+			diff = append(diff, XMLDiffLine{Op: "-", Line: start + i + 1, Text: ma[i]})
+			i++
+		default:
+			diff = append(diff, XMLDiffLine{Op: "+", Line: start + j + 1, Text: mb[j]})
+			j++
+		}
+	}
+	return diff
+}
+
+// GetRawXMLDiffHandler captures raw XML of host and compares it with previous capture for [GET] /api/xml/diff?host=... .
+// First call only captures. Line diff shows every field as vMix sent it, and semantic diff shows what parsers detected.
+func GetRawXMLDiffHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	prev, latest, err := rawXML.Capture(host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if prev == nil {
+		c.JSON(http.StatusOK, gin.H{
+			"host":   host,
+			"latest": latest,
+		})
+		return
+	}
+	a, err := xmlLines(prev.XML)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, host, err))
+		return
+	}
+	b, err := xmlLines(latest.XML)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, host, err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"host":     host,
+		"previous": prev,
+		"latest":   latest,
+		"lines":    DiffLines(a, b),
+		"semantic": DiffStates(prev.State, latest.State),
+	})
+}
+
+// GetRawXMLHandler returns raw XML captured by GetRawXMLDiffHandler for [GET] /api/xml/raw?host=...&which=latest|previous .
+func GetRawXMLHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	capture := rawXML.Get(host, c.Query("which") == "previous")
+	if capture == nil {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, host, fmt.Errorf("raw XML not captured")))
+		return
+	}
+	c.Data(http.StatusOK, "text/xml", capture.XML)
+}
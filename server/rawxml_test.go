@@ -0,0 +1,50 @@
+package main
+
+import "testing"
+
+func TestRawXMLDiff(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	h := NewRawXMLHistory()
+	if prev, _, err := h.Capture(addr); err != nil || prev != nil {
+		t.Fatalf("expected first capture without previous, got %v %v", prev, err)
+	}
+	if err := SendFunctionTo(addr, "SetDynamicValue1", map[string]string{"Value": "Goal"}); err != nil {
+		t.Fatal(err)
+	}
+	prev, latest, err := h.Capture(addr)
+	if err != nil {
+		t.Fatal(err)
+	}
+	a, err := xmlLines(prev.XML)
+	if err != nil {
+		t.Fatal(err)
+	}
+	b, err := xmlLines(latest.XML)
+	if err != nil {
+		t.Fatal(err)
+	}
+	diff := DiffLines(a, b)
+	found := false
+	for _, line := range diff {
+		if line.Op == "+" && line.Text == "      Goal" {
+			found = true
+		}
+	}
+	if !found {
+		t.Fatalf("expected added dynamic value line, got %+v", diff)
+	}
+}
+
+func TestDiffLines(t *testing.T) {
+	diff := DiffLines([]string{"a", "b", "c", "d"}, []string{"a", "c", "e", "d"})
+	want := []XMLDiffLine{{Op: "-", Line: 2, Text: "b"}, {Op: "+", Line: 3, Text: "e"}}
+	if len(diff) != len(want) {
+		t.Fatalf("unexpected diff %+v", diff)
+	}
+	for i := range want {
+		if diff[i] != want[i] {
+			t.Fatalf("unexpected diff %+v", diff)
+		}
+	}
+}
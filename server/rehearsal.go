@@ -0,0 +1,64 @@
+package main
+
+import (
+	"log"
+	"net/http"
+	"sync/atomic"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// rehearsalEnabled is 1 while rehearsal mode is on. Accessed atomically.
+var rehearsalEnabled int32
+
+// RehearsalAction is function that would have been sent to vMix in rehearsal mode.
+type RehearsalAction struct {
+	Host     string            `json:"host"`
+	Function string            `json:"function"`
+	Params   map[string]string `json:"params"`
+	At       time.Time         `json:"at"`
+}
+
+// Rehearsal reports whether rehearsal mode is on.
+func Rehearsal() bool {
+	return atomic.LoadInt32(&rehearsalEnabled) == 1
+}
+
+// SetRehearsal turns rehearsal mode on or off.
+func SetRehearsal(enabled bool) {
+	var v int32
+	if enabled {
+		v = 1
+	}
+	if atomic.SwapInt32(&rehearsalEnabled, v) != v {
+		log.Printf("Rehearsal mode : %v\n", enabled)
+		events.Publish(Event{Topic: "rehearsal", Data: gin.H{"enabled": enabled}})
+	}
+}
+
+// rehearse logs and broadcasts function instead of sending it.
+func rehearse(addr, function string, params map[string]string) {
+	log.Printf("[REHEARSAL] %s %s %v\n", addr, function, params)
+	events.Publish(Event{Topic: "rehearsal_action", Host: addr, Data: RehearsalAction{Host: addr, Function: function, Params: params, At: time.Now()}})
+}
+
+// GetRehearsalHandler returns rehearsal mode for [GET] /api/rehearsal .
+func GetRehearsalHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"enabled": Rehearsal(),
+	})
+}
+
+// PutRehearsalHandler turns rehearsal mode on or off for [PUT] /api/rehearsal .
+func PutRehearsalHandler(c *gin.Context) {
+	req := struct {
+		Enabled bool `json:"enabled"`
+	}{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	SetRehearsal(req.Enabled)
+	GetRehearsalHandler(c)
+}
@@ -0,0 +1,157 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+
+	"github.com/gin-gonic/gin"
+)
+
+// ReplayPreset is parameters of "replay moment" for a sport or show. e.g. "football" .
+type ReplayPreset struct {
+	Seconds float64 `json:"seconds"` // length of moment, marked back from live.
+	Angle   int     `json:"angle"`   // replay camera 1-8. 0 keeps current angle.
+	Speed   float64 `json:"speed"`   // playback speed 0-1. 0 keeps current speed.
+	Channel string  `json:"channel"` // replay channel "A" or "B". Empty for both.
+	Play    bool    `json:"play"`    // play marked event to output immediately.
+}
+
+// validate returns error if preset cannot be sent to vMix.
+func (p ReplayPreset) validate() error {
+	switch {
+	case p.Seconds <= 0:
+		return fmt.Errorf("seconds must be positive")
+	case p.Angle < 0 || p.Angle > 8:
+		return fmt.Errorf("angle must be 1-8")
+	case p.Speed < 0 || p.Speed > 1:
+		return fmt.Errorf("speed must be 0-1")
+	case p.Channel != "" && p.Channel != "A" && p.Channel != "B":
+		return fmt.Errorf("unknown replay channel %q", p.Channel)
+	}
+	return nil
+}
+
+// ReplayMomentActions returns functions marking last seconds from live, selecting angle and speed and playing the event.
+func ReplayMomentActions(p ReplayPreset) []FunctionCall {
+	calls := []FunctionCall{
+		{Function: "ReplayMarkInOutLive", Params: map[string]string{"Value": strconv.FormatFloat(p.Seconds, 'f', -1, 64)}},
+	}
+	if p.Angle > 0 {
+		calls = append(calls, FunctionCall{Function: "Replay" + p.Channel + "Camera" + strconv.Itoa(p.Angle)})
+	}
+	if p.Speed > 0 {
+		call := FunctionCall{Function: "ReplaySetSpeed", Params: map[string]string{"Value": strconv.FormatFloat(p.Speed, 'f', -1, 64)}}
+		if p.Channel != "" {
+			call.Params["Channel"] = p.Channel
+		}
+		calls = append(calls, call)
+	}
+	if p.Play {
+		call := FunctionCall{Function: "ReplayPlayLastEventToOutput", Params: map[string]string{}}
+		if p.Channel != "" {
+			call.Params["Channel"] = p.Channel
+		}
+		calls = append(calls, call)
+	}
+	return calls
+}
+
+// ReplayMoment sends replay moment to host. Host must support instant replay.
+func ReplayMoment(host string, p ReplayPreset) error {
+	if err := p.validate(); err != nil {
+		return NewAppError(ErrKindInvalidRequest, host, err)
+	}
+	if err := RequireFeature(host, FeatureReplay); err != nil {
+		return err
+	}
+	poller.Touch()
+	for _, call := range ReplayMomentActions(p) {
+		if err := SendFunctionTo(host, call.Function, call.Params); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// GetReplayPresetsHandler returns replay presets for [GET] /api/replay/presets .
+func GetReplayPresetsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"presets": config.Get().ReplayPresets,
+	})
+}
+
+// PutReplayPresetsHandler saves replay presets for [PUT] /api/replay/presets .
+func PutReplayPresetsHandler(c *gin.Context) {
+	presets := map[string]ReplayPreset{}
+	if err := c.ShouldBindJSON(&presets); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for name, p := range presets {
+		if err := p.validate(); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("preset %s : %v", name, err)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.ReplayPresets = presets
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetReplayPresetsHandler(c)
+}
+
+// ReplayMomentRequest is request body of ReplayMomentHandler. Non-zero fields override preset.
+type ReplayMomentRequest struct {
+	Preset  string  `json:"preset"`
+	Seconds float64 `json:"seconds"`
+	Angle   int     `json:"angle"`
+	Speed   float64 `json:"speed"`
+	Channel string  `json:"channel"`
+	Play    *bool   `json:"play"`
+}
+
+// ReplayMomentHandler marks and plays replay moment for [POST] /api/replay/moment?host=... .
+// Intended to be bound to hotkeys, e.g. by Stream Deck or vMix shortcuts calling this URL.
+func ReplayMomentHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	req := ReplayMomentRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	p := ReplayPreset{}
+	if req.Preset != "" {
+		var ok bool
+		if p, ok = config.Get().ReplayPresets[req.Preset]; !ok {
+			AbortWithAppError(c, NewAppError(ErrKindNotFound, host, fmt.Errorf("replay preset %s not found", req.Preset)))
+			return
+		}
+	}
+	if req.Seconds > 0 {
+		p.Seconds = req.Seconds
+	}
+	if req.Angle > 0 {
+		p.Angle = req.Angle
+	}
+	if req.Speed > 0 {
+		p.Speed = req.Speed
+	}
+	if req.Channel != "" {
+		p.Channel = req.Channel
+	}
+	if req.Play != nil {
+		p.Play = *req.Play
+	}
+	if err := ReplayMoment(host, p); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	announceAction(c, host, fmt.Sprintf("replay %.1fs angle %d", p.Seconds, p.Angle))
+	c.JSON(http.StatusOK, gin.H{
+		"host":   host,
+		"preset": p,
+	})
+}
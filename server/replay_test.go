@@ -0,0 +1,19 @@
+package main
+
+import "testing"
+
+func TestReplayMomentActions(t *testing.T) {
+	calls := ReplayMomentActions(ReplayPreset{Seconds: 8, Angle: 3, Speed: 0.5, Channel: "B", Play: true})
+	if len(calls) != 4 {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	if calls[0].Params["Value"] != "8" || calls[1].Function != "ReplayBCamera3" || calls[2].Params["Value"] != "0.5" || calls[3].Params["Channel"] != "B" {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	if calls := ReplayMomentActions(ReplayPreset{Seconds: 5}); len(calls) != 1 {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	if err := (ReplayPreset{Seconds: 5, Angle: 9}).validate(); err == nil {
+		t.Fatal("expected invalid angle")
+	}
+}
@@ -0,0 +1,308 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// ScoreboardTeam is one side of scoreboard.
+type ScoreboardTeam struct {
+	Name  string `json:"name"`
+	Score int    `json:"score"`
+}
+
+// Scoreboard is score data rendered by scoreboard graphic, and optionally pushed to vMix title.
+type Scoreboard struct {
+	Name   string         `json:"name"`
+	Home   ScoreboardTeam `json:"home"`
+	Away   ScoreboardTeam `json:"away"`
+	Period string         `json:"period"` // e.g. "1st", "HT" .
+	Clock  string         `json:"clock"`  // game clock. e.g. "12:34" .
+	// BindClock makes Clock follow show clock, so game clock is run by clock commands and pushed every second.
+	BindClock bool `json:"bind_clock"`
+
+	// TitleInput is vMix title input updated on change. Empty to disable.
+	TitleInput string `json:"title_input"`
+	// TitleFields maps scoreboard field ("home", "home_score", "away", "away_score", "period", "clock") to title text name. e.g. "HomeScore.Text" .
+	TitleFields map[string]string `json:"title_fields"`
+}
+
+// Fields returns fields used by scoreboard graphic template.
+func (s *Scoreboard) Fields() map[string]string {
+	return map[string]string{
+		"home":       s.Home.Name,
+		"home_score": strconv.Itoa(s.Home.Score),
+		"away":       s.Away.Name,
+		"away_score": strconv.Itoa(s.Away.Score),
+		"period":     s.Period,
+		"clock":      s.Clock,
+	}
+}
+
+// ScoreboardCommand is control command for scoreboard.
+type ScoreboardCommand struct {
+	Command string `json:"command"` // "score", "set_score", "set_team", "period", "clock", "swap" or "reset".
+	Team    string `json:"team"`    // "home" or "away", for score/set_score/set_team.
+	Value   string `json:"value"`   // team name, period, clock or score.
+	Delta   int    `json:"delta"`   // score delta for "score".
+}
+
+// Apply executes command on scoreboard.
+func (s *Scoreboard) Apply(cmd ScoreboardCommand) error {
+	team := func() (*ScoreboardTeam, error) {
+		switch cmd.Team {
+		case "home":
+			return &s.Home, nil
+		case "away":
+			return &s.Away, nil
+		}
+		return nil, fmt.Errorf("unknown team %q", cmd.Team)
+	}
+	switch cmd.Command {
+	case "score":
+		t, err := team()
+		if err != nil {
+			return err
+		}
+		t.Score += cmd.Delta
+		if t.Score < 0 {
+			t.Score = 0
+		}
+	case "set_score":
+		t, err := team()
+		if err != nil {
+			return err
+		}
+		n, err := strconv.Atoi(cmd.Value)
+		if err != nil || n < 0 {
+			return fmt.Errorf("invalid score %q", cmd.Value)
+		}
+		t.Score = n
+	case "set_team":
+		t, err := team()
+		if err != nil {
+			return err
+		}
+		t.Name = cmd.Value
+	case "period":
+		s.Period = cmd.Value
+	case "clock":
+		if s.BindClock {
+			return fmt.Errorf("clock follows show clock. Use clock commands instead")
+		}
+		s.Clock = cmd.Value
+	case "swap":
+		s.Home, s.Away = s.Away, s.Home
+	case "reset":
+		s.Home.Score, s.Away.Score = 0, 0
+		s.Period = ""
+		if !s.BindClock {
+			s.Clock = ""
+		}
+	default:
+		return fmt.Errorf("unknown command %q", cmd.Command)
+	}
+	return nil
+}
+
+// scoreboardSaveDelay is delay before changed scoreboards are saved, so bursts of score commands save settings once.
+const scoreboardSaveDelay = 2 * time.Second
+
+// ScoreboardStore keeps scoreboards, mirrors them to graphics and saves them to settings, so scores survive restart.
+type ScoreboardStore struct {
+	mu          sync.Mutex
+	scoreboards map[string]*Scoreboard
+	dirty       map[string]bool // changed since last save.
+	saveTimer   *time.Timer     // pending save, nil if none.
+}
+
+// NewScoreboardStore creates ScoreboardStore.
+func NewScoreboardStore() *ScoreboardStore {
+	return &ScoreboardStore{
+		scoreboards: make(map[string]*Scoreboard),
+		dirty:       make(map[string]bool),
+	}
+}
+
+// Restore loads scoreboards saved in settings and mirrors them to graphics, without updating vMix titles.
+func (s *ScoreboardStore) Restore(saved map[string]Scoreboard) {
+	s.mu.Lock()
+	for name, sb := range saved {
+		sb := sb
+		sb.Name = name
+		s.scoreboards[name] = &sb
+	}
+	s.mu.Unlock()
+	for name, sb := range saved {
+		fields := sb.Fields()
+		graphics.Update(name, func(g *Graphic) {
+			g.Template = GraphicTemplateScoreboard
+			g.Fields = fields
+		})
+	}
+}
+
+// Get returns copy of scoreboard.
+func (s *ScoreboardStore) Get(name string) (Scoreboard, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	sb, ok := s.scoreboards[name]
+	if !ok {
+		return Scoreboard{}, false
+	}
+	return *sb, true
+}
+
+// Update modifies scoreboard with fn, creating it if missing, then updates graphic and vMix title.
+func (s *ScoreboardStore) Update(name string, fn func(sb *Scoreboard) error) (Scoreboard, error) {
+	s.mu.Lock()
+	sb, ok := s.scoreboards[name]
+	if !ok {
+		sb = &Scoreboard{Name: name, Home: ScoreboardTeam{Name: "HOME"}, Away: ScoreboardTeam{Name: "AWAY"}}
+	}
+	updated := *sb
+	if err := fn(&updated); err != nil {
+		s.mu.Unlock()
+		return Scoreboard{}, err
+	}
+	updated.Name = name
+	if updated.BindClock {
+		updated.Clock = showClock.Status().Display
+	}
+	s.scoreboards[name] = &updated
+	s.dirty[name] = true
+	if s.saveTimer == nil {
+		s.saveTimer = time.AfterFunc(scoreboardSaveDelay, s.Flush)
+	}
+	s.mu.Unlock()
+
+	fields := updated.Fields()
+	graphics.Update(name, func(g *Graphic) {
+		g.Template = GraphicTemplateScoreboard
+		g.Fields = fields
+	})
+	pushScoreboardTitle(updated, fields, SendFunctionTo)
+	return updated, nil
+}
+
+// Flush saves changed scoreboards to settings now, instead of after scoreboardSaveDelay.
+func (s *ScoreboardStore) Flush() {
+	s.mu.Lock()
+	if s.saveTimer != nil {
+		s.saveTimer.Stop()
+		s.saveTimer = nil
+	}
+	changed := make(map[string]Scoreboard, len(s.dirty))
+	for name := range s.dirty {
+		changed[name] = *s.scoreboards[name]
+	}
+	s.dirty = make(map[string]bool)
+	s.mu.Unlock()
+	if len(changed) == 0 {
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		for name, sb := range changed {
+			cfg.Scoreboards[name] = sb
+		}
+	}); err != nil {
+		log.Printf("Failed to save scoreboards : %v\n", err)
+	}
+}
+
+// ClockTick updates clock of scoreboards bound to show clock, and mirrors it to graphics and vMix titles.
+// Bound clock is not saved, since it is restored from show clock.
+func (s *ScoreboardStore) ClockTick(display string) {
+	s.mu.Lock()
+	bound := make([]Scoreboard, 0)
+	for _, sb := range s.scoreboards {
+		if sb.BindClock && sb.Clock != display {
+			sb.Clock = display
+			bound = append(bound, *sb)
+		}
+	}
+	s.mu.Unlock()
+	for _, sb := range bound {
+		fields := sb.Fields()
+		graphics.Update(sb.Name, func(g *Graphic) {
+			g.Template = GraphicTemplateScoreboard
+			g.Fields = fields
+		})
+		pushScoreboardTitle(sb, map[string]string{"clock": display}, SendTitleUpdate)
+	}
+}
+
+// pushScoreboardTitle sends SetText functions for mapped title fields by send.
+// Bound clock is sent by SendTitleUpdate, since it changes every second.
+func pushScoreboardTitle(sb Scoreboard, fields map[string]string, send func(addr, function string, params map[string]string) error) {
+	if sb.TitleInput == "" || vmix == nil {
+		return
+	}
+	for field, selected := range sb.TitleFields {
+		value, ok := fields[field]
+		if !ok {
+			continue
+		}
+		params := map[string]string{"Input": sb.TitleInput, "SelectedName": selected, "Value": value}
+		if err := send(*vmixaddr, "SetText", params); err != nil {
+			log.Printf("Failed to update scoreboard title %s field %s : %v\n", sb.TitleInput, selected, err)
+		}
+	}
+}
+
+// GetScoreboardHandler returns scoreboard for [GET] /api/scoreboards/:name .
+func GetScoreboardHandler(c *gin.Context) {
+	sb, ok := scoreboards.Get(c.Param("name"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("scoreboard %q not found", c.Param("name"))))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"scoreboard": sb,
+	})
+}
+
+// PutScoreboardHandler creates or replaces scoreboard for [PUT] /api/scoreboards/:name .
+func PutScoreboardHandler(c *gin.Context) {
+	req := Scoreboard{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	sb, err := scoreboards.Update(c.Param("name"), func(sb *Scoreboard) error {
+		*sb = req
+		return nil
+	})
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"scoreboard": sb,
+	})
+}
+
+// ScoreboardCommandHandler executes control command for [POST] /api/scoreboards/:name/command .
+func ScoreboardCommandHandler(c *gin.Context) {
+	cmd := ScoreboardCommand{}
+	if err := c.ShouldBindJSON(&cmd); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	sb, err := scoreboards.Update(c.Param("name"), func(sb *Scoreboard) error {
+		return sb.Apply(cmd)
+	})
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"scoreboard": sb,
+	})
+}
@@ -0,0 +1,81 @@
+package main
+
+import "testing"
+
+func TestScoreboardPersisted(t *testing.T) {
+	defer config.Update(func(cfg *Config) {
+		delete(cfg.Scoreboards, "scoreboard_test")
+	})
+	if _, err := scoreboards.Update("scoreboard_test", func(sb *Scoreboard) error {
+		sb.Home.Name = "Tigers"
+		return sb.Apply(ScoreboardCommand{Command: "score", Team: "home", Delta: 3})
+	}); err != nil {
+		t.Fatal(err)
+	}
+	scoreboards.Flush()
+
+	// store of restarted utility.
+	restarted := NewScoreboardStore()
+	restarted.Restore(config.Get().Scoreboards)
+	sb, ok := restarted.Get("scoreboard_test")
+	if !ok || sb.Home.Name != "Tigers" || sb.Home.Score != 3 || sb.Away.Name != "AWAY" {
+		t.Fatalf("expected scoreboard restored from settings, got %+v %v", sb, ok)
+	}
+	if g, ok := graphics.Get("scoreboard_test"); !ok || g.Template != GraphicTemplateScoreboard || g.Fields["home_score"] != "3" {
+		t.Fatalf("expected restored scoreboard mirrored to graphic, got %+v", g)
+	}
+}
+
+func TestScoreboardSaveDebounced(t *testing.T) {
+	store := NewScoreboardStore()
+	defer config.Update(func(cfg *Config) {
+		delete(cfg.Scoreboards, "scoreboard_debounce_test")
+	})
+	for i := 0; i < 5; i++ {
+		if _, err := store.Update("scoreboard_debounce_test", func(sb *Scoreboard) error {
+			return sb.Apply(ScoreboardCommand{Command: "score", Team: "away", Delta: 1})
+		}); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if _, ok := config.Get().Scoreboards["scoreboard_debounce_test"]; ok {
+		t.Fatal("expected score commands not saved until save delay")
+	}
+	store.Flush()
+	if sb := config.Get().Scoreboards["scoreboard_debounce_test"]; sb.Away.Score != 5 {
+		t.Fatalf("expected last score saved once flushed, got %+v", sb)
+	}
+}
+
+func TestScoreboardClock(t *testing.T) {
+	store := NewScoreboardStore()
+	sb, err := store.Update("scoreboard_clock_test", func(sb *Scoreboard) error {
+		return sb.Apply(ScoreboardCommand{Command: "clock", Value: "12:34"})
+	})
+	if err != nil || sb.Clock != "12:34" || sb.Fields()["clock"] != "12:34" {
+		t.Fatalf("expected clock set by command, got %+v %v", sb, err)
+	}
+
+	if _, err := store.Update("scoreboard_clock_test", func(sb *Scoreboard) error {
+		sb.BindClock = true
+		return nil
+	}); err != nil {
+		t.Fatal(err)
+	}
+	store.ClockTick("00:00:05")
+	if sb, _ := store.Get("scoreboard_clock_test"); sb.Clock != "00:00:05" {
+		t.Fatalf("expected bound clock to follow show clock, got %q", sb.Clock)
+	}
+	if g, _ := graphics.Get("scoreboard_clock_test"); g.Fields["clock"] != "00:00:05" {
+		t.Fatalf("expected bound clock mirrored to graphic, got %+v", g.Fields)
+	}
+	if _, err := store.Update("scoreboard_clock_test", func(sb *Scoreboard) error {
+		return sb.Apply(ScoreboardCommand{Command: "clock", Value: "00:00"})
+	}); err == nil {
+		t.Fatal("expected clock command rejected while bound to show clock")
+	}
+	store.Flush()
+	config.Update(func(cfg *Config) {
+		delete(cfg.Scoreboards, "scoreboard_clock_test")
+	})
+}
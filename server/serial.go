@@ -0,0 +1,199 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// serialReconnectInterval is wait time before reopening serial port.
+const serialReconnectInterval = 3 * time.Second
+
+// SerialSettings is serial port trigger configuration, for contact closure boxes or Arduino GPIO bridges.
+type SerialSettings struct {
+	Port     string          `json:"port"` // e.g. "COM3" or "/dev/ttyUSB0". Empty to disable.
+	Baud     int             `json:"baud"` // e.g. 9600 .
+	Mode     string          `json:"mode"` // "line" matches each line, "byte" matches each received byte.
+	Mappings []SerialMapping `json:"mappings"`
+}
+
+// SerialMapping maps received line or byte to action, or to shortcut by name.
+type SerialMapping struct {
+	Match    string        `json:"match"`              // e.g. "GPI1" in line mode, "A" in byte mode.
+	Shortcut string        `json:"shortcut,omitempty"` // runs shortcut instead of action if set.
+	Action   TriggerAction `json:"action"`
+}
+
+// action returns action of mapping. Shortcut is looked up when fired, so edits of shortcut apply without reconfiguring.
+func (m SerialMapping) action() (TriggerAction, error) {
+	if m.Shortcut == "" {
+		return m.Action, nil
+	}
+	sc, ok := findShortcut(m.Shortcut)
+	if !ok {
+		return TriggerAction{}, NewAppError(ErrKindNotFound, "", fmt.Errorf("shortcut %q not found", m.Shortcut))
+	}
+	return TriggerAction{Host: sc.Host, FunctionCall: sc.FunctionCall}, nil
+}
+
+// SerialTrigger listens serial port and fires mapped actions.
+type SerialTrigger struct {
+	mu       sync.Mutex
+	settings SerialSettings
+	port     io.ReadCloser
+	restart  chan struct{}
+	lastErr  string
+}
+
+// NewSerialTrigger creates SerialTrigger. Call Run to start listening.
+func NewSerialTrigger() *SerialTrigger {
+	return &SerialTrigger{restart: make(chan struct{}, 1)}
+}
+
+// Configure replaces settings and reopens port.
+func (t *SerialTrigger) Configure(settings SerialSettings) error {
+	if settings.Mode == "" {
+		settings.Mode = "line"
+	}
+	if settings.Mode != "line" && settings.Mode != "byte" {
+		return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown mode %q", settings.Mode))
+	}
+	if settings.Port != "" && settings.Baud <= 0 {
+		return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid baud rate %d", settings.Baud))
+	}
+	for _, m := range settings.Mappings {
+		if m.Shortcut == "" && m.Action.Function == "" {
+			return NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("mapping %q has neither shortcut nor function", m.Match))
+		}
+	}
+	t.mu.Lock()
+	t.settings = settings
+	port := t.port
+	t.mu.Unlock()
+	if port != nil {
+		port.Close() // unblocks read loop.
+	}
+	select {
+	case t.restart <- struct{}{}:
+	default:
+	}
+	return nil
+}
+
+// Settings returns current settings.
+func (t *SerialTrigger) Settings() SerialSettings {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.settings
+}
+
+// Status returns settings and last error.
+func (t *SerialTrigger) Status() gin.H {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return gin.H{
+		"settings": t.settings,
+		"open":     t.port != nil,
+		"error":    t.lastErr,
+	}
+}
+
+// Run opens configured port and reads it forever, reopening on failure or configuration change.
+func (t *SerialTrigger) Run() {
+	for {
+		t.mu.Lock()
+		settings := t.settings
+		t.mu.Unlock()
+		if settings.Port != "" {
+			if err := t.runOnce(settings); err != nil {
+				log.Printf("Serial trigger %s : %v\n", settings.Port, err)
+				t.mu.Lock()
+				t.lastErr = err.Error()
+				t.mu.Unlock()
+			}
+		}
+		select {
+		case <-t.restart:
+		case <-time.After(serialReconnectInterval):
+		}
+	}
+}
+
+func (t *SerialTrigger) runOnce(settings SerialSettings) error {
+	port, err := openSerialPort(settings.Port, settings.Baud)
+	if err != nil {
+		return err
+	}
+	t.mu.Lock()
+	t.port = port
+	t.lastErr = ""
+	t.mu.Unlock()
+	defer func() {
+		t.mu.Lock()
+		t.port = nil
+		t.mu.Unlock()
+		port.Close()
+	}()
+	log.Printf("Serial trigger listening on %s (%d baud)\n", settings.Port, settings.Baud)
+
+	r := bufio.NewReader(port)
+	for {
+		var received string
+		if settings.Mode == "byte" {
+			b, err := r.ReadByte()
+			if err != nil {
+				return err
+			}
+			received = string([]byte{b})
+		} else {
+			line, err := r.ReadString('\n')
+			if err != nil {
+				return err
+			}
+			received = strings.TrimSpace(line)
+		}
+		for _, m := range settings.Mappings {
+			if m.Match != received {
+				continue
+			}
+			action, err := m.action()
+			if err != nil {
+				log.Printf("Serial trigger %s : %v\n", settings.Port, err)
+				continue
+			}
+			go action.Fire("serial " + settings.Port)
+		}
+	}
+}
+
+// GetSerialTriggerHandler returns serial trigger settings for [GET] /api/triggers/serial .
+func GetSerialTriggerHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, serialTrigger.Status())
+}
+
+// PutSerialTriggerHandler saves serial trigger settings for [PUT] /api/triggers/serial .
+func PutSerialTriggerHandler(c *gin.Context) {
+	settings := SerialSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := serialTrigger.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.SerialTrigger = serialTrigger.Settings()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetSerialTriggerHandler(c)
+}
@@ -0,0 +1,23 @@
+package main
+
+import "testing"
+
+func TestSerialMappingShortcut(t *testing.T) {
+	config.Update(func(cfg *Config) {
+		cfg.Shortcuts = append(cfg.Shortcuts, Shortcut{Name: "serial_test", Host: "http://b:8088", FunctionCall: FunctionCall{Function: "Fade"}})
+	})
+	defer config.Update(func(cfg *Config) {
+		cfg.Shortcuts = cfg.Shortcuts[:len(cfg.Shortcuts)-1]
+	})
+
+	action, err := SerialMapping{Match: "GPI1", Shortcut: "serial_test"}.action()
+	if err != nil || action.Host != "http://b:8088" || action.Function != "Fade" {
+		t.Fatalf("expected shortcut action, got %+v %v", action, err)
+	}
+	if _, err := (SerialMapping{Match: "GPI2", Shortcut: "missing"}).action(); AsAppError(err).Kind != ErrKindNotFound {
+		t.Fatalf("expected missing shortcut reported, got %v", err)
+	}
+	if err := NewSerialTrigger().Configure(SerialSettings{Mappings: []SerialMapping{{Match: "GPI3"}}}); err == nil {
+		t.Fatal("expected mapping without target refused")
+	}
+}
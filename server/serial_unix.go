@@ -0,0 +1,29 @@
+// +build !windows
+
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"runtime"
+	"strconv"
+)
+
+// openSerialPort opens tty device as raw 8N1 with baud rate, configured by stty.
+func openSerialPort(port string, baud int) (io.ReadCloser, error) {
+	f, err := os.OpenFile(port, os.O_RDWR, 0)
+	if err != nil {
+		return nil, err
+	}
+	flag := "-F"
+	if runtime.GOOS == "darwin" {
+		flag = "-f"
+	}
+	if out, err := exec.Command("stty", flag, port, strconv.Itoa(baud), "raw", "cs8", "-cstopb", "-parenb", "-echo").CombinedOutput(); err != nil {
+		f.Close()
+		return nil, fmt.Errorf("stty : %v : %s", err, out)
+	}
+	return f, nil
+}
@@ -0,0 +1,107 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"sync/atomic"
+	"syscall"
+	"unsafe"
+)
+
+var (
+	kernel32            = syscall.NewLazyDLL("kernel32.dll")
+	procGetCommState    = kernel32.NewProc("GetCommState")
+	procSetCommState    = kernel32.NewProc("SetCommState")
+	procSetCommTimeouts = kernel32.NewProc("SetCommTimeouts")
+)
+
+// serialReadTimeoutMs bounds each read, so closing port never waits for next byte from device.
+const serialReadTimeoutMs = 200
+
+// dcb is Win32 DCB structure. Bit fields are packed into flags.
+type dcb struct {
+	DCBlength  uint32
+	BaudRate   uint32
+	flags      uint32
+	wReserved  uint16
+	XonLim     uint16
+	XoffLim    uint16
+	ByteSize   byte
+	Parity     byte
+	StopBits   byte
+	XonChar    byte
+	XoffChar   byte
+	ErrorChar  byte
+	EofChar    byte
+	EvtChar    byte
+	wReserved1 uint16
+}
+
+// commTimeouts is Win32 COMMTIMEOUTS structure.
+type commTimeouts struct {
+	ReadIntervalTimeout         uint32
+	ReadTotalTimeoutMultiplier  uint32
+	ReadTotalTimeoutConstant    uint32
+	WriteTotalTimeoutMultiplier uint32
+	WriteTotalTimeoutConstant   uint32
+}
+
+// comPort is COM port whose reads time out. Timed out read is retried until port is closed,
+// since synchronous read blocks Close of COM port on Windows until it returns.
+type comPort struct {
+	f      *os.File
+	closed int32
+}
+
+func (p *comPort) Read(b []byte) (int, error) {
+	for {
+		n, err := p.f.Read(b)
+		if n > 0 || (err != nil && err != io.EOF) {
+			return n, err
+		}
+		// read timed out with nothing received, reported as EOF.
+		if atomic.LoadInt32(&p.closed) != 0 {
+			return 0, os.ErrClosed
+		}
+	}
+}
+
+func (p *comPort) Close() error {
+	atomic.StoreInt32(&p.closed, 1)
+	return p.f.Close()
+}
+
+// openSerialPort opens COM port as 8N1 with baud rate.
+func openSerialPort(port string, baud int) (io.ReadCloser, error) {
+	f, err := os.OpenFile(`\\.\`+port, os.O_RDWR, 0)
+	if err != nil {
+		return nil, err
+	}
+	d := dcb{}
+	d.DCBlength = uint32(unsafe.Sizeof(d))
+	if r, _, err := procGetCommState.Call(f.Fd(), uintptr(unsafe.Pointer(&d))); r == 0 {
+		f.Close()
+		return nil, fmt.Errorf("GetCommState : %v", err)
+	}
+	d.BaudRate = uint32(baud)
+	d.flags = 0x1 // fBinary
+	d.ByteSize = 8
+	d.Parity = 0   // NOPARITY
+	d.StopBits = 0 // ONESTOPBIT
+	if r, _, err := procSetCommState.Call(f.Fd(), uintptr(unsafe.Pointer(&d))); r == 0 {
+		f.Close()
+		return nil, fmt.Errorf("SetCommState : %v", err)
+	}
+	// return as soon as any byte arrives, or after timeout with nothing received.
+	timeouts := commTimeouts{
+		ReadIntervalTimeout:        0xFFFFFFFF, // MAXDWORD
+		ReadTotalTimeoutMultiplier: 0xFFFFFFFF,
+		ReadTotalTimeoutConstant:   serialReadTimeoutMs,
+	}
+	if r, _, err := procSetCommTimeouts.Call(f.Fd(), uintptr(unsafe.Pointer(&timeouts))); r == 0 {
+		f.Close()
+		return nil, fmt.Errorf("SetCommTimeouts : %v", err)
+	}
+	return &comPort{f: f}, nil
+}
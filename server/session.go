@@ -0,0 +1,52 @@
+package main
+
+import (
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// maxSessionWindows is maximum windows remembered per session.
+const maxSessionWindows = 16
+
+// Session is frontend state of operator, saved on change and restored after crash or update restart.
+type Session struct {
+	Route     string    `json:"route"`   // current route. e.g. "/tree" .
+	Host      string    `json:"host"`    // last selected vMix host.
+	Windows   []string  `json:"windows"` // routes of windows opened from main window. e.g. "/output/dual" .
+	UpdatedAt time.Time `json:"updated_at"`
+}
+
+// GetSessionHandler returns saved session of operator for [GET] /api/session .
+func GetSessionHandler(c *gin.Context) {
+	session, ok := config.Get().Sessions[operatorName(c)]
+	if session.Windows == nil {
+		session.Windows = []string{}
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"session": session,
+		"saved":   ok,
+	})
+}
+
+// PutSessionHandler saves session of operator for [PUT] /api/session .
+func PutSessionHandler(c *gin.Context) {
+	session := Session{}
+	if err := c.ShouldBindJSON(&session); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if len(session.Windows) > maxSessionWindows {
+		session.Windows = session.Windows[len(session.Windows)-maxSessionWindows:]
+	}
+	session.UpdatedAt = time.Now()
+	name := operatorName(c)
+	if err := config.Update(func(cfg *Config) {
+		cfg.Sessions[name] = session
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetSessionHandler(c)
+}
@@ -0,0 +1,34 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestSessionRoundTrip(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/session", GetSessionHandler)
+	r.PUT("/api/session", PutSessionHandler)
+	defer config.Update(func(cfg *Config) { delete(cfg.Sessions, "director") })
+
+	req := httptest.NewRequest(http.MethodPut, "/api/session", strings.NewReader(`{"route":"/tree","windows":["#/output/dual"]}`))
+	req.Header.Set(operatorHeader, "director")
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("unexpected status %d : %s", w.Code, w.Body)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/api/session", nil)
+	req.Header.Set(operatorHeader, "director")
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, req)
+	if body := w.Body.String(); !strings.Contains(body, `"route":"/tree"`) || !strings.Contains(body, `"saved":true`) {
+		t.Fatalf("session not restored : %s", body)
+	}
+}
@@ -0,0 +1,319 @@
+package main
+
+import (
+	"fmt"
+	"io/ioutil"
+	"net"
+	"net/http"
+	"net/url"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+const (
+	setupDialTimeout  = 300 * time.Millisecond // per address while scanning subnet.
+	setupFetchTimeout = 2 * time.Second
+	setupConcurrency  = 64
+	setupMaxHosts     = 254 // subnets larger than /24 are scanned around own address only.
+)
+
+// setupInputGroups maps vMix input type to default input group created by first-run setup.
+var setupInputGroups = map[string]string{
+	"Capture":   "Cameras",
+	"NDI":       "Cameras",
+	"Stream":    "Cameras",
+	"VideoCall": "Callers",
+	"Video":     "Videos",
+	"VideoList": "Videos",
+	"GT":        "Titles",
+	"Xaml":      "Titles",
+	"AudioFile": "Audio",
+}
+
+// DiscoveredHost is vMix instance found by setup scan.
+type DiscoveredHost struct {
+	Name    string `json:"name"` // suggested connection name, from reverse DNS or address.
+	Addr    string `json:"addr"` // e.g. "http://192.168.0.20:8088" .
+	Version string `json:"version"`
+	Edition string `json:"edition"`
+	Inputs  int    `json:"inputs"`
+	Local   bool   `json:"local"` // running on this machine.
+}
+
+// probeVMixHost checks vMix HTTP API answers at ip.
+func probeVMixHost(ip string, port int, dialTimeout time.Duration) (DiscoveredHost, bool) {
+	hostPort := net.JoinHostPort(ip, strconv.Itoa(port))
+	conn, err := net.DialTimeout("tcp", hostPort, dialTimeout)
+	if err != nil {
+		return DiscoveredHost{}, false
+	}
+	conn.Close()
+	client := &http.Client{Timeout: setupFetchTimeout}
+	resp, err := client.Get("http://" + hostPort + "/api")
+	if err != nil {
+		return DiscoveredHost{}, false
+	}
+	defer resp.Body.Close()
+	b, err := ioutil.ReadAll(resp.Body)
+	if err != nil || resp.StatusCode != http.StatusOK {
+		return DiscoveredHost{}, false
+	}
+	s, err := ParseState(b)
+	if err != nil {
+		return DiscoveredHost{}, false
+	}
+	h := DiscoveredHost{Name: ip, Addr: "http://" + hostPort, Version: s.Version, Edition: s.Edition, Inputs: len(s.Inputs)}
+	if parsed := net.ParseIP(ip); parsed != nil && parsed.IsLoopback() {
+		h.Name, h.Local = "local", true
+	} else if names, err := net.LookupAddr(ip); err == nil && len(names) > 0 {
+		h.Name = strings.SplitN(strings.TrimSuffix(names[0], "."), ".", 2)[0]
+	}
+	return h, true
+}
+
+// ScanVMixHosts probes ips concurrently and returns vMix instances found, in order of ips.
+func ScanVMixHosts(ips []string, port int, dialTimeout time.Duration) []DiscoveredHost {
+	results := make([]*DiscoveredHost, len(ips))
+	sem := make(chan struct{}, setupConcurrency)
+	wg := &sync.WaitGroup{}
+	for i, ip := range ips {
+		wg.Add(1)
+		sem <- struct{}{}
+		go func(i int, ip string) {
+			defer wg.Done()
+			defer func() { <-sem }()
+			if h, ok := probeVMixHost(ip, port, dialTimeout); ok {
+				results[i] = &h
+			}
+		}(i, ip)
+	}
+	wg.Wait()
+	found := []DiscoveredHost{}
+	for _, h := range results {
+		if h != nil {
+			found = append(found, *h)
+		}
+	}
+	return found
+}
+
+// primaryIPv4Net returns address and network of interface carrying default route.
+// Dialing UDP sends no packet, but selects source address as routing would.
+func primaryIPv4Net() (net.IP, *net.IPNet, error) {
+	conn, err := net.Dial("udp4", "192.0.2.1:9")
+	if err != nil {
+		return nil, nil, err
+	}
+	local := conn.LocalAddr().(*net.UDPAddr).IP.To4()
+	conn.Close()
+	addrs, err := net.InterfaceAddrs()
+	if err != nil {
+		return nil, nil, err
+	}
+	for _, a := range addrs {
+		if ipnet, ok := a.(*net.IPNet); ok && ipnet.IP.Equal(local) {
+			return local, ipnet, nil
+		}
+	}
+	return nil, nil, fmt.Errorf("network of %s not found", local)
+}
+
+// subnetHosts returns host addresses of network except ip itself, limited to /24 around ip.
+func subnetHosts(ip net.IP, ipnet *net.IPNet) []string {
+	ones, bits := ipnet.Mask.Size()
+	if bits-ones > 8 {
+		ipnet = &net.IPNet{IP: ip.Mask(net.CIDRMask(24, 32)), Mask: net.CIDRMask(24, 32)}
+		ones = 24
+	}
+	base := ipnet.IP.To4().Mask(ipnet.Mask)
+	hosts := []string{}
+	for i := 1; i < 1<<(32-ones)-1 && len(hosts) < setupMaxHosts; i++ {
+		h := net.IPv4(base[0], base[1], base[2], base[3]+byte(i))
+		if !h.Equal(ip) {
+			hosts = append(hosts, h.String())
+		}
+	}
+	return hosts
+}
+
+// SetupScan is result of first-run detection.
+type SetupScan struct {
+	FirstRun bool             `json:"first_run"` // settings were never saved.
+	Local    *DiscoveredHost  `json:"local"`     // vMix on this machine. Nil if not running.
+	Network  string           `json:"network"`   // scanned network. e.g. "192.168.0.0/24" .
+	Hosts    []DiscoveredHost `json:"hosts"`     // vMix instances found on network.
+}
+
+// DetectSetup detects vMix on this machine and, if scan is true, on network of primary interface.
+func DetectSetup(scan bool) (SetupScan, error) {
+	result := SetupScan{FirstRun: config.Get().UpdatedAt.IsZero(), Hosts: []DiscoveredHost{}}
+	if h, ok := probeVMixHost("127.0.0.1", diagnoseDefaultPort, setupFetchTimeout); ok {
+		result.Local = &h
+	}
+	if !scan {
+		return result, nil
+	}
+	ip, ipnet, err := primaryIPv4Net()
+	if err != nil {
+		return result, NewAppError(ErrKindInternal, "", err)
+	}
+	result.Network = ipnet.String()
+	result.Hosts = ScanVMixHosts(subnetHosts(ip, ipnet), diagnoseDefaultPort, setupDialTimeout)
+	return result, nil
+}
+
+// FirstRunSetupRequest is request body of RunFirstTimeSetup.
+type FirstRunSetupRequest struct {
+	Connections []Connection `json:"connections"`  // additional hosts to poll, such as suggested by scan.
+	HostGroup   string       `json:"host_group"`   // host group of primary and every connection. e.g. "all" . Empty to skip.
+	InputGroups bool         `json:"input_groups"` // group inputs of every host by type. e.g. "Cameras" .
+	Aliases     bool         `json:"aliases"`      // label cameras of every host "CAM 1", "CAM 2", ... .
+	Force       bool         `json:"force"`        // run even if settings were already saved.
+}
+
+// defaultInputLabels returns input groups by type and camera aliases for state.
+func defaultInputLabels(s *State) (map[string][]string, map[string]string) {
+	groups := map[string][]string{}
+	aliases := map[string]string{}
+	inputs := append([]StateInput{}, s.Inputs...)
+	sort.Slice(inputs, func(i, j int) bool { return inputs[i].Number < inputs[j].Number })
+	for _, in := range inputs {
+		group, ok := setupInputGroups[in.Type]
+		if !ok {
+			continue
+		}
+		groups[group] = append(groups[group], in.Key)
+		if group == "Cameras" {
+			aliases["CAM "+strconv.Itoa(len(groups[group]))] = in.Key
+		}
+	}
+	return groups, aliases
+}
+
+// RunFirstTimeSetup validates connections, reads inputs of every host and writes connections, groups and labels
+// in single settings update, so setup either applies entirely or leaves settings untouched.
+// Existing input groups and aliases of same name are kept.
+func RunFirstTimeSetup(req FirstRunSetupRequest) (Config, error) {
+	if !req.Force && !config.Get().UpdatedAt.IsZero() {
+		return Config{}, NewAppError(ErrKindBlocked, "", fmt.Errorf("settings already exist. Set force to run setup again"))
+	}
+	names := map[string]bool{"primary": true}
+	addrs := []string{*vmixaddr}
+	for _, conn := range req.Connections {
+		u, err := url.Parse(conn.Addr)
+		if err != nil || (u.Scheme != "http" && u.Scheme != "https") || u.Host == "" {
+			return Config{}, NewAppError(ErrKindInvalidRequest, conn.Addr, fmt.Errorf("invalid vMix address %q", conn.Addr))
+		}
+		if conn.Name == "" || names[conn.Name] {
+			return Config{}, NewAppError(ErrKindInvalidRequest, conn.Addr, fmt.Errorf("connection name %q is empty or duplicated", conn.Name))
+		}
+		if conn.Priority != "" && conn.Priority != ConnectionPrimary && conn.Priority != ConnectionSecondary {
+			return Config{}, NewAppError(ErrKindInvalidRequest, conn.Addr, fmt.Errorf("unknown priority %q", conn.Priority))
+		}
+		names[conn.Name] = true
+		if conn.Addr != *vmixaddr {
+			addrs = append(addrs, conn.Addr)
+		}
+	}
+
+	groups := map[string]map[string][]string{}
+	aliases := map[string]map[string]string{}
+	if req.InputGroups || req.Aliases {
+		for _, addr := range addrs {
+			s, err := StreamState(addr)
+			if err != nil {
+				return Config{}, err
+			}
+			groups[addr], aliases[addr] = defaultInputLabels(s)
+		}
+	}
+
+	err := config.Update(func(cfg *Config) {
+		for _, conn := range req.Connections {
+			exists := false
+			for i := range cfg.Connections {
+				if cfg.Connections[i].Addr == conn.Addr {
+					cfg.Connections[i] = conn
+					exists = true
+				}
+			}
+			if !exists && conn.Addr != *vmixaddr {
+				cfg.Connections = append(cfg.Connections, conn)
+			}
+		}
+		if req.HostGroup != "" {
+			cfg.HostGroups[req.HostGroup] = addrs
+		}
+		for _, addr := range addrs {
+			if req.InputGroups {
+				if cfg.InputGroups[addr] == nil {
+					cfg.InputGroups[addr] = map[string][]string{}
+				}
+				for name, inputs := range groups[addr] {
+					if _, ok := cfg.InputGroups[addr][name]; !ok {
+						cfg.InputGroups[addr][name] = inputs
+					}
+				}
+			}
+			if req.Aliases {
+				if cfg.InputAliases[addr] == nil {
+					cfg.InputAliases[addr] = map[string]string{}
+				}
+				for alias, key := range aliases[addr] {
+					if _, ok := cfg.InputAliases[addr][alias]; !ok {
+						cfg.InputAliases[addr][alias] = key
+					}
+				}
+			}
+		}
+	})
+	if err != nil {
+		return Config{}, err
+	}
+	return config.Get(), nil
+}
+
+// GetSetupHandler returns whether this is first run and local vMix for [GET] /api/setup .
+func GetSetupHandler(c *gin.Context) {
+	result, err := DetectSetup(false)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, result)
+}
+
+// ScanSetupHandler scans network of primary interface for vMix and suggests connections for [GET] /api/setup/scan .
+func ScanSetupHandler(c *gin.Context) {
+	result, err := DetectSetup(true)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, result)
+}
+
+// RunSetupHandler writes initial settings for [POST] /api/setup .
+func RunSetupHandler(c *gin.Context) {
+	req := FirstRunSetupRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	cfg, err := RunFirstTimeSetup(req)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"connections":   Connections(),
+		"host_groups":   cfg.HostGroups,
+		"input_groups":  cfg.InputGroups,
+		"input_aliases": cfg.InputAliases,
+	})
+}
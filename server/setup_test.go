@@ -0,0 +1,64 @@
+package main
+
+import (
+	"net"
+	"strconv"
+	"testing"
+	"time"
+)
+
+func TestScanVMixHosts(t *testing.T) {
+	m := startMock(t)
+	_, port, _ := net.SplitHostPort(m.HTTPAddr)
+	p, _ := strconv.Atoi(port)
+	hosts := ScanVMixHosts([]string{"127.0.0.1"}, p, time.Second)
+	if len(hosts) != 1 || !hosts[0].Local || hosts[0].Inputs != 6 || hosts[0].Addr != "http://127.0.0.1:"+port {
+		t.Fatalf("unexpected hosts %+v", hosts)
+	}
+
+	ip := net.IPv4(192, 168, 0, 20).To4()
+	_, ipnet, _ := net.ParseCIDR("192.168.0.0/16")
+	if subnet := subnetHosts(ip, ipnet); len(subnet) != 253 || subnet[0] != "192.168.0.1" {
+		t.Fatalf("expected /24 around own address without itself, got %d hosts", len(subnet))
+	}
+}
+
+func TestRunFirstTimeSetup(t *testing.T) {
+	m := startMock(t)
+	prev := *vmixaddr
+	*vmixaddr = "http://" + m.HTTPAddr
+	defer func() { *vmixaddr = prev }()
+	before := config.Get()
+	defer config.Update(func(cfg *Config) {
+		cfg.Connections = before.Connections
+		cfg.HostGroups = before.HostGroups
+		cfg.InputGroups = before.InputGroups
+		cfg.InputAliases = before.InputAliases
+	})
+
+	if _, err := RunFirstTimeSetup(FirstRunSetupRequest{Force: true, Connections: []Connection{{Name: "", Addr: "http://192.0.2.1:8088"}}}); err == nil {
+		t.Fatal("expected unnamed connection refused")
+	}
+	if _, err := RunFirstTimeSetup(FirstRunSetupRequest{Force: true, InputGroups: true, Connections: []Connection{{Name: "backup", Addr: "http://127.0.0.1:1"}}}); err == nil {
+		t.Fatal("expected unreachable connection to fail setup")
+	}
+	if cfg := config.Get(); len(cfg.Connections) != len(before.Connections) {
+		t.Fatalf("failed setup must not change settings, got %+v", cfg.Connections)
+	}
+
+	cfg, err := RunFirstTimeSetup(FirstRunSetupRequest{Force: true, HostGroup: "all", InputGroups: true, Aliases: true})
+	if err != nil {
+		t.Fatal(err)
+	}
+	s := DefaultMockState()
+	groups := cfg.InputGroups[*vmixaddr]
+	if len(groups["Cameras"]) != 2 || groups["Cameras"][0] != s.Inputs[1].Key || len(groups["Titles"]) != 1 {
+		t.Fatalf("unexpected input groups %v", groups)
+	}
+	if cfg.InputAliases[*vmixaddr]["CAM 2"] != s.Inputs[2].Key {
+		t.Fatalf("unexpected aliases %v", cfg.InputAliases[*vmixaddr])
+	}
+	if len(cfg.HostGroups["all"]) != 1 {
+		t.Fatalf("unexpected host groups %v", cfg.HostGroups)
+	}
+}
@@ -0,0 +1,190 @@
+package main
+
+import (
+	"encoding/xml"
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Shortcut is named function of utility, bound to hotkey and runnable by any client.
+type Shortcut struct {
+	Name string `json:"name"`
+	Key  string `json:"key"`  // hotkey as in vMix. e.g. "Ctrl+F1" .
+	Host string `json:"host"` // vMix host. Primary vMix host is used if empty.
+	FunctionCall
+}
+
+// ShortcutFromPreset converts shortcut saved by vMix. Name is derived from hotkey and function.
+func ShortcutFromPreset(ps PresetShortcut) Shortcut {
+	params := map[string]string{}
+	if ps.Input != "" {
+		params["Input"] = ps.Input
+	}
+	if ps.Value != "" {
+		params["Value"] = ps.Value
+	}
+	name := ps.Function
+	if ps.Key != "" {
+		name = ps.Key + " " + ps.Function
+	}
+	return Shortcut{Name: name, Key: ps.Key, FunctionCall: FunctionCall{Function: ps.Function, Params: params}}
+}
+
+// MergeShortcuts adds imported shortcuts. Existing shortcut with same hotkey, or same name if no hotkey, is replaced.
+func MergeShortcuts(current, imported []Shortcut) []Shortcut {
+	merged := append([]Shortcut{}, current...)
+	for _, sc := range imported {
+		replaced := false
+		for i := range merged {
+			if (sc.Key != "" && strings.EqualFold(merged[i].Key, sc.Key)) || (sc.Key == "" && merged[i].Name == sc.Name) {
+				merged[i] = sc
+				replaced = true
+				break
+			}
+		}
+		if !replaced {
+			merged = append(merged, sc)
+		}
+	}
+	return merged
+}
+
+// exportedShortcut is <Shortcut> element of exported shortcuts file, readable by ParsePreset.
+type exportedShortcut struct {
+	Key      string `xml:"Key,attr"`
+	Function string `xml:"Function,attr"`
+	Input    string `xml:"Input,attr,omitempty"`
+	Value    string `xml:"Value,attr,omitempty"`
+}
+
+// ExportShortcuts encodes shortcuts in vMix shortcuts format. Parameters other than Input and Value cannot be expressed and are dropped.
+func ExportShortcuts(shortcuts []Shortcut) ([]byte, error) {
+	doc := struct {
+		XMLName   xml.Name           `xml:"Shortcuts"`
+		Shortcuts []exportedShortcut `xml:"Shortcut"`
+	}{}
+	for _, sc := range shortcuts {
+		doc.Shortcuts = append(doc.Shortcuts, exportedShortcut{Key: sc.Key, Function: sc.Function, Input: sc.Params["Input"], Value: sc.Params["Value"]})
+	}
+	b, err := xml.MarshalIndent(doc, "", "  ")
+	if err != nil {
+		return nil, err
+	}
+	return append([]byte(xml.Header), b...), nil
+}
+
+// GetShortcutsHandler returns shortcuts for [GET] /api/shortcuts .
+func GetShortcutsHandler(c *gin.Context) {
+	shortcuts := config.Get().Shortcuts
+	if shortcuts == nil {
+		shortcuts = []Shortcut{}
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"shortcuts": shortcuts,
+	})
+}
+
+// PutShortcutsHandler saves shortcuts for [PUT] /api/shortcuts .
+func PutShortcutsHandler(c *gin.Context) {
+	shortcuts := []Shortcut{}
+	if err := c.ShouldBindJSON(&shortcuts); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	names := map[string]bool{}
+	for _, sc := range shortcuts {
+		if sc.Name == "" || sc.Function == "" || names[sc.Name] {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid or duplicated shortcut %q", sc.Name)))
+			return
+		}
+		names[sc.Name] = true
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Shortcuts = shortcuts
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetShortcutsHandler(c)
+}
+
+// ImportShortcutsHandler imports shortcuts of vMix preset or exported shortcuts file for [POST] /api/shortcuts/import?path=... .
+func ImportShortcutsHandler(c *gin.Context) {
+	p, ok := loadPresetFromQuery(c)
+	if !ok {
+		return
+	}
+	imported := make([]Shortcut, 0, len(p.Shortcuts))
+	for _, ps := range p.Shortcuts {
+		if ps.Function != "" {
+			imported = append(imported, ShortcutFromPreset(ps))
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Shortcuts = MergeShortcuts(cfg.Shortcuts, imported)
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"imported":  imported,
+		"shortcuts": config.Get().Shortcuts,
+	})
+}
+
+// ExportShortcutsHandler downloads shortcuts in vMix shortcuts format for [GET] /api/shortcuts/export .
+func ExportShortcutsHandler(c *gin.Context) {
+	b, err := ExportShortcuts(config.Get().Shortcuts)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	c.Header("Content-Disposition", `attachment; filename="vmix_shortcuts.xml"`)
+	c.Data(http.StatusOK, "application/xml; charset=utf-8", b)
+}
+
+// RunShortcutHandler sends function of shortcut for [POST] /api/shortcuts/run/:name .
+func RunShortcutHandler(c *gin.Context) {
+	sc, err := runShortcut(c, c.Param("name"))
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"shortcut": sc,
+	})
+}
+
+// findShortcut returns shortcut by name.
+func findShortcut(name string) (Shortcut, bool) {
+	for _, sc := range config.Get().Shortcuts {
+		if sc.Name == name {
+			return sc, true
+		}
+	}
+	return Shortcut{}, false
+}
+
+// runShortcut sends function of shortcut by name and announces it as action of requesting operator.
+func runShortcut(c *gin.Context, name string) (Shortcut, error) {
+	sc, ok := findShortcut(name)
+	if !ok {
+		return Shortcut{}, NewAppError(ErrKindNotFound, "", fmt.Errorf("shortcut %q not found", name))
+	}
+	host := sc.Host
+	if host == "" {
+		host = *vmixaddr
+	}
+	if err := RequireFunction(host, sc.Function); err != nil {
+		return sc, err
+	}
+	poller.Touch()
+	if err := SendFunctionTo(host, sc.Function, sc.Params); err != nil {
+		return sc, err
+	}
+	announceAction(c, host, sc.Function)
+	return sc, nil
+}
@@ -0,0 +1,34 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+)
+
+func TestShortcutsRoundTrip(t *testing.T) {
+	p, err := ParsePreset(bytes.NewReader([]byte(`<XML><Shortcuts><Shortcut Key="F1" Function="Cut" Input="k1" /><Shortcut Key="F2" Function="SetVolume" Input="k2" Value="50" /></Shortcuts></XML>`)))
+	if err != nil {
+		t.Fatal(err)
+	}
+	imported := []Shortcut{}
+	for _, ps := range p.Shortcuts {
+		imported = append(imported, ShortcutFromPreset(ps))
+	}
+	current := []Shortcut{{Name: "old", Key: "f1", FunctionCall: FunctionCall{Function: "Fade"}}, {Name: "keep", FunctionCall: FunctionCall{Function: "Cut"}}}
+	merged := MergeShortcuts(current, imported)
+	if len(merged) != 3 || merged[0].Function != "Cut" || merged[0].Params["Input"] != "k1" || merged[2].Params["Value"] != "50" {
+		t.Fatalf("unexpected shortcuts %+v", merged)
+	}
+
+	b, err := ExportShortcuts(merged)
+	if err != nil {
+		t.Fatal(err)
+	}
+	again, err := ParsePreset(bytes.NewReader(b))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(again.Shortcuts) != 3 || again.Shortcuts[2].Value != "50" || again.Shortcuts[0].Key != "F1" {
+		t.Fatalf("unexpected exported shortcuts %+v", again.Shortcuts)
+	}
+}
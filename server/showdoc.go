@@ -0,0 +1,142 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"html/template"
+	"net/http"
+	"sort"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// ShowDocInput is input listed in show documentation.
+type ShowDocInput struct {
+	Number  int
+	Type    string
+	Title   string
+	Aliases []string
+}
+
+// ShowDocHost is vMix host listed in show documentation.
+type ShowDocHost struct {
+	Name      string
+	Addr      string
+	Online    bool
+	Version   string
+	Edition   string
+	Inputs    []ShowDocInput
+	Multiview template.HTML // SVG of input grid with tally at generation.
+}
+
+// ShowDoc is printable sheet of current setup handed to crew.
+type ShowDoc struct {
+	Title       string
+	GeneratedAt time.Time
+	Hosts       []ShowDocHost
+	Shortcuts   []Shortcut
+	Cues        []TimecodeCue
+}
+
+// BuildShowDoc collects connections, inputs, shortcuts and timecode cues from polled state and config.
+func BuildShowDoc(title string, now time.Time) ShowDoc {
+	doc := ShowDoc{Title: title, GeneratedAt: now, Shortcuts: config.Get().Shortcuts, Cues: timecode.Cues()}
+	if doc.Title == "" {
+		doc.Title = "Show"
+	}
+	for _, conn := range Connections() {
+		h := ShowDocHost{Name: conn.Name, Addr: conn.Addr}
+		status, ok := poller.Status(conn.Addr)
+		if !ok || status.State == nil {
+			doc.Hosts = append(doc.Hosts, h)
+			continue
+		}
+		s := status.State
+		h.Online, h.Version, h.Edition = status.Online, s.Version, s.Edition
+		aliases := map[string][]string{}
+		for alias, ref := range InputAliases(conn.Addr) {
+			if in := s.FindInput(ref); in != nil {
+				aliases[in.Key] = append(aliases[in.Key], alias)
+			}
+		}
+		for _, in := range s.Inputs {
+			sort.Strings(aliases[in.Key])
+			h.Inputs = append(h.Inputs, ShowDocInput{Number: in.Number, Type: in.Type, Title: in.Title, Aliases: aliases[in.Key]})
+		}
+		if l, err := BuildMultiviewLayout(s, ""); err == nil {
+			h.Multiview = template.HTML(RenderMultiviewSVG(l))
+		}
+		doc.Hosts = append(doc.Hosts, h)
+	}
+	return doc
+}
+
+var showDocTemplate = template.Must(template.New("showdoc").Parse(`<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{.Title}}</title>
+<style>
+body { font-family: sans-serif; font-size: 12px; margin: 24px; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 16px; }
+th, td { border: 1px solid #999; padding: 2px 6px; text-align: left; }
+svg { width: 100%; height: auto; }
+section { page-break-after: always; }
+</style>
+</head>
+<body>
+<h1>{{.Title}}</h1>
+<p>Generated at {{.GeneratedAt.Format "2006-01-02 15:04:05"}}</p>
+<h2>Connections</h2>
+<table>
+<tr><th>Name</th><th>Address</th><th>vMix</th><th>Status</th></tr>
+{{range .Hosts}}<tr><td>{{.Name}}</td><td>{{.Addr}}</td><td>{{.Version}} {{.Edition}}</td><td>{{if .Online}}Online{{else}}Offline{{end}}</td></tr>
+{{end}}</table>
+{{if .Shortcuts}}<h2>Shortcuts</h2>
+<table>
+<tr><th>Key</th><th>Name</th><th>Host</th><th>Function</th><th>Params</th></tr>
+{{range .Shortcuts}}<tr><td>{{.Key}}</td><td>{{.Name}}</td><td>{{.Host}}</td><td>{{.Function}}</td><td>{{range $k, $v := .Params}}{{$k}}={{$v}} {{end}}</td></tr>
+{{end}}</table>
+{{end}}{{if .Cues}}<h2>Timecode cues</h2>
+<table>
+<tr><th>At</th><th>Name</th><th>Host</th><th>Function</th><th>Params</th></tr>
+{{range .Cues}}<tr><td>{{.At}}</td><td>{{.Name}}</td><td>{{.Action.Host}}</td><td>{{.Action.Function}}</td><td>{{range $k, $v := .Action.Params}}{{$k}}={{$v}} {{end}}</td></tr>
+{{end}}</table>
+{{end}}{{range .Hosts}}<section>
+<h2>{{.Name}} ({{.Addr}})</h2>
+{{if .Inputs}}<table>
+<tr><th>#</th><th>Type</th><th>Title</th><th>Aliases</th></tr>
+{{range .Inputs}}<tr><td>{{.Number}}</td><td>{{.Type}}</td><td>{{.Title}}</td><td>{{range .Aliases}}{{.}} {{end}}</td></tr>
+{{end}}</table>
+{{.Multiview}}
+{{else}}<p>vMix state not loaded.</p>
+{{end}}</section>
+{{end}}</body>
+</html>
+`))
+
+// RenderShowDoc renders show documentation as standalone HTML, printable to PDF from browser.
+func RenderShowDoc(doc ShowDoc) ([]byte, error) {
+	buf := &bytes.Buffer{}
+	if err := showDocTemplate.Execute(buf, doc); err != nil {
+		return nil, err
+	}
+	return buf.Bytes(), nil
+}
+
+// GetShowDocHandler generates show documentation for [GET] /api/docs/show?title=... .
+// Document is attached as file when download=1.
+func GetShowDocHandler(c *gin.Context) {
+	now := time.Now()
+	b, err := RenderShowDoc(BuildShowDoc(c.Query("title"), now))
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	if c.Query("download") == "1" {
+		name := unsafeFileChars.ReplaceAllString(c.DefaultQuery("title", "show"), "_")
+		c.Header("Content-Disposition", fmt.Sprintf(`attachment; filename="%s_%s.html"`, name, now.Format("20060102_150405")))
+	}
+	c.Data(http.StatusOK, "text/html; charset=utf-8", b)
+}
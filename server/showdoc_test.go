@@ -0,0 +1,41 @@
+package main
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestRenderShowDoc(t *testing.T) {
+	s := DefaultMockState()
+	s.Inputs[2].Title = "<Camera 2>"
+	p := NewPoller()
+	p.statuses[*vmixaddr] = &HostStatus{Name: "primary", Addr: *vmixaddr, Online: true, State: s}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+	if err := config.Update(func(cfg *Config) {
+		cfg.InputAliases[*vmixaddr] = map[string]string{"CAM 2": "3"}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) { delete(cfg.InputAliases, *vmixaddr) })
+
+	doc := BuildShowDoc("Finals", time.Date(2021, 4, 1, 18, 0, 0, 0, time.UTC))
+	if len(doc.Hosts) != 1 || len(doc.Hosts[0].Inputs) != len(s.Inputs) {
+		t.Fatalf("unexpected hosts %+v", doc.Hosts)
+	}
+	if aliases := doc.Hosts[0].Inputs[2].Aliases; len(aliases) != 1 || aliases[0] != "CAM 2" {
+		t.Fatalf("expected alias of input 3, got %v", aliases)
+	}
+	b, err := RenderShowDoc(doc)
+	if err != nil {
+		t.Fatal(err)
+	}
+	html := string(b)
+	for _, want := range []string{"<h1>Finals</h1>", "&lt;Camera 2&gt;", "<svg"} {
+		if !strings.Contains(html, want) {
+			t.Fatalf("expected %q in document", want)
+		}
+	}
+}
@@ -0,0 +1,164 @@
+package main
+
+import (
+	"archive/zip"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// RecordingFile is file found in recording folder of vMix host.
+type RecordingFile struct {
+	Path    string    `json:"path"`
+	Size    int64     `json:"size"`
+	ModTime time.Time `json:"mod_time"`
+}
+
+// ShowReportHost is result of collecting artifacts of vMix host.
+type ShowReportHost struct {
+	Name       string          `json:"name"`
+	Addr       string          `json:"addr"`
+	Snapshot   string          `json:"snapshot,omitempty"` // XML file in archive.
+	Recordings []RecordingFile `json:"recordings"`
+	Errors     []string        `json:"errors,omitempty"`
+}
+
+// ShowReport is summary saved as report.json in show report archive.
+type ShowReport struct {
+	Name        string           `json:"name"`
+	Since       time.Time        `json:"since"`
+	GeneratedAt time.Time        `json:"generated_at"`
+	Fleet       FleetReport      `json:"fleet"`
+	Hosts       []ShowReportHost `json:"hosts"`
+}
+
+// ShowReportRequest is request body of CollectShowReportHandler.
+type ShowReportRequest struct {
+	Folder           string            `json:"folder"`            // folder to save archive to.
+	Name             string            `json:"name"`              // show name used in file name. e.g. "finals" .
+	Since            time.Time         `json:"since"`             // show start. Zero includes everything.
+	RecordingFolders map[string]string `json:"recording_folders"` // vMix host -> recording folder reachable from this machine.
+}
+
+// unsafeFileChars matches characters replaced in archive and entry names.
+var unsafeFileChars = regexp.MustCompile(`[^A-Za-z0-9._-]+`)
+
+// listRecordings returns files in folder modified at or after since, newest last.
+func listRecordings(folder string, since time.Time) ([]RecordingFile, error) {
+	files := []RecordingFile{}
+	err := filepath.Walk(folder, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		if info.IsDir() || info.ModTime().Before(since) {
+			return nil
+		}
+		files = append(files, RecordingFile{Path: path, Size: info.Size(), ModTime: info.ModTime()})
+		return nil
+	})
+	sort.Slice(files, func(i, j int) bool { return files[i].ModTime.Before(files[j].ModTime) })
+	return files, err
+}
+
+// writeZipEntry writes b as name in archive.
+func writeZipEntry(zw *zip.Writer, name string, b []byte) error {
+	w, err := zw.Create(name)
+	if err != nil {
+		return err
+	}
+	_, err = w.Write(b)
+	return err
+}
+
+// writeZipJSON writes v as indented JSON entry.
+func writeZipJSON(zw *zip.Writer, name string, v interface{}) error {
+	b, err := json.MarshalIndent(v, "", "  ")
+	if err != nil {
+		return err
+	}
+	return writeZipEntry(zw, name, b)
+}
+
+// CollectShowReport saves zipped show report with final XML snapshot and recording list of each host,
+// action history and log into folder. Failures of single hosts are recorded in report instead of aborting.
+func CollectShowReport(req ShowReportRequest, now time.Time) (string, *ShowReport, error) {
+	if req.Folder == "" {
+		return "", nil, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("folder is required"))
+	}
+	if err := os.MkdirAll(req.Folder, 0755); err != nil {
+		return "", nil, NewAppError(ErrKindInternal, "", err)
+	}
+	name := unsafeFileChars.ReplaceAllString(req.Name, "_")
+	if name == "" {
+		name = "show"
+	}
+	path := filepath.Join(req.Folder, fmt.Sprintf("%s_%s.zip", name, now.Format("20060102_150405")))
+	f, err := os.Create(path)
+	if err != nil {
+		return "", nil, NewAppError(ErrKindInternal, "", err)
+	}
+	defer f.Close()
+	zw := zip.NewWriter(f)
+
+	report := &ShowReport{Name: req.Name, Since: req.Since, GeneratedAt: now, Fleet: GenerateFleetReport(now), Hosts: []ShowReportHost{}}
+	for _, conn := range Connections() {
+		host := ShowReportHost{Name: conn.Name, Addr: conn.Addr, Recordings: []RecordingFile{}}
+		if _, raw, err := FetchState(conn.Addr); err != nil {
+			host.Errors = append(host.Errors, fmt.Sprintf("snapshot : %v", err))
+		} else {
+			host.Snapshot = "hosts/" + unsafeFileChars.ReplaceAllString(conn.Name, "_") + ".xml"
+			if err := writeZipEntry(zw, host.Snapshot, raw); err != nil {
+				return "", nil, NewAppError(ErrKindInternal, "", err)
+			}
+		}
+		if folder, ok := req.RecordingFolders[conn.Addr]; ok {
+			files, err := listRecordings(folder, req.Since)
+			if err != nil {
+				host.Errors = append(host.Errors, fmt.Sprintf("recordings : %v", err))
+			}
+			host.Recordings = files
+		}
+		report.Hosts = append(report.Hosts, host)
+	}
+
+	for name, v := range map[string]interface{}{
+		"report.json":  report,
+		"history.json": history.Entries(req.Since),
+	} {
+		if err := writeZipJSON(zw, name, v); err != nil {
+			return "", nil, NewAppError(ErrKindInternal, "", err)
+		}
+	}
+	if err := writeZipEntry(zw, "log.txt", logBuffer.Bytes()); err != nil {
+		return "", nil, NewAppError(ErrKindInternal, "", err)
+	}
+	if err := zw.Close(); err != nil {
+		return "", nil, NewAppError(ErrKindInternal, "", err)
+	}
+	return path, report, nil
+}
+
+// CollectShowReportHandler saves show report archive for [POST] /api/reports/show .
+func CollectShowReportHandler(c *gin.Context) {
+	req := ShowReportRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	path, report, err := CollectShowReport(req, time.Now())
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"path":   path,
+		"report": report,
+	})
+}
@@ -0,0 +1,64 @@
+package main
+
+import (
+	"archive/zip"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"sort"
+	"testing"
+	"time"
+)
+
+func TestCollectShowReport(t *testing.T) {
+	dir, err := ioutil.TempDir("", "vmix_show_report")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer os.RemoveAll(dir)
+	recordings := filepath.Join(dir, "recordings")
+	os.MkdirAll(recordings, 0755)
+	ioutil.WriteFile(filepath.Join(recordings, "capture.mp4"), []byte("video"), 0644)
+
+	m := startMock(t)
+	prev := *vmixaddr
+	*vmixaddr = "http://" + m.HTTPAddr
+	defer func() { *vmixaddr = prev }()
+
+	history.Record(Event{Topic: "operator_action", Host: "primary"}, time.Now())
+	history.Record(Event{Topic: "clock"}, time.Now())
+	if n := len(history.Entries(time.Time{})); n != 1 {
+		t.Fatalf("expected 1 history entry, got %d", n)
+	}
+
+	path, report, err := CollectShowReport(ShowReportRequest{
+		Folder:           filepath.Join(dir, "reports"),
+		Name:             "Grand Final",
+		RecordingFolders: map[string]string{*vmixaddr: recordings},
+	}, time.Now())
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(report.Hosts) != 1 || len(report.Hosts[0].Recordings) != 1 || len(report.Hosts[0].Errors) != 0 {
+		t.Fatalf("unexpected report %+v", report)
+	}
+	r, err := zip.OpenReader(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer r.Close()
+	names := []string{}
+	for _, f := range r.File {
+		names = append(names, f.Name)
+	}
+	sort.Strings(names)
+	want := []string{"history.json", "hosts/primary.xml", "log.txt", "report.json"}
+	if len(names) != len(want) {
+		t.Fatalf("unexpected entries %v", names)
+	}
+	for i := range want {
+		if names[i] != want[i] {
+			t.Fatalf("unexpected entries %v", names)
+		}
+	}
+}
@@ -0,0 +1,298 @@
+package main
+
+import (
+	"encoding/csv"
+	"fmt"
+	"net/http"
+	"sort"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// InputAirTime is time input spent on program.
+type InputAirTime struct {
+	Number  int     `json:"number"`
+	Title   string  `json:"title"`
+	Seconds float64 `json:"seconds"`
+}
+
+// ShowHostStats is counters of vMix host during show.
+type ShowHostStats struct {
+	Addr           string                   `json:"addr"`
+	Cuts           int                      `json:"cuts"`            // program changes observed, by any transition or by vMix itself.
+	Functions      map[string]int           `json:"functions"`       // function -> times sent by utility.
+	FunctionErrors int                      `json:"function_errors"` // functions vMix refused or did not receive.
+	Disconnects    int                      `json:"disconnects"`     // times host went from connected to reconnecting or disconnected.
+	Program        map[string]*InputAirTime `json:"program"`         // input key -> time on program.
+
+	program      string    // key of program input, kept while offline so reconnect is not counted as cut.
+	programSince time.Time // start of uncounted program time. Zero while offline.
+}
+
+// ShowStats is statistics of single show, for post-show analytics.
+type ShowStats struct {
+	ID    int                       `json:"id"`
+	Name  string                    `json:"name"`
+	Start time.Time                 `json:"start"`
+	End   time.Time                 `json:"end"`   // zero while show is current.
+	Hosts map[string]*ShowHostStats `json:"hosts"` // connection name -> counters.
+}
+
+// host returns counters of connection, creating them.
+func (s *ShowStats) host(name, addr string) *ShowHostStats {
+	h, ok := s.Hosts[name]
+	if !ok {
+		h = &ShowHostStats{Addr: addr, Functions: make(map[string]int), Program: make(map[string]*InputAirTime)}
+		s.Hosts[name] = h
+	}
+	return h
+}
+
+// clone returns deep copy.
+func (s *ShowStats) clone() ShowStats {
+	c := *s
+	c.Hosts = make(map[string]*ShowHostStats, len(s.Hosts))
+	for name, h := range s.Hosts {
+		hc := *h
+		hc.Functions = make(map[string]int, len(h.Functions))
+		for k, v := range h.Functions {
+			hc.Functions[k] = v
+		}
+		hc.Program = make(map[string]*InputAirTime, len(h.Program))
+		for k, v := range h.Program {
+			t := *v
+			hc.Program[k] = &t
+		}
+		c.Hosts[name] = &hc
+	}
+	return c
+}
+
+// CSV returns statistics as CSV rows including header. Each row is single counter.
+func (s ShowStats) CSV() [][]string {
+	rows := [][]string{{"show", "host", "category", "name", "value"}}
+	names := make([]string, 0, len(s.Hosts))
+	for name := range s.Hosts {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		h := s.Hosts[name]
+		row := func(category, key, value string) {
+			rows = append(rows, []string{s.Name, name, category, key, value})
+		}
+		row("summary", "cuts", strconv.Itoa(h.Cuts))
+		row("summary", "disconnects", strconv.Itoa(h.Disconnects))
+		row("summary", "function_errors", strconv.Itoa(h.FunctionErrors))
+		functions := make([]string, 0, len(h.Functions))
+		for f := range h.Functions {
+			functions = append(functions, f)
+		}
+		sort.Strings(functions)
+		for _, f := range functions {
+			row("function", f, strconv.Itoa(h.Functions[f]))
+		}
+		program := make([]*InputAirTime, 0, len(h.Program))
+		for _, t := range h.Program {
+			program = append(program, t)
+		}
+		sort.Slice(program, func(i, j int) bool { return program[i].Seconds > program[j].Seconds })
+		for _, t := range program {
+			row("program_seconds", fmt.Sprintf("%d %s", t.Number, t.Title), strconv.FormatFloat(t.Seconds, 'f', 1, 64))
+		}
+	}
+	return rows
+}
+
+// ShowStatsStore aggregates counters of current show, and keeps last finished shows.
+type ShowStatsStore struct {
+	mu      sync.Mutex
+	limit   int
+	nextID  int
+	current *ShowStats
+	past    []*ShowStats
+}
+
+// NewShowStatsStore creates ShowStatsStore keeping limit finished shows. Unnamed show starts at once.
+func NewShowStatsStore(limit int, now time.Time) *ShowStatsStore {
+	s := &ShowStatsStore{limit: limit}
+	s.current = s.newShow("", now)
+	return s
+}
+
+func (s *ShowStatsStore) newShow(name string, now time.Time) *ShowStats {
+	s.nextID++
+	return &ShowStats{ID: s.nextID, Name: name, Start: now, Hosts: make(map[string]*ShowHostStats)}
+}
+
+// Start ends current show and starts new one. Program inputs carry over without counting cut.
+func (s *ShowStatsStore) Start(name string, now time.Time) ShowStats {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	prev := s.current
+	for _, h := range prev.Hosts {
+		h.accumulate(now)
+	}
+	prev.End = now
+	s.past = append(s.past, prev)
+	if over := len(s.past) - s.limit; over > 0 {
+		s.past = append(s.past[:0], s.past[over:]...)
+	}
+	s.current = s.newShow(name, now)
+	for hostName, h := range prev.Hosts {
+		next := s.current.host(hostName, h.Addr)
+		next.program = h.program
+		next.programSince = h.programSince
+		if t, ok := h.Program[h.program]; ok {
+			next.Program[h.program] = &InputAirTime{Number: t.Number, Title: t.Title}
+		}
+	}
+	return s.current.clone()
+}
+
+// accumulate adds program time until now.
+func (h *ShowHostStats) accumulate(now time.Time) {
+	if h.programSince.IsZero() || now.Before(h.programSince) {
+		return
+	}
+	if t, ok := h.Program[h.program]; ok {
+		t.Seconds += now.Sub(h.programSince).Seconds()
+	}
+	h.programSince = now
+}
+
+// Observe counts changes between previous and current status of polled host.
+func (s *ShowStatsStore) Observe(prev, status *HostStatus, now time.Time) {
+	if IsOfflineAddr(status.Addr) {
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	h := s.current.host(status.Name, status.Addr)
+	if prev != nil && prev.Connection == ConnectionConnected && status.Connection != ConnectionConnected {
+		h.Disconnects++
+	}
+	h.accumulate(now)
+	if !status.Online || status.State == nil {
+		h.programSince = time.Time{}
+		return
+	}
+	in := status.State.FindInput(strconv.Itoa(status.State.Active))
+	if in == nil {
+		h.programSince = time.Time{}
+		return
+	}
+	if h.programSince.IsZero() {
+		h.programSince = now
+	}
+	if in.Key != h.program {
+		if h.program != "" {
+			h.Cuts++
+		}
+		h.program = in.Key
+	}
+	t, ok := h.Program[in.Key]
+	if !ok {
+		t = &InputAirTime{}
+		h.Program[in.Key] = t
+	}
+	t.Number, t.Title = in.Number, in.Title
+}
+
+// RecordFunction counts function sent to host.
+func (s *ShowStatsStore) RecordFunction(addr, function string, err error) {
+	name := addr
+	for _, conn := range Connections() {
+		if conn.Addr == addr {
+			name = conn.Name
+		}
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	h := s.current.host(name, addr)
+	h.Functions[function]++
+	if err != nil {
+		h.FunctionErrors++
+	}
+}
+
+// Shows returns finished shows, oldest first, followed by current show.
+func (s *ShowStatsStore) Shows(now time.Time) []ShowStats {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	shows := make([]ShowStats, 0, len(s.past)+1)
+	for _, show := range s.past {
+		shows = append(shows, show.clone())
+	}
+	for _, h := range s.current.Hosts {
+		h.accumulate(now)
+	}
+	return append(shows, s.current.clone())
+}
+
+// Get returns show by ID, or current show for "current".
+func (s *ShowStatsStore) Get(id string, now time.Time) (ShowStats, bool) {
+	shows := s.Shows(now)
+	if id == "current" {
+		return shows[len(shows)-1], true
+	}
+	for _, show := range shows {
+		if strconv.Itoa(show.ID) == id {
+			return show, true
+		}
+	}
+	return ShowStats{}, false
+}
+
+// GetShowStatsListHandler returns statistics of finished and current shows for [GET] /api/stats/shows .
+func GetShowStatsListHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"shows": showStats.Shows(time.Now()),
+	})
+}
+
+// StartShowStatsRequest is request body of StartShowStatsHandler.
+type StartShowStatsRequest struct {
+	Name string `json:"name"` // e.g. "finals" .
+}
+
+// StartShowStatsHandler ends current show and starts counting new one for [POST] /api/stats/shows .
+func StartShowStatsHandler(c *gin.Context) {
+	req := StartShowStatsRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"show": showStats.Start(req.Name, time.Now()),
+	})
+}
+
+// GetShowStatsHandler returns statistics of show for [GET] /api/stats/shows/:id?format=json|csv . id is "current" for current show.
+func GetShowStatsHandler(c *gin.Context) {
+	show, ok := showStats.Get(c.Param("id"), time.Now())
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("show %s not found", c.Param("id"))))
+		return
+	}
+	switch format := c.DefaultQuery("format", "json"); format {
+	case "json":
+		c.JSON(http.StatusOK, gin.H{
+			"show": show,
+		})
+	case "csv":
+		name := "show"
+		if show.Name != "" {
+			name = unsafeFileChars.ReplaceAllString(show.Name, "_")
+		}
+		c.Header("Content-Disposition", fmt.Sprintf(`attachment; filename="vmix_stats_%s_%s.csv"`, name, show.Start.Format("20060102_150405")))
+		c.Header("Content-Type", "text/csv; charset=utf-8")
+		w := csv.NewWriter(c.Writer)
+		w.WriteAll(show.CSV())
+	default:
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown format %q", format)))
+	}
+}
@@ -0,0 +1,56 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestShowStatsStore(t *testing.T) {
+	now := time.Now()
+	s := NewShowStatsStore(2, now)
+	addr := "http://showstats.invalid:8088"
+	status := func(active int, conn ConnectionState) *HostStatus {
+		state := DefaultMockState()
+		state.Active = active
+		return &HostStatus{Name: "main", Addr: addr, Online: conn == ConnectionConnected, Connection: conn, State: state}
+	}
+
+	first := status(2, ConnectionConnected)
+	s.Observe(nil, first, now)
+	second := status(3, ConnectionConnected)
+	s.Observe(first, second, now.Add(10*time.Second))
+	offline := status(3, ConnectionReconnecting)
+	offline.State = nil
+	s.Observe(second, offline, now.Add(15*time.Second))
+	s.Observe(offline, status(3, ConnectionConnected), now.Add(60*time.Second))
+	s.RecordFunction(addr, "Cut", nil)
+	s.RecordFunction(addr, "Cut", NewAppError(ErrKindUnreachable, addr, nil))
+
+	show, _ := s.Get("current", now.Add(70*time.Second))
+	h := show.Hosts[addr]
+	if h == nil {
+		t.Fatalf("expected host counted by address of unknown connection, got %+v", show.Hosts)
+	}
+	host := show.Hosts["main"]
+	if host.Cuts != 1 || host.Disconnects != 1 {
+		t.Fatalf("expected reconnect not counted as cut, got %+v", host)
+	}
+	seconds := map[int]float64{}
+	for _, air := range host.Program {
+		seconds[air.Number] = air.Seconds
+	}
+	if seconds[2] != 10 || seconds[3] != 15 {
+		t.Fatalf("expected program time counted only while online, got %v", seconds)
+	}
+	if h.Functions["Cut"] != 2 || h.FunctionErrors != 1 {
+		t.Fatalf("unexpected function counters %+v", h)
+	}
+
+	next := s.Start("finals", now.Add(80*time.Second))
+	if next.ID != 2 || next.Name != "finals" || len(s.Shows(now.Add(80*time.Second))) != 2 {
+		t.Fatalf("unexpected new show %+v", next)
+	}
+	if past, ok := s.Get("1", now); !ok || past.End.IsZero() || len(past.CSV()) < 4 {
+		t.Fatalf("expected finished show kept, got %+v", past)
+	}
+}
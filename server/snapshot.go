@@ -0,0 +1,313 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Snapshot is labeled vMix state captured at certain time.
+type Snapshot struct {
+	Label   string    `json:"label"`
+	Host    string    `json:"host"`
+	TakenAt time.Time `json:"taken_at"`
+	State   *State    `json:"-"`
+	XML     []byte    `json:"-"`
+}
+
+// maxSnapshots limits snapshots kept in memory. Oldest snapshot is evicted when exceeded.
+const maxSnapshots = 50
+
+// SnapshotStore keeps snapshots in memory by label.
+type SnapshotStore struct {
+	mu        sync.Mutex
+	snapshots map[string]*Snapshot
+	evicted   uint64
+}
+
+// NewSnapshotStore creates SnapshotStore.
+func NewSnapshotStore() *SnapshotStore {
+	return &SnapshotStore{
+		snapshots: make(map[string]*Snapshot),
+	}
+}
+
+// Capture fetches current state from host and stores it as label. Existing label is overwritten.
+func (s *SnapshotStore) Capture(label, host string) (*Snapshot, error) {
+	state, raw, err := FetchState(host)
+	if err != nil {
+		return nil, err
+	}
+	snap := &Snapshot{Label: label, Host: host, TakenAt: time.Now(), State: state, XML: raw}
+	s.mu.Lock()
+	s.snapshots[label] = snap
+	for len(s.snapshots) > maxSnapshots {
+		var oldest *Snapshot
+		for _, other := range s.snapshots {
+			if oldest == nil || other.TakenAt.Before(oldest.TakenAt) {
+				oldest = other
+			}
+		}
+		delete(s.snapshots, oldest.Label)
+		s.evicted++
+	}
+	s.mu.Unlock()
+	return snap, nil
+}
+
+// Stats returns size of store.
+func (s *SnapshotStore) Stats() CacheStats {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	stats := CacheStats{Name: "snapshots", Entries: len(s.snapshots), Limit: maxSnapshots, Evicted: s.evicted}
+	for _, snap := range s.snapshots {
+		stats.Bytes += int64(len(snap.XML))
+	}
+	return stats
+}
+
+// Get returns snapshot by label.
+func (s *SnapshotStore) Get(label string) (*Snapshot, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	snap, ok := s.snapshots[label]
+	return snap, ok
+}
+
+// List returns snapshots ordered by capture time.
+func (s *SnapshotStore) List() []*Snapshot {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	list := make([]*Snapshot, 0, len(s.snapshots))
+	for _, snap := range s.snapshots {
+		list = append(list, snap)
+	}
+	sort.Slice(list, func(i, j int) bool { return list[i].TakenAt.Before(list[j].TakenAt) })
+	return list
+}
+
+// Delete removes snapshot by label.
+func (s *SnapshotStore) Delete(label string) bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	_, ok := s.snapshots[label]
+	delete(s.snapshots, label)
+	return ok
+}
+
+// FieldChange is single changed value.
+type FieldChange struct {
+	Field string `json:"field"`
+	From  string `json:"from"`
+	To    string `json:"to"`
+}
+
+// InputSummary identifies input in diff.
+type InputSummary struct {
+	Key    string `json:"key"`
+	Number int    `json:"number"`
+	Title  string `json:"title"`
+	Type   string `json:"type"`
+}
+
+// InputChange is list of changes on single input.
+type InputChange struct {
+	InputSummary
+	Changes []FieldChange `json:"changes"`
+}
+
+// StateDiff is structured difference between two states.
+type StateDiff struct {
+	InputsAdded    []InputSummary `json:"inputs_added"`
+	InputsRemoved  []InputSummary `json:"inputs_removed"`
+	InputsChanged  []InputChange  `json:"inputs_changed"`
+	Overlays       []FieldChange  `json:"overlays"`
+	Settings       []FieldChange  `json:"settings"`
+	HasDifferences bool           `json:"has_differences"`
+}
+
+func summarizeInput(in StateInput) InputSummary {
+	return InputSummary{Key: in.Key, Number: in.Number, Title: in.Title, Type: in.Type}
+}
+
+// DiffStates compares two states. Inputs are matched by key.
+func DiffStates(from, to *State) StateDiff {
+	d := StateDiff{
+		InputsAdded:   []InputSummary{},
+		InputsRemoved: []InputSummary{},
+		InputsChanged: []InputChange{},
+		Overlays:      []FieldChange{},
+		Settings:      []FieldChange{},
+	}
+
+	fromInputs := make(map[string]StateInput, len(from.Inputs))
+	for _, in := range from.Inputs {
+		fromInputs[in.Key] = in
+	}
+	toInputs := make(map[string]StateInput, len(to.Inputs))
+	for _, in := range to.Inputs {
+		toInputs[in.Key] = in
+		prev, ok := fromInputs[in.Key]
+		if !ok {
+			d.InputsAdded = append(d.InputsAdded, summarizeInput(in))
+			continue
+		}
+		if changes := diffInput(prev, in); len(changes) > 0 {
+			d.InputsChanged = append(d.InputsChanged, InputChange{InputSummary: summarizeInput(in), Changes: changes})
+		}
+	}
+	for _, in := range from.Inputs {
+		if _, ok := toInputs[in.Key]; !ok {
+			d.InputsRemoved = append(d.InputsRemoved, summarizeInput(in))
+		}
+	}
+
+	n := len(from.Overlays)
+	if len(to.Overlays) > n {
+		n = len(to.Overlays)
+	}
+	for i := 0; i < n; i++ {
+		var a, b string
+		if i < len(from.Overlays) {
+			a = from.Overlays[i].Text
+		}
+		if i < len(to.Overlays) {
+			b = to.Overlays[i].Text
+		}
+		d.Overlays = appendChange(d.Overlays, fmt.Sprintf("overlay%d", i+1), a, b)
+	}
+
+	d.Settings = appendChange(d.Settings, "version", from.Version, to.Version)
+	d.Settings = appendChange(d.Settings, "edition", from.Edition, to.Edition)
+	d.Settings = appendChange(d.Settings, "preset", from.Preset, to.Preset)
+	d.Settings = appendChange(d.Settings, "preview", strconv.Itoa(from.Preview), strconv.Itoa(to.Preview))
+	d.Settings = appendChange(d.Settings, "active", strconv.Itoa(from.Active), strconv.Itoa(to.Active))
+	d.Settings = appendChange(d.Settings, "fadeToBlack", boolString(from.FadeToBlack), boolString(to.FadeToBlack))
+	d.Settings = appendChange(d.Settings, "recording", boolString(from.Recording), boolString(to.Recording))
+	d.Settings = appendChange(d.Settings, "external", boolString(from.External), boolString(to.External))
+	d.Settings = appendChange(d.Settings, "streaming", boolString(from.Streaming), boolString(to.Streaming))
+	d.Settings = appendChange(d.Settings, "playList", boolString(from.PlayList), boolString(to.PlayList))
+	d.Settings = appendChange(d.Settings, "multiCorder", boolString(from.MultiCorder), boolString(to.MultiCorder))
+	d.Settings = appendChange(d.Settings, "fullscreen", boolString(from.FullScreen), boolString(to.FullScreen))
+	d.Settings = appendChange(d.Settings, "transitions", transitionsString(from.Transitions), transitionsString(to.Transitions))
+
+	d.HasDifferences = len(d.InputsAdded)+len(d.InputsRemoved)+len(d.InputsChanged)+len(d.Overlays)+len(d.Settings) > 0
+	return d
+}
+
+func diffInput(a, b StateInput) []FieldChange {
+	var changes []FieldChange
+	changes = appendChange(changes, "number", strconv.Itoa(a.Number), strconv.Itoa(b.Number))
+	changes = appendChange(changes, "title", a.Title, b.Title)
+	changes = appendChange(changes, "type", a.Type, b.Type)
+	changes = appendChange(changes, "state", string(a.State), string(b.State))
+	changes = appendChange(changes, "duration", strconv.Itoa(a.Duration), strconv.Itoa(b.Duration))
+	changes = appendChange(changes, "loop", boolString(a.Loop), boolString(b.Loop))
+	changes = appendChange(changes, "layers", layersString(a.Overlays), layersString(b.Overlays))
+	return changes
+}
+
+func appendChange(changes []FieldChange, field, from, to string) []FieldChange {
+	if from == to {
+		return changes
+	}
+	return append(changes, FieldChange{Field: field, From: from, To: to})
+}
+
+func boolString(b Bool) string {
+	return strconv.FormatBool(bool(b))
+}
+
+func layersString(layers []StateInputOverlay) string {
+	s := make([]string, 0, len(layers))
+	for _, l := range layers {
+		s = append(s, fmt.Sprintf("%d:%s", l.Index, l.Key))
+	}
+	return strings.Join(s, ",")
+}
+
+func transitionsString(transitions []StateTransition) string {
+	s := make([]string, 0, len(transitions))
+	for _, t := range transitions {
+		s = append(s, fmt.Sprintf("%d:%s:%d", t.Number, t.Effect, t.Duration))
+	}
+	return strings.Join(s, ",")
+}
+
+// CaptureSnapshotRequest Request JSON for CaptureSnapshotHandler
+type CaptureSnapshotRequest struct {
+	Label string `json:"label"`
+	Host  string `json:"host"` // vMix host. Primary vMix host is used if empty.
+}
+
+// CaptureSnapshotHandler captures current state of vMix host for [POST] /api/snapshots .
+func CaptureSnapshotHandler(c *gin.Context) {
+	req := CaptureSnapshotRequest{}
+	if err := c.ShouldBindJSON(&req); err != nil || strings.TrimSpace(req.Label) == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("label required")))
+		return
+	}
+	if req.Host == "" {
+		req.Host = *vmixaddr
+	}
+	snap, err := snapshots.Capture(req.Label, req.Host)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"snapshot": snap,
+	})
+}
+
+// GetSnapshotsHandler returns captured snapshots for [GET] /api/snapshots , only of host given by ?host= .
+func GetSnapshotsHandler(c *gin.Context) {
+	list := snapshots.List()
+	if host := c.Query("host"); host != "" {
+		filtered := make([]*Snapshot, 0, len(list))
+		for _, snap := range list {
+			if snap.Host == host {
+				filtered = append(filtered, snap)
+			}
+		}
+		list = filtered
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"snapshots": list,
+	})
+}
+
+// DeleteSnapshotHandler deletes snapshot for [DELETE] /api/snapshots/:label .
+func DeleteSnapshotHandler(c *gin.Context) {
+	label := c.Param("label")
+	if !snapshots.Delete(label) {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("snapshot %q not found", label)))
+		return
+	}
+	c.Status(http.StatusNoContent)
+}
+
+// DiffSnapshotsHandler compares two snapshots for [GET] /api/snapshots/diff?from=...&to=... .
+func DiffSnapshotsHandler(c *gin.Context) {
+	from, ok := snapshots.Get(c.Query("from"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("snapshot %q not found", c.Query("from"))))
+		return
+	}
+	to, ok := snapshots.Get(c.Query("to"))
+	if !ok {
+		AbortWithAppError(c, NewAppError(ErrKindNotFound, "", fmt.Errorf("snapshot %q not found", c.Query("to"))))
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"from": from,
+		"to":   to,
+		"diff": DiffStates(from.State, to.State),
+	})
+}
@@ -0,0 +1,102 @@
+package main
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestSnapshotCaptureAndDiff(t *testing.T) {
+	m := startMock(t)
+	host := "http://" + m.HTTPAddr
+	prev := snapshots
+	snapshots = NewSnapshotStore()
+	defer func() { snapshots = prev }()
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/snapshots", GetSnapshotsHandler)
+	r.POST("/api/snapshots", CaptureSnapshotHandler)
+	r.GET("/api/snapshots/diff", DiffSnapshotsHandler)
+	r.DELETE("/api/snapshots/:label", DeleteSnapshotHandler)
+	capture := func(label string) {
+		t.Helper()
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(http.MethodPost, "/api/snapshots", strings.NewReader(`{"label":"`+label+`","host":"`+host+`"}`)))
+		if w.Code != http.StatusOK {
+			t.Fatalf("expected %s captured, got %d %s", label, w.Code, w.Body)
+		}
+	}
+
+	capture("before")
+	m.Apply("http", "SetInputName", map[string]string{"Input": "1", "Value": "Renamed"})
+	m.Apply("http", "Cut", map[string]string{"Input": "4"})
+	capture("after")
+
+	if snap, ok := snapshots.Get("before"); !ok || snap.Host != host || snap.State == nil || len(snap.XML) == 0 {
+		t.Fatalf("expected snapshot of given host, got %+v", snap)
+	}
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/snapshots?host="+host, nil))
+	list := struct {
+		Snapshots []Snapshot `json:"snapshots"`
+	}{}
+	if err := json.Unmarshal(w.Body.Bytes(), &list); err != nil || len(list.Snapshots) != 2 || list.Snapshots[0].Label != "before" {
+		t.Fatalf("expected snapshots of host in capture order, got %s", w.Body)
+	}
+
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/snapshots/diff?from=before&to=after", nil))
+	res := struct {
+		Diff StateDiff `json:"diff"`
+	}{}
+	if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || w.Code != http.StatusOK {
+		t.Fatalf("expected diff, got %d %s", w.Code, w.Body)
+	}
+	d := res.Diff
+	if !d.HasDifferences || len(d.InputsAdded) != 0 || len(d.InputsRemoved) != 0 {
+		t.Fatalf("unexpected diff %+v", d)
+	}
+	if len(d.InputsChanged) != 1 || d.InputsChanged[0].Number != 1 || d.InputsChanged[0].Changes[0] != (FieldChange{Field: "title", From: "Colour", To: "Renamed"}) {
+		t.Fatalf("expected renamed input in diff, got %+v", d.InputsChanged)
+	}
+	if !hasChange(d.Settings, FieldChange{Field: "active", From: "2", To: "4"}) {
+		t.Fatalf("expected program change in diff, got %+v", d.Settings)
+	}
+
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodDelete, "/api/snapshots/before", nil))
+	if w.Code != http.StatusNoContent {
+		t.Fatalf("expected snapshot deleted, got %d", w.Code)
+	}
+	w = httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodGet, "/api/snapshots/diff?from=before&to=after", nil))
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("expected diff of deleted snapshot not found, got %d", w.Code)
+	}
+}
+
+func TestDiffStatesInputsAddedAndRemoved(t *testing.T) {
+	from, to := DefaultMockState(), DefaultMockState()
+	to.Inputs = append(to.Inputs[1:], StateInput{Key: "added", Number: 7, Type: "Colour", Title: "New"})
+	d := DiffStates(from, to)
+	if len(d.InputsAdded) != 1 || d.InputsAdded[0].Key != "added" || len(d.InputsRemoved) != 1 || d.InputsRemoved[0].Number != 1 {
+		t.Fatalf("unexpected diff %+v", d)
+	}
+	if d := DiffStates(from, DefaultMockState()); d.HasDifferences {
+		t.Fatalf("expected no differences between same states, got %+v", d)
+	}
+}
+
+func hasChange(changes []FieldChange, want FieldChange) bool {
+	for _, c := range changes {
+		if c == want {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,155 @@
+package main
+
+import (
+	"fmt"
+	"net"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// sourceCheckTimeout is timeout to check single stream URL.
+const sourceCheckTimeout = 5 * time.Second
+
+// defaultStreamPorts are ports used when stream URL has no explicit port.
+var defaultStreamPorts = map[string]string{
+	"http":  "80",
+	"https": "443",
+	"rtmp":  "1935",
+	"rtmps": "443",
+	"rtsp":  "554",
+}
+
+// InputSourceIssue is missing file or dead URL used by input.
+type InputSourceIssue struct {
+	InputKey string `json:"input_key"`
+	Number   int    `json:"number"`
+	Title    string `json:"title"`
+	Kind     string `json:"kind"` // "file" or "url".
+	Source   string `json:"source"`
+	Message  string `json:"message"`
+}
+
+// inputSources returns file paths and URLs used by input, from XML list items and preset.
+func inputSources(in StateInput, preset *Preset) []string {
+	var sources []string
+	seen := make(map[string]bool)
+	add := func(s string) {
+		if s = strings.TrimSpace(s); s != "" && !seen[s] {
+			seen[s] = true
+			sources = append(sources, s)
+		}
+	}
+	for _, item := range in.List {
+		add(item.Path)
+	}
+	if preset != nil {
+		for _, p := range preset.Inputs {
+			if p.Key == in.Key {
+				add(p.Source)
+			}
+		}
+	}
+	return sources
+}
+
+// checkSourceURL checks that stream URL is reachable. Schemes over UDP such as SRT cannot be checked and are skipped.
+func checkSourceURL(raw string) error {
+	u, err := url.Parse(raw)
+	if err != nil {
+		return err
+	}
+	switch u.Scheme {
+	case "http", "https":
+		client := &http.Client{Timeout: sourceCheckTimeout}
+		resp, err := client.Head(raw)
+		if err == nil && resp.StatusCode == http.StatusMethodNotAllowed {
+			resp.Body.Close()
+			resp, err = client.Get(raw)
+		}
+		if err != nil {
+			return err
+		}
+		resp.Body.Close()
+		if resp.StatusCode >= 400 {
+			return fmt.Errorf("unexpected status %s", resp.Status)
+		}
+		return nil
+	}
+	port, ok := defaultStreamPorts[u.Scheme]
+	if !ok {
+		return nil
+	}
+	if u.Port() != "" {
+		port = u.Port()
+	}
+	conn, err := net.DialTimeout("tcp", net.JoinHostPort(u.Hostname(), port), sourceCheckTimeout)
+	if err != nil {
+		return err
+	}
+	return conn.Close()
+}
+
+// ValidateInputSources checks files and URLs of every input. Files are checked with path mappings of hardware profile.
+func ValidateInputSources(s *State, preset *Preset, profile HardwareProfile) []InputSourceIssue {
+	issues := []InputSourceIssue{}
+	mu := sync.Mutex{}
+	wg := &sync.WaitGroup{}
+	report := func(in StateInput, kind, src, msg string) {
+		mu.Lock()
+		defer mu.Unlock()
+		issues = append(issues, InputSourceIssue{InputKey: in.Key, Number: in.Number, Title: in.Title, Kind: kind, Source: src, Message: msg})
+	}
+	for _, in := range s.Inputs {
+		for _, src := range inputSources(in, preset) {
+			switch {
+			case isFilePath(src):
+				local := mapPath(src, profile.PathMappings)
+				if _, err := os.Stat(local); err != nil {
+					report(in, "file", src, fmt.Sprintf("file not accessible at %s", local))
+				}
+			case strings.Contains(src, "://"):
+				wg.Add(1)
+				go func(in StateInput, src string) {
+					defer wg.Done()
+					if err := checkSourceURL(src); err != nil {
+						report(in, "url", src, err.Error())
+					}
+				}(in, src)
+			}
+		}
+	}
+	wg.Wait()
+	return issues
+}
+
+// ValidateInputSourcesHandler checks input sources of host for [GET] /api/inputs/sources/validate?host=... .
+// Primary vMix host is used if host is empty. Preset currently loaded in vMix is used when it is readable from this machine.
+func ValidateInputSourcesHandler(c *gin.Context) {
+	addr := c.DefaultQuery("host", *vmixaddr)
+	s, err := StreamState(addr)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	profile := config.Get().HardwareProfiles[addr]
+	var preset *Preset
+	if s.Preset != "" {
+		preset, err = LoadPreset(mapPath(s.Preset, profile.PathMappings))
+		if err != nil {
+			preset = nil
+		}
+	}
+	issues := ValidateInputSources(s, preset, profile)
+	c.JSON(http.StatusOK, gin.H{
+		"host":          addr,
+		"preset_loaded": preset != nil,
+		"valid":         len(issues) == 0,
+		"issues":        issues,
+	})
+}
@@ -0,0 +1,143 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// StartupAction is function sent once when connection is first confirmed after launch.
+// e.g. {"function":"StopStreaming","conditions":[{"field":"streaming","op":"eq","value":"true"}]} .
+type StartupAction struct {
+	FunctionCall
+	Conditions []Condition `json:"conditions"` // action is skipped unless every condition holds.
+}
+
+// StartupResult is result of startup actions of host.
+type StartupResult struct {
+	RanAt   time.Time `json:"ran_at"`
+	Sent    []string  `json:"sent"`
+	Skipped []string  `json:"skipped"`
+	Errors  []string  `json:"errors"`
+}
+
+// StartupActions runs startup actions of each host once, when host comes online for first time.
+type StartupActions struct {
+	mu      sync.Mutex
+	results map[string]*StartupResult // host -> result. Present once started.
+}
+
+// NewStartupActions creates StartupActions.
+func NewStartupActions() *StartupActions {
+	return &StartupActions{results: make(map[string]*StartupResult)}
+}
+
+// OnOnline runs startup actions of host unless they already ran. Standby secondary leaves them to primary.
+func (s *StartupActions) OnOnline(host, name string) {
+	if !pairing.IsActive() {
+		return
+	}
+	s.mu.Lock()
+	if _, ok := s.results[host]; ok {
+		s.mu.Unlock()
+		return
+	}
+	result := &StartupResult{RanAt: time.Now(), Sent: []string{}, Skipped: []string{}, Errors: []string{}}
+	s.results[host] = result
+	s.mu.Unlock()
+
+	actions := config.Get().StartupActions[host]
+	if len(actions) == 0 {
+		return
+	}
+	log.Printf("Running %d startup actions on %s\n", len(actions), name)
+	sent, skipped, errs := []string{}, []string{}, []string{}
+	for _, action := range actions {
+		cond, err := CheckConditions(host, action.Conditions)
+		switch {
+		case err != nil:
+			errs = append(errs, fmt.Sprintf("%s : %v", action.Function, err))
+			continue
+		case cond != nil:
+			skipped = append(skipped, fmt.Sprintf("%s : %s", action.Function, cond))
+			continue
+		}
+		if err := SendFunctionTo(host, action.Function, action.Params); err != nil {
+			errs = append(errs, fmt.Sprintf("%s : %v", action.Function, err))
+			continue
+		}
+		sent = append(sent, action.Function)
+	}
+	poller.TouchHost(host)
+	s.mu.Lock()
+	result.Sent, result.Skipped, result.Errors = sent, skipped, errs
+	snapshot := *result
+	s.mu.Unlock()
+	events.Publish(Event{Topic: "startup", Host: name, Data: snapshot})
+}
+
+// Reset allows startup actions of host to run again on next poll.
+func (s *StartupActions) Reset(host string) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	delete(s.results, host)
+}
+
+// Results returns results of hosts startup actions ran on.
+func (s *StartupActions) Results() map[string]StartupResult {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	results := make(map[string]StartupResult, len(s.results))
+	for host, r := range s.results {
+		results[host] = *r
+	}
+	return results
+}
+
+// GetStartupActionsHandler returns startup actions and results for [GET] /api/startup .
+func GetStartupActionsHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"actions": config.Get().StartupActions,
+		"results": startup.Results(),
+	})
+}
+
+// PutStartupActionsHandler saves startup actions of host for [PUT] /api/startup?host=... .
+func PutStartupActionsHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	actions := []StartupAction{}
+	if err := c.ShouldBindJSON(&actions); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	for _, action := range actions {
+		if action.Function == "" {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, fmt.Errorf("function required")))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.StartupActions[host] = actions
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetStartupActionsHandler(c)
+}
+
+// RerunStartupActionsHandler runs startup actions of host again for [POST] /api/startup/run?host=... .
+func RerunStartupActionsHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	status, ok := poller.Status(host)
+	if !ok || !status.Online {
+		AbortWithAppError(c, NewAppError(ErrKindUnreachable, host, fmt.Errorf("host is offline")))
+		return
+	}
+	startup.Reset(host)
+	startup.OnOnline(host, status.Name)
+	GetStartupActionsHandler(c)
+}
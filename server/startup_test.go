@@ -0,0 +1,34 @@
+package main
+
+import "testing"
+
+func TestStartupActionsRunOnce(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	if err := config.Update(func(cfg *Config) {
+		cfg.StartupActions[addr] = []StartupAction{
+			{FunctionCall: FunctionCall{Function: "StopStreaming"}, Conditions: []Condition{{Field: "streaming", Op: "eq", Value: "true"}}},
+			{FunctionCall: FunctionCall{Function: "SetVolume", Params: map[string]string{"Input": "2", "Value": "80"}}},
+		}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) { delete(cfg.StartupActions, addr) })
+
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "mock", Addr: addr, Online: true, State: DefaultMockState()}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	s := NewStartupActions()
+	s.OnOnline(addr, "mock")
+	s.OnOnline(addr, "mock")
+	r := s.Results()[addr]
+	if len(r.Sent) != 1 || len(r.Skipped) != 1 || len(r.Errors) != 0 {
+		t.Fatalf("unexpected result %+v", r)
+	}
+	if calls := m.Calls(); len(calls) != 1 || calls[0].Function != "SetVolume" {
+		t.Fatalf("expected single SetVolume, got %+v", calls)
+	}
+}
@@ -0,0 +1,450 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"encoding/xml"
+	"fmt"
+	"io"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Bool is vMix style boolean, encoded as "True"/"False" in XML.
+type Bool bool
+
+// MarshalText implements encoding.TextMarshaler.
+func (b Bool) MarshalText() ([]byte, error) {
+	if b {
+		return []byte("True"), nil
+	}
+	return []byte("False"), nil
+}
+
+// UnmarshalText implements encoding.TextUnmarshaler.
+func (b *Bool) UnmarshalText(text []byte) error {
+	s := strings.TrimSpace(string(text))
+	if s == "" {
+		*b = false
+		return nil
+	}
+	v, err := strconv.ParseBool(s)
+	if err != nil {
+		return err
+	}
+	*b = Bool(v)
+	return nil
+}
+
+// MarshalJSON encodes Bool as JSON boolean.
+func (b Bool) MarshalJSON() ([]byte, error) {
+	return json.Marshal(bool(b))
+}
+
+// UnmarshalJSON decodes JSON boolean.
+func (b *Bool) UnmarshalJSON(data []byte) error {
+	var v bool
+	if err := json.Unmarshal(data, &v); err != nil {
+		return err
+	}
+	*b = Bool(v)
+	return nil
+}
+
+// InputState is playback state of input. XML keeps vMix form such as "Running",
+// and JSON uses lower case enum such as "running", so clients never match vMix strings.
+type InputState string
+
+// Input states reported by vMix.
+const (
+	InputRunning   InputState = "Running"
+	InputPaused    InputState = "Paused"
+	InputCompleted InputState = "Completed"
+)
+
+// MarshalJSON implements json.Marshaler.
+func (s InputState) MarshalJSON() ([]byte, error) {
+	return json.Marshal(strings.ToLower(string(s)))
+}
+
+// UnmarshalJSON implements json.Unmarshaler. Both enum and vMix form are accepted.
+func (s *InputState) UnmarshalJSON(data []byte) error {
+	var v string
+	if err := json.Unmarshal(data, &v); err != nil {
+		return err
+	}
+	*s = InputState(v)
+	for _, known := range []InputState{InputRunning, InputPaused, InputCompleted} {
+		if strings.EqualFold(v, string(known)) {
+			*s = known
+		}
+	}
+	return nil
+}
+
+// State is vMix XML state returned by /api .
+type State struct {
+	XMLName     xml.Name          `xml:"vmix" json:"-"`
+	Version     string            `xml:"version" json:"version"`
+	Edition     string            `xml:"edition" json:"edition"`
+	Preset      string            `xml:"preset" json:"preset"`
+	Inputs      []StateInput      `xml:"inputs>input" json:"inputs"`
+	Overlays    []StateOverlay    `xml:"overlays>overlay" json:"overlays"`
+	Preview     int               `xml:"preview" json:"preview"`
+	Active      int               `xml:"active" json:"active"`
+	FadeToBlack Bool              `xml:"fadeToBlack" json:"fade_to_black"`
+	Transitions []StateTransition `xml:"transitions>transition" json:"transitions"`
+	Recording   Bool              `xml:"recording" json:"recording"`
+	External    Bool              `xml:"external" json:"external"`
+	Streaming   Bool              `xml:"streaming" json:"streaming"`
+	PlayList    Bool              `xml:"playList" json:"play_list"`
+	MultiCorder Bool              `xml:"multiCorder" json:"multi_corder"`
+	FullScreen  Bool              `xml:"fullscreen" json:"fullscreen"`
+	Dynamic     StateDynamic      `xml:"dynamic" json:"dynamic"`
+	Partial     bool              `xml:"-" json:"partial,omitempty"` // parsed by fallback parser. Only inputs, active and preview are reliable.
+}
+
+// StateDynamic is <dynamic> element, holding dynamic input and value slots 1-4 used by functions such as "Cut Input=Dynamic1".
+type StateDynamic struct {
+	Input1 string `xml:"input1" json:"input1"`
+	Input2 string `xml:"input2" json:"input2"`
+	Input3 string `xml:"input3" json:"input3"`
+	Input4 string `xml:"input4" json:"input4"`
+	Value1 string `xml:"value1" json:"value1"`
+	Value2 string `xml:"value2" json:"value2"`
+	Value3 string `xml:"value3" json:"value3"`
+	Value4 string `xml:"value4" json:"value4"`
+}
+
+// Slot returns pointer to input or value slot 1-4, or nil if out of range.
+func (d *StateDynamic) Slot(kind string, n int) *string {
+	var slots [4]*string
+	switch kind {
+	case "input":
+		slots = [4]*string{&d.Input1, &d.Input2, &d.Input3, &d.Input4}
+	case "value":
+		slots = [4]*string{&d.Value1, &d.Value2, &d.Value3, &d.Value4}
+	default:
+		return nil
+	}
+	if n < 1 || n > 4 {
+		return nil
+	}
+	return slots[n-1]
+}
+
+// StateInput is single <input> element.
+type StateInput struct {
+	Key        string               `xml:"key,attr" json:"key"`
+	Number     int                  `xml:"number,attr" json:"number"`
+	Type       string               `xml:"type,attr" json:"type"`
+	Title      string               `xml:"title,attr" json:"title"`
+	ShortTitle string               `xml:"shortTitle,attr,omitempty" json:"short_title"`
+	State      InputState           `xml:"state,attr" json:"state"`
+	Position   int                  `xml:"position,attr" json:"position"`
+	Duration   int                  `xml:"duration,attr" json:"duration"`
+	Loop       Bool                 `xml:"loop,attr" json:"loop"`
+	Muted      Bool                 `xml:"muted,attr" json:"muted"`
+	Volume     float64              `xml:"volume,attr,omitempty" json:"volume"` // 0-100. Absent on inputs without audio.
+	Balance    float64              `xml:"balance,attr,omitempty" json:"balance"`
+	Solo       Bool                 `xml:"solo,attr" json:"solo"`
+	AudioBuses string               `xml:"audiobusses,attr,omitempty" json:"audio_buses"` // e.g. "M,A" .
+	Fields     []StateInputField    `xml:"text" json:"fields,omitempty"`                   // text fields of title inputs.
+	Colors     []StateInputField    `xml:"color" json:"colors,omitempty"`                  // color fields of title inputs. e.g. "#FF0000" .
+	Overlays   []StateInputOverlay  `xml:"overlay" json:"overlays"`
+	List       []StateInputListItem `xml:"list>item" json:"list,omitempty"`      // files of VideoList and Photos inputs.
+	Source     string               `xml:"-" json:"source,omitempty"`            // file path or URL, where available.
+	Transform  *StateTransform      `xml:"position" json:"transform,omitempty"` // pan and zoom, present only when changed from default.
+	Text       string               `xml:",chardata" json:"text"`
+}
+
+// StateInputField is <text> or <color> field of title input.
+type StateInputField struct {
+	Index int    `xml:"index,attr" json:"index"`
+	Name  string `xml:"name,attr" json:"name"` // e.g. "Headline.Text" .
+	Value string `xml:",chardata" json:"value"`
+}
+
+// StateTransform is <position> element of input or layer.
+type StateTransform struct {
+	PanX  float64 `xml:"panX,attr" json:"pan_x"`
+	PanY  float64 `xml:"panY,attr" json:"pan_y"`
+	ZoomX float64 `xml:"zoomX,attr" json:"zoom_x"`
+	ZoomY float64 `xml:"zoomY,attr" json:"zoom_y"`
+}
+
+// StateInputListItem is file in input list.
+type StateInputListItem struct {
+	Selected Bool   `xml:"selected,attr,omitempty" json:"selected"`
+	Path     string `xml:",chardata" json:"path"`
+}
+
+// listSource returns selected list item, or first item if none is selected.
+func (in StateInput) listSource() string {
+	for _, item := range in.List {
+		if item.Selected {
+			return item.Path
+		}
+	}
+	if len(in.List) > 0 {
+		return in.List[0].Path
+	}
+	return ""
+}
+
+// StateInputOverlay is layer of input.
+type StateInputOverlay struct {
+	Index     int             `xml:"index,attr" json:"index"`
+	Key       string          `xml:"key,attr" json:"key"`
+	Transform *StateTransform `xml:"position" json:"transform,omitempty"`
+}
+
+// StateOverlay is overlay channel. Text contains active input number, empty if off.
+type StateOverlay struct {
+	Number  int    `xml:"number,attr" json:"number"`
+	Preview Bool   `xml:"preview,attr,omitempty" json:"preview"`
+	Text    string `xml:",chardata" json:"input"`
+}
+
+// StateTransition is transition button setting.
+type StateTransition struct {
+	Number   int    `xml:"number,attr" json:"number"`
+	Effect   string `xml:"effect,attr" json:"effect"`
+	Duration int    `xml:"duration,attr" json:"duration"`
+}
+
+// ParseState parses vMix XML.
+func ParseState(b []byte) (*State, error) {
+	return DecodeState(bytes.NewReader(b))
+}
+
+// StateDecodeError is error of DecodeState, with byte offset in XML where decoding stopped.
+type StateDecodeError struct {
+	Offset int64
+	Err    error
+}
+
+func (e *StateDecodeError) Error() string {
+	return fmt.Sprintf("offset %d : %v", e.Offset, e.Err)
+}
+
+// Unwrap returns underlying error.
+func (e *StateDecodeError) Unwrap() error {
+	return e.Err
+}
+
+// DecodeState parses vMix XML from r as stream. Inputs are decoded one by one,
+// so large states with thousands of inputs are never held as whole XML document.
+func DecodeState(r io.Reader) (*State, error) {
+	s := &State{}
+	dec := xml.NewDecoder(r)
+	depth := 0
+	for {
+		tok, err := dec.Token()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return nil, &StateDecodeError{Offset: dec.InputOffset(), Err: err}
+		}
+		switch t := tok.(type) {
+		case xml.StartElement:
+			if depth == 0 {
+				if t.Name.Local != "vmix" {
+					return nil, &StateDecodeError{Offset: dec.InputOffset(), Err: fmt.Errorf("unexpected root element %q", t.Name.Local)}
+				}
+				s.XMLName = t.Name
+				depth++
+				continue
+			}
+			if err := decodeStateElement(dec, t, s); err != nil {
+				return nil, &StateDecodeError{Offset: dec.InputOffset(), Err: err}
+			}
+		case xml.EndElement:
+			depth--
+		}
+	}
+	if depth != 0 || s.XMLName.Local == "" {
+		return nil, &StateDecodeError{Offset: dec.InputOffset(), Err: fmt.Errorf("unexpected end of vMix XML")}
+	}
+	return s, nil
+}
+
+// decodeStateElement decodes direct child of <vmix> into s.
+func decodeStateElement(dec *xml.Decoder, start xml.StartElement, s *State) error {
+	switch start.Name.Local {
+	case "version":
+		return dec.DecodeElement(&s.Version, &start)
+	case "edition":
+		return dec.DecodeElement(&s.Edition, &start)
+	case "preset":
+		return dec.DecodeElement(&s.Preset, &start)
+	case "preview":
+		return dec.DecodeElement(&s.Preview, &start)
+	case "active":
+		return dec.DecodeElement(&s.Active, &start)
+	case "fadeToBlack":
+		return dec.DecodeElement(&s.FadeToBlack, &start)
+	case "recording":
+		return dec.DecodeElement(&s.Recording, &start)
+	case "external":
+		return dec.DecodeElement(&s.External, &start)
+	case "streaming":
+		return dec.DecodeElement(&s.Streaming, &start)
+	case "playList":
+		return dec.DecodeElement(&s.PlayList, &start)
+	case "multiCorder":
+		return dec.DecodeElement(&s.MultiCorder, &start)
+	case "fullscreen":
+		return dec.DecodeElement(&s.FullScreen, &start)
+	case "dynamic":
+		return dec.DecodeElement(&s.Dynamic, &start)
+	case "inputs":
+		return decodeStateChildren(dec, "input", func(child xml.StartElement) error {
+			in := StateInput{}
+			if err := dec.DecodeElement(&in, &child); err != nil {
+				return err
+			}
+			in.Source = in.listSource()
+			s.Inputs = append(s.Inputs, in)
+			return nil
+		})
+	case "overlays":
+		return decodeStateChildren(dec, "overlay", func(child xml.StartElement) error {
+			ov := StateOverlay{}
+			if err := dec.DecodeElement(&ov, &child); err != nil {
+				return err
+			}
+			s.Overlays = append(s.Overlays, ov)
+			return nil
+		})
+	case "transitions":
+		return decodeStateChildren(dec, "transition", func(child xml.StartElement) error {
+			tr := StateTransition{}
+			if err := dec.DecodeElement(&tr, &child); err != nil {
+				return err
+			}
+			s.Transitions = append(s.Transitions, tr)
+			return nil
+		})
+	}
+	return dec.Skip()
+}
+
+// decodeStateChildren calls fn for each child element named name, skipping others, until parent ends.
+func decodeStateChildren(dec *xml.Decoder, name string, fn func(child xml.StartElement) error) error {
+	for {
+		tok, err := dec.Token()
+		if err != nil {
+			return err
+		}
+		switch t := tok.(type) {
+		case xml.StartElement:
+			if t.Name.Local != name {
+				if err := dec.Skip(); err != nil {
+					return err
+				}
+				continue
+			}
+			if err := fn(t); err != nil {
+				return err
+			}
+		case xml.EndElement:
+			return nil
+		}
+	}
+}
+
+// FetchState fetches and parses XML from vMix HTTP API at addr. Raw XML is returned too.
+func FetchState(addr string) (*State, []byte, error) {
+	raw := &bytes.Buffer{}
+	s, err := fetchState(addr, raw)
+	if err != nil {
+		return nil, raw.Bytes(), err
+	}
+	return s, raw.Bytes(), nil
+}
+
+// StreamState fetches and parses XML from vMix HTTP API at addr without keeping raw XML.
+func StreamState(addr string) (*State, error) {
+	return fetchState(addr, nil)
+}
+
+func fetchState(addr string, raw io.Writer) (*State, error) {
+	if IsOfflineAddr(addr) {
+		return offline.fetch(addr, raw)
+	}
+	resp, err := hostClients.Get(addr).Get(strings.TrimSuffix(addr, "/") + "/api")
+	if err != nil {
+		return nil, NewAppError(ErrKindUnreachable, addr, err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, NewAppError(ErrKindUnreachable, addr, &VMixStatusError{Code: resp.StatusCode, Status: resp.Status})
+	}
+	var body io.Reader = resp.Body
+	if raw != nil {
+		body = io.TeeReader(resp.Body, raw)
+	}
+	s, err := DecodeState(body)
+	if err != nil {
+		return refetchStateFallback(addr, raw, err)
+	}
+	return s, nil
+}
+
+// FindInput resolves input by number, key or title, same as vMix "Input" query.
+func (s *State) FindInput(ref string) *StateInput {
+	ref = strings.TrimSpace(ref)
+	if n, err := strconv.Atoi(ref); err == nil {
+		for i := range s.Inputs {
+			if s.Inputs[i].Number == n {
+				return &s.Inputs[i]
+			}
+		}
+		return nil
+	}
+	for i := range s.Inputs {
+		if s.Inputs[i].Key == ref {
+			return &s.Inputs[i]
+		}
+	}
+	for i := range s.Inputs {
+		if strings.EqualFold(s.Inputs[i].Title, ref) {
+			return &s.Inputs[i]
+		}
+	}
+	return nil
+}
+
+// Tally returns tally string used by TCP API. 0=off, 1=program, 2=preview, per input number.
+func (s *State) Tally() string {
+	sb := strings.Builder{}
+	for _, in := range s.Inputs {
+		switch {
+		case in.Number == s.Active:
+			sb.WriteByte('1')
+		case in.Number == s.Preview:
+			sb.WriteByte('2')
+		default:
+			sb.WriteByte('0')
+		}
+	}
+	return sb.String()
+}
+
+// GetStateHandler returns current vMix state as JSON for [GET] /api/state .
+func GetStateHandler(c *gin.Context) {
+	s, err := StreamState(*vmixaddr)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	inputCache.Update(s)
+	c.JSON(http.StatusOK, gin.H{
+		"state": s,
+	})
+}
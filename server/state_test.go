@@ -0,0 +1,98 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"strings"
+	"testing"
+)
+
+// largeStateReader generates vMix XML with n inputs without building whole document.
+func largeStateReader(n int) io.Reader {
+	pr, pw := io.Pipe()
+	go func() {
+		fmt.Fprint(pw, `<?xml version="1.0"?><vmix><version>24.0.0.72</version><edition>4K</edition><inputs>`)
+		for i := 1; i <= n; i++ {
+			fmt.Fprintf(pw, `<input key="key-%d" number="%d" type="Colour" title="Colour %d" state="Paused" position="0" duration="0" loop="False">Colour %d<overlay index="0" key="key-1"/></input>`, i, i, i, i)
+		}
+		fmt.Fprint(pw, `</inputs><overlays><overlay number="1">3</overlay><overlay number="2"/></overlays><preview>2</preview><active>1</active>`)
+		fmt.Fprint(pw, `<fadeToBlack>True</fadeToBlack><audio><master volume="100" muted="False"/></audio><dynamic><input1></input1></dynamic></vmix>`)
+		pw.Close()
+	}()
+	return pr
+}
+
+func TestDecodeStateLarge(t *testing.T) {
+	s, err := DecodeState(largeStateReader(5000))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(s.Inputs) != 5000 {
+		t.Fatalf("expected 5000 inputs, got %d", len(s.Inputs))
+	}
+	last := s.Inputs[4999]
+	if last.Number != 5000 || last.Title != "Colour 5000" || len(last.Overlays) != 1 {
+		t.Fatalf("unexpected last input %+v", last)
+	}
+	if s.Active != 1 || s.Preview != 2 || !bool(s.FadeToBlack) || s.Overlays[0].Text != "3" {
+		t.Fatalf("unexpected state %+v", s)
+	}
+}
+
+func TestDecodeStateInvalid(t *testing.T) {
+	for _, x := range []string{"", "<notvmix></notvmix>", "<vmix><inputs>"} {
+		if _, err := DecodeState(strings.NewReader(x)); err == nil {
+			t.Errorf("expected error for %q", x)
+		}
+	}
+}
+
+func TestDecodeStateInputList(t *testing.T) {
+	s, err := ParseState([]byte(`<vmix><inputs><input key="k1" number="1" type="VideoList" title="Clips">Clips<list><item>C:\clips\a.mp4</item><item selected="true">C:\clips\b.mp4</item></list></input></inputs></vmix>`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	in := s.Inputs[0]
+	if len(in.List) != 2 || in.Source != `C:\clips\b.mp4` || in.Text != "Clips" {
+		t.Fatalf("unexpected input %+v", in)
+	}
+	issues := ValidateInputSources(s, nil, HardwareProfile{})
+	if len(issues) != 2 || issues[0].Kind != "file" {
+		t.Fatalf("expected missing files, got %+v", issues)
+	}
+}
+
+func TestDecodeStateInputDetail(t *testing.T) {
+	s, err := ParseState([]byte(`<vmix><inputs><input key="k1" number="1" type="GT" title="Lower Third" state="Paused" position="1200" duration="5000" loop="True" muted="True" volume="75.5" balance="-0.5" solo="False" audiobusses="M,A">Lower Third<text index="0" name="Headline.Text">Hello &amp; welcome</text><color index="0" name="Rectangle1.Fill">#FF0000</color></input></inputs></vmix>`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	in := s.Inputs[0]
+	if !in.Loop || !in.Muted || in.Volume != 75.5 || in.Balance != -0.5 || in.AudioBuses != "M,A" || in.Position != 1200 {
+		t.Fatalf("unexpected input %+v", in)
+	}
+	if len(in.Fields) != 1 || in.Fields[0].Name != "Headline.Text" || in.Fields[0].Value != "Hello & welcome" {
+		t.Fatalf("unexpected text fields %+v", in.Fields)
+	}
+	if len(in.Colors) != 1 || in.Colors[0].Value != "#FF0000" || in.Text != "Lower Third" {
+		t.Fatalf("unexpected input %+v", in)
+	}
+}
+
+func TestInputStateJSON(t *testing.T) {
+	b, err := json.Marshal(StateInput{State: InputRunning})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !strings.Contains(string(b), `"state":"running"`) {
+		t.Fatalf("expected enum in JSON, got %s", b)
+	}
+	in := StateInput{}
+	if err := json.Unmarshal(b, &in); err != nil {
+		t.Fatal(err)
+	}
+	if in.State != InputRunning {
+		t.Fatalf("expected vMix form after unmarshal, got %q", in.State)
+	}
+}
@@ -0,0 +1,144 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"html"
+	"io"
+	"io/ioutil"
+	"log"
+	"regexp"
+	"strconv"
+	"strings"
+	"sync"
+)
+
+// StateFallbackParser parses XML rejected by DecodeState, such as from beta vMix versions or exotic inputs.
+// It should return State with Partial set, or error if nothing useful was found.
+type StateFallbackParser func(raw []byte) (*State, error)
+
+var (
+	stateFallbackMu      sync.RWMutex
+	stateFallbackParsers = []StateFallbackParser{ParseStateLenient}
+)
+
+// RegisterStateFallbackParser adds fallback parser tried before built-in lenient parser.
+func RegisterStateFallbackParser(p StateFallbackParser) {
+	stateFallbackMu.Lock()
+	defer stateFallbackMu.Unlock()
+	stateFallbackParsers = append([]StateFallbackParser{p}, stateFallbackParsers...)
+}
+
+// stateFragmentSize is bytes logged on each side of offset where decoding failed.
+const stateFragmentSize = 120
+
+// stateFragment returns raw XML around offset.
+func stateFragment(raw []byte, offset int64) string {
+	start, end := offset-stateFragmentSize, offset+stateFragmentSize
+	if start < 0 {
+		start = 0
+	}
+	if end > int64(len(raw)) {
+		end = int64(len(raw))
+	}
+	if start > end {
+		start = end
+	}
+	return string(raw[start:end])
+}
+
+// ParseStateWithFallback parses XML by DecodeState, falling back to fallback parsers when it fails.
+// Problematic fragment is logged so parser can be fixed later.
+func ParseStateWithFallback(addr string, raw []byte) (*State, error) {
+	s, err := ParseState(raw)
+	if err == nil {
+		return s, nil
+	}
+	decodeErr := &StateDecodeError{}
+	if errors.As(err, &decodeErr) {
+		log.Printf("Failed to parse vMix XML of %s : %v\nFragment: %s\n", addr, err, stateFragment(raw, decodeErr.Offset))
+	} else {
+		log.Printf("Failed to parse vMix XML of %s : %v\n", addr, err)
+	}
+	stateFallbackMu.RLock()
+	parsers := stateFallbackParsers
+	stateFallbackMu.RUnlock()
+	for _, p := range parsers {
+		if s, ferr := p(raw); ferr == nil {
+			s.Partial = true
+			return s, nil
+		}
+	}
+	return nil, NewAppError(ErrKindInternal, addr, err)
+}
+
+// refetchStateFallback fetches XML again as whole after streaming decode failed, and parses it with fallback parsers.
+// Streaming is kept for normal path, so whole XML is held only in this rare case.
+func refetchStateFallback(addr string, raw io.Writer, cause error) (*State, error) {
+	resp, err := hostClients.Get(addr).Get(strings.TrimSuffix(addr, "/") + "/api")
+	if err != nil {
+		return nil, NewAppError(ErrKindInternal, addr, cause)
+	}
+	defer resp.Body.Close()
+	b, err := ioutil.ReadAll(resp.Body)
+	if err != nil {
+		return nil, NewAppError(ErrKindInternal, addr, cause)
+	}
+	if buf, ok := raw.(*bytes.Buffer); ok {
+		// Replace XML partially written by failed decode.
+		buf.Reset()
+		buf.Write(b)
+	}
+	return ParseStateWithFallback(addr, b)
+}
+
+var (
+	lenientInput = regexp.MustCompile(`<input\s([^>]*)>`)
+	lenientAttr  = regexp.MustCompile(`([A-Za-z]+)="([^"]*)"`)
+)
+
+// lenientElementInt returns integer content of first element name, or 0.
+func lenientElementInt(raw []byte, name string) int {
+	m := regexp.MustCompile(`<` + name + `>\s*(\d+)\s*</` + name + `>`).FindSubmatch(raw)
+	if m == nil {
+		return 0
+	}
+	n, _ := strconv.Atoi(string(m[1]))
+	return n
+}
+
+// lenientElementText returns text content of first element name, or empty.
+func lenientElementText(raw []byte, name string) string {
+	m := regexp.MustCompile(`<` + name + `>([^<]*)</` + name + `>`).FindSubmatch(raw)
+	if m == nil {
+		return ""
+	}
+	return html.UnescapeString(strings.TrimSpace(string(m[1])))
+}
+
+// ParseStateLenient extracts at least inputs, active and preview by pattern matching, ignoring XML structure.
+func ParseStateLenient(raw []byte) (*State, error) {
+	s := &State{
+		Version: lenientElementText(raw, "version"),
+		Edition: lenientElementText(raw, "edition"),
+		Active:  lenientElementInt(raw, "active"),
+		Preview: lenientElementInt(raw, "preview"),
+		Partial: true,
+	}
+	for _, m := range lenientInput.FindAllSubmatch(raw, -1) {
+		attrs := map[string]string{}
+		for _, a := range lenientAttr.FindAllSubmatch(m[1], -1) {
+			attrs[string(a[1])] = html.UnescapeString(string(a[2]))
+		}
+		n, err := strconv.Atoi(attrs["number"])
+		if err != nil || attrs["key"] == "" {
+			continue
+		}
+		s.Inputs = append(s.Inputs, StateInput{Key: attrs["key"], Number: n, Type: attrs["type"], Title: attrs["title"], ShortTitle: attrs["shortTitle"], State: InputState(attrs["state"])})
+	}
+	if len(s.Inputs) == 0 {
+		return nil, fmt.Errorf("no inputs found")
+	}
+	return s, nil
+}
@@ -0,0 +1,21 @@
+package main
+
+import "testing"
+
+func TestParseStateWithFallback(t *testing.T) {
+	// Unescaped "&" in text is rejected by XML decoder.
+	raw := []byte(`<vmix><version>27.0.0.49</version><inputs><input key="k1" number="1" type="Capture" title="Camera 1" state="Running">Camera 1</input><input key="k2" number="2" type="Xaml" title="Tom &amp; Jerry" state="Paused">Tom & Jerry<beta/></input></inputs><preview>1</preview><active>2</active></vmix>`)
+	if _, err := ParseState(raw); err == nil {
+		t.Fatal("expected strict parser to fail")
+	}
+	s, err := ParseStateWithFallback("test", raw)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !s.Partial || s.Version != "27.0.0.49" || s.Active != 2 || s.Preview != 1 || len(s.Inputs) != 2 || s.Inputs[1].Title != "Tom & Jerry" {
+		t.Fatalf("unexpected state %+v", s)
+	}
+	if _, err := ParseStateWithFallback("test", []byte(`<html>not vMix</html>`)); err == nil {
+		t.Fatal("expected error without inputs")
+	}
+}
@@ -0,0 +1,237 @@
+package main
+
+import (
+	"bytes"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"image"
+	"image/color"
+	"image/draw"
+	"image/png"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Stream Deck action UUIDs of plugin. Shortcut key runs shortcut, and tally key previews input and shows its tally.
+const (
+	StreamDeckActionShortcut = "com.flowingspdg.vmixutility.shortcut"
+	StreamDeckActionTally    = "com.flowingspdg.vmixutility.tally"
+)
+
+const (
+	streamDeckIconSize = 72                     // key image size of standard Stream Deck.
+	streamDeckRefresh  = 250 * time.Millisecond // tally keys are re-rendered when tally changes, checked this often.
+)
+
+// StreamDeckMessage is event of Stream Deck SDK. Plugin forwards events of its keys verbatim to utility,
+// and events from utility, such as "setImage" and "setTitle", verbatim to Stream Deck.
+type StreamDeckMessage struct {
+	Event   string          `json:"event"`
+	Action  string          `json:"action,omitempty"`
+	Context string          `json:"context"` // key instance.
+	Payload json.RawMessage `json:"payload,omitempty"`
+}
+
+// StreamDeckKeySettings is settings of key, set in property inspector of plugin.
+type StreamDeckKeySettings struct {
+	Shortcut string `json:"shortcut"` // shortcut name of shortcut key.
+	Host     string `json:"host"`     // vMix host of tally key. Primary vMix host is used if empty.
+	Input    string `json:"input"`    // input or alias of tally key. e.g. "CAM 1" .
+}
+
+// streamDeckKey is key visible on Stream Deck.
+type streamDeckKey struct {
+	action   string
+	settings StreamDeckKeySettings
+	rendered string // tally and number last rendered, to skip unchanged images.
+}
+
+// RenderStreamDeckIcon renders tally key image filled by tally color and labelled by input number. Number 0 is not drawn.
+func RenderStreamDeckIcon(tally string, number int) ([]byte, error) {
+	img := image.NewRGBA(image.Rect(0, 0, streamDeckIconSize, streamDeckIconSize))
+	draw.Draw(img, img.Bounds(), image.NewUniform(multiviewColors[tally]), image.Point{}, draw.Src)
+	draw.Draw(img, img.Bounds().Inset(6), image.NewUniform(color.RGBA{R: 0x20, G: 0x20, B: 0x20, A: 0xff}), image.Point{}, draw.Src)
+	if number > 0 {
+		digits := strconv.Itoa(number)
+		scale := 6
+		if len(digits) > 2 {
+			scale = 4
+		}
+		w := (4*len(digits) - 1) * scale
+		drawDigits(img, digits, (streamDeckIconSize-w)/2, (streamDeckIconSize-5*scale)/2, scale)
+	}
+	buf := &bytes.Buffer{}
+	if err := png.Encode(buf, img); err != nil {
+		return nil, err
+	}
+	return buf.Bytes(), nil
+}
+
+// streamDeckTally returns tally and number of input of tally key, or empty tally and 0 if not found.
+func streamDeckTally(settings StreamDeckKeySettings) (string, int) {
+	host := settings.Host
+	if host == "" {
+		host = *vmixaddr
+	}
+	s, err := hostState(host)
+	if err != nil {
+		return "", 0
+	}
+	in := s.FindInput(ResolveInputAlias(host, settings.Input))
+	if in == nil {
+		return "", 0
+	}
+	return multiviewTally(s, in.Number), in.Number
+}
+
+// streamDeckSession is Stream Deck plugin connection.
+type streamDeckSession struct {
+	c    *gin.Context
+	ws   *wsConn
+	mu   sync.Mutex
+	keys map[string]*streamDeckKey // context -> key.
+}
+
+// send writes event for key context.
+func (s *streamDeckSession) send(event, context string, payload interface{}) error {
+	msg := StreamDeckMessage{Event: event, Context: context}
+	if payload != nil {
+		b, err := json.Marshal(payload)
+		if err != nil {
+			return err
+		}
+		msg.Payload = b
+	}
+	return s.ws.WriteJSON(msg)
+}
+
+// handle processes event from Stream Deck.
+func (s *streamDeckSession) handle(msg StreamDeckMessage) {
+	payload := struct {
+		Settings StreamDeckKeySettings `json:"settings"`
+	}{}
+	if len(msg.Payload) > 0 {
+		json.Unmarshal(msg.Payload, &payload)
+	}
+	switch msg.Event {
+	case "willAppear", "didReceiveSettings":
+		s.mu.Lock()
+		s.keys[msg.Context] = &streamDeckKey{action: msg.Action, settings: payload.Settings}
+		s.mu.Unlock()
+		if msg.Action == StreamDeckActionShortcut {
+			s.send("setTitle", msg.Context, gin.H{"title": payload.Settings.Shortcut, "target": 0})
+		} else {
+			s.refresh()
+		}
+	case "willDisappear":
+		s.mu.Lock()
+		delete(s.keys, msg.Context)
+		s.mu.Unlock()
+	case "keyDown":
+		var err error
+		switch msg.Action {
+		case StreamDeckActionShortcut:
+			_, err = runShortcut(s.c, payload.Settings.Shortcut)
+		case StreamDeckActionTally:
+			host := payload.Settings.Host
+			if host == "" {
+				host = *vmixaddr
+			}
+			poller.Touch()
+			if err = SendFunctionTo(host, "PreviewInput", map[string]string{"Input": payload.Settings.Input}); err == nil {
+				announceAction(s.c, host, "PreviewInput "+payload.Settings.Input)
+			}
+		default:
+			err = fmt.Errorf("unknown action %q", msg.Action)
+		}
+		if err != nil {
+			s.send("showAlert", msg.Context, nil)
+			return
+		}
+		s.send("showOk", msg.Context, nil)
+	}
+}
+
+// refresh re-renders tally keys whose tally changed.
+func (s *streamDeckSession) refresh() error {
+	type update struct {
+		context string
+		tally   string
+		number  int
+	}
+	updates := []update{}
+	s.mu.Lock()
+	for context, key := range s.keys {
+		if key.action != StreamDeckActionTally {
+			continue
+		}
+		tally, number := streamDeckTally(key.settings)
+		if rendered := fmt.Sprintf("%s/%d", tally, number); rendered != key.rendered {
+			key.rendered = rendered
+			updates = append(updates, update{context, tally, number})
+		}
+	}
+	s.mu.Unlock()
+	for _, u := range updates {
+		b, err := RenderStreamDeckIcon(u.tally, u.number)
+		if err != nil {
+			return err
+		}
+		if err := s.send("setImage", u.context, gin.H{"image": "data:image/png;base64," + base64.StdEncoding.EncodeToString(b), "target": 0}); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// StreamDeckWebSocketHandler serves Stream Deck plugin for [GET] /api/streamdeck/ws .
+// Plugin forwards Stream Deck SDK events of its keys, and utility answers with key feedback events, so plugin needs no logic of its own.
+func StreamDeckWebSocketHandler(c *gin.Context) {
+	ws, err := upgradeWebSocket(c.Writer, c.Request)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	defer ws.Close()
+	defer operators.Connect(operatorName(c), c.ClientIP())()
+
+	s := &streamDeckSession{c: c, ws: ws, keys: make(map[string]*streamDeckKey)}
+	closed := make(chan struct{})
+	go func() {
+		defer close(closed)
+		for {
+			b, err := ws.ReadMessage()
+			if err != nil {
+				return
+			}
+			msg := StreamDeckMessage{}
+			if err := json.Unmarshal(b, &msg); err != nil {
+				continue
+			}
+			s.handle(msg)
+		}
+	}()
+
+	refresh := time.NewTicker(streamDeckRefresh)
+	defer refresh.Stop()
+	ping := time.NewTicker(wsPingInterval)
+	defer ping.Stop()
+	for {
+		select {
+		case <-closed:
+			return
+		case <-ping.C:
+			if err := ws.writeFrame(wsOpPing, nil); err != nil {
+				return
+			}
+		case <-refresh.C:
+			if err := s.refresh(); err != nil {
+				return
+			}
+		}
+	}
+}
@@ -0,0 +1,37 @@
+package main
+
+import (
+	"bytes"
+	"image/png"
+	"testing"
+)
+
+func TestStreamDeckTallyIcon(t *testing.T) {
+	addr := "http://192.0.2.1:8088"
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Addr: addr, Online: true, State: DefaultMockState()}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	for input, want := range map[string]string{"2": "program", "3": "preview", "4": "", "99": ""} {
+		if tally, _ := streamDeckTally(StreamDeckKeySettings{Host: addr, Input: input}); tally != want {
+			t.Fatalf("input %s expected tally %q, got %q", input, want, tally)
+		}
+	}
+
+	b, err := RenderStreamDeckIcon("program", 2)
+	if err != nil {
+		t.Fatal(err)
+	}
+	img, err := png.Decode(bytes.NewReader(b))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if img.Bounds().Dx() != streamDeckIconSize {
+		t.Fatalf("unexpected icon size %v", img.Bounds())
+	}
+	if r, g, _, _ := img.At(1, 1).RGBA(); r>>8 != 0xe0 || g>>8 != 0x20 {
+		t.Fatalf("expected program border, got %v", img.At(1, 1))
+	}
+}
@@ -0,0 +1,444 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"log"
+	"net"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Stream health issues.
+const (
+	StreamIssueDisconnected = "disconnected"         // streaming stopped while armed.
+	StreamIssueUnreachable  = "endpoint_unreachable" // RTMP endpoint did not accept connection.
+	StreamIssueLowBitrate   = "low_bitrate"          // bitrate below minimum.
+	StreamIssueOffPlatform  = "platform_offline"     // vMix is streaming but platform shows channel offline.
+)
+
+// streamLowBitrateChecks is consecutive low bitrate checks before warning, so short dips are ignored.
+const streamLowBitrateChecks = 3
+
+const (
+	twitchStreamsURL = "https://api.twitch.tv/helix/streams"
+	youTubeSearchURL = "https://www.googleapis.com/youtube/v3/search"
+)
+
+// PlatformCheck is streaming platform polled to confirm channel is live while vMix is streaming.
+type PlatformCheck struct {
+	Platform    string `json:"platform"`     // ChatPlatformTwitch or ChatPlatformYouTube. Empty to skip.
+	Channel     string `json:"channel"`      // Twitch login or YouTube channel ID.
+	ClientID    string `json:"client_id"`    // Twitch application client ID.
+	Token       string `json:"token"`        // Twitch access token.
+	APIKey      string `json:"api_key"`      // YouTube Data API key.
+	IntervalSec int    `json:"interval_sec"` // seconds between polls. Default: 60 . YouTube search costs 100 quota units per poll.
+	GraceSec    int    `json:"grace_sec"`    // seconds after streaming starts before offline platform is warned. Default: 60 .
+}
+
+// StreamHealthSettings is stream health monitor configuration of vMix host.
+type StreamHealthSettings struct {
+	Endpoint         string         `json:"endpoint"`          // RTMP/SRT URL to probe. e.g. "rtmp://live.example.com/app" . Empty to skip.
+	StatsURL         string         `json:"stats_url"`         // URL returning JSON with bitrate, such as ingest server statistics. Empty to skip.
+	StatsField       string         `json:"stats_field"`       // bitrate field of stats JSON in kbps. Default: "bitrate_kbps" .
+	MinBitrateKbps   float64        `json:"min_bitrate_kbps"`  // warn when bitrate stays below this.
+	Unattended       bool           `json:"unattended"`        // send recovery automatically.
+	Recovery         []FunctionCall `json:"recovery"`          // recovery shortcut. Default: StopStreaming, StartStreaming .
+	RecoveryGapMs    int            `json:"recovery_gap_ms"`   // wait between recovery functions. Default: 2000 .
+	RecoveryCooldown int            `json:"recovery_cooldown"` // minimum seconds between recoveries. Default: 60 .
+	Platform         PlatformCheck  `json:"platform"`          // platform cross-check, warning when encoder is up but channel is offline.
+}
+
+// StreamHostHealth is stream health of vMix host.
+type StreamHostHealth struct {
+	Armed        bool      `json:"armed"` // stream was seen live, so stopping is treated as disconnect.
+	Streaming    bool      `json:"streaming"`
+	BitrateKbps  float64   `json:"bitrate_kbps"`
+	Issue        string    `json:"issue,omitempty"`
+	Message      string    `json:"message,omitempty"`
+	Since        time.Time `json:"since"` // start of current issue.
+	LastRecovery time.Time `json:"last_recovery"`
+	Recoveries   int       `json:"recoveries"`
+	PlatformLive *bool     `json:"platform_live"` // channel live on platform at last poll. Nil until polled.
+	PlatformPoll time.Time `json:"platform_poll"`
+
+	lowChecks      int
+	streamingSince time.Time
+}
+
+// StreamHealth monitors streaming outputs of vMix hosts, warns by "stream_health" event and triggers recovery of unattended streams.
+type StreamHealth struct {
+	mu    sync.Mutex
+	hosts map[string]*StreamHostHealth
+}
+
+// NewStreamHealth creates StreamHealth.
+func NewStreamHealth() *StreamHealth {
+	return &StreamHealth{hosts: make(map[string]*StreamHostHealth)}
+}
+
+// streamProbeClient is HTTP client fetching stream statistics.
+var streamProbeClient = &http.Client{Timeout: 3 * time.Second}
+
+// probeStreamEndpoint checks RTMP endpoint accepts TCP connection. SRT runs over UDP, so only its address is resolved.
+func probeStreamEndpoint(endpoint string) error {
+	u, err := url.Parse(endpoint)
+	if err != nil {
+		return err
+	}
+	port := u.Port()
+	switch u.Scheme {
+	case "rtmp":
+		if port == "" {
+			port = "1935"
+		}
+	case "rtmps":
+		if port == "" {
+			port = "443"
+		}
+	case "srt":
+		_, err := net.ResolveUDPAddr("udp", u.Host)
+		return err
+	default:
+		return fmt.Errorf("unsupported stream endpoint scheme %q", u.Scheme)
+	}
+	conn, err := net.DialTimeout("tcp", net.JoinHostPort(u.Hostname(), port), 3*time.Second)
+	if err != nil {
+		return err
+	}
+	return conn.Close()
+}
+
+// fetchStreamBitrate reads bitrate field from stats JSON.
+func fetchStreamBitrate(statsURL, field string) (float64, error) {
+	if field == "" {
+		field = "bitrate_kbps"
+	}
+	resp, err := streamProbeClient.Get(statsURL)
+	if err != nil {
+		return 0, err
+	}
+	defer resp.Body.Close()
+	stats := map[string]interface{}{}
+	if err := json.NewDecoder(resp.Body).Decode(&stats); err != nil {
+		return 0, err
+	}
+	v, ok := stats[field].(float64)
+	if !ok {
+		return 0, fmt.Errorf("stats field %q not found", field)
+	}
+	return v, nil
+}
+
+// Validate checks credentials required by platform are set.
+func (check PlatformCheck) Validate() error {
+	switch check.Platform {
+	case "":
+		return nil
+	case ChatPlatformTwitch:
+		if check.ClientID == "" || check.Token == "" {
+			return fmt.Errorf("Twitch client ID and token are required")
+		}
+	case ChatPlatformYouTube:
+		if check.APIKey == "" {
+			return fmt.Errorf("YouTube API key is required")
+		}
+	default:
+		return fmt.Errorf("unknown platform %q", check.Platform)
+	}
+	if check.Channel == "" {
+		return fmt.Errorf("%s channel is required", check.Platform)
+	}
+	return nil
+}
+
+// fetchPlatformLive asks streaming platform whether channel is live.
+func fetchPlatformLive(check PlatformCheck) (bool, error) {
+	var req *http.Request
+	var err error
+	switch check.Platform {
+	case ChatPlatformTwitch:
+		req, err = http.NewRequest(http.MethodGet, twitchStreamsURL+"?user_login="+url.QueryEscape(check.Channel), nil)
+		if err != nil {
+			return false, err
+		}
+		req.Header.Set("Client-Id", check.ClientID)
+		req.Header.Set("Authorization", "Bearer "+strings.TrimPrefix(check.Token, "oauth:"))
+	case ChatPlatformYouTube:
+		q := url.Values{}
+		q.Set("part", "id")
+		q.Set("channelId", check.Channel)
+		q.Set("eventType", "live")
+		q.Set("type", "video")
+		q.Set("key", check.APIKey)
+		req, err = http.NewRequest(http.MethodGet, youTubeSearchURL+"?"+q.Encode(), nil)
+		if err != nil {
+			return false, err
+		}
+	default:
+		return false, fmt.Errorf("unknown platform %q", check.Platform)
+	}
+	resp, err := streamProbeClient.Do(req)
+	if err != nil {
+		return false, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return false, fmt.Errorf("%s API responded %s", check.Platform, resp.Status)
+	}
+	// Twitch lists live streams in "data", YouTube lists live videos in "items".
+	res := struct {
+		Data  []json.RawMessage `json:"data"`
+		Items []json.RawMessage `json:"items"`
+	}{}
+	if err := json.NewDecoder(resp.Body).Decode(&res); err != nil {
+		return false, err
+	}
+	return len(res.Data)+len(res.Items) > 0, nil
+}
+
+// platformDue reports whether platform of host should be polled now.
+func (h *StreamHostHealth) platformDue(check PlatformCheck, now time.Time) bool {
+	interval := time.Duration(check.IntervalSec) * time.Second
+	if interval <= 0 {
+		interval = time.Minute
+	}
+	return now.Sub(h.PlatformPoll) >= interval
+}
+
+// evaluate updates health with latest observation and returns whether recovery should be sent.
+// bitrate is negative when not available.
+func (h *StreamHostHealth) evaluate(settings StreamHealthSettings, streaming bool, bitrate float64, probeErr error, now time.Time) bool {
+	h.Streaming = streaming
+	if streaming {
+		h.Armed = true
+		if h.streamingSince.IsZero() {
+			h.streamingSince = now
+		}
+	} else {
+		h.streamingSince = time.Time{}
+		h.PlatformLive = nil
+	}
+	grace := time.Duration(settings.Platform.GraceSec) * time.Second
+	if grace <= 0 {
+		grace = time.Minute
+	}
+	issue, message := "", ""
+	switch {
+	case h.Armed && !streaming:
+		issue, message = StreamIssueDisconnected, "streaming stopped"
+	case h.Armed && probeErr != nil:
+		issue, message = StreamIssueUnreachable, probeErr.Error()
+	case streaming && h.PlatformLive != nil && !*h.PlatformLive && now.Sub(h.streamingSince) >= grace:
+		issue, message = StreamIssueOffPlatform, fmt.Sprintf("streaming but %s shows %s offline", settings.Platform.Platform, settings.Platform.Channel)
+	case streaming && bitrate >= 0:
+		h.BitrateKbps = bitrate
+		if bitrate < settings.MinBitrateKbps {
+			h.lowChecks++
+		} else {
+			h.lowChecks = 0
+		}
+		if h.lowChecks >= streamLowBitrateChecks {
+			issue, message = StreamIssueLowBitrate, fmt.Sprintf("bitrate %.0fkbps below %.0fkbps", bitrate, settings.MinBitrateKbps)
+		}
+	}
+	if issue != h.Issue {
+		h.Since = now
+	}
+	h.Issue, h.Message = issue, message
+	if issue == "" || !settings.Unattended {
+		return false
+	}
+	cooldown := time.Duration(settings.RecoveryCooldown) * time.Second
+	if cooldown <= 0 {
+		cooldown = time.Minute
+	}
+	return now.Sub(h.LastRecovery) >= cooldown
+}
+
+// Disarm stops treating stopped stream of host as disconnect, e.g. when operator stopped it intentionally.
+func (m *StreamHealth) Disarm(host string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	if h, ok := m.hosts[host]; ok {
+		h.Armed = false
+		h.lowChecks = 0
+	}
+}
+
+// check evaluates every configured host.
+func (m *StreamHealth) check(now time.Time) {
+	for host, settings := range config.Get().StreamHealth {
+		status, ok := poller.Status(host)
+		if !ok || !status.Online {
+			continue
+		}
+		streaming := status.Outputs.Streaming
+		var probeErr error
+		if settings.Endpoint != "" && streaming {
+			probeErr = probeStreamEndpoint(settings.Endpoint)
+		}
+		bitrate := -1.0
+		if settings.StatsURL != "" && streaming {
+			if v, err := fetchStreamBitrate(settings.StatsURL, settings.StatsField); err == nil {
+				bitrate = v
+			} else {
+				log.Printf("Failed to fetch stream statistics of %s : %v\n", host, err)
+			}
+		}
+
+		m.mu.Lock()
+		h, ok := m.hosts[host]
+		if !ok {
+			h = &StreamHostHealth{}
+			m.hosts[host] = h
+		}
+		pollPlatform := settings.Platform.Platform != "" && streaming && h.platformDue(settings.Platform, now)
+		m.mu.Unlock()
+		var platformLive *bool
+		if pollPlatform {
+			if live, err := fetchPlatformLive(settings.Platform); err == nil {
+				platformLive = &live
+			} else {
+				log.Printf("Failed to check %s status of %s : %v\n", settings.Platform.Platform, host, err)
+			}
+		}
+
+		m.mu.Lock()
+		if pollPlatform {
+			h.PlatformPoll = now
+			if platformLive != nil {
+				h.PlatformLive = platformLive
+			}
+		}
+		prevIssue := h.Issue
+		recovery := h.evaluate(settings, streaming, bitrate, probeErr, now)
+		if recovery {
+			h.LastRecovery = now
+			h.Recoveries++
+		}
+		snapshot := *h
+		m.mu.Unlock()
+
+		if snapshot.Issue != prevIssue {
+			if snapshot.Issue != "" {
+				log.Printf("Stream health warning on %s : %s\n", status.Name, snapshot.Message)
+			}
+			events.Publish(Event{Topic: "stream_health", Host: status.Name, Data: snapshot})
+		}
+		if recovery && pairing.IsActive() {
+			go recoverStream(host, settings)
+		}
+	}
+}
+
+// recoverStream sends recovery shortcut.
+func recoverStream(host string, settings StreamHealthSettings) {
+	calls := settings.Recovery
+	if len(calls) == 0 {
+		calls = []FunctionCall{{Function: "StopStreaming"}, {Function: "StartStreaming"}}
+	}
+	gap := time.Duration(settings.RecoveryGapMs) * time.Millisecond
+	if gap <= 0 {
+		gap = 2 * time.Second
+	}
+	log.Printf("Sending stream recovery to %s\n", host)
+	for i, call := range calls {
+		if i > 0 {
+			time.Sleep(gap)
+		}
+		if err := SendFunctionTo(host, call.Function, call.Params); err != nil {
+			log.Printf("Stream recovery %s failed on %s : %v\n", call.Function, host, err)
+			return
+		}
+	}
+	poller.TouchHost(host)
+}
+
+// Run checks stream health every interval.
+func (m *StreamHealth) Run(interval time.Duration) {
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	for now := range t.C {
+		tasks.Beat("streamhealth", interval)
+		if !BackgroundActive() {
+			continue
+		}
+		m.check(now)
+	}
+}
+
+// Health returns health of every monitored host.
+func (m *StreamHealth) Health() map[string]StreamHostHealth {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	health := make(map[string]StreamHostHealth, len(m.hosts))
+	for host, h := range m.hosts {
+		health[host] = *h
+	}
+	return health
+}
+
+// redactStreamHealth returns settings with platform token and API key masked.
+func redactStreamHealth(settings StreamHealthSettings) StreamHealthSettings {
+	settings.Platform.Token = maskSecret(settings.Platform.Token)
+	settings.Platform.APIKey = maskSecret(settings.Platform.APIKey)
+	return settings
+}
+
+// GetStreamHealthHandler returns stream health and settings for [GET] /api/streams/health . Platform secrets are masked.
+func GetStreamHealthHandler(c *gin.Context) {
+	settings := map[string]StreamHealthSettings{}
+	for host, s := range config.Get().StreamHealth {
+		settings[host] = redactStreamHealth(s)
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"health":   streamHealth.Health(),
+		"settings": settings,
+	})
+}
+
+// PutStreamHealthHandler saves stream health settings of host for [PUT] /api/streams/health?host=... .
+// Empty body (null) stops monitoring host.
+func PutStreamHealthHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	var settings *StreamHealthSettings
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+		return
+	}
+	if settings != nil && settings.Endpoint != "" {
+		if _, err := url.Parse(settings.Endpoint); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+			return
+		}
+	}
+	if settings != nil {
+		if err := settings.Platform.Validate(); err != nil {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, host, err))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		if settings == nil {
+			delete(cfg.StreamHealth, host)
+		} else {
+			prev := cfg.StreamHealth[host]
+			settings.Platform.Token = keepSecret(settings.Platform.Token, prev.Platform.Token)
+			settings.Platform.APIKey = keepSecret(settings.Platform.APIKey, prev.Platform.APIKey)
+			cfg.StreamHealth[host] = *settings
+		}
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if settings == nil {
+		streamHealth.Disarm(host)
+	}
+	GetStreamHealthHandler(c)
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestStreamHostHealthEvaluate(t *testing.T) {
+	settings := StreamHealthSettings{MinBitrateKbps: 3000, Unattended: true}
+	h := &StreamHostHealth{}
+	now := time.Now()
+	if h.evaluate(settings, false, -1, nil, now) || h.Armed {
+		t.Fatal("idle host must not be armed")
+	}
+	for i := 0; i < streamLowBitrateChecks; i++ {
+		h.evaluate(settings, true, 1000, nil, now)
+	}
+	if h.Issue != StreamIssueLowBitrate {
+		t.Fatalf("expected low bitrate, got %+v", h)
+	}
+	if h.evaluate(settings, true, 6000, errors.New("refused"), now); h.Issue != StreamIssueUnreachable {
+		t.Fatalf("expected unreachable, got %+v", h)
+	}
+	if !h.evaluate(settings, false, -1, nil, now) || h.Issue != StreamIssueDisconnected {
+		t.Fatalf("expected recovery on disconnect, got %+v", h)
+	}
+	h.LastRecovery = now
+	if h.evaluate(settings, false, -1, nil, now.Add(time.Second)) {
+		t.Fatal("recovery must wait for cooldown")
+	}
+}
+
+func TestStreamHostHealthPlatformOffline(t *testing.T) {
+	settings := StreamHealthSettings{Platform: PlatformCheck{Platform: ChatPlatformTwitch, Channel: "channel", GraceSec: 30}}
+	h := &StreamHostHealth{}
+	now := time.Now()
+	offline := false
+	h.evaluate(settings, true, -1, nil, now)
+	h.PlatformLive = &offline
+	if h.evaluate(settings, true, -1, nil, now.Add(10*time.Second)); h.Issue != "" {
+		t.Fatalf("expected no warning within grace, got %+v", h)
+	}
+	if h.evaluate(settings, true, -1, nil, now.Add(30*time.Second)); h.Issue != StreamIssueOffPlatform {
+		t.Fatalf("expected platform offline, got %+v", h)
+	}
+	live := true
+	h.PlatformLive = &live
+	if h.evaluate(settings, true, -1, nil, now.Add(40*time.Second)); h.Issue != "" {
+		t.Fatalf("expected warning cleared when platform is live, got %+v", h)
+	}
+	if err := (PlatformCheck{Platform: ChatPlatformTwitch, Channel: "channel"}).Validate(); err == nil {
+		t.Fatal("expected Twitch without token refused")
+	}
+}
+
+func TestStreamHealthSettingsMaskSecrets(t *testing.T) {
+	const host = "http://streamhealth-secrets:8088"
+	defer config.Update(func(cfg *Config) { delete(cfg.StreamHealth, host) })
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.GET("/api/streams/health", GetStreamHealthHandler)
+	r.PUT("/api/streams/health", PutStreamHealthHandler)
+	do := func(method, body string) map[string]StreamHealthSettings {
+		t.Helper()
+		w := httptest.NewRecorder()
+		r.ServeHTTP(w, httptest.NewRequest(method, "/api/streams/health?host="+host, strings.NewReader(body)))
+		res := struct {
+			Settings map[string]StreamHealthSettings `json:"settings"`
+		}{}
+		if err := json.Unmarshal(w.Body.Bytes(), &res); err != nil || w.Code != http.StatusOK {
+			t.Fatalf("unexpected response %d %s", w.Code, w.Body.String())
+		}
+		return res.Settings
+	}
+
+	got := do(http.MethodPut, `{"platform":{"platform":"twitch","channel":"flowingspdg","client_id":"id","token":"secret"}}`)
+	if got[host].Platform.Token != secretMask {
+		t.Fatalf("expected token masked, got %q", got[host].Platform.Token)
+	}
+	b, _ := json.Marshal(do(http.MethodGet, "")[host])
+	do(http.MethodPut, string(b))
+	if token := config.Get().StreamHealth[host].Platform.Token; token != "secret" {
+		t.Fatalf("expected token kept when mask is saved back, got %q", token)
+	}
+}
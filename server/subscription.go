@@ -0,0 +1,69 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// TCPSubscription is TCP API subscription preferences of vMix host.
+type TCPSubscription struct {
+	Topics []string `json:"topics"` // "ACTS" and/or "TALLY".
+	// ActsFilter is list of ACTS activator names forwarded to event clients not choosing their own by "acts" query.
+	// e.g. "Input", "InputPreview" . Empty forwards all.
+	ActsFilter []string `json:"acts_filter"`
+}
+
+// DefaultTCPSubscription is used for hosts without preferences.
+var DefaultTCPSubscription = TCPSubscription{Topics: []string{"ACTS"}}
+
+// TCPSubscriptionFor returns subscription preferences of host.
+func TCPSubscriptionFor(host string) TCPSubscription {
+	if sub, ok := config.Get().TCPSubscriptions[host]; ok {
+		return sub
+	}
+	return DefaultTCPSubscription
+}
+
+// ApplyTCPSubscription applies preferences to TCP client. ACTS filter applies to event clients connecting afterwards.
+func ApplyTCPSubscription(client *TCPClient, sub TCPSubscription) {
+	client.SetSubscriptions(sub.Topics)
+}
+
+// GetTCPSubscriptionHandler returns TCP subscription preferences of current host for [GET] /api/tcp/subscription .
+func GetTCPSubscriptionHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"host":         *vmixaddr,
+		"subscription": TCPSubscriptionFor(*vmixaddr),
+	})
+}
+
+// PutTCPSubscriptionHandler saves and applies TCP subscription preferences for [PUT] /api/tcp/subscription .
+func PutTCPSubscriptionHandler(c *gin.Context) {
+	sub := TCPSubscription{}
+	if err := c.ShouldBindJSON(&sub); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for i, topic := range sub.Topics {
+		topic = strings.ToUpper(topic)
+		if topic != "ACTS" && topic != "TALLY" {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown topic %q", topic)))
+			return
+		}
+		sub.Topics[i] = topic
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.TCPSubscriptions[*vmixaddr] = sub
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	ApplyTCPSubscription(vmixTCP, sub)
+	c.JSON(http.StatusOK, gin.H{
+		"host":         *vmixaddr,
+		"subscription": sub,
+	})
+}
@@ -0,0 +1,195 @@
+package main
+
+import (
+	"encoding/json"
+	"log"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// syncInterval is interval of state diffs sent to sync clients.
+const syncInterval = 250 * time.Millisecond
+
+// SyncMessage is message sent to sync client. Gen is revision of synced state after message, and patch applies
+// to state of revision Base. Client whose revision is not Base missed patch, and sends {"type":"resync"} .
+// Revisions are counted by SyncFeed, and event generation reflected by state is in data.generation of snapshot.
+type SyncMessage struct {
+	Topic string      `json:"topic"`          // "snapshot" or "patch".
+	Base  uint64      `json:"base,omitempty"` // revision patch applies to.
+	Gen   uint64      `json:"gen"`
+	Data  interface{} `json:"data"` // AppSnapshot or [PatchOp...] .
+}
+
+// SyncFeed builds app snapshot once per tick and shares its diff with every sync client.
+type SyncFeed struct {
+	mu      sync.Mutex
+	state   interface{} // JSON value of latest snapshot. nil while nobody is synced.
+	gen     uint64      // revision of state.
+	clients map[chan SyncMessage]struct{}
+}
+
+// NewSyncFeed creates SyncFeed.
+func NewSyncFeed() *SyncFeed {
+	return &SyncFeed{
+		clients: make(map[chan SyncMessage]struct{}),
+	}
+}
+
+// Subscribe returns snapshot of current state and channel of patches following it, and function to unsubscribe.
+func (f *SyncFeed) Subscribe() (SyncMessage, <-chan SyncMessage, func(), error) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	if f.state == nil {
+		cur, err := toJSONValue(BuildAppSnapshot())
+		if err != nil {
+			return SyncMessage{}, nil, nil, err
+		}
+		f.state = cur
+		f.gen++
+	}
+	ch := make(chan SyncMessage, 16)
+	f.clients[ch] = struct{}{}
+	unsubscribe := func() {
+		f.mu.Lock()
+		defer f.mu.Unlock()
+		delete(f.clients, ch)
+	}
+	return f.snapshot(), ch, unsubscribe, nil
+}
+
+// Snapshot returns current state, sent to client resyncing after missed patch.
+func (f *SyncFeed) Snapshot() SyncMessage {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	return f.snapshot()
+}
+
+func (f *SyncFeed) snapshot() SyncMessage {
+	return SyncMessage{Topic: "snapshot", Gen: f.gen, Data: f.state}
+}
+
+// Run builds snapshot every interval while clients are synced, and sends its diff to every client.
+func (f *SyncFeed) Run(interval time.Duration) {
+	t := time.NewTicker(interval)
+	defer t.Stop()
+	for range t.C {
+		f.tick()
+	}
+}
+
+// tick sends diff since last tick to every client. Patch is dropped for client whose buffer is full,
+// and that client is resynced when Base of next patch does not match.
+func (f *SyncFeed) tick() {
+	f.mu.Lock()
+	if len(f.clients) == 0 {
+		f.state = nil
+		f.mu.Unlock()
+		return
+	}
+	f.mu.Unlock()
+
+	cur, err := toJSONValue(BuildAppSnapshot())
+	if err != nil {
+		log.Printf("Failed to build sync snapshot : %v\n", err)
+		return
+	}
+
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	if f.state == nil {
+		return
+	}
+	ops := DiffJSON(f.state, cur)
+	if len(ops) == 0 {
+		return
+	}
+	msg := SyncMessage{Topic: "patch", Base: f.gen, Gen: f.gen + 1, Data: ops}
+	f.state = cur
+	f.gen++
+	for ch := range f.clients {
+		select {
+		case ch <- msg:
+		default:
+		}
+	}
+}
+
+// SyncWebSocketHandler hydrates client with app snapshot, then sends JSON Patch diffs of it for [GET] /api/sync .
+// First message is {"topic":"snapshot","gen":N,"data":AppSnapshot}, followed by
+// {"topic":"patch","base":N,"gen":N+1,"data":[PatchOp...]}. Client sends {"type":"resync"} to receive snapshot
+// again, such as when base of patch does not match its revision.
+func SyncWebSocketHandler(c *gin.Context) {
+	ws, err := upgradeWebSocket(c.Writer, c.Request)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	defer ws.Close()
+
+	defer operators.Connect(operatorName(c), c.ClientIP())()
+
+	snap, patches, unsubscribe, err := syncFeed.Subscribe()
+	if err != nil {
+		ws.WriteJSON(Event{Topic: "error", Data: NewAppError(ErrKindInternal, "", err)})
+		return
+	}
+	defer unsubscribe()
+	if err := ws.WriteJSON(snap); err != nil {
+		return
+	}
+	gen := snap.Gen
+
+	closed := make(chan struct{})
+	resync := make(chan struct{}, 1)
+	go func() {
+		defer close(closed)
+		for {
+			b, err := ws.ReadMessage()
+			if err != nil {
+				return
+			}
+			req := wsRequest{}
+			if json.Unmarshal(b, &req) == nil && req.Type == "resync" {
+				select {
+				case resync <- struct{}{}:
+				default:
+				}
+			}
+		}
+	}()
+
+	ping := time.NewTicker(wsPingInterval)
+	defer ping.Stop()
+	for {
+		select {
+		case <-ping.C:
+			if err := ws.writeFrame(wsOpPing, nil); err != nil {
+				return
+			}
+		case msg := <-patches:
+			if msg.Base < gen {
+				// already reflected by snapshot sent on resync.
+				continue
+			}
+			if msg.Base > gen {
+				// patch was dropped while client was slow.
+				msg = syncFeed.Snapshot()
+			}
+			if err := ws.WriteJSON(msg); err != nil {
+				log.Printf("Failed to send patch to sync client %s : %v\n", ws.conn.RemoteAddr(), err)
+				return
+			}
+			gen = msg.Gen
+		case <-resync:
+			snap := syncFeed.Snapshot()
+			if err := ws.WriteJSON(snap); err != nil {
+				return
+			}
+			gen = snap.Gen
+		case <-closed:
+			return
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package main
+
+import "testing"
+
+func TestSyncFeedSharesPatches(t *testing.T) {
+	feed := NewSyncFeed()
+	snapA, patchesA, unsubscribeA, err := feed.Subscribe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer unsubscribeA()
+	snapB, patchesB, unsubscribeB, err := feed.Subscribe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if snapA.Topic != "snapshot" || snapA.Gen != snapB.Gen {
+		t.Fatalf("expected clients hydrated with same revision, got %d and %d", snapA.Gen, snapB.Gen)
+	}
+
+	config.Update(func(cfg *Config) { cfg.Clock.TitleInput = "sync_test" })
+	defer config.Update(func(cfg *Config) { cfg.Clock.TitleInput = "" })
+	feed.tick()
+	a, b := <-patchesA, <-patchesB
+	if a.Topic != "patch" || a.Base != snapA.Gen || a.Gen != snapA.Gen+1 {
+		t.Fatalf("expected patch from revision %d, got %+v", snapA.Gen, a)
+	}
+	opsA, opsB := a.Data.([]PatchOp), b.Data.([]PatchOp)
+	if len(opsA) == 0 || &opsA[0] != &opsB[0] {
+		t.Fatal("expected single diff shared by every client")
+	}
+
+	feed.tick()
+	select {
+	case msg := <-patchesA:
+		t.Fatalf("expected no patch without change, got %+v", msg)
+	default:
+	}
+
+	unsubscribeB()
+	unsubscribeA()
+	feed.tick()
+	snap, _, unsubscribe, err := feed.Subscribe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer unsubscribe()
+	if snap.Gen != a.Gen+1 {
+		t.Fatalf("expected state rebuilt as new revision after every client left, got %d", snap.Gen)
+	}
+}
@@ -0,0 +1,233 @@
+package main
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// TalkbackChannel is push-to-talk announcement: while pressed, Duck inputs are faded down and announcement Input is turned on.
+type TalkbackChannel struct {
+	Host          string   `json:"host"`           // vMix host. Primary vMix host is used if empty.
+	Input         string   `json:"input"`          // announcement microphone input.
+	Duck          []string `json:"duck"`           // inputs ducked while talking, such as program music.
+	DuckVolume    int      `json:"duck_volume"`    // volume of ducked inputs, 0-100.
+	RestoreVolume int      `json:"restore_volume"` // volume restored on release. Default: 100 .
+	FadeMs        int      `json:"fade_ms"`        // duck and restore fade duration.
+}
+
+// TalkbackActions returns functions of press or release.
+// Press ducks before opening microphone, and release closes microphone before restoring, so announcement never clashes with program.
+func TalkbackActions(ch TalkbackChannel, press bool) []FunctionCall {
+	volume := ch.RestoreVolume
+	if volume <= 0 {
+		volume = 100
+	}
+	if press {
+		volume = ch.DuckVolume
+	}
+	calls := []FunctionCall{}
+	if !press {
+		calls = append(calls, FunctionCall{Function: "AudioOff", Params: map[string]string{"Input": ch.Input}})
+	}
+	for _, in := range ch.Duck {
+		calls = append(calls, FunctionCall{Function: "SetVolumeFade", Params: map[string]string{"Input": in, "Value": strconv.Itoa(volume) + "," + strconv.Itoa(ch.FadeMs)}})
+	}
+	if press {
+		calls = append(calls, FunctionCall{Function: "AudioOn", Params: map[string]string{"Input": ch.Input}})
+	}
+	return calls
+}
+
+// TalkbackState is state of pressed channel, published as "talkback" event.
+type TalkbackState struct {
+	Name    string    `json:"name"`
+	Pressed bool      `json:"pressed"`
+	By      []string  `json:"by"` // holders pressing channel. Channel is released when last holder releases.
+	Since   time.Time `json:"since"`
+}
+
+// Talkback runs hold-style talkback channels. Each holder, such as WebSocket connection, must release what it pressed.
+type Talkback struct {
+	mu      sync.Mutex
+	holders map[string]map[string]bool // channel -> holders.
+	since   map[string]time.Time
+}
+
+// NewTalkback creates Talkback.
+func NewTalkback() *Talkback {
+	return &Talkback{holders: make(map[string]map[string]bool), since: make(map[string]time.Time)}
+}
+
+// talkbackChannel returns channel settings by name.
+func talkbackChannel(name string) (TalkbackChannel, error) {
+	ch, ok := config.Get().Talkback[name]
+	if !ok {
+		return ch, NewAppError(ErrKindNotFound, "", fmt.Errorf("talkback channel %q not found", name))
+	}
+	if ch.Host == "" {
+		ch.Host = *vmixaddr
+	}
+	return ch, nil
+}
+
+// Press opens channel for holder. Functions are sent only by first holder.
+func (t *Talkback) Press(name, holder string) error {
+	ch, err := talkbackChannel(name)
+	if err != nil {
+		return err
+	}
+	t.mu.Lock()
+	first := len(t.holders[name]) == 0
+	if t.holders[name] == nil {
+		t.holders[name] = make(map[string]bool)
+	}
+	t.holders[name][holder] = true
+	if first {
+		t.since[name] = time.Now()
+	}
+	t.mu.Unlock()
+	if first {
+		if err := t.send(ch, true); err != nil {
+			t.mu.Lock()
+			delete(t.holders, name)
+			t.mu.Unlock()
+			return err
+		}
+	}
+	t.publish(name)
+	return nil
+}
+
+// Release closes channel for holder. Functions are sent when last holder released.
+func (t *Talkback) Release(name, holder string) error {
+	t.mu.Lock()
+	if !t.holders[name][holder] {
+		t.mu.Unlock()
+		return nil
+	}
+	delete(t.holders[name], holder)
+	last := len(t.holders[name]) == 0
+	if last {
+		delete(t.holders, name)
+		delete(t.since, name)
+	}
+	t.mu.Unlock()
+	t.publish(name)
+	if !last {
+		return nil
+	}
+	ch, err := talkbackChannel(name)
+	if err != nil {
+		return err
+	}
+	return t.send(ch, false)
+}
+
+// ReleaseAll releases every channel pressed by holder, such as when WebSocket connection closed while pressing.
+func (t *Talkback) ReleaseAll(holder string) {
+	t.mu.Lock()
+	names := []string{}
+	for name, holders := range t.holders {
+		if holders[holder] {
+			names = append(names, name)
+		}
+	}
+	t.mu.Unlock()
+	for _, name := range names {
+		if err := t.Release(name, holder); err != nil {
+			log.Printf("Failed to release talkback %s : %v\n", name, err)
+		}
+	}
+}
+
+func (t *Talkback) send(ch TalkbackChannel, press bool) error {
+	poller.Touch()
+	for _, call := range TalkbackActions(ch, press) {
+		if err := SendFunctionTo(ch.Host, call.Function, call.Params); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// State returns state of channel.
+func (t *Talkback) State(name string) TalkbackState {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	st := TalkbackState{Name: name, By: []string{}, Since: t.since[name]}
+	for holder := range t.holders[name] {
+		st.By = append(st.By, holder)
+	}
+	st.Pressed = len(st.By) > 0
+	return st
+}
+
+func (t *Talkback) publish(name string) {
+	events.Publish(Event{Topic: "talkback", Data: t.State(name)})
+}
+
+// GetTalkbackHandler returns talkback channels and their state for [GET] /api/talkback .
+func GetTalkbackHandler(c *gin.Context) {
+	channels := config.Get().Talkback
+	states := make(map[string]TalkbackState, len(channels))
+	for name := range channels {
+		states[name] = talkback.State(name)
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"channels": channels,
+		"states":   states,
+	})
+}
+
+// PutTalkbackHandler saves talkback channels for [PUT] /api/talkback .
+func PutTalkbackHandler(c *gin.Context) {
+	channels := map[string]TalkbackChannel{}
+	if err := c.ShouldBindJSON(&channels); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	for name, ch := range channels {
+		if ch.Input == "" || ch.DuckVolume < 0 || ch.DuckVolume > 100 || ch.RestoreVolume < 0 || ch.RestoreVolume > 100 || ch.FadeMs < 0 {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, ch.Host, fmt.Errorf("invalid talkback channel %q", name)))
+			return
+		}
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Talkback = channels
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetTalkbackHandler(c)
+}
+
+// PressTalkbackHandler opens talkback channel for [POST] /api/talkback/press/:name .
+// Held by operator until released. WebSocket clients should prefer "talkback_press" messages, released automatically on disconnect.
+func PressTalkbackHandler(c *gin.Context) {
+	name := c.Param("name")
+	if err := talkback.Press(name, operatorName(c)); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"talkback": talkback.State(name),
+	})
+}
+
+// ReleaseTalkbackHandler closes talkback channel for [POST] /api/talkback/release/:name .
+func ReleaseTalkbackHandler(c *gin.Context) {
+	name := c.Param("name")
+	if err := talkback.Release(name, operatorName(c)); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"talkback": talkback.State(name),
+	})
+}
@@ -0,0 +1,46 @@
+package main
+
+import "testing"
+
+func TestTalkbackHoldersShareChannel(t *testing.T) {
+	m := startMock(t)
+	addr := "http://" + m.HTTPAddr
+	if err := config.Update(func(cfg *Config) {
+		cfg.Talkback["PA"] = TalkbackChannel{Host: addr, Input: "3", Duck: []string{"4"}, DuckVolume: 20, FadeMs: 500}
+	}); err != nil {
+		t.Fatal(err)
+	}
+	defer config.Update(func(cfg *Config) { delete(cfg.Talkback, "PA") })
+
+	tb := NewTalkback()
+	if err := tb.Press("PA", "director"); err != nil {
+		t.Fatal(err)
+	}
+	if err := tb.Press("PA", "floor"); err != nil {
+		t.Fatal(err)
+	}
+	if err := tb.Release("PA", "director"); err != nil {
+		t.Fatal(err)
+	}
+	if !tb.State("PA").Pressed {
+		t.Fatal("expected channel held by remaining holder")
+	}
+	tb.ReleaseAll("floor")
+	if tb.State("PA").Pressed {
+		t.Fatal("expected channel released")
+	}
+
+	want := []string{"SetVolumeFade", "AudioOn", "AudioOff", "SetVolumeFade"}
+	calls := m.Calls()
+	if len(calls) != len(want) {
+		t.Fatalf("unexpected calls %+v", calls)
+	}
+	for i, fn := range want {
+		if calls[i].Function != fn {
+			t.Fatalf("call %d : expected %s, got %+v", i, fn, calls[i])
+		}
+	}
+	if v := calls[3].Params["Value"]; v != "100,500" {
+		t.Fatalf("expected volume restored to 100, got %s", v)
+	}
+}
@@ -0,0 +1,268 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"log"
+	"net"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// tcpReconnectInterval is wait time before reconnecting to vMix TCP API.
+const tcpReconnectInterval = 3 * time.Second
+
+// TCPMessage is single message received from vMix TCP API.
+type TCPMessage struct {
+	Command    string    // command name. e.g. "ACTS" .
+	Status     string    // "OK" or "ER". Empty for XML payload.
+	Body       string    // rest of the line, or XML payload.
+	ReceivedAt time.Time // time when message was read from socket.
+}
+
+// TCPClient is vMix TCP API client. Responses to Request are returned to caller,
+// other messages such as subscribed ACTS/TALLY are passed to OnMessage.
+type TCPClient struct {
+	Addr      string
+	OnMessage func(msg TCPMessage)
+
+	mu      sync.Mutex
+	conn    net.Conn
+	pending map[string][]chan TCPMessage // command -> waiters in request order.
+	topics  map[string]bool              // subscribed topics, re-subscribed on reconnect.
+}
+
+// NewTCPClient creates TCPClient. Call Run to connect.
+func NewTCPClient(addr string) *TCPClient {
+	return &TCPClient{
+		Addr:    addr,
+		pending: make(map[string][]chan TCPMessage),
+		topics:  make(map[string]bool),
+	}
+}
+
+// DefaultTCPAddr returns vMix TCP API address (port 8099) for vMix HTTP API URL.
+func DefaultTCPAddr(httpAddr string) string {
+	u, err := url.Parse(httpAddr)
+	if err != nil || u.Hostname() == "" {
+		return "localhost:8099"
+	}
+	return net.JoinHostPort(u.Hostname(), "8099")
+}
+
+// Run connects to vMix and reads messages forever, reconnecting on failure.
+func (t *TCPClient) Run() {
+	for {
+		if err := t.runOnce(); err != nil {
+			log.Printf("vMix TCP API %s disconnected : %v\n", t.Addr, err)
+		}
+		time.Sleep(tcpReconnectInterval)
+	}
+}
+
+func (t *TCPClient) runOnce() error {
+	conn, err := net.DialTimeout("tcp", t.Addr, 5*time.Second)
+	if err != nil {
+		return err
+	}
+	defer conn.Close()
+
+	t.mu.Lock()
+	t.conn = conn
+	topics := make([]string, 0, len(t.topics))
+	for topic := range t.topics {
+		topics = append(topics, topic)
+	}
+	t.mu.Unlock()
+	defer t.disconnect()
+
+	for _, topic := range topics {
+		if _, err := fmt.Fprintf(conn, "SUBSCRIBE %s\r\n", topic); err != nil {
+			return err
+		}
+	}
+
+	r := bufio.NewReader(conn)
+	for {
+		msg, err := readTCPMessage(r)
+		if err != nil {
+			return err
+		}
+		t.dispatch(msg)
+	}
+}
+
+// Connected reports whether connection to vMix is open.
+func (t *TCPClient) Connected() bool {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.conn != nil
+}
+
+// disconnect clears connection and fails pending requests.
+func (t *TCPClient) disconnect() {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	t.conn = nil
+	for cmd, waiters := range t.pending {
+		for _, ch := range waiters {
+			close(ch)
+		}
+		delete(t.pending, cmd)
+	}
+}
+
+func (t *TCPClient) dispatch(msg TCPMessage) {
+	t.mu.Lock()
+	waiters := t.pending[msg.Command]
+	if len(waiters) > 0 {
+		ch := waiters[0]
+		t.pending[msg.Command] = waiters[1:]
+		t.mu.Unlock()
+		ch <- msg
+		close(ch)
+		return
+	}
+	t.mu.Unlock()
+	if t.OnMessage != nil {
+		t.OnMessage(msg)
+	}
+}
+
+// readTCPMessage reads single message. XML responses are read with their payload.
+func readTCPMessage(r *bufio.Reader) (TCPMessage, error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return TCPMessage{}, err
+	}
+	msg := TCPMessage{ReceivedAt: time.Now()}
+	line = strings.TrimRight(line, "\r\n")
+	parts := strings.SplitN(line, " ", 3)
+	msg.Command = parts[0]
+	if len(parts) > 1 {
+		msg.Status = parts[1]
+	}
+	if len(parts) > 2 {
+		msg.Body = parts[2]
+	}
+	if msg.Command == "XML" && msg.Status != "ER" {
+		n, err := strconv.Atoi(msg.Status)
+		if err != nil {
+			return TCPMessage{}, fmt.Errorf("invalid XML length %q", msg.Status)
+		}
+		b := make([]byte, n)
+		if _, err := io.ReadFull(r, b); err != nil {
+			return TCPMessage{}, err
+		}
+		msg.Status = ""
+		msg.Body = strings.TrimRight(string(b), "\r\n")
+	}
+	return msg, nil
+}
+
+// Request sends command and waits for response with same command name.
+func (t *TCPClient) Request(command, arg string, timeout time.Duration) (TCPMessage, error) {
+	if strings.ContainsAny(command+arg, "\r\n") {
+		return TCPMessage{}, NewAppError(ErrKindInvalidRequest, t.Addr, fmt.Errorf("%s contains line break", command))
+	}
+	ch := make(chan TCPMessage, 1)
+	t.mu.Lock()
+	if t.conn == nil {
+		t.mu.Unlock()
+		return TCPMessage{}, NewAppError(ErrKindUnreachable, t.Addr, fmt.Errorf("vMix TCP API not connected"))
+	}
+	line := command
+	if arg != "" {
+		line += " " + arg
+	}
+	if _, err := t.conn.Write([]byte(line + "\r\n")); err != nil {
+		t.mu.Unlock()
+		return TCPMessage{}, NewAppError(ErrKindUnreachable, t.Addr, err)
+	}
+	t.pending[command] = append(t.pending[command], ch)
+	t.mu.Unlock()
+
+	select {
+	case msg, ok := <-ch:
+		if !ok {
+			return TCPMessage{}, NewAppError(ErrKindUnreachable, t.Addr, fmt.Errorf("vMix TCP API disconnected"))
+		}
+		if msg.Status == "ER" {
+			return msg, NewAppError(ErrKindFunctionFailed, t.Addr, fmt.Errorf("%s", msg.Body))
+		}
+		return msg, nil
+	case <-time.After(timeout):
+		t.removeWaiter(command, ch)
+		return TCPMessage{}, NewAppError(ErrKindUnreachable, t.Addr, fmt.Errorf("%s timed out", command))
+	}
+}
+
+// Function sends vMix function by TCP API. Parameters are encoded same as HTTP API.
+func (t *TCPClient) Function(function string, params map[string]string, timeout time.Duration) error {
+	arg, err := EncodeFunctionCommand(function, params)
+	if err != nil {
+		return NewAppError(ErrKindInvalidRequest, t.Addr, err)
+	}
+	_, err = t.Request("FUNCTION", arg, timeout)
+	return err
+}
+
+func (t *TCPClient) removeWaiter(command string, ch chan TCPMessage) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	waiters := t.pending[command]
+	for i, w := range waiters {
+		if w == ch {
+			t.pending[command] = append(waiters[:i], waiters[i+1:]...)
+			return
+		}
+	}
+}
+
+// Subscribe subscribes topic ("ACTS" or "TALLY"). Subscription is kept across reconnects.
+func (t *TCPClient) Subscribe(topic string) {
+	t.mu.Lock()
+	t.topics[topic] = true
+	conn := t.conn
+	t.mu.Unlock()
+	if conn != nil {
+		fmt.Fprintf(conn, "SUBSCRIBE %s\r\n", topic)
+	}
+}
+
+// Unsubscribe unsubscribes topic.
+func (t *TCPClient) Unsubscribe(topic string) {
+	t.mu.Lock()
+	delete(t.topics, topic)
+	conn := t.conn
+	t.mu.Unlock()
+	if conn != nil {
+		fmt.Fprintf(conn, "UNSUBSCRIBE %s\r\n", topic)
+	}
+}
+
+// SetSubscriptions subscribes exactly given topics, unsubscribing others.
+func (t *TCPClient) SetSubscriptions(topics []string) {
+	want := make(map[string]bool, len(topics))
+	for _, topic := range topics {
+		want[topic] = true
+	}
+	t.mu.Lock()
+	var remove []string
+	for topic := range t.topics {
+		if !want[topic] {
+			remove = append(remove, topic)
+		}
+	}
+	t.mu.Unlock()
+	for _, topic := range remove {
+		t.Unsubscribe(topic)
+	}
+	for topic := range want {
+		t.Subscribe(topic)
+	}
+}
@@ -0,0 +1,364 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"log"
+	"net"
+	"net/http"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// timecodeChaseWindow is largest forward jump that still fires cues in between.
+// Larger jumps are treated as locate, so skipped cues are not fired all at once.
+const timecodeChaseWindow = time.Second
+
+// Timecode is SMPTE timecode.
+type Timecode struct {
+	Hours   int     `json:"hours"`
+	Minutes int     `json:"minutes"`
+	Seconds int     `json:"seconds"`
+	Frames  int     `json:"frames"`
+	FPS     float64 `json:"fps"` // 24, 25, 29.97 or 30.
+}
+
+// ParseTimecode parses "HH:MM:SS:FF" (or "HH:MM:SS;FF" drop frame).
+func ParseTimecode(s string, fps float64) (Timecode, error) {
+	parts := strings.FieldsFunc(strings.TrimSpace(s), func(r rune) bool { return r == ':' || r == ';' || r == '.' })
+	if len(parts) != 4 {
+		return Timecode{}, fmt.Errorf("invalid timecode %q", s)
+	}
+	n := [4]int{}
+	for i, p := range parts {
+		v, err := strconv.Atoi(p)
+		if err != nil || v < 0 {
+			return Timecode{}, fmt.Errorf("invalid timecode %q", s)
+		}
+		n[i] = v
+	}
+	tc := Timecode{Hours: n[0], Minutes: n[1], Seconds: n[2], Frames: n[3], FPS: fps}
+	if tc.Hours > 23 || tc.Minutes > 59 || tc.Seconds > 59 || float64(tc.Frames) >= fps+0.5 {
+		return Timecode{}, fmt.Errorf("timecode out of range %q", s)
+	}
+	return tc, nil
+}
+
+// String formats timecode as HH:MM:SS:FF.
+func (t Timecode) String() string {
+	return fmt.Sprintf("%02d:%02d:%02d:%02d", t.Hours, t.Minutes, t.Seconds, t.Frames)
+}
+
+// Duration returns time since 00:00:00:00. Drop frame is approximated by nominal frame duration.
+func (t Timecode) Duration() time.Duration {
+	fps := t.FPS
+	if fps <= 0 {
+		fps = 30
+	}
+	secs := time.Duration(t.Hours*3600+t.Minutes*60+t.Seconds) * time.Second
+	return secs + time.Duration(float64(t.Frames)/fps*float64(time.Second))
+}
+
+// mtcRates is frame rate of MTC rate bits.
+var mtcRates = [4]float64{24, 25, 29.97, 30}
+
+// MTCDecoder decodes MIDI Time Code quarter frame and full frame messages.
+type MTCDecoder struct {
+	pieces [8]byte
+	seen   byte // bit mask of received pieces.
+}
+
+// QuarterFrame feeds quarter frame data byte (after 0xF1). Timecode is returned when piece 7 completes full set.
+// Timecode is 2 frames behind when completed, compensated here as MTC specifies.
+func (d *MTCDecoder) QuarterFrame(data byte) (Timecode, bool) {
+	piece := data >> 4 & 0x07
+	d.pieces[piece] = data & 0x0F
+	d.seen |= 1 << piece
+	if piece != 7 || d.seen != 0xFF {
+		return Timecode{}, false
+	}
+	d.seen = 0
+	p := d.pieces
+	tc := Timecode{
+		Frames:  int(p[0] | p[1]<<4),
+		Seconds: int(p[2] | p[3]<<4),
+		Minutes: int(p[4] | p[5]<<4),
+		Hours:   int(p[6] | (p[7]&0x01)<<4),
+		FPS:     mtcRates[p[7]>>1&0x03],
+	}
+	return tc.add(2), true
+}
+
+// FullFrame decodes full frame SysEx. e.g. F0 7F 7F 01 01 hh mm ss ff F7 .
+func (d *MTCDecoder) FullFrame(msg []byte) (Timecode, bool) {
+	if len(msg) < 10 || msg[0] != 0xF0 || msg[1] != 0x7F || msg[3] != 0x01 || msg[4] != 0x01 {
+		return Timecode{}, false
+	}
+	return Timecode{
+		Hours:   int(msg[5] & 0x1F),
+		Minutes: int(msg[6]),
+		Seconds: int(msg[7]),
+		Frames:  int(msg[8]),
+		FPS:     mtcRates[msg[5]>>5&0x03],
+	}, true
+}
+
+// add returns timecode n frames later.
+func (t Timecode) add(n int) Timecode {
+	fps := int(t.FPS + 0.5)
+	t.Frames += n
+	for t.Frames >= fps {
+		t.Frames -= fps
+		t.Seconds++
+	}
+	for t.Seconds >= 60 {
+		t.Seconds -= 60
+		t.Minutes++
+	}
+	for t.Minutes >= 60 {
+		t.Minutes -= 60
+		t.Hours = (t.Hours + 1) % 24
+	}
+	return t
+}
+
+// TimecodeSettings is timecode source configuration.
+// LTC audio is out of scope: "ltc" source is refused, and LTC is fed by "udp" from LTC reader software instead.
+type TimecodeSettings struct {
+	Source     string  `json:"source"`      // "midi", "udp" or empty to disable.
+	MIDIDevice int     `json:"midi_device"` // MIDI input device index for "midi".
+	Listen     string  `json:"listen"`      // UDP listen address for "udp", receiving "HH:MM:SS:FF" lines from LTC readers.
+	FPS        float64 `json:"fps"`         // frame rate of "udp" timecode and cue values.
+}
+
+// TimecodeCue is action armed against timecode.
+type TimecodeCue struct {
+	ID     string        `json:"id"`
+	Name   string        `json:"name"` // shown on talent monitors by cue warnings. ID is used if empty.
+	At     string        `json:"at"`   // "HH:MM:SS:FF".
+	Action TriggerAction `json:"action"`
+	Fired  bool          `json:"fired"`
+
+	at time.Duration
+}
+
+// TimecodeChase follows external timecode and fires cues when timecode passes them.
+type TimecodeChase struct {
+	mu       sync.Mutex
+	settings TimecodeSettings
+	current  Timecode
+	lastSeen time.Time
+	cues     []TimecodeCue
+	stop     func()
+	lastErr  string
+}
+
+// NewTimecodeChase creates TimecodeChase. Source is started by Configure.
+func NewTimecodeChase() *TimecodeChase {
+	return &TimecodeChase{}
+}
+
+// Update handles new timecode from source.
+func (c *TimecodeChase) Update(tc Timecode, now time.Time) {
+	c.mu.Lock()
+	prev := c.current.Duration()
+	cur := tc.Duration()
+	first := c.lastSeen.IsZero()
+	c.current = tc
+	c.lastSeen = now
+	var fire []TimecodeCue
+	for i := range c.cues {
+		cue := &c.cues[i]
+		switch {
+		case cur < cue.at:
+			cue.Fired = false // re-arm after rewind.
+		case !cue.Fired && !first && prev < cue.at && cur-prev <= timecodeChaseWindow:
+			cue.Fired = true
+			fire = append(fire, *cue)
+		case cur-prev > timecodeChaseWindow || first:
+			cue.Fired = true // located past cue.
+		}
+	}
+	c.mu.Unlock()
+	for _, cue := range fire {
+		go cue.Action.Fire("timecode " + cue.At)
+	}
+}
+
+// SetCues replaces cues. Cues before current timecode are not fired.
+func (c *TimecodeChase) SetCues(cues []TimecodeCue) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	fps := c.settings.FPS
+	if fps <= 0 {
+		fps = 30
+	}
+	cur := c.current.Duration()
+	for i := range cues {
+		tc, err := ParseTimecode(cues[i].At, fps)
+		if err != nil {
+			return NewAppError(ErrKindInvalidRequest, "", err)
+		}
+		cues[i].at = tc.Duration()
+		cues[i].Fired = !c.lastSeen.IsZero() && cues[i].at <= cur
+	}
+	sort.Slice(cues, func(i, j int) bool { return cues[i].at < cues[j].at })
+	c.cues = cues
+	return nil
+}
+
+// Cues returns armed cues in timecode order.
+func (c *TimecodeChase) Cues() []TimecodeCue {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	cues := make([]TimecodeCue, len(c.cues))
+	copy(cues, c.cues)
+	return cues
+}
+
+// NextCue returns next cue to fire and time left until it. false is returned before timecode is received or after last cue.
+func (c *TimecodeChase) NextCue() (TimecodeCue, time.Duration, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if c.lastSeen.IsZero() {
+		return TimecodeCue{}, 0, false
+	}
+	cur := c.current.Duration()
+	for _, cue := range c.cues {
+		if !cue.Fired && cue.at >= cur {
+			return cue, cue.at - cur, true
+		}
+	}
+	return TimecodeCue{}, 0, false
+}
+
+// Configure replaces source settings and restarts source.
+func (c *TimecodeChase) Configure(settings TimecodeSettings) error {
+	if settings.FPS <= 0 {
+		settings.FPS = 30
+	}
+	c.mu.Lock()
+	if c.stop != nil {
+		c.stop()
+		c.stop = nil
+	}
+	c.settings = settings
+	c.lastErr = ""
+	c.mu.Unlock()
+
+	var stop func()
+	var err error
+	switch settings.Source {
+	case "":
+		return nil
+	case "midi":
+		stop, err = openMIDITimecode(settings.MIDIDevice, c.Update)
+	case "udp":
+		stop, err = listenUDPTimecode(settings.Listen, settings.FPS, c.Update)
+	case "ltc":
+		err = NewAppError(ErrKindUnsupported, "", fmt.Errorf("LTC audio input is not supported. Run LTC reader software sending \"HH:MM:SS:FF\" lines to \"udp\" source instead"))
+	default:
+		err = NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("unknown timecode source %q", settings.Source))
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if err != nil {
+		c.lastErr = err.Error()
+		return err
+	}
+	c.stop = stop
+	return nil
+}
+
+// Status returns current timecode, settings and cues.
+func (c *TimecodeChase) Status() gin.H {
+	cues := c.Cues()
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return gin.H{
+		"settings":  c.settings,
+		"timecode":  c.current.String(),
+		"last_seen": c.lastSeen,
+		"cues":      cues,
+		"error":     c.lastErr,
+	}
+}
+
+// listenUDPTimecode receives "HH:MM:SS:FF" text lines over UDP, such as from LTC reader software.
+func listenUDPTimecode(addr string, fps float64, fn func(Timecode, time.Time)) (func(), error) {
+	if addr == "" {
+		addr = ":9998"
+	}
+	conn, err := net.ListenPacket("udp", addr)
+	if err != nil {
+		return nil, NewAppError(ErrKindInternal, "", err)
+	}
+	go func() {
+		buf := make([]byte, 512)
+		for {
+			n, _, err := conn.ReadFrom(buf)
+			if err != nil {
+				return
+			}
+			sc := bufio.NewScanner(strings.NewReader(string(buf[:n])))
+			for sc.Scan() {
+				if tc, err := ParseTimecode(sc.Text(), fps); err == nil {
+					fn(tc, time.Now())
+				}
+			}
+		}
+	}()
+	log.Printf("Timecode listening on UDP %s\n", addr)
+	return func() { conn.Close() }, nil
+}
+
+// GetTimecodeHandler returns timecode status for [GET] /api/timecode .
+func GetTimecodeHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, timecode.Status())
+}
+
+// PutTimecodeSettingsHandler configures timecode source for [PUT] /api/timecode/settings .
+func PutTimecodeSettingsHandler(c *gin.Context) {
+	settings := TimecodeSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := timecode.Configure(settings); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Timecode = settings
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetTimecodeHandler(c)
+}
+
+// PutTimecodeCuesHandler arms cues against timecode for [PUT] /api/timecode/cues .
+// Cues are saved in settings and armed again after restart.
+func PutTimecodeCuesHandler(c *gin.Context) {
+	cues := []TimecodeCue{}
+	if err := c.ShouldBindJSON(&cues); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if err := timecode.SetCues(cues); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.TimecodeCues = timecode.Cues()
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetTimecodeHandler(c)
+}
@@ -0,0 +1,13 @@
+// +build !windows
+
+package main
+
+import (
+	"fmt"
+	"time"
+)
+
+// openMIDITimecode is available only on Windows. Use "udp" source with LTC/MTC reader software instead.
+func openMIDITimecode(device int, fn func(Timecode, time.Time)) (func(), error) {
+	return nil, NewAppError(ErrKindUnsupported, "", fmt.Errorf("MIDI timecode input is supported only on Windows"))
+}
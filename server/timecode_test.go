@@ -0,0 +1,117 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestMTCQuarterFrame(t *testing.T) {
+	// 01:02:03:04 at 25 fps.
+	values := []byte{4, 0, 3, 0, 2, 0, 1, 1 << 1}
+	d := &MTCDecoder{}
+	var tc Timecode
+	var ok bool
+	for piece, v := range values {
+		tc, ok = d.QuarterFrame(byte(piece)<<4 | v&0x0F)
+	}
+	if !ok || tc.String() != "01:02:03:06" || tc.FPS != 25 {
+		t.Fatalf("unexpected timecode %+v %v", tc, ok)
+	}
+
+	full, ok := d.FullFrame([]byte{0xF0, 0x7F, 0x7F, 0x01, 0x01, 3<<5 | 10, 20, 30, 15, 0xF7})
+	if !ok || full.String() != "10:20:30:15" || full.FPS != 30 {
+		t.Fatalf("unexpected full frame %+v", full)
+	}
+}
+
+func TestTimecodeChase(t *testing.T) {
+	c := NewTimecodeChase()
+	c.settings.FPS = 25
+	if err := c.SetCues([]TimecodeCue{{ID: "a", At: "00:00:10:00"}, {ID: "b", At: "00:01:00:00"}}); err != nil {
+		t.Fatal(err)
+	}
+	tc := func(s string) Timecode {
+		v, err := ParseTimecode(s, 25)
+		if err != nil {
+			t.Fatal(err)
+		}
+		return v
+	}
+	now := time.Now()
+	c.Update(tc("00:00:09:20"), now)
+	c.Update(tc("00:00:10:01"), now)
+	if !c.cues[0].Fired || c.cues[1].Fired {
+		t.Fatalf("unexpected cues %+v", c.cues)
+	}
+	// locate past cue b without firing it.
+	c.Update(tc("00:02:00:00"), now)
+	// rewind re-arms cues.
+	c.Update(tc("00:00:00:00"), now)
+	if c.cues[0].Fired || c.cues[1].Fired {
+		t.Fatalf("cues not re-armed %+v", c.cues)
+	}
+}
+
+func TestCueWarningTexts(t *testing.T) {
+	c := NewTimecodeChase()
+	c.settings.FPS = 25
+	if err := c.SetCues([]TimecodeCue{{ID: "a", Name: "Opening VT", At: "00:00:10:00"}, {ID: "b", At: "00:01:00:00"}}); err != nil {
+		t.Fatal(err)
+	}
+	if _, _, ok := c.NextCue(); ok {
+		t.Fatal("no cue is upcoming before timecode is received")
+	}
+	tc, _ := ParseTimecode("00:00:05:00", 25)
+	c.Update(tc, time.Now())
+	cue, remaining, ok := c.NextCue()
+	name, countdown := cueWarningTexts(CueWarningSettings{}, cue, remaining, ok)
+	if name != "Opening VT" || countdown != "00:00:05" {
+		t.Fatalf("unexpected texts %q %q", name, countdown)
+	}
+	c.cues[0].Fired = true
+	cue, remaining, ok = c.NextCue()
+	if name, _ = cueWarningTexts(CueWarningSettings{}, cue, remaining, ok); name != "b" {
+		t.Fatalf("unexpected name %q", name)
+	}
+	if name, countdown = cueWarningTexts(CueWarningSettings{IdleText: "END"}, TimecodeCue{}, 0, false); name != "END" || countdown != "" {
+		t.Fatalf("unexpected idle texts %q %q", name, countdown)
+	}
+}
+
+func TestTimecodeCuesPersisted(t *testing.T) {
+	prev := timecode
+	timecode = NewTimecodeChase()
+	defer func() { timecode = prev }()
+	defer config.Update(func(cfg *Config) { cfg.TimecodeCues = nil })
+
+	gin.SetMode(gin.TestMode)
+	r := gin.New()
+	r.PUT("/api/timecode/cues", PutTimecodeCuesHandler)
+	w := httptest.NewRecorder()
+	r.ServeHTTP(w, httptest.NewRequest(http.MethodPut, "/api/timecode/cues",
+		strings.NewReader(`[{"id":"b","at":"00:01:00:00"},{"id":"a","at":"00:00:10:00","action":{"function":"Cut"}}]`)))
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected cues armed, got %d %s", w.Code, w.Body)
+	}
+
+	// chase of restarted utility.
+	restarted := NewTimecodeChase()
+	if err := restarted.SetCues(config.Get().TimecodeCues); err != nil {
+		t.Fatal(err)
+	}
+	if cues := restarted.Cues(); len(cues) != 2 || cues[0].ID != "a" || cues[0].Action.Function != "Cut" {
+		t.Fatalf("expected cues restored from settings, got %+v", cues)
+	}
+}
+
+func TestTimecodeLTCSourceRefused(t *testing.T) {
+	c := NewTimecodeChase()
+	if err := c.Configure(TimecodeSettings{Source: "ltc"}); AsAppError(err).Kind != ErrKindUnsupported {
+		t.Fatalf("expected LTC audio source refused as unsupported, got %v", err)
+	}
+}
@@ -0,0 +1,156 @@
+package main
+
+import (
+	"fmt"
+	"sync"
+	"syscall"
+	"time"
+	"unsafe"
+)
+
+// winmm MIDI input constants.
+const (
+	callbackFunction = 0x30000
+	mimData          = 0x3C3
+	mimLongData      = 0x3C4
+)
+
+var (
+	winmm                   = syscall.NewLazyDLL("winmm.dll")
+	procMidiInOpen          = winmm.NewProc("midiInOpen")
+	procMidiStart           = winmm.NewProc("midiInStart")
+	procMidiStop            = winmm.NewProc("midiInStop")
+	procMidiReset           = winmm.NewProc("midiInReset")
+	procMidiClose           = winmm.NewProc("midiInClose")
+	procMidiPrepareHeader   = winmm.NewProc("midiInPrepareHeader")
+	procMidiUnprepareHeader = winmm.NewProc("midiInUnprepareHeader")
+	procMidiAddBuffer       = winmm.NewProc("midiInAddBuffer")
+)
+
+// midiHdr is winmm MIDIHDR, describing buffer receiving SysEx message.
+type midiHdr struct {
+	data          uintptr
+	bufferLength  uint32
+	bytesRecorded uint32
+	user          uintptr
+	flags         uint32
+	next          uintptr
+	reserved      uintptr
+	offset        uint32
+	reserved2     [8]uintptr
+}
+
+// midiSysEx is SysEx buffer handed to winmm. Full frame message is 10 bytes, so buffer fits any of them.
+type midiSysEx struct {
+	hdr  midiHdr
+	data [64]byte
+}
+
+// midiHandler is timecode handler of open MIDI device. Callback is created once,
+// since Windows callbacks created by syscall.NewCallback are never released.
+// SysEx buffers are package level, so they stay at fixed address while winmm holds them.
+var (
+	midiMu       sync.Mutex
+	midiDecoder  = &MTCDecoder{}
+	midiHandler  func(Timecode, time.Time)
+	midiClosing  bool // buffers returned by midiInReset are not added again.
+	midiBuffers  [4]midiSysEx
+	midiCallback uintptr
+	midiOnce     sync.Once
+)
+
+// midiBuffer returns SysEx buffer whose header address is param1 of MIM_LONGDATA.
+func midiBuffer(param1 uintptr) *midiSysEx {
+	for i := range midiBuffers {
+		if uintptr(unsafe.Pointer(&midiBuffers[i].hdr)) == param1 {
+			return &midiBuffers[i]
+		}
+	}
+	return nil
+}
+
+func midiInProc(handle, msg, instance, param1, param2 uintptr) uintptr {
+	var tc Timecode
+	var ok bool
+	switch msg {
+	case mimData:
+		if byte(param1) != 0xF1 {
+			return 0
+		}
+		midiMu.Lock()
+		tc, ok = midiDecoder.QuarterFrame(byte(param1 >> 8))
+	case mimLongData:
+		b := midiBuffer(param1)
+		if b == nil {
+			return 0
+		}
+		n := int(b.hdr.bytesRecorded)
+		if n > len(b.data) {
+			n = len(b.data)
+		}
+		midiMu.Lock()
+		tc, ok = midiDecoder.FullFrame(b.data[:n])
+		if !midiClosing {
+			// buffer is returned once filled, so it is queued again for next SysEx.
+			procMidiAddBuffer.Call(handle, uintptr(unsafe.Pointer(&b.hdr)), unsafe.Sizeof(b.hdr))
+		}
+	default:
+		return 0
+	}
+	fn := midiHandler
+	midiMu.Unlock()
+	if ok && fn != nil {
+		fn(tc, time.Now())
+	}
+	return 0
+}
+
+// openMIDITimecode receives MTC quarter frames and full frame SysEx from MIDI input device.
+// Full frame locates timecode while transport is stopped or jumps, and quarter frames follow playback.
+func openMIDITimecode(device int, fn func(Timecode, time.Time)) (func(), error) {
+	midiOnce.Do(func() {
+		midiCallback = syscall.NewCallback(midiInProc)
+	})
+	midiMu.Lock()
+	midiDecoder = &MTCDecoder{}
+	midiHandler = fn
+	midiClosing = false
+	midiMu.Unlock()
+	cb := midiCallback
+	var handle uintptr
+	if r, _, _ := procMidiInOpen.Call(uintptr(unsafe.Pointer(&handle)), uintptr(device), cb, 0, callbackFunction); r != 0 {
+		return nil, NewAppError(ErrKindInternal, "", fmt.Errorf("midiInOpen device %d failed with code %d", device, r))
+	}
+	for i := range midiBuffers {
+		b := &midiBuffers[i]
+		b.hdr = midiHdr{data: uintptr(unsafe.Pointer(&b.data[0])), bufferLength: uint32(len(b.data))}
+		if r, _, _ := procMidiPrepareHeader.Call(handle, uintptr(unsafe.Pointer(&b.hdr)), unsafe.Sizeof(b.hdr)); r != 0 {
+			closeMIDI(handle, i)
+			return nil, NewAppError(ErrKindInternal, "", fmt.Errorf("midiInPrepareHeader failed with code %d", r))
+		}
+		if r, _, _ := procMidiAddBuffer.Call(handle, uintptr(unsafe.Pointer(&b.hdr)), unsafe.Sizeof(b.hdr)); r != 0 {
+			closeMIDI(handle, i+1)
+			return nil, NewAppError(ErrKindInternal, "", fmt.Errorf("midiInAddBuffer failed with code %d", r))
+		}
+	}
+	if r, _, _ := procMidiStart.Call(handle); r != 0 {
+		closeMIDI(handle, len(midiBuffers))
+		return nil, NewAppError(ErrKindInternal, "", fmt.Errorf("midiInStart failed with code %d", r))
+	}
+	return func() {
+		procMidiStop.Call(handle)
+		closeMIDI(handle, len(midiBuffers))
+	}, nil
+}
+
+// closeMIDI returns first prepared SysEx buffers from device, then closes device.
+func closeMIDI(handle uintptr, prepared int) {
+	midiMu.Lock()
+	midiClosing = true
+	midiMu.Unlock()
+	procMidiReset.Call(handle)
+	for i := 0; i < prepared; i++ {
+		procMidiUnprepareHeader.Call(handle, uintptr(unsafe.Pointer(&midiBuffers[i].hdr)), unsafe.Sizeof(midiBuffers[i].hdr))
+	}
+	procMidiClose.Call(handle)
+}
@@ -0,0 +1,252 @@
+package main
+
+import (
+	"bytes"
+	"html/template"
+	"net/http"
+	"sort"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// timeOnAirTick is how often polled tally of hosts without TCP API tally is sampled.
+const timeOnAirTick = 250 * time.Millisecond
+
+// InputTimeOnAir is accumulated program time of input. Inputs visible on program together, such as layers, are all counted.
+type InputTimeOnAir struct {
+	Key     string  `json:"key"`
+	Number  int     `json:"number"`
+	Title   string  `json:"title"`
+	Seconds float64 `json:"seconds"`
+	OnAir   bool    `json:"on_air"`
+}
+
+// airHost is time on air of single vMix host.
+type airHost struct {
+	since  time.Time // last reset.
+	inputs map[string]*InputTimeOnAir
+	onAir  map[string]time.Time // input key -> start of current time on program.
+}
+
+// TimeOnAir accumulates program time per input from tally, for panel shows and casters tracking screen time.
+// Primary host follows TCP API tally events, and other hosts, or primary without TCP API, follow polled state.
+type TimeOnAir struct {
+	mu    sync.Mutex
+	hosts map[string]*airHost // vMix host -> time on air.
+}
+
+// NewTimeOnAir creates TimeOnAir.
+func NewTimeOnAir() *TimeOnAir {
+	return &TimeOnAir{hosts: make(map[string]*airHost)}
+}
+
+func (t *TimeOnAir) host(addr string, now time.Time) *airHost {
+	h, ok := t.hosts[addr]
+	if !ok {
+		h = &airHost{since: now, inputs: make(map[string]*InputTimeOnAir), onAir: make(map[string]time.Time)}
+		t.hosts[addr] = h
+	}
+	return h
+}
+
+// Observe applies tally of host, "1" per input number on program as in TCP API. Nil state stops every input,
+// such as while host is offline.
+func (t *TimeOnAir) Observe(addr string, s *State, tally string, now time.Time) {
+	program := map[string]bool{}
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	h := t.host(addr, now)
+	if s != nil {
+		for i := 0; i < len(tally); i++ {
+			if tally[i] != '1' {
+				continue
+			}
+			in := s.FindInput(strconv.Itoa(i + 1))
+			if in == nil {
+				continue
+			}
+			program[in.Key] = true
+			air, ok := h.inputs[in.Key]
+			if !ok {
+				air = &InputTimeOnAir{Key: in.Key}
+				h.inputs[in.Key] = air
+			}
+			air.Number, air.Title = in.Number, in.Title
+			if _, ok := h.onAir[in.Key]; !ok {
+				h.onAir[in.Key] = now
+			}
+		}
+	}
+	for key, start := range h.onAir {
+		if program[key] {
+			continue
+		}
+		if now.After(start) {
+			h.inputs[key].Seconds += now.Sub(start).Seconds()
+		}
+		delete(h.onAir, key)
+	}
+}
+
+// Get returns inputs of host that were on program since last reset, longest first.
+func (t *TimeOnAir) Get(addr string, now time.Time) (time.Time, []InputTimeOnAir) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	h := t.host(addr, now)
+	list := make([]InputTimeOnAir, 0, len(h.inputs))
+	for key, air := range h.inputs {
+		in := *air
+		if start, ok := h.onAir[key]; ok {
+			in.OnAir = true
+			if now.After(start) {
+				in.Seconds += now.Sub(start).Seconds()
+			}
+		}
+		list = append(list, in)
+	}
+	sort.Slice(list, func(i, j int) bool {
+		if list[i].Seconds != list[j].Seconds {
+			return list[i].Seconds > list[j].Seconds
+		}
+		return list[i].Number < list[j].Number
+	})
+	return h.since, list
+}
+
+// Reset clears time of every input of host. Inputs on program keep counting from now.
+func (t *TimeOnAir) Reset(addr string, now time.Time) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	h := t.host(addr, now)
+	h.since = now
+	for key, air := range h.inputs {
+		air.Seconds = 0
+		if _, ok := h.onAir[key]; ok {
+			h.onAir[key] = now
+		} else {
+			delete(h.inputs, key)
+		}
+	}
+}
+
+// Run follows tally events of primary host and polled state of other hosts forever.
+func (t *TimeOnAir) Run() {
+	for {
+		ch, unsubscribe := events.SubscribeTo(16, "timeonair", func(topic string) bool { return topic == "tally" })
+		tick := time.NewTicker(timeOnAirTick)
+	loop:
+		for {
+			select {
+			case ev, ok := <-ch:
+				if !ok {
+					break loop
+				}
+				if tally, ok := ev.Data.(string); ok {
+					s, _ := hostState(*vmixaddr)
+					t.Observe(*vmixaddr, s, tally, time.Now())
+				}
+			case now := <-tick.C:
+				for _, status := range poller.Statuses() {
+					if IsOfflineAddr(status.Addr) || (status.Addr == *vmixaddr && vmixTCP != nil && vmixTCP.Connected()) {
+						continue
+					}
+					if !status.Online || status.State == nil {
+						t.Observe(status.Addr, nil, "", now)
+						continue
+					}
+					t.Observe(status.Addr, status.State, status.State.Tally(), now)
+				}
+			}
+		}
+		tick.Stop()
+		unsubscribe()
+	}
+}
+
+// GetTimeOnAirHandler returns time on air of inputs for [GET] /api/timeonair?host=... .
+func GetTimeOnAirHandler(c *gin.Context) {
+	host := c.DefaultQuery("host", *vmixaddr)
+	since, inputs := timeOnAir.Get(host, time.Now())
+	c.JSON(http.StatusOK, gin.H{
+		"host":   host,
+		"since":  since,
+		"inputs": inputs,
+	})
+}
+
+// ResetTimeOnAirHandler clears time on air of inputs for [DELETE] /api/timeonair?host=... , such as at start of segment.
+func ResetTimeOnAirHandler(c *gin.Context) {
+	timeOnAir.Reset(c.DefaultQuery("host", *vmixaddr), time.Now())
+	GetTimeOnAirHandler(c)
+}
+
+// timeOnAirOverlayTemplate is leaderboard page for browser input of vMix, transparent outside of rows.
+var timeOnAirOverlayTemplate = template.Must(template.New("timeonair").Parse(`<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Time on air</title>
+<style>
+body { margin: 0; padding: 24px; background: transparent; font-family: sans-serif; color: #fff; }
+.row { display: flex; align-items: center; margin-bottom: 8px; padding: 8px 16px; background: rgba(0, 0, 0, 0.7); border-left: 8px solid #555; font-size: 32px; }
+.row.on-air { border-left-color: #e53935; }
+.title { flex: 1; overflow: hidden; white-space: nowrap; text-overflow: ellipsis; }
+.time { font-variant-numeric: tabular-nums; margin-left: 24px; }
+</style>
+</head>
+<body>
+<div id="board"></div>
+<script>
+const host = {{.Host}};
+const limit = {{.Limit}};
+function format(seconds) {
+  const s = Math.floor(seconds);
+  const m = Math.floor(s / 60);
+  return m + ":" + String(s % 60).padStart(2, "0");
+}
+async function update() {
+  try {
+    const res = await fetch("/api/timeonair?host=" + encodeURIComponent(host));
+    const body = await res.json();
+    const board = document.getElementById("board");
+    board.textContent = "";
+    for (const input of body.inputs.slice(0, limit)) {
+      const row = document.createElement("div");
+      row.className = input.on_air ? "row on-air" : "row";
+      const title = document.createElement("span");
+      title.className = "title";
+      title.textContent = input.title;
+      const time = document.createElement("span");
+      time.className = "time";
+      time.textContent = format(input.seconds);
+      row.append(title, time);
+      board.append(row);
+    }
+  } catch (e) {
+    // keeps last board while utility is unreachable.
+  }
+}
+update();
+setInterval(update, 1000);
+</script>
+</body>
+</html>
+`))
+
+// GetTimeOnAirOverlayHandler returns live leaderboard page for [GET] /api/timeonair/overlay?host=...&limit=10 ,
+// added to vMix as browser input.
+func GetTimeOnAirOverlayHandler(c *gin.Context) {
+	limit, err := strconv.Atoi(c.DefaultQuery("limit", "10"))
+	if err != nil || limit <= 0 {
+		limit = 10
+	}
+	buf := &bytes.Buffer{}
+	if err := timeOnAirOverlayTemplate.Execute(buf, gin.H{"Host": c.DefaultQuery("host", *vmixaddr), "Limit": limit}); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInternal, "", err))
+		return
+	}
+	c.Data(http.StatusOK, "text/html; charset=utf-8", buf.Bytes())
+}
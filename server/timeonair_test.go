@@ -0,0 +1,33 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestTimeOnAir(t *testing.T) {
+	tracker := NewTimeOnAir()
+	addr := "http://timeonair.invalid:8088"
+	s := DefaultMockState()
+	now := time.Now()
+
+	tracker.Observe(addr, s, "1010", now) // inputs 1 and 3 on program, such as layer.
+	tracker.Observe(addr, s, "0100", now.Add(10*time.Second))
+	tracker.Observe(addr, nil, "", now.Add(15*time.Second))
+	tracker.Observe(addr, s, "0100", now.Add(20*time.Second))
+
+	_, inputs := tracker.Get(addr, now.Add(30*time.Second))
+	seconds := map[int]float64{}
+	for _, in := range inputs {
+		seconds[in.Number] = in.Seconds
+	}
+	if len(inputs) != 3 || inputs[0].Number != 2 || !inputs[0].OnAir || seconds[2] != 15 || seconds[1] != 10 || seconds[3] != 10 {
+		t.Fatalf("expected program time counted per input while online, got %+v", inputs)
+	}
+
+	tracker.Reset(addr, now.Add(30*time.Second))
+	since, inputs := tracker.Get(addr, now.Add(35*time.Second))
+	if !since.Equal(now.Add(30*time.Second)) || len(inputs) != 1 || inputs[0].Seconds != 5 {
+		t.Fatalf("expected reset to keep only input on program, got %v %+v", since, inputs)
+	}
+}
@@ -0,0 +1,147 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// TransitionStep is transition to one destination of transition macro.
+type TransitionStep struct {
+	Destination string `json:"destination"` // "program", "mix:N", "output:N", "external2", "fullscreen" or "fullscreen2".
+	Effect      string `json:"effect"`      // vMix transition function, e.g. "Merge" . Destinations other than mixes only support "Cut".
+	DurationMs  int    `json:"duration_ms"` // transition duration.
+	DelayMs     int    `json:"delay_ms"`    // delay from start of macro.
+	Input       string `json:"input"`       // input of this step. Macro input is used if empty.
+}
+
+// TransitionMacro performs different transitions to different destinations, each started at its own delay.
+type TransitionMacro struct {
+	Host  string           `json:"host"`  // vMix host. Primary vMix host is used if empty.
+	Input string           `json:"input"` // input transitioned to.
+	Steps []TransitionStep `json:"steps"`
+}
+
+// outputFunctionsByDestination maps output destination to vMix function routing input to it.
+var outputFunctionsByDestination = map[string]string{
+	"output:2":    "SetOutput2",
+	"output:3":    "SetOutput3",
+	"output:4":    "SetOutput4",
+	"external2":   "SetOutputExternal2",
+	"fullscreen":  "SetOutputFullscreen",
+	"fullscreen2": "SetOutputFullscreen2",
+}
+
+// StepFunction returns function performing step for input key.
+func (step TransitionStep) StepFunction(inputKey string) (FunctionCall, error) {
+	effect := step.Effect
+	if effect == "" {
+		effect = "Cut"
+	}
+	if function, ok := outputFunctionsByDestination[step.Destination]; ok {
+		if effect != "Cut" {
+			return FunctionCall{}, fmt.Errorf("%s only supports Cut", step.Destination)
+		}
+		return FunctionCall{Function: function, Params: map[string]string{"Input": inputKey, "Value": "Input"}}, nil
+	}
+	call := FunctionCall{Function: effect, Params: map[string]string{"Input": inputKey}}
+	if step.DurationMs > 0 && effect != "Cut" {
+		call.Params["Duration"] = strconv.Itoa(step.DurationMs)
+	}
+	switch {
+	case step.Destination == "" || step.Destination == "program":
+	case strings.HasPrefix(step.Destination, "mix:"):
+		n, err := strconv.Atoi(strings.TrimPrefix(step.Destination, "mix:"))
+		if err != nil || n < 1 || n > 16 {
+			return FunctionCall{}, fmt.Errorf("invalid mix %q", step.Destination)
+		}
+		// Mix parameter is zero based. Mix 1 is program.
+		if n > 1 {
+			call.Params["Mix"] = strconv.Itoa(n - 1)
+		}
+	default:
+		return FunctionCall{}, fmt.Errorf("unknown destination %q", step.Destination)
+	}
+	return call, nil
+}
+
+// Run validates every step, then sends each step at its delay concurrently and waits for all of them.
+func (m TransitionMacro) Run() error {
+	if m.Host == "" {
+		m.Host = *vmixaddr
+	}
+	if len(m.Steps) == 0 {
+		return NewAppError(ErrKindInvalidRequest, m.Host, fmt.Errorf("no steps"))
+	}
+	s, err := hostState(m.Host)
+	if err != nil {
+		return err
+	}
+	calls := make([]FunctionCall, len(m.Steps))
+	for i, step := range m.Steps {
+		ref := step.Input
+		if ref == "" {
+			ref = m.Input
+		}
+		in := s.FindInput(ResolveInputAlias(m.Host, ref))
+		if in == nil {
+			return NewAppError(ErrKindNotFound, m.Host, fmt.Errorf("input %q not found", ref))
+		}
+		if step.DelayMs < 0 {
+			return NewAppError(ErrKindInvalidRequest, m.Host, fmt.Errorf("step %d : negative delay", i+1))
+		}
+		if calls[i], err = step.StepFunction(in.Key); err != nil {
+			return NewAppError(ErrKindInvalidRequest, m.Host, fmt.Errorf("step %d : %v", i+1, err))
+		}
+	}
+
+	poller.Touch()
+	start := time.Now()
+	errs := make([]error, len(calls))
+	wg := &sync.WaitGroup{}
+	for i := range calls {
+		wg.Add(1)
+		go func(i int) {
+			defer wg.Done()
+			time.Sleep(time.Until(start.Add(time.Duration(m.Steps[i].DelayMs) * time.Millisecond)))
+			errs[i] = SendFunctionTo(m.Host, calls[i].Function, calls[i].Params)
+		}(i)
+	}
+	wg.Wait()
+	for _, err := range errs {
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// TransitionMacroHandler runs transition macro for [POST] /api/transitions/macro .
+// Response is returned after last step was sent.
+func TransitionMacroHandler(c *gin.Context) {
+	m := TransitionMacro{}
+	if err := c.ShouldBindJSON(&m); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if m.Host == "" {
+		m.Host = *vmixaddr
+	}
+	if err := m.Run(); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	destinations := make([]string, len(m.Steps))
+	for i, step := range m.Steps {
+		destinations[i] = step.Destination
+	}
+	announceAction(c, m.Host, fmt.Sprintf("transition macro %s to %s", m.Input, strings.Join(destinations, ", ")))
+	c.JSON(http.StatusOK, gin.H{
+		"macro": m,
+	})
+}
@@ -0,0 +1,24 @@
+package main
+
+import "testing"
+
+func TestTransitionStepFunction(t *testing.T) {
+	call, err := TransitionStep{Destination: "program", Effect: "Merge", DurationMs: 1000}.StepFunction("k1")
+	if err != nil || call.Function != "Merge" || call.Params["Duration"] != "1000" || call.Params["Mix"] != "" {
+		t.Fatalf("unexpected call %+v %v", call, err)
+	}
+	call, err = TransitionStep{Destination: "mix:3", Effect: "Fade"}.StepFunction("k1")
+	if err != nil || call.Params["Mix"] != "2" {
+		t.Fatalf("unexpected call %+v %v", call, err)
+	}
+	call, err = TransitionStep{Destination: "output:2"}.StepFunction("k1")
+	if err != nil || call.Function != "SetOutput2" || call.Params["Value"] != "Input" {
+		t.Fatalf("unexpected call %+v %v", call, err)
+	}
+	if _, err = (TransitionStep{Destination: "external2", Effect: "Merge"}).StepFunction("k1"); err == nil {
+		t.Fatal("outputs must not accept effects")
+	}
+	if _, err = (TransitionStep{Destination: "stage"}).StepFunction("k1"); err == nil {
+		t.Fatal("expected unknown destination error")
+	}
+}
@@ -0,0 +1,37 @@
+package main
+
+import (
+	"log"
+	"time"
+)
+
+// TriggerAction is vMix function fired by external trigger such as GPIO or DMX.
+type TriggerAction struct {
+	Host string `json:"host"` // vMix host. Primary vMix host is used if empty.
+	FunctionCall
+}
+
+// Fire sends function and publishes "trigger" event with source of trigger. e.g. "serial COM3" .
+// Triggers are ignored while standing by as secondary.
+func (a TriggerAction) Fire(source string) error {
+	host := a.Host
+	if host == "" {
+		host = *vmixaddr
+	}
+	if !pairing.IsActive() {
+		return standbyError(host)
+	}
+	poller.TouchHost(host)
+	err := SendFunctionTo(host, a.Function, a.Params)
+	if err != nil {
+		log.Printf("Trigger %s failed to send %s : %v\n", source, a.Function, err)
+	}
+	events.Publish(Event{Topic: "trigger", Host: host, Data: map[string]interface{}{
+		"source":   source,
+		"function": a.Function,
+		"params":   a.Params,
+		"error":    err,
+		"at":       time.Now(),
+	}})
+	return err
+}
@@ -0,0 +1,171 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io/ioutil"
+	"net/http"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// LatestVersion is known latest vMix version, maintained by user or fetched from URL.
+type LatestVersion struct {
+	Version   string    `json:"version"`    // e.g. "27.0.0.49" .
+	URL       string    `json:"url"`        // optional URL returning version as plain text or {"version":"..."} .
+	UpdatedAt time.Time `json:"updated_at"` // when version was last set or fetched.
+}
+
+// UpgradeAdvice is upgrade status of vMix host.
+type UpgradeAdvice struct {
+	Name     string `json:"name"`
+	Addr     string `json:"addr"`
+	Online   bool   `json:"online"`
+	Version  string `json:"version"`
+	Latest   string `json:"latest"`
+	Outdated bool   `json:"outdated"`
+}
+
+// CompareVersions compares dotted numeric versions. It returns -1, 0 or 1.
+func CompareVersions(a, b string) int {
+	as, bs := strings.Split(a, "."), strings.Split(b, ".")
+	for i := 0; i < len(as) || i < len(bs); i++ {
+		var x, y int
+		if i < len(as) {
+			x, _ = strconv.Atoi(as[i])
+		}
+		if i < len(bs) {
+			y, _ = strconv.Atoi(bs[i])
+		}
+		switch {
+		case x < y:
+			return -1
+		case x > y:
+			return 1
+		}
+	}
+	return 0
+}
+
+// adviseUpgrade returns upgrade advice of host status. Hosts with unknown version are never outdated.
+func adviseUpgrade(status *HostStatus, latest string) UpgradeAdvice {
+	return UpgradeAdvice{
+		Name:     status.Name,
+		Addr:     status.Addr,
+		Online:   status.Online,
+		Version:  status.Version,
+		Latest:   latest,
+		Outdated: latest != "" && status.Version != "" && CompareVersions(status.Version, latest) < 0,
+	}
+}
+
+// UpgradeAdvices returns upgrade advice of every polled host.
+func UpgradeAdvices() []UpgradeAdvice {
+	latest := config.Get().LatestVersion.Version
+	statuses := poller.Statuses()
+	advices := make([]UpgradeAdvice, 0, len(statuses))
+	for _, s := range statuses {
+		advices = append(advices, adviseUpgrade(s, latest))
+	}
+	return advices
+}
+
+// checkUpgrade publishes "upgrade" event if host runs outdated vMix.
+func checkUpgrade(status *HostStatus) {
+	advice := adviseUpgrade(status, config.Get().LatestVersion.Version)
+	if advice.Outdated {
+		events.Publish(Event{Topic: "upgrade", Host: status.Name, Data: advice})
+	}
+}
+
+// fetchLatestVersion fetches latest version from URL.
+func fetchLatestVersion(url string) (string, error) {
+	client := &http.Client{Timeout: 10 * time.Second}
+	resp, err := client.Get(url)
+	if err != nil {
+		return "", NewAppError(ErrKindUnreachable, url, err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return "", NewAppError(ErrKindUnreachable, url, fmt.Errorf("unexpected status %s", resp.Status))
+	}
+	b, err := ioutil.ReadAll(resp.Body)
+	if err != nil {
+		return "", NewAppError(ErrKindUnreachable, url, err)
+	}
+	v := struct {
+		Version string `json:"version"`
+	}{}
+	if err := json.Unmarshal(b, &v); err != nil {
+		v.Version = strings.TrimSpace(string(b))
+	}
+	if majorVersion(v.Version) == 0 {
+		return "", NewAppError(ErrKindInvalidRequest, url, fmt.Errorf("invalid version %q", v.Version))
+	}
+	return v.Version, nil
+}
+
+// setLatestVersion saves latest version and re-checks every host.
+func setLatestVersion(latest LatestVersion) error {
+	latest.UpdatedAt = time.Now()
+	if err := config.Update(func(cfg *Config) {
+		cfg.LatestVersion = latest
+	}); err != nil {
+		return err
+	}
+	for _, s := range poller.Statuses() {
+		if s.Online {
+			checkUpgrade(s)
+		}
+	}
+	return nil
+}
+
+// GetUpgradeAdviceHandler returns outdated hosts for [GET] /api/upgrades .
+func GetUpgradeAdviceHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"latest": config.Get().LatestVersion,
+		"hosts":  UpgradeAdvices(),
+	})
+}
+
+// PutLatestVersionHandler sets known latest version for [PUT] /api/upgrades/latest .
+func PutLatestVersionHandler(c *gin.Context) {
+	latest := LatestVersion{}
+	if err := c.ShouldBindJSON(&latest); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if latest.Version != "" && majorVersion(latest.Version) == 0 {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid version %q", latest.Version)))
+		return
+	}
+	if err := setLatestVersion(latest); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetUpgradeAdviceHandler(c)
+}
+
+// FetchLatestVersionHandler fetches latest version from configured URL for [POST] /api/upgrades/latest/fetch .
+func FetchLatestVersionHandler(c *gin.Context) {
+	latest := config.Get().LatestVersion
+	if latest.URL == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("latest version URL not configured")))
+		return
+	}
+	v, err := fetchLatestVersion(latest.URL)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	latest.Version = v
+	if err := setLatestVersion(latest); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetUpgradeAdviceHandler(c)
+}
@@ -0,0 +1,20 @@
+package main
+
+import "testing"
+
+func TestCompareVersions(t *testing.T) {
+	cases := []struct {
+		a, b string
+		want int
+	}{
+		{"24.0.0.72", "24.0.0.72", 0},
+		{"24.0.0.72", "25.0.0.34", -1},
+		{"26.0.0.40", "26.0.0.9", 1},
+		{"27", "27.0.0.0", 0},
+	}
+	for _, tc := range cases {
+		if got := CompareVersions(tc.a, tc.b); got != tc.want {
+			t.Errorf("CompareVersions(%q, %q) = %d, want %d", tc.a, tc.b, got, tc.want)
+		}
+	}
+}
@@ -0,0 +1,241 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"log"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// Watchdog escalation stages.
+const (
+	WatchdogOK        = ""          // host answered recently.
+	WatchdogWarn      = "warn"      // "watchdog" event published.
+	WatchdogReconnect = "reconnect" // HTTP client recreated.
+	WatchdogAlert     = "alert"     // webhook alerted.
+)
+
+// WatchdogSettings is idle watchdog configuration. Zero thresholds use defaults.
+type WatchdogSettings struct {
+	Disabled     bool   `json:"disabled"`
+	WarnSec      int    `json:"warn_sec"`      // seconds without successful poll before warning. Default: 10 .
+	ReconnectSec int    `json:"reconnect_sec"` // seconds before recreating HTTP client. Default: 30 .
+	AlertSec     int    `json:"alert_sec"`     // seconds before alerting webhook. Default: 120 .
+	WebhookURL   string `json:"webhook_url"`   // URL receiving JSON POST on alert. Empty to skip.
+}
+
+func (s WatchdogSettings) thresholds() (warn, reconnect, alert time.Duration) {
+	sec := func(v, def int) time.Duration {
+		if v <= 0 {
+			v = def
+		}
+		return time.Duration(v) * time.Second
+	}
+	return sec(s.WarnSec, 10), sec(s.ReconnectSec, 30), sec(s.AlertSec, 120)
+}
+
+// WatchdogHost is watchdog state of vMix host, published as "watchdog" event.
+type WatchdogHost struct {
+	Name       string    `json:"name"`
+	Addr       string    `json:"addr"`
+	Stage      string    `json:"stage"`
+	LastSeen   time.Time `json:"last_seen"` // last successful poll. Watchdog start if never seen.
+	IdleSec    float64   `json:"idle_sec"`
+	Reconnects int       `json:"reconnects"`
+	AlertError string    `json:"alert_error,omitempty"`
+}
+
+// Watchdog tracks time since last successful poll of each host and escalates:
+// warn event, then forced reconnect, then webhook alert.
+type Watchdog struct {
+	mu      sync.Mutex
+	started time.Time
+	hosts   map[string]*WatchdogHost
+	alert   func(url string, h WatchdogHost) error
+}
+
+// NewWatchdog creates Watchdog.
+func NewWatchdog() *Watchdog {
+	return &Watchdog{
+		started: time.Now(),
+		hosts:   make(map[string]*WatchdogHost),
+		alert:   postWatchdogAlert,
+	}
+}
+
+// Hosts returns watchdog state of hosts.
+func (w *Watchdog) Hosts() map[string]WatchdogHost {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	hosts := make(map[string]WatchdogHost, len(w.hosts))
+	for addr, h := range w.hosts {
+		hosts[addr] = *h
+	}
+	return hosts
+}
+
+// nextStage returns stage to escalate to for idle duration, or false when already there.
+func nextStage(stage string, idle time.Duration, settings WatchdogSettings) (string, bool) {
+	warn, reconnect, alert := settings.thresholds()
+	next := WatchdogOK
+	switch {
+	case idle >= alert:
+		next = WatchdogAlert
+	case idle >= reconnect:
+		next = WatchdogReconnect
+	case idle >= warn:
+		next = WatchdogWarn
+	}
+	return next, next != stage
+}
+
+// tick checks every host polled by poller.
+func (w *Watchdog) tick(now time.Time) {
+	settings := config.Get().Watchdog
+	if settings.Disabled || !BackgroundActive() {
+		return
+	}
+	seen := make(map[string]bool)
+	for _, status := range poller.Statuses() {
+		seen[status.Addr] = true
+		w.check(status, settings, now)
+	}
+	w.mu.Lock()
+	for addr := range w.hosts {
+		if !seen[addr] {
+			delete(w.hosts, addr)
+		}
+	}
+	w.mu.Unlock()
+}
+
+func (w *Watchdog) check(status *HostStatus, settings WatchdogSettings, now time.Time) {
+	w.mu.Lock()
+	h, ok := w.hosts[status.Addr]
+	if !ok {
+		h = &WatchdogHost{Name: status.Name, Addr: status.Addr}
+		w.hosts[status.Addr] = h
+	}
+	h.LastSeen = status.LastSeen
+	if h.LastSeen.IsZero() {
+		h.LastSeen = w.started
+	}
+	// Hosts are not polled while background activity is paused.
+	if resumed := Background().Since; h.LastSeen.Before(resumed) {
+		h.LastSeen = resumed
+	}
+	idle := now.Sub(h.LastSeen)
+	if status.Online {
+		idle = 0
+	}
+	h.IdleSec = idle.Seconds()
+	stage, changed := nextStage(h.Stage, idle, settings)
+	if !changed {
+		w.mu.Unlock()
+		return
+	}
+	// Skipped stages are not run, except reconnect which is worth trying before alerting.
+	reconnect := stage == WatchdogReconnect || (stage == WatchdogAlert && h.Stage != WatchdogReconnect)
+	if reconnect {
+		h.Reconnects++
+	}
+	h.Stage = stage
+	h.AlertError = ""
+	snapshot := *h
+	w.mu.Unlock()
+
+	switch {
+	case stage == WatchdogOK:
+		log.Printf("Watchdog : %s (%s) recovered\n", status.Name, status.Addr)
+	case stage == WatchdogWarn:
+		log.Printf("Watchdog : no response from %s (%s) for %.0fs\n", status.Name, status.Addr, snapshot.IdleSec)
+	}
+	if reconnect {
+		log.Printf("Watchdog : recreating HTTP client of %s (%s)\n", status.Name, status.Addr)
+		hostClients.Reset(status.Addr)
+		poller.TouchHost(status.Addr)
+	}
+	if stage == WatchdogAlert && settings.WebhookURL != "" && pairing.IsActive() {
+		if err := w.alert(settings.WebhookURL, snapshot); err != nil {
+			log.Printf("Watchdog : failed to alert webhook : %v\n", err)
+			w.mu.Lock()
+			h.AlertError = err.Error()
+			snapshot = *h
+			w.mu.Unlock()
+		}
+	}
+	events.Publish(Event{Topic: "watchdog", Host: status.Name, Data: snapshot})
+}
+
+// postWatchdogAlert posts watchdog state of host as JSON to webhook.
+func postWatchdogAlert(webhook string, h WatchdogHost) error {
+	b, err := json.Marshal(gin.H{
+		"text":     fmt.Sprintf("vMix %s (%s) has not responded for %.0f seconds", h.Name, h.Addr, h.IdleSec),
+		"watchdog": h,
+	})
+	if err != nil {
+		return err
+	}
+	client := &http.Client{Timeout: 10 * time.Second}
+	resp, err := client.Post(webhook, "application/json", bytes.NewReader(b))
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode/100 != 2 {
+		return fmt.Errorf("webhook responded %s", resp.Status)
+	}
+	return nil
+}
+
+// Run checks hosts every tick.
+func (w *Watchdog) Run(tick time.Duration) {
+	t := time.NewTicker(tick)
+	defer t.Stop()
+	for now := range t.C {
+		tasks.Beat("watchdog", tick)
+		w.tick(now)
+	}
+}
+
+// GetWatchdogHandler returns watchdog settings and host states for [GET] /api/watchdog .
+func GetWatchdogHandler(c *gin.Context) {
+	c.JSON(http.StatusOK, gin.H{
+		"settings": config.Get().Watchdog,
+		"hosts":    watchdog.Hosts(),
+	})
+}
+
+// PutWatchdogHandler saves watchdog settings for [PUT] /api/watchdog .
+func PutWatchdogHandler(c *gin.Context) {
+	settings := WatchdogSettings{}
+	if err := c.ShouldBindJSON(&settings); err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	if settings.WebhookURL != "" {
+		if u, err := url.Parse(settings.WebhookURL); err != nil || !strings.HasPrefix(u.Scheme, "http") {
+			AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("invalid webhook URL %q", settings.WebhookURL)))
+			return
+		}
+	}
+	warn, reconnect, alert := settings.thresholds()
+	if warn > reconnect || reconnect > alert {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("thresholds must be warn <= reconnect <= alert")))
+		return
+	}
+	if err := config.Update(func(cfg *Config) {
+		cfg.Watchdog = settings
+	}); err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	GetWatchdogHandler(c)
+}
@@ -0,0 +1,50 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestWatchdogEscalates(t *testing.T) {
+	addr := "http://192.0.2.1:8088"
+	seen := time.Now()
+	p := NewPoller()
+	p.statuses[addr] = &HostStatus{Name: "backup", Addr: addr, LastSeen: seen}
+	prev := poller
+	poller = p
+	defer func() { poller = prev }()
+
+	settings := WatchdogSettings{WarnSec: 5, ReconnectSec: 10, AlertSec: 20, WebhookURL: "http://example.com/hook"}
+	alerts := 0
+	w := NewWatchdog()
+	w.alert = func(url string, h WatchdogHost) error {
+		alerts++
+		return nil
+	}
+	steps := []struct {
+		after time.Duration
+		stage string
+	}{
+		{time.Second, WatchdogOK},
+		{6 * time.Second, WatchdogWarn},
+		{11 * time.Second, WatchdogReconnect},
+		{12 * time.Second, WatchdogReconnect},
+		{25 * time.Second, WatchdogAlert},
+		{30 * time.Second, WatchdogAlert},
+	}
+	for _, step := range steps {
+		w.check(p.statuses[addr], settings, seen.Add(step.after))
+		if h := w.Hosts()[addr]; h.Stage != step.stage {
+			t.Fatalf("after %v expected stage %q, got %q", step.after, step.stage, h.Stage)
+		}
+	}
+	if h := w.Hosts()[addr]; h.Reconnects != 1 || alerts != 1 {
+		t.Fatalf("expected single reconnect and alert, got %d and %d", h.Reconnects, alerts)
+	}
+
+	p.statuses[addr] = &HostStatus{Name: "backup", Addr: addr, Online: true, LastSeen: seen.Add(31 * time.Second)}
+	w.check(p.statuses[addr], settings, seen.Add(31*time.Second))
+	if h := w.Hosts()[addr]; h.Stage != WatchdogOK {
+		t.Fatalf("expected recovery, got %q", h.Stage)
+	}
+}
@@ -0,0 +1,381 @@
+package main
+
+import (
+	"bufio"
+	"crypto/sha1"
+	"encoding/base64"
+	"encoding/binary"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log"
+	"net"
+	"net/http"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/FlowingSPDG/vmix-utility/server/core"
+	"github.com/gin-gonic/gin"
+)
+
+// wsPingInterval is interval of ping frames. Write fails on dead connection, so closed windows are unregistered even without close frame.
+const wsPingInterval = 30 * time.Second
+
+// websocketGUID is magic string defined by RFC 6455.
+const websocketGUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+
+// WebSocket opcodes.
+const (
+	wsOpText  = 0x1
+	wsOpClose = 0x8
+	wsOpPing  = 0x9
+	wsOpPong  = 0xA
+)
+
+// wsConn is minimal server side WebSocket connection.
+type wsConn struct {
+	conn net.Conn
+	r    *bufio.Reader
+	mu   sync.Mutex // guards writes.
+}
+
+// upgradeWebSocket performs WebSocket handshake on HTTP request.
+func upgradeWebSocket(w http.ResponseWriter, r *http.Request) (*wsConn, error) {
+	if !strings.EqualFold(r.Header.Get("Upgrade"), "websocket") {
+		return nil, fmt.Errorf("not a WebSocket handshake")
+	}
+	key := r.Header.Get("Sec-WebSocket-Key")
+	if key == "" {
+		return nil, fmt.Errorf("Sec-WebSocket-Key missing")
+	}
+	hj, ok := w.(http.Hijacker)
+	if !ok {
+		return nil, fmt.Errorf("connection does not support hijacking")
+	}
+	conn, rw, err := hj.Hijack()
+	if err != nil {
+		return nil, err
+	}
+	h := sha1.New()
+	h.Write([]byte(key + websocketGUID))
+	accept := base64.StdEncoding.EncodeToString(h.Sum(nil))
+	rw.WriteString("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: " + accept + "\r\n\r\n")
+	if err := rw.Flush(); err != nil {
+		conn.Close()
+		return nil, err
+	}
+	return &wsConn{conn: conn, r: rw.Reader}, nil
+}
+
+// writeFrame writes single unmasked frame.
+func (c *wsConn) writeFrame(op byte, payload []byte) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	header := []byte{0x80 | op}
+	switch n := len(payload); {
+	case n < 126:
+		header = append(header, byte(n))
+	case n <= 0xFFFF:
+		header = append(header, 126, 0, 0)
+		binary.BigEndian.PutUint16(header[2:], uint16(n))
+	default:
+		header = append(header, 127, 0, 0, 0, 0, 0, 0, 0, 0)
+		binary.BigEndian.PutUint64(header[2:], uint64(n))
+	}
+	c.conn.SetWriteDeadline(time.Now().Add(5 * time.Second))
+	if _, err := c.conn.Write(header); err != nil {
+		return err
+	}
+	_, err := c.conn.Write(payload)
+	return err
+}
+
+// WriteJSON sends v as text frame.
+func (c *wsConn) WriteJSON(v interface{}) error {
+	b, err := json.Marshal(v)
+	if err != nil {
+		return err
+	}
+	return c.writeFrame(wsOpText, b)
+}
+
+// ReadMessage reads next text message. Ping and close frames are handled internally.
+func (c *wsConn) ReadMessage() ([]byte, error) {
+	for {
+		var h [2]byte
+		if _, err := io.ReadFull(c.r, h[:]); err != nil {
+			return nil, err
+		}
+		op := h[0] & 0x0F
+		masked := h[1]&0x80 != 0
+		n := uint64(h[1] & 0x7F)
+		switch n {
+		case 126:
+			var ext [2]byte
+			if _, err := io.ReadFull(c.r, ext[:]); err != nil {
+				return nil, err
+			}
+			n = uint64(binary.BigEndian.Uint16(ext[:]))
+		case 127:
+			var ext [8]byte
+			if _, err := io.ReadFull(c.r, ext[:]); err != nil {
+				return nil, err
+			}
+			n = binary.BigEndian.Uint64(ext[:])
+		}
+		if n > 1<<20 {
+			return nil, fmt.Errorf("WebSocket frame too large")
+		}
+		var mask [4]byte
+		if masked {
+			if _, err := io.ReadFull(c.r, mask[:]); err != nil {
+				return nil, err
+			}
+		}
+		payload := make([]byte, n)
+		if _, err := io.ReadFull(c.r, payload); err != nil {
+			return nil, err
+		}
+		if masked {
+			for i := range payload {
+				payload[i] ^= mask[i%4]
+			}
+		}
+		switch op {
+		case wsOpClose:
+			c.writeFrame(wsOpClose, nil)
+			return nil, io.EOF
+		case wsOpPing:
+			if err := c.writeFrame(wsOpPong, payload); err != nil {
+				return nil, err
+			}
+		case wsOpText:
+			return payload, nil
+		}
+	}
+}
+
+// Close closes underlying connection.
+func (c *wsConn) Close() error {
+	return c.conn.Close()
+}
+
+// wsRequest is message sent by WebSocket client to change its subscription, hold talkback or report focus.
+// e.g. {"type":"subscribe","topics":["status"],"hosts":["studio-b"]} or {"type":"talkback_press","name":"PA"} .
+type wsRequest struct {
+	Type   string   `json:"type"` // "subscribe", "unsubscribe", "talkback_press", "talkback_release", "focus", "blur" or "resync".
+	Topics []string `json:"topics"`
+	Hosts  []string `json:"hosts"`
+	Acts   []string `json:"acts"` // ACTS activator names. e.g. "Input", "Overlay1", "Recording" .
+	Name   string   `json:"name"` // talkback channel.
+}
+
+// wsFilter is topics, hosts and ACTS activators subscribed by WebSocket client. Empty set matches everything.
+type wsFilter struct {
+	mu     sync.RWMutex
+	topics map[string]bool
+	hosts  map[string]bool
+	acts   map[string]bool
+}
+
+func newWSFilter(topics, hosts, acts []string) *wsFilter {
+	f := &wsFilter{topics: make(map[string]bool), hosts: make(map[string]bool), acts: make(map[string]bool)}
+	f.apply(wsRequest{Type: "subscribe", Topics: topics, Hosts: hosts, Acts: acts})
+	return f
+}
+
+// wsFilterOf returns filter of "topics", "hosts" and "acts" queries. Without "acts" query, ACTS activators
+// preferred for primary host are subscribed, so each client may narrow busy activator traffic on its own.
+func wsFilterOf(c *gin.Context) *wsFilter {
+	acts, ok := c.GetQuery("acts")
+	if !ok {
+		return newWSFilter(splitQuery(c.Query("topics")), splitQuery(c.Query("hosts")), TCPSubscriptionFor(*vmixaddr).ActsFilter)
+	}
+	return newWSFilter(splitQuery(c.Query("topics")), splitQuery(c.Query("hosts")), splitQuery(acts))
+}
+
+// apply updates filter with subscribe/unsubscribe request.
+func (f *wsFilter) apply(req wsRequest) error {
+	var set bool
+	switch req.Type {
+	case "subscribe":
+		set = true
+	case "unsubscribe":
+	default:
+		return fmt.Errorf("unknown request type %q", req.Type)
+	}
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	for _, topic := range req.Topics {
+		if set {
+			f.topics[topic] = true
+		} else {
+			delete(f.topics, topic)
+		}
+	}
+	for _, host := range req.Hosts {
+		if set {
+			f.hosts[host] = true
+		} else {
+			delete(f.hosts, host)
+		}
+	}
+	for _, name := range req.Acts {
+		if set {
+			f.acts[name] = true
+		} else {
+			delete(f.acts, name)
+		}
+	}
+	return nil
+}
+
+// Match reports whether event should be sent to client. Events without host are not filtered by hosts.
+func (f *wsFilter) Match(ev Event) bool {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	if len(f.topics) > 0 && !f.topics[ev.Topic] {
+		return false
+	}
+	if len(f.hosts) > 0 && ev.Host != "" && !f.hosts[ev.Host] {
+		return false
+	}
+	if a, ok := ev.Data.(ActsEvent); ok && len(f.acts) > 0 && !f.acts[a.Name] {
+		return false
+	}
+	return true
+}
+
+// WantsTopic reports whether filter matches topic of any host.
+func (f *wsFilter) WantsTopic(topic string) bool {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	return len(f.topics) == 0 || f.topics[topic]
+}
+
+// Subscription returns current topics and hosts.
+func (f *wsFilter) Subscription() gin.H {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	topics := make([]string, 0, len(f.topics))
+	for topic := range f.topics {
+		topics = append(topics, topic)
+	}
+	hosts := make([]string, 0, len(f.hosts))
+	for host := range f.hosts {
+		hosts = append(hosts, host)
+	}
+	acts := make([]string, 0, len(f.acts))
+	for name := range f.acts {
+		acts = append(acts, name)
+	}
+	sort.Strings(topics)
+	sort.Strings(hosts)
+	sort.Strings(acts)
+	return gin.H{"topics": topics, "hosts": hosts, "acts": acts}
+}
+
+// splitQuery splits comma separated query parameter.
+func splitQuery(q string) []string {
+	var list []string
+	for _, v := range strings.Split(q, ",") {
+		if v = strings.TrimSpace(v); v != "" {
+			list = append(list, v)
+		}
+	}
+	return list
+}
+
+// WebSocketHandler streams events to WebSocket clients for [GET] /api/ws?client=...&topics=...&hosts=... .
+// Clients receive every event unless they subscribe to specific topics or hosts, either by query or by sending wsRequest.
+// client names window or panel, such as "main" or "list-manager", shown in event statistics.
+func WebSocketHandler(c *gin.Context) {
+	ws, err := upgradeWebSocket(c.Writer, c.Request)
+	if err != nil {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", err))
+		return
+	}
+	defer ws.Close()
+
+	defer operators.Connect(operatorName(c), c.ClientIP())()
+
+	// Talkback pressed by connection is released when connection closes, so dropped client never leaves microphone open.
+	holder := operatorName(c) + " " + ws.conn.RemoteAddr().String()
+	defer talkback.ReleaseAll(holder)
+	// Focus reported by client speeds up polling while operator watches, until it blurs or disconnects.
+	defer poller.SetFocus(holder, false)
+
+	filter := wsFilterOf(c)
+	ch, unsubscribe := events.SubscribeTo(64, c.DefaultQuery("client", c.ClientIP()), filter.WantsTopic)
+	defer unsubscribe()
+
+	closed := make(chan struct{})
+	go func() {
+		defer close(closed)
+		for {
+			b, err := ws.ReadMessage()
+			if err != nil {
+				return
+			}
+			req := wsRequest{}
+			if err := json.Unmarshal(b, &req); err != nil {
+				ws.WriteJSON(Event{Topic: "error", Data: NewAppError(ErrKindInvalidRequest, "", err)})
+				continue
+			}
+			switch req.Type {
+			case "talkback_press", "talkback_release":
+				if req.Type == "talkback_press" {
+					err = talkback.Press(req.Name, holder)
+				} else {
+					err = talkback.Release(req.Name, holder)
+				}
+				if err != nil {
+					ws.WriteJSON(Event{Topic: "error", Data: AsAppError(err)})
+				}
+				continue
+			case "focus", "blur":
+				poller.SetFocus(holder, req.Type == "focus")
+				continue
+			}
+			if err := filter.apply(req); err != nil {
+				ws.WriteJSON(Event{Topic: "error", Data: NewAppError(ErrKindInvalidRequest, "", err)})
+				continue
+			}
+			ws.WriteJSON(Event{Topic: "subscribed", Data: filter.Subscription()})
+		}
+	}()
+
+	ping := time.NewTicker(wsPingInterval)
+	defer ping.Stop()
+	for {
+		select {
+		case <-ping.C:
+			if err := ws.writeFrame(wsOpPing, nil); err != nil {
+				return
+			}
+		case ev, ok := <-ch:
+			if !ok {
+				// Evicted as slow subscriber. Client should reconnect and fetch state again.
+				ws.WriteJSON(Event{Topic: "evicted"})
+				ws.writeFrame(wsOpClose, nil)
+				return
+			}
+			if !filter.Match(ev) {
+				continue
+			}
+			if err := ws.WriteJSON(ev); err != nil {
+				log.Printf("Failed to send event to WebSocket client %s : %v\n", ws.conn.RemoteAddr(), err)
+				return
+			}
+			if ev.Trace != nil {
+				now := time.Now()
+				latency.Record(core.LatencyStageEmitToSend, now.Sub(ev.Trace.Emitted))
+				latency.Record(core.LatencyStageTotal, now.Sub(ev.Trace.Received))
+			}
+		case <-closed:
+			return
+		}
+	}
+}
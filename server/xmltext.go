@@ -0,0 +1,208 @@
+package main
+
+import (
+	"bytes"
+	"encoding/xml"
+	"fmt"
+	"log"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// XMLText queries single value from vMix XML with XPath over TCP API. e.g. "vmix/inputs/input[2]/@title" .
+func (t *TCPClient) XMLText(path string) (string, error) {
+	msg, err := t.Request("XMLTEXT", path, 3*time.Second)
+	if err != nil {
+		return "", err
+	}
+	return msg.Body, nil
+}
+
+// ProgramRefresher fetches program and preview of host with two XMLTEXT queries when tally changes,
+// so they update without waiting for next poll of full XML. Tally changes arriving during refresh
+// are coalesced into one more refresh.
+type ProgramRefresher struct {
+	Host  string
+	Query func(path string) (string, error) // e.g. XMLText of TCP client.
+
+	mu      sync.Mutex
+	running bool
+	pending bool
+}
+
+// Trigger refreshes program and preview in background.
+func (r *ProgramRefresher) Trigger() {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.running {
+		r.pending = true
+		return
+	}
+	r.running = true
+	go r.run()
+}
+
+func (r *ProgramRefresher) run() {
+	for {
+		if err := r.refresh(); err != nil {
+			log.Printf("Failed to refresh program of %s : %v\n", r.Host, err)
+		}
+		r.mu.Lock()
+		if !r.pending {
+			r.running = false
+			r.mu.Unlock()
+			return
+		}
+		r.pending = false
+		r.mu.Unlock()
+	}
+}
+
+// refresh queries program and preview and patches them into latest status of host.
+func (r *ProgramRefresher) refresh() error {
+	values := [2]int{}
+	for i, path := range []string{"vmix/active", "vmix/preview"} {
+		v, err := r.Query(path)
+		if err != nil {
+			return err
+		}
+		if values[i], err = strconv.Atoi(strings.TrimSpace(v)); err != nil {
+			return fmt.Errorf("%s : %v", path, err)
+		}
+	}
+	poller.PatchProgram(r.Host, values[0], values[1])
+	return nil
+}
+
+// xmlNode is generic XML element tree, used to evaluate XMLTEXT paths.
+type xmlNode struct {
+	Name     string
+	Attrs    map[string]string
+	Text     string
+	Children []*xmlNode
+}
+
+// parseXMLNode parses XML into generic tree and returns root element.
+func parseXMLNode(b []byte) (*xmlNode, error) {
+	dec := xml.NewDecoder(bytes.NewReader(b))
+	var stack []*xmlNode
+	var root *xmlNode
+	for {
+		tok, err := dec.Token()
+		if err != nil {
+			break
+		}
+		switch t := tok.(type) {
+		case xml.StartElement:
+			n := &xmlNode{Name: t.Name.Local, Attrs: make(map[string]string, len(t.Attr))}
+			for _, a := range t.Attr {
+				n.Attrs[a.Name.Local] = a.Value
+			}
+			if len(stack) > 0 {
+				parent := stack[len(stack)-1]
+				parent.Children = append(parent.Children, n)
+			} else {
+				root = n
+			}
+			stack = append(stack, n)
+		case xml.CharData:
+			if len(stack) > 0 {
+				stack[len(stack)-1].Text += string(t)
+			}
+		case xml.EndElement:
+			if len(stack) > 0 {
+				stack = stack[:len(stack)-1]
+			}
+		}
+	}
+	if root == nil {
+		return nil, fmt.Errorf("empty XML")
+	}
+	return root, nil
+}
+
+// EvalXMLText evaluates simple XPath used by vMix XMLTEXT.
+// Supported steps are "name", "name[n]" (1-based), "name[@attr='value']" and final "@attr".
+func EvalXMLText(root *xmlNode, path string) (string, error) {
+	steps := strings.Split(strings.Trim(path, "/"), "/")
+	if len(steps) == 0 || steps[0] != root.Name {
+		return "", fmt.Errorf("path must start with %q", root.Name)
+	}
+	cur := root
+	for _, step := range steps[1:] {
+		if strings.HasPrefix(step, "@") {
+			v, ok := cur.Attrs[step[1:]]
+			if !ok {
+				return "", fmt.Errorf("attribute %s not found", step)
+			}
+			return v, nil
+		}
+		name, pred := step, ""
+		if i := strings.IndexByte(step, '['); i >= 0 && strings.HasSuffix(step, "]") {
+			name, pred = step[:i], step[i+1:len(step)-1]
+		}
+		var matches []*xmlNode
+		for _, c := range cur.Children {
+			if c.Name == name {
+				matches = append(matches, c)
+			}
+		}
+		next, err := selectXMLNode(matches, pred)
+		if err != nil {
+			return "", fmt.Errorf("%s : %v", step, err)
+		}
+		cur = next
+	}
+	return strings.TrimSpace(cur.Text), nil
+}
+
+func selectXMLNode(nodes []*xmlNode, pred string) (*xmlNode, error) {
+	if pred == "" {
+		if len(nodes) == 0 {
+			return nil, fmt.Errorf("not found")
+		}
+		return nodes[0], nil
+	}
+	if n, err := strconv.Atoi(pred); err == nil {
+		if n < 1 || n > len(nodes) {
+			return nil, fmt.Errorf("index out of range")
+		}
+		return nodes[n-1], nil
+	}
+	if strings.HasPrefix(pred, "@") {
+		kv := strings.SplitN(pred[1:], "=", 2)
+		if len(kv) == 2 {
+			want := strings.Trim(kv[1], `'"`)
+			for _, n := range nodes {
+				if n.Attrs[kv[0]] == want {
+					return n, nil
+				}
+			}
+			return nil, fmt.Errorf("not found")
+		}
+	}
+	return nil, fmt.Errorf("unsupported predicate %q", pred)
+}
+
+// GetXMLTextHandler queries single value over TCP API for [GET] /api/xmltext?path=... .
+func GetXMLTextHandler(c *gin.Context) {
+	path := c.Query("path")
+	if path == "" {
+		AbortWithAppError(c, NewAppError(ErrKindInvalidRequest, "", fmt.Errorf("path required")))
+		return
+	}
+	v, err := vmixTCP.XMLText(path)
+	if err != nil {
+		AbortWithAppError(c, err)
+		return
+	}
+	c.JSON(http.StatusOK, gin.H{
+		"path":  path,
+		"value": v,
+	})
+}
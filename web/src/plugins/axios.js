@@ -9,13 +9,20 @@ import axios from "axios";
 // axios.defaults.headers.post['Content-Type'] = 'application/x-www-form-urlencoded';
 
 const config = {
-  // baseURL: process.env.baseURL || process.env.apiUrl || ""
+  // Directory of index.html, so API works when served under reverse proxy path prefix. e.g. "/vmix" .
+  baseURL: location.pathname.replace(/\/[^/]*$/, ""),
   // timeout: 60 * 1000, // Timeout
   // withCredentials: true, // Check cross-site Access-Control
 };
 
 const _axios = axios.create(config);
 
+// Operator name shown to other operators. Saved per browser.
+if (!localStorage.getItem("operator")) {
+  localStorage.setItem("operator", `Operator-${Math.random().toString(36).slice(2, 6)}`);
+}
+_axios.defaults.headers.common["X-Operator"] = localStorage.getItem("operator");
+
 _axios.interceptors.request.use(
   function(config) {
     // Do something before request is sent
@@ -4,6 +4,9 @@ import Home from '../views/Home.vue'
 import Developer from '../views/Developer.vue'
 import Tree from '../views/Tree.vue'
 import BlankGen from '../views/BlankGen.vue'
+import Output from '../views/Output.vue'
+import Graphics from '../views/Graphics.vue'
+import RunShortcut from '../views/RunShortcut.vue'
 import api from '../utils/api.vue'
 
 Vue.use(VueRouter)
@@ -31,6 +34,29 @@ const routes = [
     path: '/developer',
     name: 'Developer',
     component: Developer
+  },
+  {
+    // Full screen program/preview display. bus is "program", "preview" or "dual".
+    path: '/output/:bus',
+    name: 'Output',
+    component: Output,
+    props: true,
+    meta: { bare: true }
+  },
+  {
+    // Transparent overlay graphic for vMix Web Browser input.
+    path: '/graphics/:name',
+    name: 'Graphics',
+    component: Graphics,
+    props: true,
+    meta: { bare: true }
+  },
+  {
+    // Confirmation of shortcut deep link. Shortcut runs only when confirmed.
+    path: '/shortcut/:name',
+    name: 'RunShortcut',
+    component: RunShortcut,
+    props: true
   }
 ]
 